@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use crate::constants::AGENT_SEED;
+use crate::errors::DimmError;
+use crate::state::AgentAccount;
+
+/// Invoke `program` with `data` and `accounts`, in order, signed by the
+/// agent PDA wherever it appears among them.
+pub fn invoke_as_agent<'info>(
+    agent_account: &Account<'info, AgentAccount>,
+    program: &AccountInfo<'info>,
+    data: Vec<u8>,
+    accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    require!(program.executable, DimmError::InvalidProgram);
+    require_keys_neq!(*program.key, crate::ID, DimmError::InvalidProgram);
+
+    let agent_key = agent_account.key();
+    let metas = accounts
+        .iter()
+        .map(|info| AccountMeta {
+            pubkey: *info.key,
+            is_signer: info.is_signer || *info.key == agent_key,
+            is_writable: info.is_writable,
+        })
+        .collect();
+
+    let instruction = Instruction {
+        program_id: *program.key,
+        accounts: metas,
+        data,
+    };
+
+    let mut account_infos = accounts.to_vec();
+    account_infos.push(program.clone());
+
+    let agent_seeds = &[
+        AGENT_SEED,
+        agent_account.main_wallet.as_ref(),
+        &agent_account.agent_id.to_le_bytes(),
+        &[agent_account.bump],
+    ];
+
+    invoke_signed(&instruction, &account_infos, &[&agent_seeds[..]])?;
+
+    Ok(())
+}
+
+/// Log `memo` through the SPL Memo program with the agent PDA as signer, so
+/// tools keying on memos can attribute the transfer to the agent
+pub fn memo_as_agent<'info>(
+    agent_account: &Account<'info, AgentAccount>,
+    memo_program: &AccountInfo<'info>,
+    memo: &str,
+) -> Result<()> {
+    invoke_as_agent(
+        agent_account,
+        memo_program,
+        memo.as_bytes().to_vec(),
+        &[agent_account.to_account_info()],
+    )
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::{AgentAccount, EmergencyState, SessionKey};
+
+/// Role a signer holds over an agent or wallet, resolved once per instruction
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// Owner of the agent and its wallet-level accounts
+    MainWallet,
+    
+    /// The agent's own signer key
+    AgentSigner,
+    
+    /// Emergency contact registered on the wallet's emergency state
+    EmergencyContact,
+    
+    /// Unexpired session key opened for the agent
+    SessionKey,
+}
+
+/// Action a role may be allowed to take
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Capability {
+    /// Execute, propose or stage agent transactions
+    Execute,
+    
+    /// Pause the wallet or cancel pending withdrawals
+    EmergencyAction,
+}
+
+impl Role {
+    /// Check if this role grants a capability
+    pub fn can(&self, capability: Capability) -> bool {
+        match capability {
+            Capability::Execute => {
+                matches!(self, Role::MainWallet | Role::AgentSigner | Role::SessionKey)
+            }
+            Capability::EmergencyAction => {
+                matches!(self, Role::MainWallet | Role::EmergencyContact)
+            }
+        }
+    }
+}
+
+/// Signer of an instruction together with its resolved role
+#[derive(Clone, Copy, Debug)]
+pub struct Caller {
+    pub key: Pubkey,
+    pub role: Role,
+}
+
+impl Caller {
+    /// Resolve `key` against the wallet's accounts, strongest role first.
+    /// Pass whichever of the agent and emergency state the instruction has.
+    pub fn resolve(
+        key: Pubkey,
+        main_wallet: &Pubkey,
+        agent_account: Option<&AgentAccount>,
+        emergency_state: Option<&EmergencyState>,
+    ) -> Result<Caller> {
+        let role = if &key == main_wallet {
+            Role::MainWallet
+        } else if agent_account.is_some_and(|agent| agent.agent_signer == key) {
+            Role::AgentSigner
+        } else if emergency_state.is_some_and(|state| state.emergency_contacts.contains(&key)) {
+            Role::EmergencyContact
+        } else {
+            return err!(DimmError::SignerHasNoRole);
+        };
+
+        Ok(Caller { key, role })
+    }
+
+    /// Resolve `key` like `resolve`, falling back to the agent's session key.
+    /// An expired session fails with `SessionExpired`.
+    pub fn resolve_with_session(
+        key: Pubkey,
+        main_wallet: &Pubkey,
+        agent_account: Option<&AgentAccount>,
+        session_key: Option<&SessionKey>,
+        current_time: i64,
+    ) -> Result<Caller> {
+        if let Ok(caller) = Self::resolve(key, main_wallet, agent_account, None) {
+            return Ok(caller);
+        }
+        let session = session_key
+            .filter(|session| session.session_key == key)
+            .ok_or(DimmError::SignerHasNoRole)?;
+
+        require!(session.is_active(current_time), DimmError::SessionExpired);
+        Ok(Caller { key, role: Role::SessionKey })
+    }
+
+    /// Fail with the capability's error unless the caller's role grants it
+    pub fn require(&self, capability: Capability) -> Result<()> {
+        if self.role.can(capability) {
+            return Ok(());
+        }
+
+        match capability {
+            Capability::Execute => err!(DimmError::ExecuteNotAllowed),
+            Capability::EmergencyAction => err!(DimmError::EmergencyActionNotAllowed),
+        }
+    }
+
+    /// Resolve the caller and require a capability in one step
+    pub fn authorize(
+        key: Pubkey,
+        main_wallet: &Pubkey,
+        agent_account: Option<&AgentAccount>,
+        emergency_state: Option<&EmergencyState>,
+        capability: Capability,
+    ) -> Result<Caller> {
+        let caller = Self::resolve(key, main_wallet, agent_account, emergency_state)?;
+        caller.require(capability)?;
+        Ok(caller)
+    }
+}
@@ -0,0 +1,330 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use mpl_bubblegum::cpi::{self, accounts::{Burn, Delegate, MintV1, Transfer, UpdateMetadata}};
+use mpl_bubblegum::state::metaplex_adapter::{
+    Creator, MetadataArgs, TokenProgramVersion, TokenStandard, UpdateArgs,
+};
+use crate::constants::*;
+use crate::errors::DimmError;
+use crate::state::{AgentAccount, AgentLeafArgs};
+
+/// Bubblegum's `LeafSchema::V1` version byte
+const LEAF_SCHEMA_V1: u8 = 1;
+
+/// Offset of `total_mint_capacity` in Bubblegum's `TreeConfig`
+/// (discriminator, tree_creator, tree_delegate)
+const TREE_CONFIG_CAPACITY_OFFSET: usize = 8 + 32 + 32;
+
+/// Offset of `num_minted` in Bubblegum's `TreeConfig`, right after `total_mint_capacity`
+const TREE_CONFIG_NUM_MINTED_OFFSET: usize = TREE_CONFIG_CAPACITY_OFFSET + 8;
+
+/// Bubblegum and compression accounts shared by every CPI touching an agent's cNFT
+pub struct TreeAccounts<'info> {
+    pub tree_authority: AccountInfo<'info>,
+    pub merkle_tree: AccountInfo<'info>,
+    pub bubblegum_program: AccountInfo<'info>,
+    pub compression_program: AccountInfo<'info>,
+    pub log_wrapper: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+/// Number of leaves minted into a tree so far, i.e. the index of the next mint
+pub fn next_leaf_index(tree_authority: &AccountInfo) -> Result<u32> {
+    let data = tree_authority.try_borrow_data()?;
+    let bytes = data
+        .get(TREE_CONFIG_NUM_MINTED_OFFSET..TREE_CONFIG_NUM_MINTED_OFFSET + 8)
+        .ok_or(DimmError::InvalidMerkleProof)?;
+    let num_minted = u64::from_le_bytes(bytes.try_into().unwrap());
+
+    u32::try_from(num_minted).map_err(|_| error!(DimmError::NumericalOverflow))
+}
+
+/// Whether every leaf of a tree has been minted
+pub fn tree_is_full(tree_authority: &AccountInfo) -> Result<bool> {
+    let data = tree_authority.try_borrow_data()?;
+    let bytes = data
+        .get(TREE_CONFIG_CAPACITY_OFFSET..TREE_CONFIG_NUM_MINTED_OFFSET + 8)
+        .ok_or(DimmError::InvalidMerkleTree)?;
+    let capacity = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+    let num_minted = u64::from_le_bytes(bytes[8..].try_into().unwrap());
+
+    Ok(num_minted >= capacity)
+}
+
+/// Check a tree has been set up through Bubblegum's `create_tree`, with
+/// `tree_authority` as its tree config
+pub fn check_tree(merkle_tree: &AccountInfo, tree_authority: &AccountInfo) -> Result<()> {
+    require_keys_eq!(
+        *merkle_tree.owner,
+        spl_account_compression::id(),
+        DimmError::InvalidMerkleTree
+    );
+    let (expected_authority, _) =
+        Pubkey::find_program_address(&[merkle_tree.key.as_ref()], &mpl_bubblegum::ID);
+    require_keys_eq!(*tree_authority.key, expected_authority, DimmError::InvalidMerkleTree);
+    require!(
+        *tree_authority.owner == mpl_bubblegum::ID && !tree_authority.data_is_empty(),
+        DimmError::InvalidMerkleTree
+    );
+
+    Ok(())
+}
+
+/// cNFT metadata for an agent. Permissions and limits are committed to in
+/// the URI so the leaf's data hash covers them.
+pub fn agent_metadata(agent_account: &AgentAccount, agent: &Pubkey) -> MetadataArgs {
+    let permissions = agent_account.permissions.bits;
+    let uri = format!(
+        "{}/{}?permissions={}&max_tx={}&daily={}",
+        AGENT_METADATA_URI_BASE,
+        agent,
+        permissions,
+        agent_account.max_sol_per_transaction,
+        agent_account.daily_limit,
+    );
+
+    metadata_args(agent_account.name.clone(), uri, agent_account.main_wallet)
+}
+
+/// Agent cNFT metadata with the given name and URI, created by `creator`
+pub fn metadata_args(name: String, uri: String, creator: Pubkey) -> MetadataArgs {
+    MetadataArgs {
+        name,
+        symbol: AGENT_NFT_SYMBOL.to_string(),
+        uri,
+        seller_fee_basis_points: 0,
+        primary_sale_happened: true,
+        is_mutable: true,
+        edition_nonce: None,
+        token_standard: Some(TokenStandard::NonFungible),
+        collection: None,
+        uses: None,
+        token_program_version: TokenProgramVersion::Original,
+        creators: vec![Creator {
+            address: creator,
+            verified: true,
+            share: 100,
+        }],
+    }
+}
+
+/// Leaf hash Bubblegum stores for an agent's cNFT, built from the agent's
+/// current state. Minted leaves use their leaf index as their nonce.
+pub fn agent_leaf_hash(
+    agent_account: &AgentAccount,
+    agent: &Pubkey,
+    leaf_delegate: &Pubkey,
+) -> Result<[u8; 32]> {
+    let metadata = agent_metadata(agent_account, agent);
+    let metadata_hash = keccak::hashv(&[&metadata.try_to_vec()?]);
+    let data_hash = keccak::hashv(&[
+        &metadata_hash.to_bytes(),
+        &metadata.seller_fee_basis_points.to_le_bytes(),
+    ]);
+    let creator_data = metadata
+        .creators
+        .iter()
+        .map(|c| [c.address.as_ref(), &[c.verified as u8], &[c.share]].concat())
+        .collect::<Vec<_>>();
+    let creator_hash = keccak::hashv(&creator_data.iter().map(|c| c.as_slice()).collect::<Vec<_>>());
+
+    let nonce = agent_account.leaf_index as u64;
+    let (asset_id, _) = Pubkey::find_program_address(
+        &[b"asset", agent_account.merkle_tree.as_ref(), &nonce.to_le_bytes()],
+        &mpl_bubblegum::ID,
+    );
+
+    Ok(keccak::hashv(&[
+        &[LEAF_SCHEMA_V1],
+        asset_id.as_ref(),
+        agent_account.main_wallet.as_ref(),
+        leaf_delegate.as_ref(),
+        &nonce.to_le_bytes(),
+        &data_hash.to_bytes(),
+        &creator_hash.to_bytes(),
+    ])
+    .to_bytes())
+}
+
+/// Mint an agent's cNFT into the tree. The payer and tree delegate must sign.
+pub fn mint_leaf<'info>(
+    tree: &TreeAccounts<'info>,
+    leaf_owner: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    tree_delegate: &AccountInfo<'info>,
+    metadata: MetadataArgs,
+) -> Result<()> {
+    let cpi_context = CpiContext::new(
+        tree.bubblegum_program.clone(),
+        MintV1 {
+            tree_authority: tree.tree_authority.clone(),
+            leaf_owner: leaf_owner.clone(),
+            leaf_delegate: leaf_owner.clone(),
+            merkle_tree: tree.merkle_tree.clone(),
+            payer: payer.clone(),
+            tree_delegate: tree_delegate.clone(),
+            log_wrapper: tree.log_wrapper.clone(),
+            compression_program: tree.compression_program.clone(),
+            system_program: tree.system_program.clone(),
+        },
+    );
+
+    cpi::mint_v1(cpi_context, metadata)
+}
+
+/// Set the delegate of an agent's cNFT. The leaf owner must sign.
+pub fn delegate_leaf<'info>(
+    tree: &TreeAccounts<'info>,
+    leaf_owner: &AccountInfo<'info>,
+    previous_leaf_delegate: &AccountInfo<'info>,
+    new_leaf_delegate: &AccountInfo<'info>,
+    leaf: &AgentLeafArgs,
+    proof: &[AccountInfo<'info>],
+) -> Result<()> {
+    let cpi_context = CpiContext::new(
+        tree.bubblegum_program.clone(),
+        Delegate {
+            tree_authority: tree.tree_authority.clone(),
+            leaf_owner: leaf_owner.clone(),
+            previous_leaf_delegate: previous_leaf_delegate.clone(),
+            new_leaf_delegate: new_leaf_delegate.clone(),
+            merkle_tree: tree.merkle_tree.clone(),
+            log_wrapper: tree.log_wrapper.clone(),
+            compression_program: tree.compression_program.clone(),
+            system_program: tree.system_program.clone(),
+        },
+    )
+    .with_remaining_accounts(proof.to_vec());
+
+    cpi::delegate(
+        cpi_context,
+        leaf.root,
+        leaf.data_hash,
+        leaf.creator_hash,
+        leaf.nonce,
+        leaf.index,
+    )
+}
+
+/// Move an agent's cNFT to a new owner, signed by the leaf delegate's seeds
+pub fn transfer_leaf<'info>(
+    tree: &TreeAccounts<'info>,
+    leaf_owner: &AccountInfo<'info>,
+    leaf_delegate: &AccountInfo<'info>,
+    new_leaf_owner: &AccountInfo<'info>,
+    leaf: &AgentLeafArgs,
+    proof: &[AccountInfo<'info>],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let cpi_context = CpiContext::new_with_signer(
+        tree.bubblegum_program.clone(),
+        Transfer {
+            tree_authority: tree.tree_authority.clone(),
+            leaf_owner: leaf_owner.clone(),
+            leaf_delegate: leaf_delegate.clone(),
+            new_leaf_owner: new_leaf_owner.clone(),
+            merkle_tree: tree.merkle_tree.clone(),
+            log_wrapper: tree.log_wrapper.clone(),
+            compression_program: tree.compression_program.clone(),
+            system_program: tree.system_program.clone(),
+        },
+        signer_seeds,
+    )
+    .with_remaining_accounts(proof.to_vec());
+
+    cpi::transfer(
+        cpi_context,
+        leaf.root,
+        leaf.data_hash,
+        leaf.creator_hash,
+        leaf.nonce,
+        leaf.index,
+    )
+}
+
+/// Burn an agent's cNFT. The leaf owner must sign.
+pub fn burn_leaf<'info>(
+    tree: &TreeAccounts<'info>,
+    leaf_owner: &AccountInfo<'info>,
+    leaf_delegate: &AccountInfo<'info>,
+    leaf: &AgentLeafArgs,
+    proof: &[AccountInfo<'info>],
+) -> Result<()> {
+    let cpi_context = CpiContext::new(
+        tree.bubblegum_program.clone(),
+        Burn {
+            tree_authority: tree.tree_authority.clone(),
+            leaf_owner: leaf_owner.clone(),
+            leaf_delegate: leaf_delegate.clone(),
+            merkle_tree: tree.merkle_tree.clone(),
+            log_wrapper: tree.log_wrapper.clone(),
+            compression_program: tree.compression_program.clone(),
+            system_program: tree.system_program.clone(),
+        },
+    )
+    .with_remaining_accounts(proof.to_vec());
+
+    cpi::burn(
+        cpi_context,
+        leaf.root,
+        leaf.data_hash,
+        leaf.creator_hash,
+        leaf.nonce,
+        leaf.index,
+    )
+}
+
+/// Replace the name and URI of an agent's cNFT, signed by the tree's
+/// creator or delegate. `current` must be the metadata the leaf was minted
+/// or last updated with.
+#[allow(clippy::too_many_arguments)]
+pub fn update_leaf_metadata<'info>(
+    tree: &TreeAccounts<'info>,
+    tree_delegate: &AccountInfo<'info>,
+    leaf_owner: &AccountInfo<'info>,
+    leaf_delegate: &AccountInfo<'info>,
+    token_metadata_program: &AccountInfo<'info>,
+    leaf: &AgentLeafArgs,
+    current: MetadataArgs,
+    updated: &MetadataArgs,
+    proof: &[AccountInfo<'info>],
+) -> Result<()> {
+    let cpi_context = CpiContext::new(
+        tree.bubblegum_program.clone(),
+        UpdateMetadata {
+            tree_authority: tree.tree_authority.clone(),
+            authority: tree_delegate.clone(),
+            // Agent cNFTs have no collection; Bubblegum takes its own ID for
+            // the unused collection accounts
+            collection_mint: tree.bubblegum_program.clone(),
+            collection_metadata: tree.bubblegum_program.clone(),
+            collection_authority_record_pda: tree.bubblegum_program.clone(),
+            leaf_owner: leaf_owner.clone(),
+            leaf_delegate: leaf_delegate.clone(),
+            payer: tree_delegate.clone(),
+            merkle_tree: tree.merkle_tree.clone(),
+            log_wrapper: tree.log_wrapper.clone(),
+            compression_program: tree.compression_program.clone(),
+            token_metadata_program: token_metadata_program.clone(),
+            system_program: tree.system_program.clone(),
+        },
+    )
+    .with_remaining_accounts(proof.to_vec());
+
+    cpi::update_metadata(
+        cpi_context,
+        leaf.root,
+        leaf.nonce,
+        leaf.index,
+        current,
+        UpdateArgs {
+            name: Some(updated.name.clone()),
+            symbol: None,
+            uri: Some(updated.uri.clone()),
+            creators: None,
+            seller_fee_basis_points: None,
+            primary_sale_happened: None,
+            is_mutable: None,
+        },
+    )
+}
@@ -9,9 +9,140 @@ pub const AGENT_SEED: &[u8] = b"dimm_agent";
 #[constant]
 pub const ACTIVITY_SEED: &[u8] = b"dimm_activity";
 
+/// Activity records under the retention ring. Records from before it, seeded
+/// by `total_transactions` under `ACTIVITY_SEED`, keep their own addresses.
+#[constant]
+pub const ACTIVITY_SLOT_SEED: &[u8] = b"dimm_activity_slot";
+
 #[constant]
 pub const TREE_AUTHORITY_SEED: &[u8] = b"tree_authority";
 
+#[constant]
+pub const TREASURY_SEED: &[u8] = b"dimm_treasury";
+
+#[constant]
+pub const CREDIT_LINE_SEED: &[u8] = b"dimm_credit_line";
+
+#[constant]
+pub const WATCHTOWER_SEED: &[u8] = b"dimm_watchtower";
+
+#[constant]
+pub const EMERGENCY_SEED: &[u8] = b"dimm_emergency";
+
+#[constant]
+pub const WITHDRAWAL_SEED: &[u8] = b"dimm_withdrawal";
+
+#[constant]
+pub const WITHDRAWAL_ADDRESSES_SEED: &[u8] = b"dimm_withdrawal_addresses";
+
+#[constant]
+pub const HOOK_SEED: &[u8] = b"dimm_hooks";
+
+#[constant]
+pub const PRIORITY_LANES_SEED: &[u8] = b"dimm_priority_lanes";
+
+#[constant]
+pub const TOKEN_LIMIT_SEED: &[u8] = b"dimm_token_limit";
+
+#[constant]
+pub const WHITELIST_SEED: &[u8] = b"dimm_whitelist";
+
+#[constant]
+pub const LISTING_SEED: &[u8] = b"dimm_listing";
+
+#[constant]
+pub const EXECUTION_BUFFER_SEED: &[u8] = b"dimm_execution_buffer";
+
+#[constant]
+pub const SPEND_APPROVAL_SEED: &[u8] = b"dimm_spend_approval";
+
+#[constant]
+pub const PENDING_TRANSACTION_SEED: &[u8] = b"dimm_pending_tx";
+
+#[constant]
+pub const RENT_POOL_SEED: &[u8] = b"dimm_rent_pool";
+
+#[constant]
+pub const SCHEDULE_SEED: &[u8] = b"dimm_schedule";
+
+#[constant]
+pub const STAKE_SEED: &[u8] = b"dimm_stake";
+
+#[constant]
+pub const BOUNTY_SEED: &[u8] = b"dimm_bounty";
+
+#[constant]
+pub const STATS_SEED: &[u8] = b"dimm_stats";
+
+#[constant]
+pub const RATE_LIMIT_SEED: &[u8] = b"dimm_rate_limit";
+
+#[constant]
+pub const ACTIVITY_TREE_SEED: &[u8] = b"dimm_activity_tree";
+
+#[constant]
+pub const DIGEST_SEED: &[u8] = b"dimm_digest";
+
+#[constant]
+pub const ADDRESS_BOOK_SEED: &[u8] = b"dimm_address_book";
+
+#[constant]
+pub const DESTINATION_LIMIT_SEED: &[u8] = b"dimm_destination_limit";
+
+#[constant]
+pub const BLACKLIST_SEED: &[u8] = b"dimm_blacklist";
+
+#[constant]
+pub const PROGRAM_REGISTRY_SEED: &[u8] = b"dimm_program_registry";
+
+#[constant]
+pub const PLAN_SEED: &[u8] = b"dimm_plan";
+
+#[constant]
+pub const SCOPED_PERMISSIONS_SEED: &[u8] = b"dimm_scoped_permissions";
+
+#[constant]
+pub const STAKE_ACCOUNT_SEED: &[u8] = b"dimm_stake_account";
+
+#[constant]
+pub const SOL_DEPOSIT_SEED: &[u8] = b"dimm_sol_deposit";
+
+#[constant]
+pub const DAILY_SNAPSHOT_SEED: &[u8] = b"dimm_snapshot";
+
+#[constant]
+pub const GLOBAL_STATS_SEED: &[u8] = b"dimm_global_stats";
+
+#[constant]
+pub const ALLOWANCE_SEED: &[u8] = b"dimm_allowance";
+
+#[constant]
+pub const FUNDING_STREAM_SEED: &[u8] = b"dimm_stream";
+
+#[constant]
+pub const AGENT_ESCROW_SEED: &[u8] = b"dimm_agent_escrow";
+
+#[constant]
+pub const FUNDING_REQUEST_SEED: &[u8] = b"dimm_funding_request";
+
+#[constant]
+pub const CO_SIGNER_SET_SEED: &[u8] = b"dimm_co_signers";
+
+#[constant]
+pub const SESSION_KEY_SEED: &[u8] = b"dimm_session";
+
+#[constant]
+pub const RELAYER_REGISTRY_SEED: &[u8] = b"dimm_relayers";
+
+#[constant]
+pub const WALLET_BUDGET_SEED: &[u8] = b"dimm_wallet_budget";
+
+/// Decimals of native SOL amounts (lamports)
+pub const SOL_DECIMALS: u8 = 9;
+
+/// Lamports in one SOL
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
 /// Maximum number of agents per main wallet
 pub const MAX_AGENTS_PER_WALLET: u16 = 10000;
 
@@ -21,12 +152,48 @@ pub const MAX_TRANSACTION_SIZE: usize = 1232;
 /// Maximum length for agent names
 pub const MAX_AGENT_NAME_LENGTH: usize = 32;
 
+/// Maximum length for agent descriptions
+pub const MAX_AGENT_DESCRIPTION_LENGTH: usize = 256;
+
+/// Maximum length for agent metadata URIs
+pub const MAX_METADATA_URI_LENGTH: usize = 200;
+
+/// Maximum length for agent model identifiers
+pub const MAX_MODEL_ID_LENGTH: usize = 64;
+
+/// Maximum activity records a retention policy may keep per agent
+pub const MAX_RETENTION_RECORDS: u32 = 10000;
+
 /// Maximum length for activity reasons
 pub const MAX_REASON_LENGTH: usize = 128;
 
+/// Maximum length of an activity payload sealed to the agent's reporting key
+pub const MAX_ENCRYPTED_PAYLOAD_LENGTH: usize = 128;
+
+/// Symbol of agent cNFTs
+pub const AGENT_NFT_SYMBOL: &str = "DIMM";
+
+/// Base URI of agent cNFT metadata
+pub const AGENT_METADATA_URI_BASE: &str = "https://dimm.ai/metadata/agent";
+
+/// Maximum instruction data staged in an execution buffer (fits a single `init`)
+pub const MAX_EXECUTION_BUFFER_SIZE: u32 = 10_000;
+
+/// Maximum legs in one `execute_batch`
+pub const MAX_BATCH_LEGS: usize = 8;
+
+/// Maximum steps in one execution plan
+pub const MAX_PLAN_STEPS: usize = 8;
+
+/// Maximum instruction data of one plan step
+pub const MAX_PLAN_STEP_DATA: usize = 256;
+
 /// Minimum SOL balance to keep in agent account (rent exempt + buffer)
 pub const MIN_AGENT_BALANCE: u64 = 5_000_000; // 0.005 SOL
 
+/// Base network fee for one signature, refunded from an agent's fee tank
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5000;
+
 /// Default daily limit for new agents (in lamports)
 pub const DEFAULT_DAILY_LIMIT: u64 = 1_000_000_000; // 1 SOL
 
@@ -36,4 +203,113 @@ pub const DEFAULT_TX_LIMIT: u64 = 100_000_000; // 0.1 SOL
 /// Time window for daily limits (in seconds)
 pub const DAILY_WINDOW_SECONDS: i64 = 86400; // 24 hours
 
+/// How long new agents run under trial limits (in seconds)
+pub const TRIAL_PERIOD_SECONDS: i64 = 86400; // 24 hours
+
+/// Per-transaction cap during the trial period (in lamports)
+pub const TRIAL_TX_LIMIT: u64 = 10_000_000; // 0.01 SOL
+
+/// Daily cap during the trial period (in lamports)
+pub const TRIAL_DAILY_LIMIT: u64 = 50_000_000; // 0.05 SOL
+
+/// Default daily maintenance allowance for fee-only spends (in lamports);
+/// off until the main wallet sets one with `update_limits`
+pub const DEFAULT_MAINTENANCE_ALLOWANCE: u64 = 0;
+
+/// Hard cap on the daily maintenance allowance (in lamports)
+pub const MAX_MAINTENANCE_ALLOWANCE: u64 = 10_000_000; // 0.01 SOL
+
+/// Longest delay a withdrawal policy may impose (in seconds)
+pub const MAX_WITHDRAWAL_DELAY_SECONDS: i64 = 604800; // 7 days
+
+/// Delay before a newly added withdrawal address can receive funds (in seconds)
+pub const WITHDRAWAL_ADDRESS_TIMELOCK_SECONDS: i64 = 172800; // 48 hours
+
+/// Longest execution lease a runtime may hold in one acquisition (in seconds)
+pub const MAX_EXECUTION_LEASE_SECONDS: i64 = 300; // 5 minutes
+
+/// Longest freeze a watchtower may impose in one call (in seconds)
+pub const MAX_WATCHTOWER_FREEZE_SECONDS: i64 = 86400; // 24 hours
+
+
+
+/// Oldest oracle price accepted for USD-denominated limits (in seconds)
+pub const MAX_PRICE_AGE_SECONDS: i64 = 60;
+
+/// Widest oracle confidence interval accepted, relative to the price (in basis points)
+pub const MAX_PRICE_CONFIDENCE_BPS: u64 = 200; // 2%
+
+/// Jupiter v6 aggregator, the router `execute_swap` invokes
+pub const JUPITER_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5L3vYN4Bz");
+
+/// SPL stake pool program, which `deposit_stake_pool` deposits through
+pub const STAKE_POOL_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+
+/// Max slippage a newly created agent may swap with (in basis points)
+pub const DEFAULT_MAX_SLIPPAGE_BPS: u16 = 50; // 0.5%
+
+/// Max token accounts an agent may hold open through the program
+pub const MAX_AGENT_TOKEN_ACCOUNTS: u8 = 16;
+
+/// Current `AgentAccount` layout version
+pub const AGENT_SCHEMA_VERSION: u8 = 1;
+
+/// Current `AgentStats` layout version
+pub const AGENT_STATS_VERSION: u8 = 1;
+
+/// Current `RateLimit` layout version
+pub const RATE_LIMIT_VERSION: u8 = 1;
+
+/// Max co-signers in an agent's co-signer set
+pub const MAX_CO_SIGNERS: usize = 8;
+
+/// Max amount bands an agent may configure
+pub const MAX_AMOUNT_BANDS: usize = 4;
+
+/// Longest a session key may live
+pub const MAX_SESSION_DURATION_SECONDS: i64 = 604800; // 7 days
+
+/// Max activity types a session key may allow (one per type)
+pub const MAX_SESSION_ACTIVITY_TYPES: usize = 11;
+
+/// Domain tag prefixed to signed intents so the signature cannot be
+/// replayed as any other message
+pub const INTENT_DOMAIN: &[u8] = b"dimm_intent_v1";
+
+/// Max relayers a wallet may allow
+pub const MAX_RELAYERS: usize = 10;
+
+/// Most a relayer may be reimbursed per sponsored execution
+pub const MAX_RELAYER_REIMBURSEMENT: u64 = 100_000; // 0.0001 SOL
+
+/// SPL Memo program, which `execute_transaction` invokes for memos
+pub const MEMO_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+/// Max memo length on an execution (in bytes)
+pub const MAX_MEMO_LENGTH: usize = 256;
+
+/// Seconds in a UTC day
+pub const SECONDS_PER_DAY: i64 = 86400; // 24 hours
+
+/// Weekday bitmask allowing every day
+pub const ALL_WEEKDAYS: u8 = 0b111_1111;
+
+/// Length of the hourly velocity window
+pub const HOURLY_WINDOW_SECONDS: i64 = 3600; // 1 hour
+
+/// Successful transactions an agent needs before its trailing average is
+/// trusted by the anomaly rules
+pub const ANOMALY_MIN_HISTORY: u64 = 10;
+
+/// Longest delay an agent can put on permission and limit increases
+pub const MAX_CHANGE_DELAY_SECONDS: i64 = 2_592_000; // 30 days
+
+/// Most merkle trees a wallet's agents can be minted into
+pub const MAX_MERKLE_TREES: usize = 16;
 
+/// Metaplex Token Metadata program, which Bubblegum requires for metadata updates
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
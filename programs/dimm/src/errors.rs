@@ -52,6 +52,538 @@ pub enum DimmError {
 
     #[msg("Activity window calculation failed")]
     InvalidActivityWindow,
-}
 
+    #[msg("Fee basis points cannot exceed 10000")]
+    InvalidFeeConfiguration,
+
+    #[msg("Credit line is frozen due to a missed repayment")]
+    CreditLineFrozen,
+
+    #[msg("Draw would exceed the approved credit limit")]
+    ExceedsCreditLimit,
+
+    #[msg("Repayment exceeds the outstanding credit balance")]
+    ExceedsOutstandingCredit,
+
+    #[msg("Treasury has insufficient SOL for operation")]
+    InsufficientTreasuryBalance,
+
+    #[msg("Spend would exceed the daily maintenance allowance")]
+    ExceedsMaintenanceAllowance,
+
+    #[msg("Invalid activity retention policy")]
+    InvalidRetentionPolicy,
+
+    #[msg("Agent is frozen and cannot perform operations")]
+    AgentFrozen,
+
+    #[msg("Freeze duration exceeds the allowed maximum")]
+    InvalidFreezeDuration,
+
+    #[msg("Withdrawal exceeds the threshold and must be initiated first")]
+    WithdrawalRequiresTimelock,
+
+    #[msg("Withdrawal delay has not elapsed yet")]
+    WithdrawalStillLocked,
+
+    #[msg("Invalid withdrawal policy")]
+    InvalidWithdrawalPolicy,
+
+    #[msg("Withdrawal destination is not a verified address")]
+    DestinationNotVerified,
+
+    #[msg("Maximum number of withdrawal addresses reached")]
+    MaxWithdrawalAddressesReached,
+
+    #[msg("Label is too long")]
+    LabelTooLong,
+
+    #[msg("Invalid or duplicate hook program")]
+    InvalidHook,
+
+    #[msg("Maximum number of hooks reached")]
+    MaxHooksReached,
+
+    #[msg("Registered hook program was not provided")]
+    HookProgramMissing,
+
+    #[msg("Action was rejected by a policy hook")]
+    HookRejected,
+
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+
+    #[msg("Post-execution hook failed")]
+    PostHookFailed,
+
+    #[msg("Display decimals cannot exceed the mint's decimals")]
+    InvalidDisplayDecimals,
+
+    #[msg("Destination is not whitelisted")]
+    DestinationNotWhitelisted,
+
+    #[msg("Whitelist is full")]
+    WhitelistFull,
+
+    #[msg("Destination whitelist account is required")]
+    WhitelistRequired,
+
+    #[msg("Cannot buy an agent from your own wallet")]
+    CannotBuyOwnAgent,
+
+    #[msg("Listing price exceeds the buyer's maximum")]
+    ListingPriceTooHigh,
+
+    #[msg("Agent has no reporting key registered")]
+    ReportingKeyNotSet,
+
+    #[msg("Encrypted payload too long")]
+    EncryptedPayloadTooLong,
+
+    #[msg("Execution buffer too large")]
+    ExecutionBufferTooLarge,
+
+    #[msg("Chunk does not continue the execution buffer")]
+    InvalidExecutionBufferWrite,
+
+    #[msg("Execution buffer is not fully written")]
+    ExecutionBufferIncomplete,
+
+    #[msg("Execution buffer does not match the requested execution")]
+    ExecutionBufferMismatch,
+
+    #[msg("Emergency pause is active")]
+    EmergencyPaused,
+
+    #[msg("Too many emergency contacts")]
+    TooManyEmergencyContacts,
+
+    #[msg("spend_on_behalf can only be called via CPI")]
+    SpendRequiresCpi,
+
+    #[msg("Amount exceeds the spender's remaining allowance")]
+    ExceedsSpendAllowance,
+
+    #[msg("Spend approval has expired")]
+    SpendApprovalExpired,
+
+    #[msg("Token transfers require the token limit, token accounts and token program")]
+    TokenAccountsRequired,
+
+    #[msg("Token accounts do not match the transfer")]
+    InvalidTokenTransfer,
+
+    #[msg("Activity records must be passed in sequence order")]
+    ActivityChainBroken,
+
+    #[msg("Transaction exceeds the approval threshold and needs an approved proposal")]
+    ApprovalRequired,
+
+    #[msg("Proposal is not pending")]
+    ProposalNotPending,
+
+    #[msg("Proposal has not been approved")]
+    ProposalNotApproved,
+
+    #[msg("Transaction does not match the approved proposal")]
+    ProposalMismatch,
+
+    #[msg("Sponsored activity records can only be pruned with the rent pool")]
+    RentPoolRequired,
+
+    #[msg("Target program cannot be invoked by an agent")]
+    InvalidProgram,
+
+    #[msg("Program is not whitelisted for this agent")]
+    ProgramNotWhitelisted,
+
+    #[msg("Exceeds the limits of the agent's trial period")]
+    TrialLimitExceeded,
+
+    #[msg("Signer is not the main wallet, agent signer or an emergency contact")]
+    SignerHasNoRole,
+
+    #[msg("Signer's role cannot execute for this agent")]
+    ExecuteNotAllowed,
+
+    #[msg("Signer's role cannot take emergency actions")]
+    EmergencyActionNotAllowed,
+
+    #[msg("Agent is in a blackout window")]
+    AgentBlackedOut,
+
+    #[msg("Blackout window must end after it starts and after now")]
+    InvalidBlackoutWindow,
+
+    #[msg("Too many blackout windows")]
+    TooManyBlackouts,
+
+    #[msg("Exceeds daily donation allowance")]
+    ExceedsDonationAllowance,
+
+    #[msg("Agent count exceeds the quota earned by the wallet's stake")]
+    AgentQuotaExceeded,
+
+    #[msg("Aggregate daily limit exceeds the quota earned by the wallet's stake")]
+    LimitQuotaExceeded,
+
+    #[msg("Insufficient stake")]
+    InsufficientStake,
+
+    #[msg("Account extension data is malformed")]
+    InvalidExtensionData,
+
+    #[msg("No reserved space left for the extension")]
+    ExtensionSpaceExhausted,
+
+    #[msg("Transfer is below the agent's minimum transfer amount")]
+    BelowMinTransferAmount,
+
+    #[msg("Agent is not revoked")]
+    AgentNotRevoked,
+
+    #[msg("Agent is too new for a spend this large")]
+    ColdStartRestricted,
+
+    #[msg("Invalid cold-start policy")]
+    InvalidColdStartPolicy,
+
+    #[msg("Agent must be revoked or drained before it can be closed")]
+    AgentNotClosable,
+
+    #[msg("Agent is listed for sale")]
+    AgentListed,
+
+    #[msg("Bounty is not open for claims")]
+    BountyNotOpen,
+
+    #[msg("Bounty has no claim to settle")]
+    BountyNotClaimed,
+
+    #[msg("No activity records to merkleize")]
+    EmptyActivityBatch,
+
+    #[msg("Source whitelist has a different type")]
+    WhitelistTypeMismatch,
+
+    #[msg("Activity record is under dispute")]
+    ActivityDisputed,
+
+    #[msg("Activity record is still within the retention period")]
+    ActivityNotExpired,
+
+    #[msg("Activity tree is full")]
+    ActivityTreeFull,
+
+    #[msg("Soft limit crossed; the main wallet must acknowledge before further spends")]
+    SoftLimitNotAcknowledged,
+
+    #[msg("Batch is empty or has too many legs")]
+    InvalidBatchSize,
+
+    #[msg("Batch legs cannot carry token transfers or program calls")]
+    InvalidBatchLeg,
+
+    #[msg("Agents with policy hooks must execute one transaction at a time")]
+    HooksRequireSingleExecution,
+
+    #[msg("Destination account missing from remaining accounts")]
+    DestinationAccountMissing,
+
+    #[msg("Number of amounts does not match the agents passed")]
+    FundingBatchMismatch,
+
+    #[msg("Execution lease is held by another runtime")]
+    ExecutionLeaseHeld,
+
+    #[msg("Execution requires the agent's live execution lease")]
+    ExecutionLeaseRequired,
+
+    #[msg("Invalid execution lease duration")]
+    InvalidLeaseDuration,
+
+    #[msg("Address book is full")]
+    AddressBookFull,
+
+    #[msg("Amount exceeds the destination's risk tier per-transaction limit")]
+    ExceedsTierTransactionLimit,
+
+    #[msg("Amount exceeds the destination's risk tier daily limit")]
+    ExceedsTierDailyLimit,
+
+    #[msg("Amount exceeds the per-transaction limit towards this destination")]
+    ExceedsDestinationTransactionLimit,
+
+    #[msg("Amount exceeds the daily limit towards this destination")]
+    ExceedsDestinationDailyLimit,
+
+    #[msg("Destination limit account missing from remaining accounts")]
+    DestinationLimitAccountMissing,
 
+    #[msg("Blacklist is full")]
+    BlacklistFull,
+
+    #[msg("Destination is blacklisted")]
+    DestinationBlacklisted,
+
+    #[msg("Program registry is full")]
+    ProgramRegistryFull,
+
+    #[msg("Program is already registered under another id")]
+    ProgramAlreadyRegistered,
+
+    #[msg("Program is not in the registry")]
+    ProgramNotRegistered,
+
+    #[msg("Wallet policy only allows programs labeled in the registry")]
+    UnlabeledProgram,
+
+    #[msg("Plan is empty or has too many steps")]
+    InvalidPlanSize,
+
+    #[msg("Plan steps must be a SOL transfer or a program call")]
+    InvalidPlanStep,
+
+    #[msg("Plan has no steps left to execute")]
+    PlanNotActive,
+
+    #[msg("Accounts do not match the plan step")]
+    PlanStepMismatch,
+
+    #[msg("Program call moved more lamports out of the agent than the step allows")]
+    PlanStepOverspent,
+
+    #[msg("Agent has too many scoped permissions")]
+    TooManyScopes,
+
+    #[msg("Program is outside the agent's scoped permissions")]
+    ProgramOutOfScope,
+
+    #[msg("Amount exceeds the scoped permission's cap")]
+    ExceedsScopedAmount,
+
+    #[msg("Exceeds the agent's fee tank cap")]
+    ExceedsFeeTankCap,
+
+    #[msg("Configuration does not match the expected hash")]
+    ConfigHashMismatch,
+
+    #[msg("Configuration has blackout windows but no schedule account was provided")]
+    ScheduleRequired,
+
+    #[msg("Permission expiry must be in the future")]
+    InvalidPermissionExpiry,
+
+    #[msg("Agent has USD limits but no price feed was provided")]
+    PriceFeedRequired,
+
+    #[msg("Price feed is not the protocol's SOL/USD feed or is malformed")]
+    InvalidPriceFeed,
+
+    #[msg("Oracle price is stale or not trading")]
+    StalePrice,
+
+    #[msg("Oracle price confidence interval is too wide")]
+    PriceConfidenceTooWide,
+
+    #[msg("Transaction exceeds the per-transaction USD limit")]
+    ExceedsUsdTransactionLimit,
+
+    #[msg("Transaction exceeds the daily USD limit")]
+    ExceedsUsdDailyLimit,
+
+    #[msg("Slippage must be at most 10000 basis points")]
+    InvalidSlippage,
+
+    #[msg("Token mint is not in the agent's token whitelist")]
+    TokenNotWhitelisted,
+
+    #[msg("Swap route is not a supported Jupiter route instruction")]
+    InvalidSwapRoute,
+
+    #[msg("Swap slippage exceeds the agent's maximum")]
+    ExceedsMaxSlippage,
+
+    #[msg("Swap spent more or returned less than its route allowed")]
+    SwapOutputTooLow,
+
+    #[msg("Validator is not in the agent's validator whitelist")]
+    ValidatorNotWhitelisted,
+
+    #[msg("Stake pool deposit returned fewer pool tokens than the minimum")]
+    InsufficientPoolTokens,
+
+    #[msg("Realm is not in the agent's realm whitelist")]
+    RealmNotWhitelisted,
+
+    #[msg("Agent has reached its maximum number of open token accounts")]
+    TooManyTokenAccounts,
+
+    #[msg("Snapshot is not this agent's snapshot for the current day")]
+    SnapshotMismatch,
+
+    #[msg("Agent description is too long")]
+    DescriptionTooLong,
+
+    #[msg("Metadata URI is too long")]
+    MetadataUriTooLong,
+
+    #[msg("Model ID is too long")]
+    ModelIdTooLong,
+
+    #[msg("Account can only grow, by at most 10 KiB per resize")]
+    InvalidAccountSize,
+
+    #[msg("Account type cannot be migrated")]
+    UnsupportedAccountType,
+
+    #[msg("Account is already on the current layout")]
+    AccountAlreadyMigrated,
+
+    #[msg("Protocol config is required")]
+    ProtocolConfigRequired,
+
+    #[msg("Allowance period must be positive")]
+    InvalidAllowancePeriod,
+
+    #[msg("Allowance period has not elapsed")]
+    AllowanceNotDue,
+
+    #[msg("Stream must start no later than its cliff and end after it starts")]
+    InvalidStreamSchedule,
+
+    #[msg("Nothing has vested since the last withdrawal")]
+    NothingVested,
+
+    #[msg("Agent escrow is required while escrow funding is on")]
+    EscrowRequired,
+
+    #[msg("Turn escrow funding off first")]
+    EscrowFundingEnabled,
+
+    #[msg("Inactivity timeout must not be negative")]
+    InvalidInactivityPolicy,
+
+    #[msg("Agent has no inactivity timeout or has not been inactive long enough")]
+    InactivityNotEnforceable,
+
+    #[msg("Amount bands must ascend, each needing more co-signers than the last")]
+    InvalidAmountBands,
+
+    #[msg("Too many co-signers")]
+    TooManyCoSigners,
+
+    #[msg("Co-signer listed twice")]
+    DuplicateCoSigner,
+
+    #[msg("Signer is not one of the agent's co-signers")]
+    NotCoSigner,
+
+    #[msg("Transaction needs more co-signer approvals")]
+    CoSignersRequired,
+
+    #[msg("Session must expire in the future and within 7 days")]
+    InvalidSessionExpiry,
+
+    #[msg("Session must allow between one and every activity type")]
+    InvalidSessionActivityTypes,
+
+    #[msg("Session key has expired")]
+    SessionExpired,
+
+    #[msg("Session key may not execute this activity type")]
+    SessionActivityNotAllowed,
+
+    #[msg("Session key spend budget exceeded")]
+    SessionBudgetExceeded,
+
+    #[msg("Intent is not signed by the agent signer in a preceding Ed25519 instruction")]
+    InvalidIntentSignature,
+
+    #[msg("Intent has expired")]
+    IntentExpired,
+
+    #[msg("Intent nonce is not the agent's next intent nonce")]
+    InvalidIntentNonce,
+
+    #[msg("Execution nonce does not match the agent's nonce")]
+    NonceMismatch,
+
+    #[msg("Too many relayers")]
+    TooManyRelayers,
+
+    #[msg("Relayer listed more than once")]
+    DuplicateRelayer,
+
+    #[msg("Relayer reimbursement exceeds the protocol maximum")]
+    ReimbursementTooHigh,
+
+    #[msg("Fee payer is not an allowed relayer")]
+    RelayerNotAllowed,
+
+    #[msg("Successful activities cannot carry an error code or failure category")]
+    InvalidFailureRecord,
+
+    #[msg("Memo too long")]
+    MemoTooLong,
+
+    #[msg("Active hours must be within a day and allow at least one weekday")]
+    InvalidActiveHours,
+
+    #[msg("Agent is outside its active hours")]
+    OutsideActiveHours,
+
+    #[msg("Exceeds hourly spending limit")]
+    ExceedsHourlyLimit,
+
+    #[msg("Exceeds the wallet's daily budget across all agents")]
+    ExceedsWalletBudget,
+
+    #[msg("Tier ceilings must not decrease with trust level")]
+    InvalidTierConfig,
+
+    #[msg("Limits exceed the agent's trust tier ceiling")]
+    ExceedsTierCeiling,
+
+    #[msg("Agent has not earned promotion to the next trust tier")]
+    PromotionNotEarned,
+
+    #[msg("Change delay out of range")]
+    InvalidChangeDelay,
+
+    #[msg("Loosening permissions or limits must wait out the change delay")]
+    ChangeDelayActive,
+
+    #[msg("No changes are staged")]
+    NoPendingChanges,
+
+    #[msg("Staged changes are not yet effective")]
+    ChangesNotYetEffective,
+
+    #[msg("Agent configuration is locked against increases")]
+    ConfigLocked,
+
+    #[msg("Limits exceed the protocol cap")]
+    ExceedsProtocolCap,
+
+    #[msg("Treasury account required to change fees")]
+    TreasuryRequired,
+
+    #[msg("Merkle tree is not a Bubblegum tree or is already in use")]
+    InvalidMerkleTree,
+
+    #[msg("Too many merkle trees")]
+    TooManyMerkleTrees,
+
+    #[msg("Agent's cNFT has been burned")]
+    AgentCnftBurned,
+
+    #[msg("Destination account does not match the destination in the parameters")]
+    DestinationMismatch,
+
+    #[msg("Loosening the withdrawal policy must be staged with set_withdrawal_policy")]
+    WithdrawalPolicyLoosened,
+
+    #[msg("The agent still has open token accounts")]
+    TokenAccountsOpen,
+}
@@ -0,0 +1,460 @@
+use anchor_lang::prelude::*;
+use crate::state::{ActivityType, AgentConfig, AnomalyKind, GovernanceVote, InactivityAction};
+
+/// Render a base-unit amount as a decimal string, truncated to
+/// `display_decimals` places (e.g. 1_500_000_000 with 9/4 -> "1.5000")
+pub fn ui_amount(amount: u64, decimals: u8, display_decimals: u8) -> String {
+    let scale = match 10u128.checked_pow(decimals as u32) {
+        Some(scale) => scale,
+        None => return amount.to_string(),
+    };
+    let amount = amount as u128;
+    let whole = amount / scale;
+    let display_decimals = display_decimals.min(decimals);
+
+    if display_decimals == 0 {
+        return whole.to_string();
+    }
+
+    let fraction = (amount % scale) / 10u128.pow((decimals - display_decimals) as u32);
+    format!("{}.{:0width$}", whole, fraction, width = display_decimals as usize)
+}
+
+/// Emitted on every agent execution for the wallet's watchtower
+#[event]
+pub struct WatchtowerAlert {
+    pub watchtower: Pubkey,
+    pub main_wallet: Pubkey,
+    pub agent: Pubkey,
+    pub receipt_sequence: u64,
+    pub activity_type: ActivityType,
+    pub amount: u64,
+    pub decimals: u8,
+    pub ui_amount: String,
+    pub destination: Option<Pubkey>,
+    pub spent_today: u64,
+    pub heartbeat_overdue: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when a watchtower heartbeat is received
+#[event]
+pub struct HeartbeatReceived {
+    pub watchtower: Pubkey,
+    pub main_wallet: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a warn-only post-execution hook does not acknowledge
+#[event]
+pub struct PostHookWarning {
+    pub agent: Pubkey,
+    pub hook_program: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a watchtower freezes an agent
+#[event]
+pub struct AgentFrozenByWatchtower {
+    pub watchtower: Pubkey,
+    pub agent: Pubkey,
+    pub frozen_until: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted by `verify_invariants` with the outcome of each check
+#[event]
+pub struct InvariantReport {
+    pub agent: Pubkey,
+    pub first_sequence: u64,
+    pub records_checked: u64,
+    /// Records chain up to the agent's `activity_hash`
+    pub head_matches: bool,
+    /// Replayed counters match live state; `None` without the full history
+    pub spent_matches: Option<bool>,
+    pub transactions_match: Option<bool>,
+    pub timestamp: i64,
+}
+
+/// Emitted when a revoked agent is reactivated
+#[event]
+pub struct AgentReactivated {
+    pub agent: Pubkey,
+    pub main_wallet: Pubkey,
+    pub revoked_at: i64,
+    /// Seconds the agent spent revoked
+    pub revoked_for: i64,
+    pub counters_reset: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when the main wallet freezes an agent
+#[event]
+pub struct AgentFrozen {
+    pub agent: Pubkey,
+    pub frozen_until: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the main wallet lifts an agent's freeze
+#[event]
+pub struct AgentUnfrozen {
+    pub agent: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a bounty is escrowed from the treasury
+#[event]
+pub struct BountyCreated {
+    pub authority: Pubkey,
+    pub bounty_id: u64,
+    pub reward: u64,
+    pub scope_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Emitted when a researcher claims a bounty
+#[event]
+pub struct BountyClaimed {
+    pub authority: Pubkey,
+    pub bounty_id: u64,
+    pub claimant: Pubkey,
+    pub report_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Emitted when the authority pays out a bounty
+#[event]
+pub struct BountyPaid {
+    pub authority: Pubkey,
+    pub bounty_id: u64,
+    pub claimant: Pubkey,
+    pub reward: u64,
+    pub report_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Emitted when a batch of activity records is merkleized
+#[event]
+pub struct ActivityMerkleized {
+    pub agent: Pubkey,
+    pub first_sequence: u64,
+    pub count: u64,
+    pub root: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Emitted for every execution. `sequence` increases by one per execution
+/// of the agent, so consumers can spot a gap and reconcile against
+/// `AgentAccount.receipt_sequence`.
+#[event]
+pub struct ExecutionReceipt {
+    pub agent: Pubkey,
+    pub sequence: u64,
+    pub activity_type: ActivityType,
+    pub amount: u64,
+    pub mint: Option<Pubkey>,
+    pub destination: Option<Pubkey>,
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an activity record is appended to the wallet's activity tree
+#[event]
+pub struct CompressedActivityRecorded {
+    pub agent: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub leaf_index: u32,
+    pub sequence: u64,
+    pub leaf: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Emitted when a spend takes an agent past its soft daily limit
+#[event]
+pub struct SoftLimitExceeded {
+    pub agent: Pubkey,
+    pub soft_daily_limit: u64,
+    pub spent_today: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a runtime acquires or renews an agent's execution lease
+#[event]
+pub struct ExecutionLeaseAcquired {
+    pub agent: Pubkey,
+    pub holder: Pubkey,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an execution lease is released before it lapses
+#[event]
+pub struct ExecutionLeaseReleased {
+    pub agent: Pubkey,
+    pub holder: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted for every executed step of an execution plan
+#[event]
+pub struct PlanStepExecuted {
+    pub agent: Pubkey,
+    pub plan_id: u64,
+    pub step: u8,
+    pub amount: u64,
+    pub checkpoint_balance: u64,
+    pub completed: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when a plan is closed, aborting any steps not yet executed
+#[event]
+pub struct PlanAborted {
+    pub agent: Pubkey,
+    pub plan_id: u64,
+    pub steps_executed: u8,
+    pub executed_notional: u64,
+    pub completed: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted by `export_agent_config`; `config_hash` is what
+/// `import_agent_config` checks the configuration against
+#[event]
+pub struct AgentConfigExported {
+    pub agent: Pubkey,
+    pub config_hash: [u8; 32],
+    pub config: AgentConfig,
+    pub timestamp: i64,
+}
+
+/// Emitted when a configuration is applied to an agent
+#[event]
+pub struct AgentConfigImported {
+    pub agent: Pubkey,
+    pub config_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Emitted when lapsed timed grants are revoked
+#[event]
+pub struct PermissionsExpired {
+    pub agent: Pubkey,
+    /// Bitmask of the permissions revoked
+    pub permissions: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an agent swaps through Jupiter
+#[event]
+pub struct SwapExecuted {
+    pub agent: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    /// Input base units actually spent
+    pub in_amount: u64,
+    /// Output base units actually received
+    pub out_amount: u64,
+    pub slippage_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Emitted when an agent delegates SOL to a validator
+#[event]
+pub struct StakeDelegated {
+    pub agent: Pubkey,
+    pub stake_account: Pubkey,
+    pub vote_account: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an agent's stake account is deactivated
+#[event]
+pub struct StakeDeactivated {
+    pub agent: Pubkey,
+    pub stake_account: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when lamports are withdrawn from a stake account back into the agent
+#[event]
+pub struct StakeWithdrawn {
+    pub agent: Pubkey,
+    pub stake_account: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an agent deposits SOL into a stake pool
+#[event]
+pub struct StakePoolDeposited {
+    pub agent: Pubkey,
+    pub stake_pool: Pubkey,
+    pub pool_mint: Pubkey,
+    /// Lamports deposited
+    pub amount: u64,
+    /// Liquid staking tokens received, in the pool mint's base units
+    pub pool_tokens_received: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an agent votes on a governance proposal
+#[event]
+pub struct GovernanceVoteCast {
+    pub agent: Pubkey,
+    pub realm: Pubkey,
+    pub proposal: Pubkey,
+    pub vote: GovernanceVote,
+    pub timestamp: i64,
+}
+
+/// Emitted when an agent's allowance is paid out of escrow
+#[event]
+pub struct AllowanceClaimed {
+    pub agent: Pubkey,
+    pub amount: u64,
+    /// Lamports left in escrow
+    pub remaining: u64,
+    pub next_claim_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when vested funds move from a funding stream into the agent
+#[event]
+pub struct StreamWithdrawn {
+    pub agent: Pubkey,
+    pub amount: u64,
+    /// Lamports withdrawn over the stream's life
+    pub withdrawn: u64,
+    /// Lamports still to vest or withdraw
+    pub remaining: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an agent asks its main wallet for SOL
+#[event]
+pub struct FundingRequested {
+    pub agent: Pubkey,
+    pub request_id: u64,
+    pub amount: u64,
+    pub reason: String,
+    pub timestamp: i64,
+}
+
+/// Emitted when the main wallet approves or denies a funding request
+#[event]
+pub struct FundingRequestResolved {
+    pub agent: Pubkey,
+    pub request_id: u64,
+    pub amount: u64,
+    pub approved: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when a timelocked withdrawal starts its delay, so monitors can
+/// cancel it in time
+#[event]
+pub struct WithdrawalInitiated {
+    pub agent: Pubkey,
+    pub main_wallet: Pubkey,
+    pub amount: u64,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a timelocked withdrawal is paid out
+#[event]
+pub struct WithdrawalFinalized {
+    pub agent: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a timelocked withdrawal is cancelled during its delay
+#[event]
+pub struct WithdrawalCancelled {
+    pub agent: Pubkey,
+    pub amount: u64,
+    pub cancelled_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a keeper revokes or freezes an inactive agent
+#[event]
+pub struct InactivityEnforced {
+    pub agent: Pubkey,
+    pub action: InactivityAction,
+    pub last_used_at: i64,
+    pub keeper: Pubkey,
+    /// Lamports paid to the keeper
+    pub reward: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an agent moves up a trust tier
+#[event]
+pub struct AgentPromoted {
+    pub agent: Pubkey,
+    pub trust_level: u8,
+    /// Whether the agent earned it rather than the main wallet granting it
+    pub automatic: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when an execution trips an anomaly rule and freezes the agent
+#[event]
+pub struct AnomalyDetected {
+    pub agent: Pubkey,
+    pub kind: AnomalyKind,
+    /// Lamports the blocked execution would have moved
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an agent's permissions and limits are locked against increases
+#[event]
+pub struct AgentConfigLocked {
+    pub agent: Pubkey,
+    pub permissions: u64,
+    pub max_sol_per_transaction: u64,
+    pub daily_limit: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when `update_protocol_config` pauses or unpauses the protocol
+#[event]
+pub struct ProtocolPauseUpdated {
+    pub authority: Pubkey,
+    pub paused: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when the protocol fee parameters change
+#[event]
+pub struct ProtocolFeeUpdated {
+    pub authority: Pubkey,
+    pub fee_bps: u16,
+    pub min_fee: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when new agents start minting into a different merkle tree
+#[event]
+pub struct MerkleTreeRotated {
+    pub authority: Pubkey,
+    pub previous_tree: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the caps on agent limits change
+#[event]
+pub struct LimitCapsUpdated {
+    pub authority: Pubkey,
+    pub max_agent_daily_limit: u64,
+    pub max_agent_tx_limit: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{get_return_data, invoke};
+use crate::errors::DimmError;
+use crate::events::PostHookWarning;
+use crate::state::ActivityType;
+
+/// Anchor discriminator of a hook program's `approve_action` instruction
+/// (`sha256("global:approve_action")[..8]`)
+pub const APPROVE_ACTION_DISCRIMINATOR: [u8; 8] = [200, 117, 44, 13, 133, 139, 131, 36];
+
+/// Anchor discriminator of a hook program's `after_execution` instruction
+/// (`sha256("global:after_execution")[..8]`)
+pub const AFTER_EXECUTION_DISCRIMINATOR: [u8; 8] = [241, 145, 123, 95, 4, 229, 170, 78];
+
+/// Return data a hook must set to approve the action or acknowledge the summary
+pub const HOOK_APPROVED: u8 = 1;
+
+/// Proposed action passed to policy hook programs
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HookAction {
+    pub agent: Pubkey,
+    pub main_wallet: Pubkey,
+    pub activity_type: ActivityType,
+    pub amount: u64,
+    pub destination: Option<Pubkey>,
+    pub spent_today: u64,
+    pub timestamp: i64,
+}
+
+/// Execution summary passed to post-execution hook programs
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExecutionSummary {
+    pub agent: Pubkey,
+    pub main_wallet: Pubkey,
+    pub activity_type: ActivityType,
+    pub amount: u64,
+    pub destination: Option<Pubkey>,
+    pub spent_today: u64,
+    pub total_spent: u64,
+    pub total_transactions: u64,
+    pub timestamp: i64,
+}
+
+/// Find a hook program among the instruction's remaining accounts
+pub fn find_hook_program<'info>(
+    remaining_accounts: &[AccountInfo<'info>],
+    program_id: &Pubkey,
+) -> Result<AccountInfo<'info>> {
+    remaining_accounts
+        .iter()
+        .find(|info| info.key == program_id && info.executable)
+        .cloned()
+        .ok_or_else(|| error!(DimmError::HookProgramMissing))
+}
+
+/// CPI into a policy hook and require it to approve the action.
+/// The hook receives `[agent_account, destination]`, both read-only.
+pub fn invoke_approval_hook<'info>(
+    hook_program: &AccountInfo<'info>,
+    agent_account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    action: &HookAction,
+) -> Result<()> {
+    let mut data = APPROVE_ACTION_DISCRIMINATOR.to_vec();
+    action.serialize(&mut data)?;
+
+    if !invoke_hook(hook_program, agent_account, destination, data)? {
+        msg!("Action rejected by hook {}", hook_program.key);
+        return err!(DimmError::HookRejected);
+    }
+
+    Ok(())
+}
+
+/// CPI into a post-execution hook with the execution summary.
+/// A hook that does not acknowledge fails the transaction only when `fatal`;
+/// otherwise a `PostHookWarning` is emitted. Errors raised inside the hook
+/// program itself always abort the transaction, as with any CPI.
+pub fn invoke_post_execution_hook<'info>(
+    hook_program: &AccountInfo<'info>,
+    agent_account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    summary: &ExecutionSummary,
+    fatal: bool,
+) -> Result<()> {
+    let mut data = AFTER_EXECUTION_DISCRIMINATOR.to_vec();
+    summary.serialize(&mut data)?;
+
+    if invoke_hook(hook_program, agent_account, destination, data)? {
+        return Ok(());
+    }
+
+    if fatal {
+        msg!("Post-execution hook {} failed", hook_program.key);
+        return err!(DimmError::PostHookFailed);
+    }
+
+    emit!(PostHookWarning {
+        agent: summary.agent,
+        hook_program: *hook_program.key,
+        timestamp: summary.timestamp,
+    });
+
+    Ok(())
+}
+
+/// Invoke a hook and report whether it returned `HOOK_APPROVED`
+fn invoke_hook<'info>(
+    hook_program: &AccountInfo<'info>,
+    agent_account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    data: Vec<u8>,
+) -> Result<bool> {
+    let instruction = Instruction {
+        program_id: *hook_program.key,
+        accounts: vec![
+            AccountMeta::new_readonly(*agent_account.key, false),
+            AccountMeta::new_readonly(*destination.key, false),
+        ],
+        data,
+    };
+
+    invoke(
+        &instruction,
+        &[agent_account.clone(), destination.clone(), hook_program.clone()],
+    )?;
+
+    Ok(matches!(
+        get_return_data(),
+        Some((program_id, data))
+            if program_id == *hook_program.key && data.first() == Some(&HOOK_APPROVED)
+    ))
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::events::PlanAborted;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct AbortPlan<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        seeds = [PLAN_SEED, agent_account.key().as_ref(), &plan.plan_id.to_le_bytes()],
+        bump = plan.bump,
+        has_one = creator,
+        close = creator
+    )]
+    pub plan: Box<Account<'info, Plan>>,
+
+    /// CHECK: Receives the plan's rent; checked against `plan.creator`
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// Plan creator or the main wallet
+    pub authority: Signer<'info>,
+}
+
+/// Stop a plan before its remaining steps run and close it. Executed steps
+/// stay executed, and the notional charged by the first step is not
+/// returned to the daily limit.
+pub fn handler(ctx: Context<AbortPlan>) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let plan = &ctx.accounts.plan;
+
+    require!(
+        authority == plan.creator || authority == ctx.accounts.agent_account.main_wallet,
+        DimmError::Unauthorized
+    );
+
+    emit!(PlanAborted {
+        agent: plan.agent,
+        plan_id: plan.plan_id,
+        steps_executed: plan.next_step,
+        executed_notional: plan.executed_notional,
+        completed: plan.status == PlanStatus::Completed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Plan closed");
+    msg!("Agent: {}", plan.agent);
+    msg!("Plan ID: {}", plan.plan_id);
+    msg!("Steps executed: {}/{}", plan.next_step, plan.steps.len());
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct AcknowledgeSoftLimit<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcknowledgeSoftLimit>) -> Result<()> {
+    let agent_account = &mut ctx.accounts.agent_account;
+
+    agent_account.soft_limit_pending_ack = false;
+
+    msg!("Soft limit acknowledged");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Spent today: {} lamports", agent_account.spent_today);
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct AddBlackoutWindow<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Created on first use
+    #[account(
+        init_if_needed,
+        payer = main_wallet,
+        space = ActivitySchedule::LEN,
+        seeds = [SCHEDULE_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub schedule: Account<'info, ActivitySchedule>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddBlackoutWindow>, window: BlackoutWindow) -> Result<()> {
+    let agent_key = ctx.accounts.agent_account.key();
+    let schedule = &mut ctx.accounts.schedule;
+    let clock = Clock::get()?;
+
+    schedule.agent = agent_key;
+    schedule.bump = ctx.bumps.schedule;
+    schedule.add_blackout(window, clock.unix_timestamp)?;
+    schedule.last_updated = clock.unix_timestamp;
+
+    msg!("Blackout window added");
+    msg!("Agent: {}", agent_key);
+    msg!("Start: {}", window.start);
+    msg!("End: {}", window.end);
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::cnft;
+use crate::events::MerkleTreeRotated;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct AddMerkleTree<'info> {
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED, authority.key().as_ref()],
+        bump = protocol_config.bump,
+        has_one = authority
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Tree to queue; checked to be a Bubblegum tree
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum tree config of `merkle_tree`; checked in the handler
+    pub tree_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum tree config of the tree agents are minted into now
+    #[account(
+        seeds = [protocol_config.merkle_tree.as_ref()],
+        bump,
+        seeds::program = mpl_bubblegum::ID
+    )]
+    pub current_tree_authority: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Queue a fresh tree for new agents. `create_agent` rolls onto the next
+/// queued tree when it fills the current one; if the current tree is
+/// already full, new agents move onto the queue straight away.
+pub fn handler(ctx: Context<AddMerkleTree>) -> Result<()> {
+    cnft::check_tree(&ctx.accounts.merkle_tree, &ctx.accounts.tree_authority)?;
+
+    let protocol_config = &mut ctx.accounts.protocol_config;
+    protocol_config.add_merkle_tree(ctx.accounts.merkle_tree.key())?;
+
+    let previous_tree = protocol_config.merkle_tree;
+    if cnft::tree_is_full(&ctx.accounts.current_tree_authority)?
+        && protocol_config.advance_merkle_tree()
+    {
+        emit!(MerkleTreeRotated {
+            authority: protocol_config.authority,
+            previous_tree,
+            merkle_tree: protocol_config.merkle_tree,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
+    msg!("Merkle tree added");
+    msg!("Authority: {}", protocol_config.authority);
+    msg!("Merkle Tree: {}", ctx.accounts.merkle_tree.key());
+    msg!("Current Merkle Tree: {}", protocol_config.merkle_tree);
+    msg!("Trees: {}", protocol_config.merkle_trees.len());
+
+    Ok(())
+}
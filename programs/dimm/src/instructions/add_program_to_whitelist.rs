@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct AddProgramToWhitelist<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Created on first use
+    #[account(
+        init_if_needed,
+        payer = main_wallet,
+        space = Whitelist::LEN,
+        seeds = [
+            WHITELIST_SEED,
+            agent_account.key().as_ref(),
+            &WhitelistType::Programs.seed()
+        ],
+        bump
+    )]
+    pub program_whitelist: Account<'info, Whitelist>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddProgramToWhitelist>, program: Pubkey) -> Result<()> {
+    require_keys_neq!(program, crate::ID, DimmError::InvalidProgram);
+
+    let agent_key = ctx.accounts.agent_account.key();
+    let program_whitelist = &mut ctx.accounts.program_whitelist;
+    let clock = Clock::get()?;
+
+    if program_whitelist.owner == Pubkey::default() {
+        program_whitelist.owner = agent_key;
+        program_whitelist.addresses = Vec::new();
+        program_whitelist.enabled = true;
+        program_whitelist.whitelist_type = WhitelistType::Programs;
+        program_whitelist.bump = ctx.bumps.program_whitelist;
+    }
+
+    program_whitelist.add_address(program)?;
+    program_whitelist.last_updated = clock.unix_timestamp;
+
+    msg!("Program added to whitelist");
+    msg!("Agent: {}", agent_key);
+    msg!("Program: {}", program);
+
+    Ok(())
+}
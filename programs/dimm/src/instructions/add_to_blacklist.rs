@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct AddToBlacklist<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Created on first use
+    #[account(
+        init_if_needed,
+        payer = main_wallet,
+        space = Blacklist::LEN,
+        seeds = [BLACKLIST_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub blacklist: Account<'info, Blacklist>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddToBlacklist>, address: Pubkey) -> Result<()> {
+    let agent_key = ctx.accounts.agent_account.key();
+    let blacklist = &mut ctx.accounts.blacklist;
+    let clock = Clock::get()?;
+
+    if blacklist.agent == Pubkey::default() {
+        blacklist.agent = agent_key;
+        blacklist.addresses = Vec::new();
+        blacklist.bump = ctx.bumps.blacklist;
+    }
+
+    blacklist.add_address(address)?;
+    blacklist.last_updated = clock.unix_timestamp;
+
+    msg!("Address added to blacklist");
+    msg!("Agent: {}", agent_key);
+    msg!("Address: {}", address);
+
+    Ok(())
+}
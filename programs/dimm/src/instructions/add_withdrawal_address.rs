@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct AddWithdrawalAddress<'info> {
+    #[account(
+        mut,
+        seeds = [WITHDRAWAL_ADDRESSES_SEED, main_wallet.key().as_ref()],
+        bump = withdrawal_addresses.bump,
+        has_one = main_wallet
+    )]
+    pub withdrawal_addresses: Account<'info, WithdrawalAddressBook>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AddWithdrawalAddress>, address: Pubkey, label: String) -> Result<()> {
+    require!(
+        label.len() <= WithdrawalAddressBook::MAX_LABEL_LENGTH,
+        DimmError::LabelTooLong
+    );
+
+    let withdrawal_addresses = &mut ctx.accounts.withdrawal_addresses;
+    let clock = Clock::get()?;
+
+    require!(
+        !withdrawal_addresses.entries.iter().any(|entry| entry.address == address),
+        DimmError::InvalidWithdrawalPolicy
+    );
+    require!(
+        withdrawal_addresses.entries.len() < WithdrawalAddressBook::MAX_ADDRESSES,
+        DimmError::MaxWithdrawalAddressesReached
+    );
+
+    let active_at = clock
+        .unix_timestamp
+        .checked_add(WITHDRAWAL_ADDRESS_TIMELOCK_SECONDS)
+        .ok_or(DimmError::NumericalOverflow)?;
+
+    withdrawal_addresses.entries.push(WithdrawalAddress {
+        address,
+        label,
+        active_at,
+    });
+    withdrawal_addresses.last_updated = clock.unix_timestamp;
+
+    msg!("Withdrawal address added");
+    msg!("Address: {}", address);
+    msg!("Active at: {}", active_at);
+
+    Ok(())
+}
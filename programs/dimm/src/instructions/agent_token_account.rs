@@ -0,0 +1,212 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{self, AssociatedToken, Create};
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount};
+use crate::auth::{Caller, Capability};
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct CreateAgentTokenAccount<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        seeds = [PROTOCOL_SEED, agent_account.main_wallet.as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Wallet's emergency state; may be uninitialized
+    #[account(
+        seeds = [EMERGENCY_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub emergency_state: UncheckedAccount<'info>,
+
+    /// Main wallet or the agent's own signer key
+    pub authority: Signer<'info>,
+
+    /// CHECK: Agent's system-owned funding PDA; empty before and after
+    #[account(
+        mut,
+        seeds = [SOL_DEPOSIT_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub deposit_funding: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's associated token account for `mint`, created here
+    #[account(
+        mut,
+        address = associated_token::get_associated_token_address(&agent_account.key(), &mint.key())
+    )]
+    pub token_account: UncheckedAccount<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAgentTokenAccount<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        seeds = [PROTOCOL_SEED, agent_account.main_wallet.as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Wallet's emergency state; may be uninitialized
+    #[account(
+        seeds = [EMERGENCY_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub emergency_state: UncheckedAccount<'info>,
+
+    /// Main wallet or the agent's own signer key
+    pub authority: Signer<'info>,
+
+    /// Empty token account owned by the agent
+    #[account(
+        mut,
+        constraint = token_account.owner == agent_account.key() @ DimmError::InvalidTokenTransfer
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Create the agent's associated token account for `mint`, with the rent
+/// paid from the agent's balance
+pub fn create_handler(ctx: Context<CreateAgentTokenAccount>) -> Result<()> {
+    Caller::authorize(
+        ctx.accounts.authority.key(),
+        &ctx.accounts.agent_account.main_wallet,
+        Some(&*ctx.accounts.agent_account),
+        None,
+        Capability::Execute,
+    )?;
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
+    EmergencyState::require_not_paused(&ctx.accounts.emergency_state)?;
+
+    let clock = Clock::get()?;
+    let agent_account = &mut ctx.accounts.agent_account;
+    require!(!agent_account.revoked, DimmError::AgentRevoked);
+    agent_account.expire_permissions(clock.unix_timestamp);
+    require!(
+        agent_account.has_permission(&AgentPermission::TokenAccounts),
+        DimmError::InsufficientPermissions
+    );
+    require!(
+        agent_account.open_token_accounts < MAX_AGENT_TOKEN_ACCOUNTS,
+        DimmError::TooManyTokenAccounts
+    );
+
+    let rent = Rent::get()?.minimum_balance(TokenAccount::LEN);
+    let required_balance = rent
+        .checked_add(MIN_AGENT_BALANCE)
+        .and_then(|v| v.checked_add(agent_account.fee_tank))
+        .ok_or(DimmError::NumericalOverflow)?;
+    require!(
+        agent_account.to_account_info().lamports() >= required_balance,
+        DimmError::InsufficientAgentBalance
+    );
+
+    // The associated token program pays rent with a system transfer, which
+    // the agent account cannot sign for, so it passes through an empty system PDA
+    **agent_account.to_account_info().try_borrow_mut_lamports()? -= rent;
+    **ctx.accounts.deposit_funding.to_account_info().try_borrow_mut_lamports()? += rent;
+
+    let agent_key = agent_account.key();
+    let funding_seeds = &[
+        SOL_DEPOSIT_SEED,
+        agent_key.as_ref(),
+        &[ctx.bumps.deposit_funding],
+    ];
+    associated_token::create(CpiContext::new_with_signer(
+        ctx.accounts.associated_token_program.to_account_info(),
+        Create {
+            payer: ctx.accounts.deposit_funding.to_account_info(),
+            associated_token: ctx.accounts.token_account.to_account_info(),
+            authority: agent_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+        &[&funding_seeds[..]],
+    ))?;
+
+    agent_account.open_token_accounts += 1;
+
+    msg!("Agent token account created");
+    msg!("Agent: {}", agent_key);
+    msg!("Mint: {}", ctx.accounts.mint.key());
+    msg!("Token account: {}", ctx.accounts.token_account.key());
+
+    Ok(())
+}
+
+/// Close an empty token account of the agent, returning its rent to the agent
+pub fn close_handler(ctx: Context<CloseAgentTokenAccount>) -> Result<()> {
+    Caller::authorize(
+        ctx.accounts.authority.key(),
+        &ctx.accounts.agent_account.main_wallet,
+        Some(&*ctx.accounts.agent_account),
+        None,
+        Capability::Execute,
+    )?;
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
+    EmergencyState::require_not_paused(&ctx.accounts.emergency_state)?;
+
+    let clock = Clock::get()?;
+    let agent_account = &mut ctx.accounts.agent_account;
+    agent_account.expire_permissions(clock.unix_timestamp);
+    require!(
+        agent_account.has_permission(&AgentPermission::TokenAccounts),
+        DimmError::InsufficientPermissions
+    );
+
+    let agent_seeds = &[
+        AGENT_SEED,
+        agent_account.main_wallet.as_ref(),
+        &agent_account.agent_id.to_le_bytes(),
+        &[agent_account.bump],
+    ];
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.token_account.to_account_info(),
+            destination: agent_account.to_account_info(),
+            authority: agent_account.to_account_info(),
+        },
+        &[&agent_seeds[..]],
+    ))?;
+
+    agent_account.open_token_accounts = agent_account.open_token_accounts.saturating_sub(1);
+
+    msg!("Agent token account closed");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Token account: {}", ctx.accounts.token_account.key());
+
+    Ok(())
+}
@@ -0,0 +1,190 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::constants::*;
+use crate::errors::DimmError;
+use crate::events::AllowanceClaimed;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetAllowance<'info> {
+    #[account(
+        seeds = [PROTOCOL_SEED, main_wallet.key().as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = main_wallet,
+        space = Allowance::LEN,
+        seeds = [ALLOWANCE_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub allowance: Account<'info, Allowance>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimAllowance<'info> {
+    #[account(
+        seeds = [PROTOCOL_SEED, agent_account.main_wallet.as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        seeds = [ALLOWANCE_SEED, agent_account.key().as_ref()],
+        bump = allowance.bump,
+        constraint = allowance.agent == agent_account.key() @ DimmError::Unauthorized
+    )]
+    pub allowance: Account<'info, Allowance>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAllowance<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        close = main_wallet,
+        seeds = [ALLOWANCE_SEED, agent_account.key().as_ref()],
+        bump = allowance.bump,
+        has_one = main_wallet
+    )]
+    pub allowance: Account<'info, Allowance>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+}
+
+/// Set the agent's allowance terms and escrow `deposit` more lamports for it.
+/// The first payout can be claimed one period from now.
+pub fn set_handler(
+    ctx: Context<SetAllowance>,
+    amount_per_period: u64,
+    period_seconds: i64,
+    deposit: u64,
+) -> Result<()> {
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
+    require!(!ctx.accounts.agent_account.revoked, DimmError::AgentRevoked);
+    require!(amount_per_period > 0, DimmError::InvalidAmount);
+    require!(period_seconds > 0, DimmError::InvalidAllowancePeriod);
+
+    let clock = Clock::get()?;
+    let allowance = &mut ctx.accounts.allowance;
+    allowance.agent = ctx.accounts.agent_account.key();
+    allowance.main_wallet = ctx.accounts.main_wallet.key();
+    allowance.amount_per_period = amount_per_period;
+    allowance.period_seconds = period_seconds;
+    allowance.next_claim_at = clock
+        .unix_timestamp
+        .checked_add(period_seconds)
+        .ok_or(DimmError::NumericalOverflow)?;
+    allowance.bump = ctx.bumps.allowance;
+
+    if deposit > 0 {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.main_wallet.to_account_info(),
+                to: ctx.accounts.allowance.to_account_info(),
+            },
+        );
+        transfer(cpi_context, deposit)?;
+    }
+
+    msg!("Allowance set");
+    msg!("Agent: {}", ctx.accounts.agent_account.key());
+    msg!("Amount per period: {} lamports", amount_per_period);
+    msg!("Period: {} seconds", period_seconds);
+
+    Ok(())
+}
+
+/// Pay the current period's allowance from escrow into the agent.
+/// Permissionless; periods that pass unclaimed lapse.
+pub fn claim_handler(ctx: Context<ClaimAllowance>) -> Result<()> {
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
+    require!(!ctx.accounts.agent_account.revoked, DimmError::AgentRevoked);
+
+    let clock = Clock::get()?;
+    let allowance = &mut ctx.accounts.allowance;
+    require!(allowance.is_claimable(clock.unix_timestamp), DimmError::AllowanceNotDue);
+
+    let allowance_info = allowance.to_account_info();
+    let rent_exempt = Rent::get()?.minimum_balance(allowance_info.data_len());
+    let escrowed = allowance_info.lamports().saturating_sub(rent_exempt);
+    let amount = allowance.amount_per_period;
+    require!(escrowed >= amount, DimmError::InsufficientBalance);
+
+    **allowance_info.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.agent_account.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    allowance.next_claim_at = clock
+        .unix_timestamp
+        .checked_add(allowance.period_seconds)
+        .ok_or(DimmError::NumericalOverflow)?;
+    allowance.total_claimed = allowance
+        .total_claimed
+        .checked_add(amount)
+        .ok_or(DimmError::NumericalOverflow)?;
+
+    emit!(AllowanceClaimed {
+        agent: allowance.agent,
+        amount,
+        remaining: escrowed - amount,
+        next_claim_at: allowance.next_claim_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Allowance claimed");
+    msg!("Agent: {}", allowance.agent);
+    msg!("Amount: {} lamports", amount);
+
+    Ok(())
+}
+
+/// Close the allowance, returning the escrow and rent to the main wallet
+pub fn close_handler(ctx: Context<CloseAllowance>) -> Result<()> {
+    msg!("Allowance closed");
+    msg!("Agent: {}", ctx.accounts.agent_account.key());
+    msg!("Returned: {} lamports", ctx.accounts.allowance.to_account_info().lamports());
+
+    Ok(())
+}
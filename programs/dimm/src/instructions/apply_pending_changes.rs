@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ApplyPendingChanges<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED, main_wallet.key().as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Wallet's protocol stake; may be uninitialized
+    #[account(
+        seeds = [STAKE_SEED, main_wallet.key().as_ref()],
+        bump
+    )]
+    pub wallet_stake: UncheckedAccount<'info>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+/// Apply the permission, limit and delay changes staged by `update_permissions`,
+/// `update_limits`, `set_change_delay` and `set_withdrawal_policy` once the
+/// change delay has passed.
+/// Limits are checked again as they would be in `update_limits`.
+pub fn handler(ctx: Context<ApplyPendingChanges>) -> Result<()> {
+    let clock = Clock::get()?;
+    let agent_account = &mut ctx.accounts.agent_account;
+    require!(agent_account.has_pending_changes(), DimmError::NoPendingChanges);
+    require!(
+        clock.unix_timestamp >= agent_account.pending_effective_at,
+        DimmError::ChangesNotYetEffective
+    );
+
+    if agent_account.pending_permissions != 0 {
+        agent_account.permissions = PermissionSet::from_bits(
+            agent_account.permissions.bits | agent_account.pending_permissions,
+        )?;
+    }
+
+    if let Some(limits) = agent_account.pending_limits.take() {
+        let previous_daily_limit = agent_account.daily_limit;
+        agent_account.apply_limits(&limits)?;
+        ctx.accounts.protocol_config.commit_agent_limits(
+            agent_account,
+            previous_daily_limit,
+            &ctx.accounts.wallet_stake,
+        )?;
+    }
+
+    if let Some(delay_seconds) = agent_account.pending_change_delay {
+        agent_account.change_delay_seconds = delay_seconds;
+    }
+
+    if let Some(policy) = agent_account.pending_withdrawal_policy.take() {
+        agent_account.withdrawal_threshold = policy.threshold;
+        agent_account.withdrawal_delay = policy.delay_seconds;
+    }
+
+    agent_account.clear_pending_changes();
+
+    msg!("Pending changes applied");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Permissions: {:#b}", agent_account.permissions.bits);
+    msg!("Max per transaction: {} lamports", agent_account.max_sol_per_transaction);
+    msg!("Daily limit: {} lamports", agent_account.daily_limit);
+    msg!("Change delay: {} seconds", agent_account.change_delay_seconds);
+
+    Ok(())
+}
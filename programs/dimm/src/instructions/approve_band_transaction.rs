@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ApproveBandTransaction<'info> {
+    #[account(
+        mut,
+        seeds = [
+            PENDING_TRANSACTION_SEED,
+            pending_transaction.agent.as_ref(),
+            &pending_transaction.proposal_id.to_le_bytes()
+        ],
+        bump = pending_transaction.bump
+    )]
+    pub pending_transaction: Account<'info, PendingTransaction>,
+
+    #[account(
+        seeds = [CO_SIGNER_SET_SEED, pending_transaction.agent.as_ref()],
+        bump = co_signer_set.bump
+    )]
+    pub co_signer_set: Account<'info, CoSignerSet>,
+
+    pub co_signer: Signer<'info>,
+}
+
+/// Add a co-signer's approval to a proposed transaction
+pub fn handler(ctx: Context<ApproveBandTransaction>) -> Result<()> {
+    let co_signer_set = &ctx.accounts.co_signer_set;
+    let index = co_signer_set
+        .index_of(&ctx.accounts.co_signer.key())
+        .ok_or(DimmError::NotCoSigner)?;
+
+    let pending_transaction = &mut ctx.accounts.pending_transaction;
+
+    // Approvals given under an older set are dropped
+    if pending_transaction.co_signer_generation != co_signer_set.generation {
+        pending_transaction.co_signer_approvals = 0;
+        pending_transaction.co_signer_generation = co_signer_set.generation;
+    }
+    pending_transaction.co_signer_approvals |= 1 << index;
+
+    msg!("Transaction co-signed");
+    msg!("Agent: {}", pending_transaction.agent);
+    msg!("Proposal ID: {}", pending_transaction.proposal_id);
+    msg!("Co-signer: {}", ctx.accounts.co_signer.key());
+    msg!(
+        "Approvals: {}",
+        pending_transaction.co_signer_approvals.count_ones()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::events::BountyPaid;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ApproveBounty<'info> {
+    /// Rent returns to the authority once the reward is paid
+    #[account(
+        mut,
+        seeds = [BOUNTY_SEED, authority.key().as_ref(), &bounty.bounty_id.to_le_bytes()],
+        bump = bounty.bump,
+        has_one = authority,
+        constraint = bounty.claimant == Some(claimant.key()) @ DimmError::BountyNotClaimed,
+        close = authority
+    )]
+    pub bounty: Account<'info, Bounty>,
+
+    /// CHECK: Matched against the bounty's claimant
+    #[account(mut)]
+    pub claimant: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ApproveBounty>) -> Result<()> {
+    let bounty = &ctx.accounts.bounty;
+    let clock = Clock::get()?;
+
+    require!(bounty.status == BountyStatus::Claimed, DimmError::BountyNotClaimed);
+
+    // The bounty is owned by this program, so the escrow moves directly
+    **bounty.to_account_info().try_borrow_mut_lamports()? -= bounty.reward;
+    **ctx.accounts.claimant.to_account_info().try_borrow_mut_lamports()? += bounty.reward;
+
+    emit!(BountyPaid {
+        authority: bounty.authority,
+        bounty_id: bounty.bounty_id,
+        claimant: ctx.accounts.claimant.key(),
+        reward: bounty.reward,
+        report_hash: bounty.report_hash,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Bounty paid");
+    msg!("Bounty ID: {}", bounty.bounty_id);
+    msg!("Claimant: {}", ctx.accounts.claimant.key());
+    msg!("Reward: {} lamports", bounty.reward);
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ApproveCreditLine<'info> {
+    #[account(
+        seeds = [PROTOCOL_SEED, authority.key().as_ref()],
+        bump = protocol_config.bump,
+        has_one = authority
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        seeds = [TREASURY_SEED, authority.key().as_ref()],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            authority.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        constraint = agent_account.main_wallet == authority.key() @ DimmError::Unauthorized
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = CreditLine::LEN,
+        seeds = [CREDIT_LINE_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub credit_line: Account<'info, CreditLine>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ApproveCreditLine>, params: CreditLineParams) -> Result<()> {
+    require!(!ctx.accounts.agent_account.revoked, DimmError::AgentRevoked);
+    require!(params.credit_limit > 0, DimmError::InvalidAmount);
+    require!(params.repayment_interval > 0, DimmError::InvalidActivityWindow);
+
+    let credit_line = &mut ctx.accounts.credit_line;
+    let clock = Clock::get()?;
+
+    credit_line.agent = ctx.accounts.agent_account.key();
+    credit_line.treasury = ctx.accounts.treasury.key();
+    credit_line.credit_limit = params.credit_limit;
+    credit_line.outstanding = 0;
+    credit_line.total_borrowed = 0;
+    credit_line.total_repaid = 0;
+    credit_line.repayment_interval = params.repayment_interval;
+    credit_line.min_repayment = params.min_repayment;
+    credit_line.next_due_at = 0;
+    credit_line.repaid_this_period = 0;
+    credit_line.frozen = false;
+    credit_line.approved_at = clock.unix_timestamp;
+    credit_line.bump = ctx.bumps.credit_line;
+
+    msg!("Credit line approved");
+    msg!("Agent: {}", credit_line.agent);
+    msg!("Credit limit: {} lamports", credit_line.credit_limit);
+    msg!(
+        "Repayment: {} lamports every {} seconds",
+        credit_line.min_repayment,
+        credit_line.repayment_interval
+    );
+
+    Ok(())
+}
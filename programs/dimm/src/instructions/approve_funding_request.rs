@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::errors::DimmError;
+use crate::events::FundingRequestResolved;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ApproveFundingRequest<'info> {
+    #[account(
+        seeds = [PROTOCOL_SEED, main_wallet.key().as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            FUNDING_REQUEST_SEED,
+            agent_account.key().as_ref(),
+            &funding_request.request_id.to_le_bytes()
+        ],
+        bump = funding_request.bump,
+        has_one = main_wallet,
+        has_one = requester,
+        close = requester
+    )]
+    pub funding_request: Account<'info, FundingRequest>,
+
+    /// CHECK: Wallet's emergency state; may be uninitialized
+    #[account(
+        seeds = [EMERGENCY_SEED, main_wallet.key().as_ref()],
+        bump
+    )]
+    pub emergency_state: UncheckedAccount<'info>,
+
+    /// Agent's escrow; required while escrow funding is on
+    #[account(
+        mut,
+        seeds = [AGENT_ESCROW_SEED, agent_account.key().as_ref()],
+        bump = agent_escrow.bump
+    )]
+    pub agent_escrow: Option<Account<'info, AgentEscrow>>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    /// CHECK: Receives the request rent; checked against the request
+    #[account(mut)]
+    pub requester: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ApproveFundingRequest>) -> Result<()> {
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
+    EmergencyState::require_not_paused(&ctx.accounts.emergency_state)?;
+    require!(!ctx.accounts.agent_account.revoked, DimmError::AgentRevoked);
+
+    let funding_request = &ctx.accounts.funding_request;
+    let amount = funding_request.amount;
+
+    // Pay the agent, or its escrow while escrow funding is on
+    let recipient = if ctx.accounts.agent_account.escrow_funding {
+        ctx.accounts
+            .agent_escrow
+            .as_ref()
+            .ok_or(DimmError::EscrowRequired)?
+            .to_account_info()
+    } else {
+        ctx.accounts.agent_account.to_account_info()
+    };
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.main_wallet.to_account_info(),
+            to: recipient,
+        },
+    );
+    transfer(cpi_context, amount)?;
+
+    emit!(FundingRequestResolved {
+        agent: funding_request.agent,
+        request_id: funding_request.request_id,
+        amount,
+        approved: true,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Funding request approved");
+    msg!("Agent: {}", funding_request.agent);
+    msg!("Request ID: {}", funding_request.request_id);
+    msg!("Amount: {} lamports", amount);
+
+    Ok(())
+}
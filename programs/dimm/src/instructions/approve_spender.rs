@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+#[instruction(params: ApproveSpenderParams)]
+pub struct ApproveSpender<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Re-approving resets the allowance
+    #[account(
+        init_if_needed,
+        payer = main_wallet,
+        space = SpendApproval::LEN,
+        seeds = [
+            SPEND_APPROVAL_SEED,
+            agent_account.key().as_ref(),
+            params.spender.as_ref()
+        ],
+        bump
+    )]
+    pub spend_approval: Account<'info, SpendApproval>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ApproveSpender>, params: ApproveSpenderParams) -> Result<()> {
+    let spend_approval = &mut ctx.accounts.spend_approval;
+    let clock = Clock::get()?;
+
+    spend_approval.agent = ctx.accounts.agent_account.key();
+    spend_approval.spender = params.spender;
+    spend_approval.allowance = params.allowance;
+    spend_approval.spent = 0;
+    spend_approval.expires_at = params.expires_at;
+    spend_approval.last_updated = clock.unix_timestamp;
+    spend_approval.bump = ctx.bumps.spend_approval;
+
+    msg!("Spender approved");
+    msg!("Agent: {}", spend_approval.agent);
+    msg!("Spender: {}", spend_approval.spender);
+    msg!("Allowance: {} lamports", spend_approval.allowance);
+
+    Ok(())
+}
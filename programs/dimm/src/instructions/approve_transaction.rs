@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ApproveTransaction<'info> {
+    #[account(
+        mut,
+        seeds = [
+            PENDING_TRANSACTION_SEED,
+            pending_transaction.agent.as_ref(),
+            &pending_transaction.proposal_id.to_le_bytes()
+        ],
+        bump = pending_transaction.bump,
+        has_one = main_wallet
+    )]
+    pub pending_transaction: Account<'info, PendingTransaction>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ApproveTransaction>) -> Result<()> {
+    let pending_transaction = &mut ctx.accounts.pending_transaction;
+    let clock = Clock::get()?;
+
+    require!(
+        pending_transaction.status == ProposalStatus::Pending,
+        DimmError::ProposalNotPending
+    );
+
+    pending_transaction.status = ProposalStatus::Approved;
+    pending_transaction.approved_at = clock.unix_timestamp;
+
+    msg!("Transaction approved");
+    msg!("Agent: {}", pending_transaction.agent);
+    msg!("Proposal ID: {}", pending_transaction.proposal_id);
+
+    Ok(())
+}
@@ -0,0 +1,307 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use mpl_bubblegum::program::Bubblegum;
+use spl_account_compression::{program::SplAccountCompression, Noop};
+use crate::cnft::{self, TreeAccounts};
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct BuyAgent<'info> {
+    /// Settled into the seller's wallet, price included
+    #[account(
+        mut,
+        seeds = [LISTING_SEED, agent_account.key().as_ref()],
+        bump = listing.bump,
+        has_one = seller,
+        close = seller
+    )]
+    pub listing: Box<Account<'info, AgentListing>>,
+
+    /// Closed in favor of the buyer's copy; remaining balance returns to the seller
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            seller.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        constraint = agent_account.main_wallet == seller.key() @ DimmError::Unauthorized,
+        has_one = merkle_tree,
+        close = seller
+    )]
+    pub agent_account: Box<Account<'info, AgentAccount>>,
+
+    /// CHECK: Matched against the listing
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    /// The sold agent leaves the seller's active count and aggregate
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED, seller.key().as_ref()],
+        bump = seller_protocol_config.bump
+    )]
+    pub seller_protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED, buyer.key().as_ref()],
+        bump = buyer_protocol_config.bump
+    )]
+    pub buyer_protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        init,
+        payer = buyer,
+        space = AgentAccount::LEN,
+        seeds = [
+            AGENT_SEED,
+            buyer.key().as_ref(),
+            &buyer_protocol_config.total_agents.to_le_bytes()
+        ],
+        bump
+    )]
+    pub new_agent_account: Box<Account<'info, AgentAccount>>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// CHECK: Buyer's protocol stake; may be uninitialized
+    #[account(
+        seeds = [STAKE_SEED, buyer.key().as_ref()],
+        bump
+    )]
+    pub buyer_wallet_stake: UncheckedAccount<'info>,
+
+    /// CHECK: Tree authority PDA
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = bubblegum_program.key()
+    )]
+    pub tree_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the agent's merkle tree
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    pub bubblegum_program: Program<'info, Bubblegum>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Agent PDAs are derived from their main wallet, so the sale moves the
+/// configuration into a new agent under the buyer and closes the old one.
+/// The cNFT's merkle proof must be passed as remaining accounts.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BuyAgent<'info>>,
+    max_price: u64,
+    leaf: AgentLeafArgs,
+) -> Result<()> {
+    require!(!ctx.accounts.buyer_protocol_config.paused, DimmError::ProtocolPaused);
+    let clock = Clock::get()?;
+
+    let seller = ctx.accounts.seller.key();
+    let buyer = ctx.accounts.buyer.key();
+    let price = ctx.accounts.listing.price;
+
+    require_keys_neq!(seller, buyer, DimmError::CannotBuyOwnAgent);
+    require!(price <= max_price, DimmError::ListingPriceTooHigh);
+
+    let agent_account = &ctx.accounts.agent_account;
+    require!(!agent_account.revoked, DimmError::AgentRevoked);
+    require!(!agent_account.escrow_funding, DimmError::EscrowFundingEnabled);
+    require!(
+        !agent_account.is_frozen(clock.unix_timestamp),
+        DimmError::AgentFrozen
+    );
+    require!(leaf.index == agent_account.leaf_index, DimmError::InvalidMerkleProof);
+    // Token accounts are owned by the old agent PDA and would be stranded
+    require!(agent_account.open_token_accounts == 0, DimmError::TokenAccountsOpen);
+
+    // The agent joins the buyer's wallet, so its limits must fit the buyer's
+    // caps, tier ceilings and stake quota as a newly created agent would
+    let buyer_protocol_config = &ctx.accounts.buyer_protocol_config;
+    require!(
+        buyer_protocol_config.total_agents < MAX_AGENTS_PER_WALLET as u64,
+        DimmError::MaxAgentsReached
+    );
+    require!(
+        !agent_account.has_usd_limits() || buyer_protocol_config.sol_usd_price_feed != Pubkey::default(),
+        DimmError::InvalidPriceFeed
+    );
+    buyer_protocol_config.check_tier_limits(
+        agent_account.trust_level,
+        agent_account.max_sol_per_transaction,
+        agent_account.daily_limit,
+    )?;
+    buyer_protocol_config.check_limit_caps(agent_account.max_sol_per_transaction, agent_account.daily_limit)?;
+    let agent_count = buyer_protocol_config
+        .active_agents
+        .checked_add(1)
+        .ok_or(DimmError::NumericalOverflow)?;
+    let aggregate_daily_limit = buyer_protocol_config
+        .aggregate_daily_limit
+        .checked_add(agent_account.daily_limit)
+        .ok_or(DimmError::NumericalOverflow)?;
+    let effective_stake = WalletStake::load_effective(&ctx.accounts.buyer_wallet_stake, clock.epoch)?;
+    buyer_protocol_config.check_stake_quotas(effective_stake, agent_count, aggregate_daily_limit)?;
+
+    // Pay into the listing escrow; it is closed into the seller's wallet on success
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.buyer.to_account_info(),
+            to: ctx.accounts.listing.to_account_info(),
+        },
+    );
+    transfer(cpi_context, price)?;
+
+    // The escrow is the cNFT's delegate and signs the transfer to the buyer
+    let agent_key = agent_account.key();
+    let listing_seeds = &[
+        LISTING_SEED,
+        agent_key.as_ref(),
+        &[ctx.accounts.listing.bump],
+    ];
+    let signer_seeds = &[&listing_seeds[..]];
+
+    let tree = TreeAccounts {
+        tree_authority: ctx.accounts.tree_authority.to_account_info(),
+        merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+        bubblegum_program: ctx.accounts.bubblegum_program.to_account_info(),
+        compression_program: ctx.accounts.compression_program.to_account_info(),
+        log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+    };
+    cnft::transfer_leaf(
+        &tree,
+        &ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.listing.to_account_info(),
+        &ctx.accounts.buyer.to_account_info(),
+        &leaf,
+        ctx.remaining_accounts,
+        signer_seeds,
+    )?;
+
+    // Carry the configuration over; usage counters start fresh
+    let buyer_protocol_config = &mut ctx.accounts.buyer_protocol_config;
+    let new_agent_account = &mut ctx.accounts.new_agent_account;
+
+    new_agent_account.main_wallet = buyer;
+    new_agent_account.agent_id = buyer_protocol_config.total_agents;
+    new_agent_account.name = agent_account.name.clone();
+    // The seller's agent key must not keep control; the buyer rotates in their own
+    new_agent_account.agent_signer = buyer;
+    new_agent_account.permissions = agent_account.permissions;
+    new_agent_account.max_sol_per_transaction = agent_account.max_sol_per_transaction;
+    new_agent_account.daily_limit = agent_account.daily_limit;
+    new_agent_account.spent_today = 0;
+    new_agent_account.last_daily_reset = clock.unix_timestamp;
+    new_agent_account.total_spent = 0;
+    new_agent_account.total_transactions = 0;
+    new_agent_account.revoked = false;
+    new_agent_account.created_at = agent_account.created_at;
+    new_agent_account.last_used_at = clock.unix_timestamp;
+    new_agent_account.leaf_index = agent_account.leaf_index;
+    new_agent_account.merkle_tree = agent_account.merkle_tree;
+    new_agent_account.maintenance_allowance = agent_account.maintenance_allowance;
+    new_agent_account.maintenance_spent_today = 0;
+    new_agent_account.activity_sequence = 0;
+    new_agent_account.activity_hash = [0; 32];
+    new_agent_account.retention_max_records = agent_account.retention_max_records;
+    new_agent_account.retention_max_age = agent_account.retention_max_age;
+    new_agent_account.frozen_until = 0;
+    new_agent_account.withdrawal_threshold = agent_account.withdrawal_threshold;
+    new_agent_account.withdrawal_delay = agent_account.withdrawal_delay;
+    new_agent_account.critical_reserve = agent_account.critical_reserve;
+    new_agent_account.fee_tank = 0;
+    new_agent_account.fee_tank_cap = 0;
+    new_agent_account.fee_tank_spent = 0;
+    // Whitelists are keyed by agent and stay behind with the old account
+    new_agent_account.require_destination_whitelist = false;
+    // Notes are sealed to the seller's key; the buyer registers their own
+    new_agent_account.reporting_key = None;
+    new_agent_account.approval_threshold = agent_account.approval_threshold;
+    new_agent_account.proposal_count = 0;
+    new_agent_account.rent_sponsored = false;
+    new_agent_account.trial_ends_at = agent_account.trial_ends_at;
+    new_agent_account.donation_allowance = agent_account.donation_allowance;
+    new_agent_account.donation_spent_today = 0;
+    new_agent_account.revoked_at = 0;
+    new_agent_account.activity_root = [0; 32];
+    new_agent_account.merkleized_through = 0;
+    new_agent_account.merkleized_head = [0; 32];
+    new_agent_account.receipt_sequence = 0;
+    new_agent_account.soft_daily_limit = agent_account.soft_daily_limit;
+    new_agent_account.soft_limit_pending_ack = false;
+    new_agent_account.lease_holder = Pubkey::default();
+    new_agent_account.lease_expires_at = 0;
+    new_agent_account.lease_threshold = agent_account.lease_threshold;
+    new_agent_account.timed_permissions = agent_account.timed_permissions.clone();
+    new_agent_account.daily_limit_usd = agent_account.daily_limit_usd;
+    new_agent_account.max_usd_per_transaction = agent_account.max_usd_per_transaction;
+    new_agent_account.usd_spent_today = 0;
+    new_agent_account.max_slippage_bps = agent_account.max_slippage_bps;
+    new_agent_account.open_token_accounts = 0;
+    new_agent_account.last_active_epoch = 0;
+    new_agent_account.description = None;
+    new_agent_account.metadata_uri = None;
+    new_agent_account.model_id = None;
+    new_agent_account.schema_version = AGENT_SCHEMA_VERSION;
+    new_agent_account.escrow_funding = false;
+    new_agent_account.funding_request_count = 0;
+    new_agent_account.inactivity_timeout_seconds = 0;
+    new_agent_account.inactivity_action = InactivityAction::Revoke;
+    new_agent_account.inactivity_keeper_reward = 0;
+    new_agent_account.inactivity_reset_at = 0;
+    new_agent_account.amount_bands = Vec::new();
+    new_agent_account.intent_nonce = agent_account.intent_nonce;
+    new_agent_account.nonce = agent_account.nonce;
+    new_agent_account.active_hours_start = agent_account.active_hours_start;
+    new_agent_account.active_hours_end = agent_account.active_hours_end;
+    new_agent_account.active_weekdays = agent_account.active_weekdays;
+    new_agent_account.max_sol_per_hour = agent_account.max_sol_per_hour;
+    new_agent_account.spent_this_hour = agent_account.spent_this_hour;
+    new_agent_account.last_hourly_reset = agent_account.last_hourly_reset;
+    new_agent_account.trust_level = agent_account.trust_level;
+    new_agent_account.anomaly_rules = agent_account.anomaly_rules;
+    new_agent_account.failures_this_hour = 0;
+    new_agent_account.failure_window_start = 0;
+    new_agent_account.change_delay_seconds = 0;
+    new_agent_account.pending_permissions = 0;
+    new_agent_account.pending_limits = None;
+    new_agent_account.pending_change_delay = None;
+    new_agent_account.pending_effective_at = 0;
+    new_agent_account.config_locked = agent_account.config_locked;
+    new_agent_account.cnft_burned = false;
+    new_agent_account.pending_withdrawal_policy = None;
+    new_agent_account.withdrawn_in_window = 0;
+    new_agent_account.withdrawal_window_start = 0;
+    new_agent_account.fee_tank_refund_slot = 0;
+    new_agent_account.bump = ctx.bumps.new_agent_account;
+
+    buyer_protocol_config.total_agents = buyer_protocol_config
+        .total_agents
+        .checked_add(1)
+        .ok_or(DimmError::NumericalOverflow)?;
+    buyer_protocol_config.aggregate_daily_limit = aggregate_daily_limit;
+    buyer_protocol_config.active_agents = agent_count;
+
+    ctx.accounts
+        .seller_protocol_config
+        .release_agent(agent_account.daily_limit);
+
+    msg!("Agent sold");
+    msg!("Previous Agent Address: {}", agent_key);
+    msg!("New Agent Address: {}", new_agent_account.key());
+    msg!("Seller: {}", seller);
+    msg!("Buyer: {}", buyer);
+    msg!("Price: {} lamports", price);
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct CancelBounty<'info> {
+    /// Reward returns to the treasury, rent to the authority
+    #[account(
+        mut,
+        seeds = [BOUNTY_SEED, authority.key().as_ref(), &bounty.bounty_id.to_le_bytes()],
+        bump = bounty.bump,
+        has_one = authority,
+        close = authority
+    )]
+    pub bounty: Account<'info, Bounty>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, authority.key().as_ref()],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelBounty>) -> Result<()> {
+    let bounty = &ctx.accounts.bounty;
+
+    // A pending claim must be settled or rejected first
+    require!(bounty.status == BountyStatus::Open, DimmError::BountyNotOpen);
+
+    **bounty.to_account_info().try_borrow_mut_lamports()? -= bounty.reward;
+    **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? += bounty.reward;
+
+    msg!("Bounty cancelled");
+    msg!("Bounty ID: {}", bounty.bounty_id);
+    msg!("Returned to treasury: {} lamports", bounty.reward);
+
+    Ok(())
+}
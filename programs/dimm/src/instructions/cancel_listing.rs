@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use mpl_bubblegum::program::Bubblegum;
+use spl_account_compression::{program::SplAccountCompression, Noop};
+use crate::cnft::{self, TreeAccounts};
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct CancelListing<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet,
+        has_one = merkle_tree
+    )]
+    pub agent_account: Box<Account<'info, AgentAccount>>,
+
+    #[account(
+        mut,
+        seeds = [LISTING_SEED, agent_account.key().as_ref()],
+        bump = listing.bump,
+        constraint = listing.seller == main_wallet.key() @ crate::errors::DimmError::Unauthorized,
+        close = main_wallet
+    )]
+    pub listing: Box<Account<'info, AgentListing>>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    /// CHECK: Tree authority PDA
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = bubblegum_program.key()
+    )]
+    pub tree_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the agent's merkle tree
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    pub bubblegum_program: Program<'info, Bubblegum>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub system_program: Program<'info, System>,
+}
+
+/// The cNFT's merkle proof must be passed as remaining accounts.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CancelListing<'info>>,
+    leaf: AgentLeafArgs,
+) -> Result<()> {
+    // Hand the cNFT delegation back to the seller
+    let tree = TreeAccounts {
+        tree_authority: ctx.accounts.tree_authority.to_account_info(),
+        merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+        bubblegum_program: ctx.accounts.bubblegum_program.to_account_info(),
+        compression_program: ctx.accounts.compression_program.to_account_info(),
+        log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+    };
+    cnft::delegate_leaf(
+        &tree,
+        &ctx.accounts.main_wallet.to_account_info(),
+        &ctx.accounts.listing.to_account_info(),
+        &ctx.accounts.main_wallet.to_account_info(),
+        &leaf,
+        ctx.remaining_accounts,
+    )?;
+
+    msg!("Agent listing cancelled");
+    msg!("Agent: {}", ctx.accounts.listing.agent);
+    msg!("Seller: {}", ctx.accounts.listing.seller);
+
+    Ok(())
+}
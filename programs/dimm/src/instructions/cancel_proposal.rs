@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    #[account(
+        mut,
+        seeds = [
+            PENDING_TRANSACTION_SEED,
+            pending_transaction.agent.as_ref(),
+            &pending_transaction.proposal_id.to_le_bytes()
+        ],
+        bump = pending_transaction.bump,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub pending_transaction: Account<'info, PendingTransaction>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelProposal>) -> Result<()> {
+    let pending_transaction = &ctx.accounts.pending_transaction;
+
+    msg!("Proposal cancelled");
+    msg!("Agent: {}", pending_transaction.agent);
+    msg!("Proposal ID: {}", pending_transaction.proposal_id);
+
+    Ok(())
+}
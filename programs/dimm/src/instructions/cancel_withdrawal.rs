@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use crate::auth::{Caller, Capability};
+use crate::events::WithdrawalCancelled;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct CancelWithdrawal<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        seeds = [WITHDRAWAL_SEED, agent_account.key().as_ref()],
+        bump = pending_withdrawal.bump,
+        has_one = main_wallet,
+        close = main_wallet
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+
+    /// CHECK: Receives the pending withdrawal rent; checked against the agent owner
+    #[account(mut, address = agent_account.main_wallet)]
+    pub main_wallet: UncheckedAccount<'info>,
+
+    /// Required when an emergency contact cancels
+    #[account(
+        seeds = [EMERGENCY_SEED, agent_account.main_wallet.as_ref()],
+        bump = emergency_state.bump
+    )]
+    pub emergency_state: Option<Account<'info, EmergencyState>>,
+
+    /// CHECK: Wallet's watchtower; may be uninitialized
+    #[account(
+        seeds = [WATCHTOWER_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub watchtower: UncheckedAccount<'info>,
+
+    /// Main wallet, one of its emergency contacts, or its watchtower
+    pub canceller: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelWithdrawal>) -> Result<()> {
+    // The watchtower monitoring the wallet can stop a withdrawal it flags
+    let watchtower = Watchtower::load(&ctx.accounts.watchtower)?;
+    let canceller = match watchtower {
+        Some(w) if w.watchtower == ctx.accounts.canceller.key() => w.watchtower,
+        _ => {
+            Caller::authorize(
+                ctx.accounts.canceller.key(),
+                &ctx.accounts.main_wallet.key(),
+                None,
+                ctx.accounts.emergency_state.as_deref(),
+                Capability::EmergencyAction,
+            )?
+            .key
+        }
+    };
+
+    emit!(WithdrawalCancelled {
+        agent: ctx.accounts.agent_account.key(),
+        amount: ctx.accounts.pending_withdrawal.amount,
+        cancelled_by: canceller,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Withdrawal cancelled");
+    msg!("Agent: {}", ctx.accounts.agent_account.key());
+    msg!("Amount: {} lamports", ctx.accounts.pending_withdrawal.amount);
+    msg!("Cancelled by: {}", canceller);
+
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use crate::auth::{Caller, Capability};
+use crate::errors::DimmError;
+use crate::events::GovernanceVoteCast;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct CastGovernanceVote<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        seeds = [PROTOCOL_SEED, agent_account.main_wallet.as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Main wallet or the agent's own signer key; pays for the vote record
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Realms the agent may vote in
+    #[account(
+        seeds = [
+            WHITELIST_SEED,
+            agent_account.key().as_ref(),
+            &WhitelistType::Realms.seed()
+        ],
+        bump = realm_whitelist.bump
+    )]
+    pub realm_whitelist: Box<Account<'info, Whitelist>>,
+
+    /// CHECK: Realm, checked against the whitelist; owned by `governance_program`
+    pub realm: UncheckedAccount<'info>,
+
+    /// CHECK: Checked by the governance program
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Checked by the governance program
+    #[account(mut)]
+    pub proposal: UncheckedAccount<'info>,
+
+    /// CHECK: Proposal owner's token owner record; checked by the governance program
+    #[account(mut)]
+    pub proposal_owner_record: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's token owner record; checked by the governance program
+    #[account(mut)]
+    pub voter_token_owner_record: UncheckedAccount<'info>,
+
+    /// CHECK: Vote record created by the governance program
+    #[account(mut)]
+    pub vote_record: UncheckedAccount<'info>,
+
+    /// CHECK: Checked by the governance program
+    pub governing_token_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Realm config; checked by the governance program
+    pub realm_config: UncheckedAccount<'info>,
+
+    /// CHECK: SPL governance program instance that owns the realm
+    #[account(executable)]
+    pub governance_program: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet's emergency state; may be uninitialized
+    #[account(
+        seeds = [EMERGENCY_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub emergency_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Vote on a proposal in a whitelisted realm, with the agent PDA as the
+/// governance authority of its token owner record. Voter weight records
+/// required by the realm's plugins are passed as remaining accounts.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CastGovernanceVote<'info>>,
+    vote: GovernanceVote,
+) -> Result<()> {
+    Caller::authorize(
+        ctx.accounts.authority.key(),
+        &ctx.accounts.agent_account.main_wallet,
+        Some(&*ctx.accounts.agent_account),
+        None,
+        Capability::Execute,
+    )?;
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
+    EmergencyState::require_not_paused(&ctx.accounts.emergency_state)?;
+
+    let clock = Clock::get()?;
+    let agent_account = &mut ctx.accounts.agent_account;
+    require!(!agent_account.revoked, DimmError::AgentRevoked);
+    require!(
+        !agent_account.is_frozen(clock.unix_timestamp),
+        DimmError::AgentFrozen
+    );
+    agent_account.expire_permissions(clock.unix_timestamp);
+    require!(
+        agent_account.has_permission(&AgentPermission::Governance),
+        DimmError::InsufficientPermissions
+    );
+
+    let realm_key = ctx.accounts.realm.key();
+    ctx.accounts.realm_whitelist.require_realm(&realm_key)?;
+    require_keys_eq!(
+        *ctx.accounts.realm.owner,
+        ctx.accounts.governance_program.key(),
+        DimmError::InvalidProgram
+    );
+
+    let agent_key = agent_account.key();
+    let mut accounts = vec![
+        AccountMeta::new_readonly(realm_key, false),
+        AccountMeta::new_readonly(ctx.accounts.governance.key(), false),
+        AccountMeta::new(ctx.accounts.proposal.key(), false),
+        AccountMeta::new(ctx.accounts.proposal_owner_record.key(), false),
+        AccountMeta::new(ctx.accounts.voter_token_owner_record.key(), false),
+        AccountMeta::new_readonly(agent_key, true),
+        AccountMeta::new(ctx.accounts.vote_record.key(), false),
+        AccountMeta::new_readonly(ctx.accounts.governing_token_mint.key(), false),
+        AccountMeta::new(ctx.accounts.authority.key(), true),
+        AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
+        AccountMeta::new_readonly(ctx.accounts.realm_config.key(), false),
+    ];
+    let mut account_infos = vec![
+        ctx.accounts.realm.to_account_info(),
+        ctx.accounts.governance.to_account_info(),
+        ctx.accounts.proposal.to_account_info(),
+        ctx.accounts.proposal_owner_record.to_account_info(),
+        ctx.accounts.voter_token_owner_record.to_account_info(),
+        agent_account.to_account_info(),
+        ctx.accounts.vote_record.to_account_info(),
+        ctx.accounts.governing_token_mint.to_account_info(),
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.realm_config.to_account_info(),
+    ];
+    for info in ctx.remaining_accounts {
+        accounts.push(AccountMeta::new_readonly(info.key(), false));
+        account_infos.push(info.clone());
+    }
+    account_infos.push(ctx.accounts.governance_program.to_account_info());
+
+    let instruction = Instruction {
+        program_id: ctx.accounts.governance_program.key(),
+        accounts,
+        data: vote.cast_vote_data()?,
+    };
+    let agent_seeds = &[
+        AGENT_SEED,
+        agent_account.main_wallet.as_ref(),
+        &agent_account.agent_id.to_le_bytes(),
+        &[agent_account.bump],
+    ];
+    invoke_signed(&instruction, &account_infos, &[&agent_seeds[..]])?;
+
+    agent_account.last_used_at = clock.unix_timestamp;
+
+    emit!(GovernanceVoteCast {
+        agent: agent_key,
+        realm: realm_key,
+        proposal: ctx.accounts.proposal.key(),
+        vote,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Governance vote cast");
+    msg!("Agent: {}", agent_key);
+    msg!("Proposal: {}", ctx.accounts.proposal.key());
+
+    Ok(())
+}
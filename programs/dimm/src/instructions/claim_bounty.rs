@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::events::BountyClaimed;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ClaimBounty<'info> {
+    #[account(
+        mut,
+        seeds = [BOUNTY_SEED, bounty.authority.as_ref(), &bounty.bounty_id.to_le_bytes()],
+        bump = bounty.bump
+    )]
+    pub bounty: Account<'info, Bounty>,
+
+    pub claimant: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ClaimBounty>, report_hash: [u8; 32]) -> Result<()> {
+    let bounty = &mut ctx.accounts.bounty;
+    let clock = Clock::get()?;
+
+    require!(bounty.status == BountyStatus::Open, DimmError::BountyNotOpen);
+
+    bounty.claimant = Some(ctx.accounts.claimant.key());
+    bounty.report_hash = report_hash;
+    bounty.status = BountyStatus::Claimed;
+    bounty.claimed_at = clock.unix_timestamp;
+
+    emit!(BountyClaimed {
+        authority: bounty.authority,
+        bounty_id: bounty.bounty_id,
+        claimant: ctx.accounts.claimant.key(),
+        report_hash,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Bounty claimed");
+    msg!("Bounty ID: {}", bounty.bounty_id);
+    msg!("Claimant: {}", ctx.accounts.claimant.key());
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct CloseActivity<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        constraint = activity.agent == agent_account.key() @ DimmError::Unauthorized,
+        constraint = !activity.disputed @ DimmError::ActivityDisputed
+    )]
+    pub activity: Account<'info, AgentActivity>,
+
+    /// CHECK: Receives the rent; must be the record's payer
+    #[account(
+        mut,
+        constraint = rent_recipient.key() == activity.payer @ DimmError::Unauthorized
+    )]
+    pub rent_recipient: UncheckedAccount<'info>,
+
+    /// Wallet's rent pool; receives the rent of sponsored records instead
+    #[account(
+        mut,
+        seeds = [RENT_POOL_SEED, agent_account.main_wallet.as_ref()],
+        bump = rent_pool.bump
+    )]
+    pub rent_pool: Option<Account<'info, RentPool>>,
+
+    /// The record's payer or the main wallet
+    #[account(
+        constraint = closer.key() == activity.payer
+            || closer.key() == agent_account.main_wallet @ DimmError::Unauthorized
+    )]
+    pub closer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CloseActivity>) -> Result<()> {
+    let clock = Clock::get()?;
+    let agent_account = &ctx.accounts.agent_account;
+    let activity = &ctx.accounts.activity;
+
+    require!(
+        agent_account.is_activity_expired(activity.timestamp, clock.unix_timestamp),
+        DimmError::ActivityNotExpired
+    );
+
+    let sequence = activity.sequence;
+    if activity.rent_sponsored {
+        let rent_pool = ctx
+            .accounts
+            .rent_pool
+            .as_mut()
+            .ok_or(DimmError::RentPoolRequired)?;
+        rent_pool.record_reclaim(activity.to_account_info().lamports())?;
+        activity.close(rent_pool.to_account_info())?;
+    } else {
+        activity.close(ctx.accounts.rent_recipient.to_account_info())?;
+    }
+
+    msg!("Activity closed");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Sequence: {}", sequence);
+
+    Ok(())
+}
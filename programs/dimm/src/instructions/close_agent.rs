@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct CloseAgent<'info> {
+    /// All remaining lamports, rent included, are swept to the main wallet
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet,
+        close = main_wallet
+    )]
+    pub agent_account: Box<Account<'info, AgentAccount>>,
+
+    /// CHECK: Must be empty; a listed agent is closed through `cancel_listing` first
+    #[account(
+        seeds = [LISTING_SEED, agent_account.key().as_ref()],
+        bump,
+        constraint = listing.data_is_empty() @ DimmError::AgentListed
+    )]
+    pub listing: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [HOOK_SEED, agent_account.key().as_ref()],
+        bump = hook_registry.bump,
+        close = main_wallet
+    )]
+    pub hook_registry: Option<Box<Account<'info, HookRegistry>>>,
+
+    #[account(
+        mut,
+        seeds = [PRIORITY_LANES_SEED, agent_account.key().as_ref()],
+        bump = priority_lanes.bump,
+        close = main_wallet
+    )]
+    pub priority_lanes: Option<Box<Account<'info, PriorityLanes>>>,
+
+    #[account(
+        mut,
+        seeds = [SCHEDULE_SEED, agent_account.key().as_ref()],
+        bump = schedule.bump,
+        close = main_wallet
+    )]
+    pub schedule: Option<Box<Account<'info, ActivitySchedule>>>,
+
+    #[account(
+        mut,
+        seeds = [STATS_SEED, agent_account.key().as_ref()],
+        bump = stats.bump,
+        close = main_wallet
+    )]
+    pub stats: Option<Box<Account<'info, AgentStats>>>,
+
+    #[account(
+        mut,
+        seeds = [RATE_LIMIT_SEED, agent_account.key().as_ref()],
+        bump = rate_limit.bump,
+        close = main_wallet
+    )]
+    pub rate_limit: Option<Box<Account<'info, RateLimit>>>,
+
+    #[account(
+        mut,
+        seeds = [ADDRESS_BOOK_SEED, agent_account.key().as_ref()],
+        bump = address_book.bump,
+        close = main_wallet
+    )]
+    pub address_book: Option<Box<Account<'info, AddressBook>>>,
+
+    #[account(
+        mut,
+        seeds = [BLACKLIST_SEED, agent_account.key().as_ref()],
+        bump = blacklist.bump,
+        close = main_wallet
+    )]
+    pub blacklist: Option<Box<Account<'info, Blacklist>>>,
+
+    #[account(
+        mut,
+        seeds = [SCOPED_PERMISSIONS_SEED, agent_account.key().as_ref()],
+        bump = scoped_permissions.bump,
+        close = main_wallet
+    )]
+    pub scoped_permissions: Option<Box<Account<'info, ScopedPermissions>>>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED, main_wallet.key().as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CloseAgent>) -> Result<()> {
+    let agent_account = &ctx.accounts.agent_account;
+    let agent_info = agent_account.to_account_info();
+    require!(!agent_account.escrow_funding, DimmError::EscrowFundingEnabled);
+    // Token accounts are owned by the agent PDA and would be stranded
+    require!(agent_account.open_token_accounts == 0, DimmError::TokenAccountsOpen);
+
+    // A live agent must be drained down to its rent first
+    let rent_exempt = Rent::get()?.minimum_balance(agent_info.data_len());
+    require!(
+        agent_account.revoked || agent_info.lamports() <= rent_exempt,
+        DimmError::AgentNotClosable
+    );
+
+    // A revoked agent's balance above rent is a withdrawal like any other, so
+    // a large one has to be drawn down with withdraw_from_agent first
+    let sweep = agent_info.lamports().saturating_sub(rent_exempt);
+    require!(
+        sweep == 0 || !agent_account.requires_timelocked_withdrawal(sweep, Clock::get()?.unix_timestamp),
+        DimmError::WithdrawalRequiresTimelock
+    );
+
+    // A revoked agent was released when it was revoked
+    if !agent_account.revoked {
+        ctx.accounts.protocol_config.release_agent(agent_account.daily_limit);
+    }
+
+    msg!("Agent closed");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Agent ID: {}", agent_account.agent_id);
+    msg!("Swept: {} lamports", agent_info.lamports());
+
+    Ok(())
+}
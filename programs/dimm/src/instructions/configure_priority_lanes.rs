@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ConfigurePriorityLanes<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = main_wallet,
+        space = PriorityLanes::LEN,
+        seeds = [PRIORITY_LANES_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub priority_lanes: Account<'info, PriorityLanes>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ConfigurePriorityLanes>, params: PriorityLaneParams) -> Result<()> {
+    let agent_account = &mut ctx.accounts.agent_account;
+    let priority_lanes = &mut ctx.accounts.priority_lanes;
+    let clock = Clock::get()?;
+
+    require!(
+        params.reserve <= agent_account.daily_limit,
+        DimmError::InvalidLimitConfiguration
+    );
+    require!(
+        params.destinations.len() <= PriorityLanes::MAX_DESTINATIONS,
+        DimmError::InvalidLimitConfiguration
+    );
+    require!(
+        params.activity_types.len() <= PriorityLanes::MAX_ACTIVITY_TYPES,
+        DimmError::InvalidLimitConfiguration
+    );
+
+    agent_account.critical_reserve = params.reserve;
+
+    priority_lanes.agent = agent_account.key();
+    priority_lanes.destinations = params.destinations;
+    priority_lanes.activity_types = params.activity_types;
+    priority_lanes.last_updated = clock.unix_timestamp;
+    priority_lanes.bump = ctx.bumps.priority_lanes;
+
+    msg!("Priority lanes configured");
+    msg!("Agent: {}", priority_lanes.agent);
+    msg!("Critical reserve: {} lamports", agent_account.critical_reserve);
+    msg!("Critical destinations: {}", priority_lanes.destinations.len());
+    msg!("Critical activity types: {:?}", priority_lanes.activity_types);
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+#[instruction(whitelist_type: WhitelistType)]
+pub struct CopyWhitelist<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Another agent's whitelist or a protocol-published template
+    #[account(
+        constraint = source.whitelist_type == whitelist_type @ DimmError::WhitelistTypeMismatch
+    )]
+    pub source: Account<'info, Whitelist>,
+
+    #[account(
+        init,
+        payer = main_wallet,
+        space = Whitelist::LEN,
+        seeds = [
+            WHITELIST_SEED,
+            agent_account.key().as_ref(),
+            &whitelist_type.seed()
+        ],
+        bump
+    )]
+    pub whitelist: Account<'info, Whitelist>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CopyWhitelist>, whitelist_type: WhitelistType) -> Result<()> {
+    let agent_account = &mut ctx.accounts.agent_account;
+    let source = &ctx.accounts.source;
+    let whitelist = &mut ctx.accounts.whitelist;
+    let clock = Clock::get()?;
+
+    whitelist.owner = agent_account.key();
+    whitelist.set_addresses(source.addresses.clone())?;
+    whitelist.enabled = true;
+    whitelist.whitelist_type = whitelist_type;
+    whitelist.last_updated = clock.unix_timestamp;
+    whitelist.bump = ctx.bumps.whitelist;
+
+    if whitelist_type == WhitelistType::Destinations {
+        agent_account.require_destination_whitelist = true;
+    }
+
+    msg!("Whitelist copied");
+    msg!("Agent: {}", whitelist.owner);
+    msg!("Source: {}", source.key());
+    msg!("Addresses: {}", whitelist.addresses.len());
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+#[instruction(day_index: u64)]
+pub struct CrankSnapshot<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = DailySnapshot::LEN,
+        seeds = [DAILY_SNAPSHOT_SEED, agent_account.key().as_ref(), &day_index.to_le_bytes()],
+        bump
+    )]
+    pub snapshot: Account<'info, DailySnapshot>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Open the agent's snapshot for the current day. Permissionless; executions
+/// and activity records that pass it afterwards accumulate into it.
+pub fn handler(ctx: Context<CrankSnapshot>, day_index: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        day_index == DailySnapshot::day_index(clock.unix_timestamp),
+        DimmError::SnapshotMismatch
+    );
+
+    let snapshot = &mut ctx.accounts.snapshot;
+    snapshot.agent = ctx.accounts.agent_account.key();
+    snapshot.day_index = day_index;
+    snapshot.spent = 0;
+    snapshot.transactions = 0;
+    snapshot.failures = 0;
+    snapshot.bump = ctx.bumps.snapshot;
+
+    msg!("Daily snapshot opened");
+    msg!("Agent: {}", snapshot.agent);
+    msg!("Day: {}", day_index);
+
+    Ok(())
+}
@@ -1,7 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer};
+use mpl_bubblegum::program::Bubblegum;
+use spl_account_compression::{program::SplAccountCompression, Noop};
+use crate::cnft::{self, TreeAccounts};
 use crate::constants::*;
 use crate::errors::DimmError;
+use crate::events::MerkleTreeRotated;
 use crate::state::*;
 
 #[derive(Accounts)]
@@ -12,7 +16,7 @@ pub struct CreateAgent<'info> {
         seeds = [PROTOCOL_SEED, main_wallet.key().as_ref()],
         bump = protocol_config.bump
     )]
-    pub protocol_config: Account<'info, ProtocolConfig>,
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
 
     #[account(
         init,
@@ -25,15 +29,48 @@ pub struct CreateAgent<'info> {
         ],
         bump
     )]
-    pub agent_account: Account<'info, AgentAccount>,
+    pub agent_account: Box<Account<'info, AgentAccount>>,
 
+    /// Payer and tree delegate for the agent's cNFT
     #[account(mut)]
     pub main_wallet: Signer<'info>,
 
+    /// CHECK: Wallet's protocol stake; may be uninitialized
+    #[account(
+        seeds = [STAKE_SEED, main_wallet.key().as_ref()],
+        bump
+    )]
+    pub wallet_stake: UncheckedAccount<'info>,
+
+    /// CHECK: Protocol-wide stats; may be uninitialized
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATS_SEED],
+        bump
+    )]
+    pub global_stats: UncheckedAccount<'info>,
+
+    /// CHECK: Tree authority PDA
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = bubblegum_program.key()
+    )]
+    pub tree_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the protocol's merkle tree
+    #[account(mut, address = protocol_config.merkle_tree)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    pub bubblegum_program: Program<'info, Bubblegum>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub log_wrapper: Program<'info, Noop>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<CreateAgent>, params: CreateAgentParams) -> Result<()> {
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
     let protocol_config = &mut ctx.accounts.protocol_config;
     let agent_account = &mut ctx.accounts.agent_account;
     let clock = Clock::get()?;
@@ -48,17 +85,33 @@ pub fn handler(ctx: Context<CreateAgent>, params: CreateAgentParams) -> Result<(
         params.daily_limit >= params.max_sol_per_transaction,
         DimmError::InvalidLimitConfiguration
     );
+    protocol_config.check_tier_limits(0, params.max_sol_per_transaction, params.daily_limit)?;
+    protocol_config.check_limit_caps(params.max_sol_per_transaction, params.daily_limit)?;
 
     require!(
         protocol_config.total_agents < MAX_AGENTS_PER_WALLET as u64,
         DimmError::MaxAgentsReached
     );
 
+    let agent_count = protocol_config
+        .active_agents
+        .checked_add(1)
+        .ok_or(DimmError::NumericalOverflow)?;
+    let aggregate_daily_limit = protocol_config
+        .aggregate_daily_limit
+        .checked_add(params.daily_limit)
+        .ok_or(DimmError::NumericalOverflow)?;
+    let effective_stake = WalletStake::load_effective(&ctx.accounts.wallet_stake, clock.epoch)?;
+    protocol_config.check_stake_quotas(effective_stake, agent_count, aggregate_daily_limit)?;
+    protocol_config.aggregate_daily_limit = aggregate_daily_limit;
+    protocol_config.active_agents = agent_count;
+
     // Initialize agent account
     agent_account.main_wallet = ctx.accounts.main_wallet.key();
     agent_account.agent_id = protocol_config.total_agents;
     agent_account.name = params.name.clone();
-    agent_account.permissions = params.permissions;
+    agent_account.agent_signer = params.agent_signer;
+    agent_account.permissions = PermissionSet::from_bits(params.permissions)?;
     agent_account.max_sol_per_transaction = params.max_sol_per_transaction;
     agent_account.daily_limit = params.daily_limit;
     agent_account.spent_today = 0;
@@ -68,20 +121,129 @@ pub fn handler(ctx: Context<CreateAgent>, params: CreateAgentParams) -> Result<(
     agent_account.revoked = false;
     agent_account.created_at = clock.unix_timestamp;
     agent_account.last_used_at = clock.unix_timestamp;
-    agent_account.leaf_index = protocol_config.total_agents as u32;
+    agent_account.leaf_index = cnft::next_leaf_index(&ctx.accounts.tree_authority)?;
+    agent_account.merkle_tree = protocol_config.merkle_tree;
+    agent_account.maintenance_allowance = DEFAULT_MAINTENANCE_ALLOWANCE;
+    agent_account.maintenance_spent_today = 0;
+    agent_account.activity_sequence = 0;
+    agent_account.activity_hash = [0; 32];
+    agent_account.retention_max_records = 0;
+    agent_account.retention_max_age = 0;
+    agent_account.frozen_until = 0;
+    agent_account.withdrawal_threshold = 0;
+    agent_account.withdrawal_delay = 0;
+    agent_account.critical_reserve = 0;
+    agent_account.fee_tank = 0;
+    agent_account.fee_tank_cap = 0;
+    agent_account.fee_tank_spent = 0;
+    agent_account.require_destination_whitelist = false;
+    agent_account.reporting_key = None;
+    agent_account.approval_threshold = 0;
+    agent_account.proposal_count = 0;
+    agent_account.rent_sponsored = false;
+    agent_account.trial_ends_at = clock
+        .unix_timestamp
+        .checked_add(TRIAL_PERIOD_SECONDS)
+        .ok_or(DimmError::NumericalOverflow)?;
+    agent_account.donation_allowance = 0;
+    agent_account.donation_spent_today = 0;
+    agent_account.revoked_at = 0;
+    agent_account.activity_root = [0; 32];
+    agent_account.merkleized_through = 0;
+    agent_account.merkleized_head = [0; 32];
+    agent_account.receipt_sequence = 0;
+    agent_account.soft_daily_limit = 0;
+    agent_account.soft_limit_pending_ack = false;
+    agent_account.lease_holder = Pubkey::default();
+    agent_account.lease_expires_at = 0;
+    agent_account.lease_threshold = 0;
+    agent_account.timed_permissions = Vec::new();
+    agent_account.daily_limit_usd = 0;
+    agent_account.max_usd_per_transaction = 0;
+    agent_account.usd_spent_today = 0;
+    agent_account.max_slippage_bps = DEFAULT_MAX_SLIPPAGE_BPS;
+    agent_account.open_token_accounts = 0;
+    agent_account.last_active_epoch = 0;
+    agent_account.description = None;
+    agent_account.metadata_uri = None;
+    agent_account.model_id = None;
+    agent_account.schema_version = AGENT_SCHEMA_VERSION;
+    agent_account.escrow_funding = false;
+    agent_account.funding_request_count = 0;
+    agent_account.inactivity_timeout_seconds = 0;
+    agent_account.inactivity_action = InactivityAction::Revoke;
+    agent_account.inactivity_keeper_reward = 0;
+    agent_account.inactivity_reset_at = 0;
+    agent_account.amount_bands = Vec::new();
+    agent_account.intent_nonce = 0;
+    agent_account.nonce = 0;
+    agent_account.active_hours_start = 0;
+    agent_account.active_hours_end = 0;
+    agent_account.active_weekdays = ALL_WEEKDAYS;
+    agent_account.max_sol_per_hour = 0;
+    agent_account.spent_this_hour = 0;
+    agent_account.last_hourly_reset = clock.unix_timestamp;
+    agent_account.trust_level = 0;
+    agent_account.anomaly_rules = AnomalyRules::default();
+    agent_account.failures_this_hour = 0;
+    agent_account.failure_window_start = 0;
+    agent_account.change_delay_seconds = 0;
+    agent_account.pending_permissions = 0;
+    agent_account.pending_limits = None;
+    agent_account.pending_change_delay = None;
+    agent_account.pending_effective_at = 0;
+    agent_account.config_locked = false;
+    agent_account.cnft_burned = false;
+    agent_account.pending_withdrawal_policy = None;
+    agent_account.withdrawn_in_window = 0;
+    agent_account.withdrawal_window_start = 0;
+    agent_account.fee_tank_refund_slot = 0;
     agent_account.bump = ctx.bumps.agent_account;
 
+    // Mint the agent's cNFT to the main wallet
+    let tree = TreeAccounts {
+        tree_authority: ctx.accounts.tree_authority.to_account_info(),
+        merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+        bubblegum_program: ctx.accounts.bubblegum_program.to_account_info(),
+        compression_program: ctx.accounts.compression_program.to_account_info(),
+        log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+    };
+    cnft::mint_leaf(
+        &tree,
+        &ctx.accounts.main_wallet.to_account_info(),
+        &ctx.accounts.main_wallet.to_account_info(),
+        &ctx.accounts.main_wallet.to_account_info(),
+        cnft::agent_metadata(agent_account, &agent_account.key()),
+    )?;
+
+    // Roll new agents onto the next queued tree once this one fills up
+    if cnft::tree_is_full(&ctx.accounts.tree_authority)? && protocol_config.advance_merkle_tree() {
+        emit!(MerkleTreeRotated {
+            authority: protocol_config.authority,
+            previous_tree: agent_account.merkle_tree,
+            merkle_tree: protocol_config.merkle_tree,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
     // Increment total agents
     protocol_config.total_agents = protocol_config
         .total_agents
         .checked_add(1)
         .ok_or(DimmError::NumericalOverflow)?;
 
+    if let Some(mut global_stats) = GlobalStats::load(&ctx.accounts.global_stats)? {
+        global_stats.record_agent_created()?;
+        global_stats.store(&ctx.accounts.global_stats)?;
+    }
+
     msg!("Agent created successfully");
     msg!("Agent ID: {}", agent_account.agent_id);
     msg!("Agent Name: {}", agent_account.name);
-    msg!("Agent Address: {}", ctx.accounts.agent_account.key());
+    msg!("Agent Address: {}", agent_account.key());
     msg!("Main Wallet: {}", agent_account.main_wallet);
+    msg!("Leaf Index: {}", agent_account.leaf_index);
 
     Ok(())
 }
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::events::BountyCreated;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+#[instruction(params: CreateBountyParams)]
+pub struct CreateBounty<'info> {
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, authority.key().as_ref()],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Bounty::LEN,
+        seeds = [BOUNTY_SEED, authority.key().as_ref(), &params.bounty_id.to_le_bytes()],
+        bump
+    )]
+    pub bounty: Account<'info, Bounty>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateBounty>, params: CreateBountyParams) -> Result<()> {
+    require!(params.reward > 0, DimmError::InvalidAmount);
+
+    // Keep the treasury rent exempt
+    let treasury_info = ctx.accounts.treasury.to_account_info();
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(Treasury::LEN);
+    let available_balance = treasury_info
+        .lamports()
+        .checked_sub(rent_exempt_minimum)
+        .ok_or(DimmError::InsufficientTreasuryBalance)?;
+
+    require!(
+        params.reward <= available_balance,
+        DimmError::InsufficientTreasuryBalance
+    );
+
+    // Escrow the reward alongside the bounty's own rent
+    **treasury_info.try_borrow_mut_lamports()? -= params.reward;
+    **ctx.accounts.bounty.to_account_info().try_borrow_mut_lamports()? += params.reward;
+
+    let clock = Clock::get()?;
+    let bounty = &mut ctx.accounts.bounty;
+    bounty.authority = ctx.accounts.authority.key();
+    bounty.bounty_id = params.bounty_id;
+    bounty.reward = params.reward;
+    bounty.scope_hash = params.scope_hash;
+    bounty.claimant = None;
+    bounty.report_hash = [0; 32];
+    bounty.status = BountyStatus::Open;
+    bounty.created_at = clock.unix_timestamp;
+    bounty.claimed_at = 0;
+    bounty.bump = ctx.bumps.bounty;
+    bounty.reserved = [0; 32];
+
+    emit!(BountyCreated {
+        authority: bounty.authority,
+        bounty_id: bounty.bounty_id,
+        reward: bounty.reward,
+        scope_hash: bounty.scope_hash,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Bounty created");
+    msg!("Bounty ID: {}", bounty.bounty_id);
+    msg!("Reward: {} lamports", bounty.reward);
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use crate::auth::{Caller, Capability};
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+#[instruction(plan_id: u64)]
+pub struct CreatePlan<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = Plan::LEN,
+        seeds = [PLAN_SEED, agent_account.key().as_ref(), &plan_id.to_le_bytes()],
+        bump
+    )]
+    pub plan: Box<Account<'info, Plan>>,
+
+    /// Main wallet or the agent's own signer key
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Store a plan after checking each step is permitted and the aggregate
+/// notional fits a single transaction of the agent
+pub fn handler(ctx: Context<CreatePlan>, plan_id: u64, steps: Vec<PlanStep>) -> Result<()> {
+    Caller::authorize(
+        ctx.accounts.creator.key(),
+        &ctx.accounts.agent_account.main_wallet,
+        Some(&*ctx.accounts.agent_account),
+        None,
+        Capability::Execute,
+    )?;
+
+    let agent_account = &ctx.accounts.agent_account;
+    require!(!agent_account.revoked, DimmError::AgentRevoked);
+    require!(
+        !steps.is_empty() && steps.len() <= MAX_PLAN_STEPS,
+        DimmError::InvalidPlanSize
+    );
+
+    let mut total_notional: u64 = 0;
+    for step in steps.iter() {
+        step.validate()?;
+        if let Some(required_permission) = step.activity_type.required_permission() {
+            require!(
+                agent_account.has_permission(&required_permission),
+                DimmError::InsufficientPermissions
+            );
+        }
+        total_notional = total_notional
+            .checked_add(step.amount)
+            .ok_or(DimmError::NumericalOverflow)?;
+    }
+
+    require!(
+        total_notional <= agent_account.max_sol_per_transaction,
+        DimmError::ExceedsTransactionLimit
+    );
+    require!(
+        !agent_account.requires_approval(total_notional),
+        DimmError::ApprovalRequired
+    );
+    require!(
+        agent_account.required_co_signers(total_notional) == 0,
+        DimmError::CoSignersRequired
+    );
+
+    let clock = Clock::get()?;
+    let plan = &mut ctx.accounts.plan;
+
+    plan.agent = agent_account.key();
+    plan.plan_id = plan_id;
+    plan.creator = ctx.accounts.creator.key();
+    plan.steps = steps;
+    plan.next_step = 0;
+    plan.status = PlanStatus::Pending;
+    plan.total_notional = total_notional;
+    plan.executed_notional = 0;
+    plan.checkpoint_balance = agent_account.to_account_info().lamports();
+    plan.checkpoint_at = clock.unix_timestamp;
+    plan.created_at = clock.unix_timestamp;
+    plan.bump = ctx.bumps.plan;
+
+    msg!("Plan created");
+    msg!("Agent: {}", plan.agent);
+    msg!("Plan ID: {}", plan_id);
+    msg!("Steps: {}", plan.steps.len());
+    msg!("Total notional: {} lamports", total_notional);
+
+    Ok(())
+}
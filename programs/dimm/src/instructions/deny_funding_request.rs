@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::events::FundingRequestResolved;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct DenyFundingRequest<'info> {
+    #[account(
+        mut,
+        seeds = [
+            FUNDING_REQUEST_SEED,
+            funding_request.agent.as_ref(),
+            &funding_request.request_id.to_le_bytes()
+        ],
+        bump = funding_request.bump,
+        has_one = main_wallet,
+        has_one = requester,
+        close = requester
+    )]
+    pub funding_request: Account<'info, FundingRequest>,
+
+    pub main_wallet: Signer<'info>,
+
+    /// CHECK: Receives the request rent; checked against the request
+    #[account(mut)]
+    pub requester: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<DenyFundingRequest>) -> Result<()> {
+    let funding_request = &ctx.accounts.funding_request;
+
+    emit!(FundingRequestResolved {
+        agent: funding_request.agent,
+        request_id: funding_request.request_id,
+        amount: funding_request.amount,
+        approved: false,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Funding request denied");
+    msg!("Agent: {}", funding_request.agent);
+    msg!("Request ID: {}", funding_request.request_id);
+
+    Ok(())
+}
@@ -0,0 +1,262 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::auth::{Caller, Capability};
+use crate::errors::DimmError;
+use crate::events::{SoftLimitExceeded, StakePoolDeposited};
+use crate::state::*;
+use crate::constants::*;
+
+/// `StakePoolInstruction::DepositSol` tag in the SPL stake pool program
+const DEPOSIT_SOL_TAG: u8 = 14;
+
+#[derive(Accounts)]
+pub struct DepositStakePool<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        seeds = [PROTOCOL_SEED, agent_account.main_wallet.as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Main wallet or the agent's own signer key
+    pub authority: Signer<'info>,
+
+    /// CHECK: Agent's system-owned funding PDA; empty before and after
+    #[account(
+        mut,
+        seeds = [SOL_DEPOSIT_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub deposit_funding: UncheckedAccount<'info>,
+
+    /// CHECK: Stake pool; checked by the stake pool program
+    #[account(mut, owner = STAKE_POOL_PROGRAM_ID @ DimmError::InvalidProgram)]
+    pub stake_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Stake pool's withdraw authority; checked by the stake pool program
+    pub stake_pool_withdraw_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Stake pool's reserve stake account; checked by the stake pool program
+    #[account(mut)]
+    pub reserve_stake: UncheckedAccount<'info>,
+
+    /// Agent's token account for the pool's liquid staking token
+    #[account(
+        mut,
+        constraint = pool_token_account.owner == agent_account.key() @ DimmError::InvalidTokenTransfer,
+        constraint = pool_token_account.mint == pool_mint.key() @ DimmError::InvalidTokenTransfer
+    )]
+    pub pool_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Pool manager's fee account; checked by the stake pool program
+    #[account(mut)]
+    pub manager_fee_account: UncheckedAccount<'info>,
+
+    /// CHECK: Referrer fee account; checked by the stake pool program
+    #[account(mut)]
+    pub referrer_pool_tokens_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub pool_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Protocol's Pyth SOL/USD price account; required if the agent has USD limits
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Wallet's emergency state; may be uninitialized
+    #[account(
+        seeds = [EMERGENCY_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub emergency_state: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's activity schedule; may be uninitialized
+    #[account(
+        seeds = [SCHEDULE_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub schedule: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's stats; may be uninitialized
+    #[account(
+        mut,
+        seeds = [STATS_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+
+    /// CHECK: SPL stake pool program
+    #[account(address = STAKE_POOL_PROGRAM_ID @ DimmError::InvalidProgram)]
+    pub stake_pool_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Deposit `amount` lamports of agent SOL into an SPL stake pool for its
+/// liquid staking token. The deposit is an ordinary spend against the
+/// agent's limits, and fails unless at least `min_pool_tokens` arrive.
+pub fn handler(ctx: Context<DepositStakePool>, amount: u64, min_pool_tokens: u64) -> Result<()> {
+    Caller::authorize(
+        ctx.accounts.authority.key(),
+        &ctx.accounts.agent_account.main_wallet,
+        Some(&*ctx.accounts.agent_account),
+        None,
+        Capability::Execute,
+    )?;
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
+    EmergencyState::require_not_paused(&ctx.accounts.emergency_state)?;
+    require!(amount > 0, DimmError::InvalidAmount);
+
+    let clock = Clock::get()?;
+    let usd_price = match &ctx.accounts.price_feed {
+        Some(price_feed) => Some(SolUsdPrice::load(
+            price_feed,
+            &ctx.accounts.protocol_config.sol_usd_price_feed,
+            clock.unix_timestamp,
+        )?),
+        None => None,
+    };
+
+    let agent_account = &mut ctx.accounts.agent_account;
+    require!(!agent_account.revoked, DimmError::AgentRevoked);
+    require!(
+        !agent_account.is_frozen(clock.unix_timestamp),
+        DimmError::AgentFrozen
+    );
+    ActivitySchedule::require_active(&ctx.accounts.schedule, clock.unix_timestamp)?;
+    require!(
+        agent_account.is_within_active_hours(clock.unix_timestamp),
+        DimmError::OutsideActiveHours
+    );
+    agent_account.expire_permissions(clock.unix_timestamp);
+    require!(
+        agent_account.has_permission(&AgentPermission::Staking),
+        DimmError::InsufficientPermissions
+    );
+
+    let soft_limit_crossed = agent_account.charge_spend(
+        &ctx.accounts.protocol_config,
+        usd_price.as_ref(),
+        amount,
+        clock.unix_timestamp,
+    )?;
+    if soft_limit_crossed {
+        emit!(SoftLimitExceeded {
+            agent: agent_account.key(),
+            soft_daily_limit: agent_account.soft_daily_limit,
+            spent_today: agent_account.spent_today,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    let required_balance = amount
+        .checked_add(MIN_AGENT_BALANCE)
+        .and_then(|v| v.checked_add(agent_account.fee_tank))
+        .ok_or(DimmError::NumericalOverflow)?;
+    require!(
+        agent_account.to_account_info().lamports() >= required_balance,
+        DimmError::InsufficientAgentBalance
+    );
+
+    // The pool pulls lamports with a system transfer, which the agent
+    // account cannot sign for, so they pass through an empty system PDA
+    **agent_account.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.deposit_funding.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    let agent_key = agent_account.key();
+    let mut data = Vec::with_capacity(1 + 8);
+    data.push(DEPOSIT_SOL_TAG);
+    data.extend_from_slice(&amount.to_le_bytes());
+    let instruction = Instruction {
+        program_id: STAKE_POOL_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(ctx.accounts.stake_pool.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.stake_pool_withdraw_authority.key(), false),
+            AccountMeta::new(ctx.accounts.reserve_stake.key(), false),
+            AccountMeta::new(ctx.accounts.deposit_funding.key(), true),
+            AccountMeta::new(ctx.accounts.pool_token_account.key(), false),
+            AccountMeta::new(ctx.accounts.manager_fee_account.key(), false),
+            AccountMeta::new(ctx.accounts.referrer_pool_tokens_account.key(), false),
+            AccountMeta::new(ctx.accounts.pool_mint.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),
+        ],
+        data,
+    };
+    let funding_seeds = &[
+        SOL_DEPOSIT_SEED,
+        agent_key.as_ref(),
+        &[ctx.bumps.deposit_funding],
+    ];
+
+    let pool_tokens_before = ctx.accounts.pool_token_account.amount;
+    invoke_signed(
+        &instruction,
+        &[
+            ctx.accounts.stake_pool.to_account_info(),
+            ctx.accounts.stake_pool_withdraw_authority.to_account_info(),
+            ctx.accounts.reserve_stake.to_account_info(),
+            ctx.accounts.deposit_funding.to_account_info(),
+            ctx.accounts.pool_token_account.to_account_info(),
+            ctx.accounts.manager_fee_account.to_account_info(),
+            ctx.accounts.referrer_pool_tokens_account.to_account_info(),
+            ctx.accounts.pool_mint.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.stake_pool_program.to_account_info(),
+        ],
+        &[&funding_seeds[..]],
+    )?;
+
+    ctx.accounts.pool_token_account.reload()?;
+    let pool_tokens_received = ctx
+        .accounts
+        .pool_token_account
+        .amount
+        .saturating_sub(pool_tokens_before);
+    require!(
+        pool_tokens_received >= min_pool_tokens,
+        DimmError::InsufficientPoolTokens
+    );
+
+    let agent_account = &mut ctx.accounts.agent_account;
+    agent_account.total_transactions = agent_account
+        .total_transactions
+        .checked_add(1)
+        .ok_or(DimmError::NumericalOverflow)?;
+    agent_account.last_used_at = clock.unix_timestamp;
+
+    if let Some(mut stats) = AgentStats::load(&ctx.accounts.stats)? {
+        stats.record_activity_time(clock.unix_timestamp);
+        stats.record_transaction(amount, true, &ActivityType::Staking)?;
+        stats.store(&ctx.accounts.stats)?;
+    }
+
+    emit!(StakePoolDeposited {
+        agent: agent_key,
+        stake_pool: ctx.accounts.stake_pool.key(),
+        pool_mint: ctx.accounts.pool_mint.key(),
+        amount,
+        pool_tokens_received,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Stake pool deposit");
+    msg!("Agent: {}", agent_key);
+    msg!("Deposited: {} lamports", amount);
+    msg!("Received: {} pool tokens", pool_tokens_received);
+
+    Ok(())
+}
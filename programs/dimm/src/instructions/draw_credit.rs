@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use crate::auth::{Caller, Capability};
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct DrawCredit<'info> {
+    #[account(
+        seeds = [PROTOCOL_SEED, main_wallet.key().as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        seeds = [CREDIT_LINE_SEED, agent_account.key().as_ref()],
+        bump = credit_line.bump,
+        constraint = credit_line.agent == agent_account.key() @ DimmError::Unauthorized,
+        constraint = credit_line.treasury == treasury.key() @ DimmError::Unauthorized
+    )]
+    pub credit_line: Account<'info, CreditLine>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, main_wallet.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// CHECK: Owner of the agent; checked by `has_one` on the agent account
+    pub main_wallet: UncheckedAccount<'info>,
+
+    /// Main wallet or the agent's own signer
+    pub signer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<DrawCredit>, amount: u64) -> Result<()> {
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
+    require!(amount > 0, DimmError::InvalidAmount);
+    Caller::authorize(
+        ctx.accounts.signer.key(),
+        &ctx.accounts.main_wallet.key(),
+        Some(&ctx.accounts.agent_account),
+        None,
+        Capability::Execute,
+    )?;
+    require!(!ctx.accounts.agent_account.revoked, DimmError::AgentRevoked);
+
+    let clock = Clock::get()?;
+    require!(
+        !ctx.accounts.agent_account.is_frozen(clock.unix_timestamp),
+        DimmError::AgentFrozen
+    );
+
+    let credit_line = &mut ctx.accounts.credit_line;
+    if credit_line.frozen {
+        return err!(DimmError::CreditLineFrozen);
+    }
+
+    // A missed repayment freezes the line and the agent. It must return
+    // success, or the freeze would be rolled back with the draw.
+    credit_line.check_repayment_schedule(clock.unix_timestamp)?;
+    if credit_line.frozen {
+        ctx.accounts.agent_account.frozen_until = i64::MAX;
+
+        msg!("Credit line frozen: repayment missed");
+        msg!("Agent frozen until the main wallet unfreezes it");
+        msg!("Agent: {}", ctx.accounts.agent_account.key());
+        return Ok(());
+    }
+
+    credit_line.record_draw(amount, clock.unix_timestamp)?;
+
+    // Treasury must stay rent exempt after the draw
+    let treasury_info = ctx.accounts.treasury.to_account_info();
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(treasury_info.data_len());
+    let available_balance = treasury_info
+        .lamports()
+        .checked_sub(rent_exempt_minimum)
+        .ok_or(DimmError::InsufficientTreasuryBalance)?;
+
+    require!(
+        amount <= available_balance,
+        DimmError::InsufficientTreasuryBalance
+    );
+
+    // Both accounts are owned by this program, so lamports move directly
+    **treasury_info.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.agent_account.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_distributed = treasury
+        .total_distributed
+        .checked_add(amount)
+        .ok_or(DimmError::NumericalOverflow)?;
+
+    msg!("Credit drawn");
+    msg!("Agent: {}", ctx.accounts.agent_account.key());
+    msg!("Amount: {} lamports", amount);
+    msg!("Outstanding: {} lamports", ctx.accounts.credit_line.outstanding);
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::auth::{Caller, Capability};
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct EmergencyPause<'info> {
+    #[account(
+        mut,
+        seeds = [EMERGENCY_SEED, emergency_state.authority.as_ref()],
+        bump = emergency_state.bump
+    )]
+    pub emergency_state: Account<'info, EmergencyState>,
+
+    /// Authority or one of its emergency contacts
+    pub caller: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<EmergencyPause>, reason: String) -> Result<()> {
+    Caller::authorize(
+        ctx.accounts.caller.key(),
+        &ctx.accounts.emergency_state.authority,
+        None,
+        Some(&ctx.accounts.emergency_state),
+        Capability::EmergencyAction,
+    )?;
+    require!(
+        reason.len() <= EmergencyState::MAX_REASON_LENGTH,
+        DimmError::ReasonTooLong
+    );
+
+    let emergency_state = &mut ctx.accounts.emergency_state;
+    let clock = Clock::get()?;
+
+    emergency_state.paused = true;
+    emergency_state.pause_reason = reason;
+    emergency_state.paused_at = clock.unix_timestamp;
+    emergency_state.paused_by = ctx.accounts.caller.key();
+    emergency_state.pause_count = emergency_state
+        .pause_count
+        .checked_add(1)
+        .ok_or(DimmError::NumericalOverflow)?;
+
+    msg!("Emergency pause activated");
+    msg!("Authority: {}", emergency_state.authority);
+    msg!("Paused by: {}", emergency_state.paused_by);
+    msg!("Reason: {}", emergency_state.pause_reason);
+
+    Ok(())
+}
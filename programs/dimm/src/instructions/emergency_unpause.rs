@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::auth::{Caller, Capability};
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct EmergencyUnpause<'info> {
+    #[account(
+        mut,
+        seeds = [EMERGENCY_SEED, emergency_state.authority.as_ref()],
+        bump = emergency_state.bump
+    )]
+    pub emergency_state: Account<'info, EmergencyState>,
+
+    /// Authority or one of its emergency contacts
+    pub caller: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<EmergencyUnpause>) -> Result<()> {
+    Caller::authorize(
+        ctx.accounts.caller.key(),
+        &ctx.accounts.emergency_state.authority,
+        None,
+        Some(&ctx.accounts.emergency_state),
+        Capability::EmergencyAction,
+    )?;
+    let emergency_state = &mut ctx.accounts.emergency_state;
+
+    emergency_state.paused = false;
+    emergency_state.pause_reason = String::new();
+
+    msg!("Emergency pause lifted");
+    msg!("Authority: {}", emergency_state.authority);
+    msg!("Unpaused by: {}", ctx.accounts.caller.key());
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::events::InactivityEnforced;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct EnforceInactivity<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// A revoked agent leaves the wallet's active count and aggregate
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED, agent_account.main_wallet.as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Anyone; receives the keeper reward
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+}
+
+/// Revoke or freeze an agent that has been inactive past its timeout.
+/// Permissionless; the keeper is paid the agent's reward, capped at what
+/// the agent holds above its rent, `MIN_AGENT_BALANCE` and fee tank.
+pub fn handler(ctx: Context<EnforceInactivity>) -> Result<()> {
+    let agent_account = &mut ctx.accounts.agent_account;
+    let clock = Clock::get()?;
+
+    require!(!agent_account.revoked, DimmError::AgentRevoked);
+    require!(
+        !agent_account.is_frozen(clock.unix_timestamp),
+        DimmError::AgentFrozen
+    );
+    let inactive_after = agent_account
+        .inactive_after()
+        .ok_or(DimmError::InactivityNotEnforceable)?;
+    require!(
+        clock.unix_timestamp > inactive_after,
+        DimmError::InactivityNotEnforceable
+    );
+
+    match agent_account.inactivity_action {
+        InactivityAction::Revoke => {
+            agent_account.revoked = true;
+            agent_account.revoked_at = clock.unix_timestamp;
+            ctx.accounts.protocol_config.release_agent(agent_account.daily_limit);
+        }
+        InactivityAction::Freeze => {
+            agent_account.frozen_until = i64::MAX;
+        }
+    }
+
+    let agent_info = agent_account.to_account_info();
+    let reserve = Rent::get()?
+        .minimum_balance(agent_info.data_len())
+        .saturating_add(MIN_AGENT_BALANCE)
+        .saturating_add(agent_account.fee_tank);
+    let reward = agent_account
+        .inactivity_keeper_reward
+        .min(agent_info.lamports().saturating_sub(reserve));
+    if reward > 0 {
+        **agent_info.try_borrow_mut_lamports()? -= reward;
+        **ctx.accounts.keeper.to_account_info().try_borrow_mut_lamports()? += reward;
+    }
+
+    emit!(InactivityEnforced {
+        agent: agent_account.key(),
+        action: agent_account.inactivity_action,
+        last_used_at: agent_account.last_used_at,
+        keeper: ctx.accounts.keeper.key(),
+        reward,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Inactivity enforced");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Action: {:?}", agent_account.inactivity_action);
+    msg!("Keeper reward: {} lamports", reward);
+
+    Ok(())
+}
@@ -0,0 +1,431 @@
+use anchor_lang::prelude::*;
+use crate::auth::{Caller, Capability};
+use crate::errors::DimmError;
+use crate::events::{ExecutionReceipt, SoftLimitExceeded};
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ExecuteBatch<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        seeds = [PROTOCOL_SEED, agent_account.main_wallet.as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Main wallet or the agent's own signer key
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Required when the agent enforces destination whitelisting
+    #[account(
+        seeds = [
+            WHITELIST_SEED,
+            agent_account.key().as_ref(),
+            &WhitelistType::Destinations.seed()
+        ],
+        bump = destination_whitelist.bump
+    )]
+    pub destination_whitelist: Option<Account<'info, Whitelist>>,
+
+    /// CHECK: Agent's destination blacklist; may be uninitialized
+    #[account(
+        seeds = [BLACKLIST_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet's emergency state; may be uninitialized
+    #[account(
+        seeds = [EMERGENCY_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub emergency_state: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's activity schedule; may be uninitialized
+    #[account(
+        seeds = [SCHEDULE_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub schedule: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet's treasury, collecting protocol fees; may be uninitialized
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's policy hook registry; may be uninitialized
+    #[account(
+        seeds = [HOOK_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub hook_registry: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's stats; may be uninitialized
+    #[account(
+        mut,
+        seeds = [STATS_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet's activity digest; may be uninitialized
+    #[account(
+        mut,
+        seeds = [DIGEST_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub digest: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet's budget across all agents; may be uninitialized
+    #[account(
+        mut,
+        seeds = [WALLET_BUDGET_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub wallet_budget: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's address book of risk tiers; may be uninitialized
+    #[account(
+        mut,
+        seeds = [ADDRESS_BOOK_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub address_book: UncheckedAccount<'info>,
+
+    /// Runtime holding the agent's execution lease; required at or above the lease threshold
+    pub lease_holder: Option<Signer<'info>>,
+
+    /// CHECK: Protocol's Pyth SOL/USD price account; required if the agent has USD limits
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Agent's snapshot for the current day, opened with `crank_snapshot`
+    #[account(mut)]
+    pub snapshot: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Execute up to `MAX_BATCH_LEGS` SOL legs atomically; any failing leg
+/// fails the whole instruction. Each leg is checked on its own against
+/// permissions, whitelist and the per-transaction limit, and the legs
+/// together against the daily limit and balance, before anything moves.
+/// Transfer destinations are passed as remaining accounts.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteBatch<'info>>,
+    legs: Vec<ExecuteTransactionParams>,
+) -> Result<()> {
+    require!(
+        !legs.is_empty() && legs.len() <= MAX_BATCH_LEGS,
+        DimmError::InvalidBatchSize
+    );
+
+    Caller::authorize(
+        ctx.accounts.authority.key(),
+        &ctx.accounts.agent_account.main_wallet,
+        Some(&*ctx.accounts.agent_account),
+        None,
+        Capability::Execute,
+    )?;
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
+    EmergencyState::require_not_paused(&ctx.accounts.emergency_state)?;
+
+    // Hooks approve and observe one action at a time
+    if let Some(hook_registry) = HookRegistry::load(&ctx.accounts.hook_registry)? {
+        require!(
+            hook_registry.hooks.is_empty(),
+            DimmError::HooksRequireSingleExecution
+        );
+    }
+
+    let agent_account = &mut ctx.accounts.agent_account;
+    let clock = Clock::get()?;
+
+    require!(!agent_account.revoked, DimmError::AgentRevoked);
+    require!(
+        !agent_account.is_frozen(clock.unix_timestamp),
+        DimmError::AgentFrozen
+    );
+    ActivitySchedule::require_active(&ctx.accounts.schedule, clock.unix_timestamp)?;
+    require!(
+        agent_account.is_within_active_hours(clock.unix_timestamp),
+        DimmError::OutsideActiveHours
+    );
+    require!(
+        !agent_account.soft_limit_pending_ack,
+        DimmError::SoftLimitNotAcknowledged
+    );
+
+    agent_account.check_and_reset_daily_limit(clock.unix_timestamp)?;
+    agent_account.expire_permissions(clock.unix_timestamp);
+
+    let mut treasury = Treasury::load(&ctx.accounts.treasury)?;
+    let mut address_book = AddressBook::load(&ctx.accounts.address_book)?;
+    let min_transfer_amount = agent_account.min_transfer_amount()?;
+    let usd_price = if agent_account.has_usd_limits() {
+        let price_feed = ctx.accounts.price_feed.as_ref().ok_or(DimmError::PriceFeedRequired)?;
+        Some(SolUsdPrice::load(
+            price_feed,
+            &ctx.accounts.protocol_config.sol_usd_price_feed,
+            clock.unix_timestamp,
+        )?)
+    } else {
+        None
+    };
+
+    // Validate every leg before any funds move
+    let mut budget_total: u64 = 0;
+    let mut total_amount: u64 = 0;
+    let mut total_out: u64 = 0;
+    let mut fees = Vec::with_capacity(legs.len());
+    for leg in legs.iter() {
+        require!(
+            leg.mint.is_none() && leg.instruction_data.is_empty() && leg.memo.is_none(),
+            DimmError::InvalidBatchLeg
+        );
+        agent_account.consume_nonce(leg.expected_nonce)?;
+
+        if let Some(required_permission) = leg.required_permission() {
+            require!(
+                agent_account.has_permission(&required_permission),
+                DimmError::InsufficientPermissions
+            );
+        }
+
+        if let Some(destination) = &leg.destination {
+            Blacklist::require_not_blacklisted(&ctx.accounts.blacklist, destination)?;
+            if agent_account.require_destination_whitelist {
+                let whitelist = ctx
+                    .accounts
+                    .destination_whitelist
+                    .as_ref()
+                    .ok_or(DimmError::WhitelistRequired)?;
+                require!(
+                    whitelist.is_whitelisted(destination),
+                    DimmError::DestinationNotWhitelisted
+                );
+            }
+        }
+
+        if leg.activity_type == ActivityType::Transfer && leg.amount > 0 {
+            require!(
+                leg.amount >= min_transfer_amount,
+                DimmError::BelowMinTransferAmount
+            );
+        }
+
+        // Proposals are approved and co-signed one transaction at a time
+        require!(
+            !agent_account.requires_approval(leg.amount),
+            DimmError::ApprovalRequired
+        );
+        require!(
+            agent_account.required_co_signers(leg.amount) == 0,
+            DimmError::CoSignersRequired
+        );
+
+        let is_allowance_spend = matches!(
+            leg.activity_type,
+            ActivityType::Maintenance | ActivityType::Donation
+        );
+        if leg.amount > 0 && !is_allowance_spend {
+            require!(
+                leg.amount <= agent_account.max_sol_per_transaction,
+                DimmError::ExceedsTransactionLimit
+            );
+            ctx.accounts
+                .protocol_config
+                .check_cold_start(agent_account, leg.amount, clock.unix_timestamp)?;
+            if let Some(price) = &usd_price {
+                agent_account.record_usd_spend(price, leg.amount)?;
+            }
+            if let Some(destination) = &leg.destination {
+                if let Some(address_book) = address_book.as_mut() {
+                    address_book.record_spend(destination, leg.amount, clock.unix_timestamp)?;
+                }
+
+                // Legs to the same destination accumulate in its account
+                let limit_address = DestinationLimit::address(&agent_account.key(), destination);
+                let limit_info = ctx
+                    .remaining_accounts
+                    .iter()
+                    .find(|info| info.key() == limit_address)
+                    .ok_or(DimmError::DestinationLimitAccountMissing)?;
+                if let Some(mut destination_limit) = DestinationLimit::load(limit_info)? {
+                    destination_limit.record_spend(leg.amount, clock.unix_timestamp)?;
+                    destination_limit.store(limit_info)?;
+                }
+            }
+            budget_total = budget_total
+                .checked_add(leg.amount)
+                .ok_or(DimmError::NumericalOverflow)?;
+        }
+
+        let fee = match &treasury {
+            Some(treasury) => treasury.calculate_fee(leg.amount)?,
+            None => 0,
+        };
+        fees.push(fee);
+        total_amount = total_amount
+            .checked_add(leg.amount)
+            .ok_or(DimmError::NumericalOverflow)?;
+        total_out = total_out
+            .checked_add(leg.amount)
+            .and_then(|v| v.checked_add(fee))
+            .ok_or(DimmError::NumericalOverflow)?;
+    }
+
+    agent_account.require_lease(
+        ctx.accounts.lease_holder.as_ref().map(|holder| holder.key()),
+        total_amount,
+        clock.unix_timestamp,
+    )?;
+
+    // The legs together count once against the daily and trial limits
+    require!(
+        agent_account.can_spend(budget_total)?,
+        DimmError::ExceedsDailyLimit
+    );
+    require!(
+        agent_account.within_trial_limits(budget_total, clock.unix_timestamp)?,
+        DimmError::TrialLimitExceeded
+    );
+    require!(
+        agent_account.within_hourly_limit(budget_total)?,
+        DimmError::ExceedsHourlyLimit
+    );
+    WalletBudget::charge(&ctx.accounts.wallet_budget, budget_total, clock.unix_timestamp)?;
+
+    let required_balance = total_out
+        .checked_add(MIN_AGENT_BALANCE)
+        .and_then(|v| v.checked_add(agent_account.fee_tank))
+        .ok_or(DimmError::NumericalOverflow)?;
+    require!(
+        agent_account.to_account_info().lamports() >= required_balance,
+        DimmError::InsufficientAgentBalance
+    );
+
+    let mut stats = AgentStats::load(&ctx.accounts.stats)?;
+    let mut digest = WalletDigest::load(&ctx.accounts.digest)?;
+    let mut snapshot = match &ctx.accounts.snapshot {
+        Some(info) => Some(DailySnapshot::load_current(info, &agent_account.key(), clock.unix_timestamp)?),
+        None => None,
+    };
+    let spent_before = agent_account.spent_today;
+
+    for (leg, fee) in legs.iter().zip(fees) {
+        let is_maintenance = leg.activity_type == ActivityType::Maintenance;
+        let is_donation = leg.activity_type == ActivityType::Donation;
+        let is_transfer =
+            leg.activity_type == ActivityType::Transfer || is_maintenance || is_donation;
+
+        if leg.amount > 0 {
+            if let (true, Some(destination)) = (is_transfer, leg.destination) {
+                let destination_info = ctx
+                    .remaining_accounts
+                    .iter()
+                    .find(|info| info.key() == destination && info.is_writable)
+                    .ok_or(DimmError::DestinationAccountMissing)?;
+
+                // The agent account is owned by this program, so lamports move directly
+                **agent_account.to_account_info().try_borrow_mut_lamports()? -= leg.amount;
+                **destination_info.try_borrow_mut_lamports()? += leg.amount;
+            }
+
+            if is_maintenance {
+                agent_account.record_maintenance_spend(leg.amount)?;
+            } else if is_donation {
+                agent_account.record_donation_spend(leg.amount)?;
+            } else {
+                agent_account.record_spend(leg.amount)?;
+            }
+        }
+
+        if let Some(treasury) = &mut treasury {
+            if fee > 0 {
+                **agent_account.to_account_info().try_borrow_mut_lamports()? -= fee;
+                **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? += fee;
+            }
+            treasury.record_fee(fee, clock.unix_timestamp)?;
+        }
+
+        if let Some(stats) = &mut stats {
+            stats.record_activity_time(clock.unix_timestamp);
+            stats.record_transaction(leg.amount, true, &leg.activity_type)?;
+            stats.record_gas(leg.fee_lamports);
+            if let Some(destination) = &leg.destination {
+                stats.record_destination(destination)?;
+            }
+            if !is_maintenance && !is_donation {
+                stats.record_limit_hits(leg.amount, agent_account)?;
+            }
+        }
+
+        if let Some(snapshot) = &mut snapshot {
+            snapshot.record_transaction(leg.amount)?;
+        }
+
+        if let Some(digest) = &mut digest {
+            digest.record_execution(agent_account.key(), leg.amount, fee, false, clock.unix_timestamp)?;
+        }
+
+        emit!(ExecutionReceipt {
+            agent: agent_account.key(),
+            sequence: agent_account.next_receipt()?,
+            activity_type: leg.activity_type.clone(),
+            amount: leg.amount,
+            mint: None,
+            destination: leg.destination,
+            fee,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    if let Some(treasury) = &treasury {
+        treasury.store(&ctx.accounts.treasury)?;
+    }
+    if let Some(stats) = &stats {
+        stats.store(&ctx.accounts.stats)?;
+    }
+    if let Some(digest) = &digest {
+        digest.store(&ctx.accounts.digest)?;
+    }
+    if let (Some(snapshot), Some(info)) = (&snapshot, &ctx.accounts.snapshot) {
+        snapshot.store(info)?;
+    }
+    if let Some(address_book) = &address_book {
+        address_book.store(&ctx.accounts.address_book)?;
+    }
+
+    if agent_account.record_soft_limit_crossing(spent_before) {
+        emit!(SoftLimitExceeded {
+            agent: agent_account.key(),
+            soft_daily_limit: agent_account.soft_daily_limit,
+            spent_today: agent_account.spent_today,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    agent_account.last_used_at = clock.unix_timestamp;
+
+    msg!("Batch executed successfully");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Legs: {}", legs.len());
+    msg!("Total spent today: {} lamports", agent_account.spent_today);
+
+    Ok(())
+}
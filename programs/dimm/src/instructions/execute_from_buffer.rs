@@ -0,0 +1,198 @@
+use anchor_lang::prelude::*;
+use crate::agent_cpi;
+use crate::auth::{Caller, Capability};
+use crate::errors::DimmError;
+use crate::events::{AnomalyDetected, ExecutionReceipt};
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ExecuteFromBuffer<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        seeds = [PROTOCOL_SEED, agent_account.main_wallet.as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Consumed and closed on execution
+    #[account(
+        mut,
+        seeds = [EXECUTION_BUFFER_SEED, agent_account.key().as_ref()],
+        bump = execution_buffer.bump,
+        constraint = execution_buffer.agent == agent_account.key() @ DimmError::Unauthorized,
+        close = authority
+    )]
+    pub execution_buffer: Account<'info, ExecutionBuffer>,
+
+    /// CHECK: Must match the buffer's target program
+    #[account(executable)]
+    pub target_program: UncheckedAccount<'info>,
+
+    /// Programs the agent may invoke
+    #[account(
+        seeds = [
+            WHITELIST_SEED,
+            agent_account.key().as_ref(),
+            &WhitelistType::Programs.seed()
+        ],
+        bump = program_whitelist.bump
+    )]
+    pub program_whitelist: Account<'info, Whitelist>,
+
+    /// CHECK: Protocol's program registry; may be uninitialized
+    #[account(
+        seeds = [PROGRAM_REGISTRY_SEED, protocol_config.key().as_ref()],
+        bump
+    )]
+    pub program_registry: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's scoped program permissions; may be uninitialized
+    #[account(
+        seeds = [SCOPED_PERMISSIONS_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub scoped_permissions: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's activity schedule; may be uninitialized
+    #[account(
+        seeds = [SCHEDULE_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub schedule: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet's emergency state; may be uninitialized
+    #[account(
+        seeds = [EMERGENCY_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub emergency_state: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's stats; may be uninitialized
+    #[account(
+        seeds = [STATS_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+
+    /// Main wallet or the agent's own signer key
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Accounts for the buffered instruction are passed as remaining accounts,
+/// in order; the agent signs wherever it appears among them.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ExecuteFromBuffer<'info>>) -> Result<()> {
+    Caller::authorize(
+        ctx.accounts.authority.key(),
+        &ctx.accounts.agent_account.main_wallet,
+        Some(&*ctx.accounts.agent_account),
+        None,
+        Capability::Execute,
+    )?;
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
+    EmergencyState::require_not_paused(&ctx.accounts.emergency_state)?;
+
+    let clock = Clock::get()?;
+    ctx.accounts.agent_account.expire_permissions(clock.unix_timestamp);
+
+    let agent_account = &mut ctx.accounts.agent_account;
+
+    require!(!agent_account.revoked, DimmError::AgentRevoked);
+    require!(
+        !agent_account.is_frozen(clock.unix_timestamp),
+        DimmError::AgentFrozen
+    );
+    ActivitySchedule::require_active(&ctx.accounts.schedule, clock.unix_timestamp)?;
+    require!(
+        agent_account.is_within_active_hours(clock.unix_timestamp),
+        DimmError::OutsideActiveHours
+    );
+
+    // As in execute_transaction, an anomalous execution freezes the agent
+    // and returns success so the freeze is kept
+    let stats = AgentStats::load(&ctx.accounts.stats)?;
+    if let Some(kind) = agent_account.detect_anomaly(stats.as_ref(), 0, clock.unix_timestamp) {
+        agent_account.frozen_until = i64::MAX;
+        emit!(AnomalyDetected {
+            agent: agent_account.key(),
+            kind,
+            amount: 0,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Anomaly detected; agent frozen until the main wallet unfreezes it");
+        msg!("Agent: {}", agent_account.key());
+        msg!("Rule: {:?}", kind);
+        return Ok(());
+    }
+    require!(
+        !agent_account.soft_limit_pending_ack,
+        DimmError::SoftLimitNotAcknowledged
+    );
+
+    let agent_account = &ctx.accounts.agent_account;
+    let execution_buffer = &ctx.accounts.execution_buffer;
+    require!(
+        agent_account.has_permission(&AgentPermission::ExecutePrograms),
+        DimmError::InsufficientPermissions
+    );
+
+    require!(execution_buffer.is_complete(), DimmError::ExecutionBufferIncomplete);
+    require_keys_eq!(
+        ctx.accounts.target_program.key(),
+        execution_buffer.target_program,
+        DimmError::ExecutionBufferMismatch
+    );
+    ctx.accounts
+        .program_whitelist
+        .require_program(&execution_buffer.target_program)?;
+    ProgramRegistry::require_labeled(
+        &ctx.accounts.program_registry,
+        ctx.accounts.protocol_config.restrict_unlabeled_programs,
+        &execution_buffer.target_program,
+    )?;
+    ScopedPermissions::require_scope(
+        &ctx.accounts.scoped_permissions,
+        &execution_buffer.target_program,
+        0,
+    )?;
+
+    agent_cpi::invoke_as_agent(
+        agent_account,
+        &ctx.accounts.target_program.to_account_info(),
+        execution_buffer.data.clone(),
+        ctx.remaining_accounts,
+    )?;
+
+    let agent_account = &mut ctx.accounts.agent_account;
+    agent_account.last_used_at = clock.unix_timestamp;
+
+    emit!(ExecutionReceipt {
+        agent: agent_account.key(),
+        sequence: agent_account.next_receipt()?,
+        activity_type: ActivityType::Other,
+        amount: 0,
+        mint: None,
+        destination: None,
+        fee: 0,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Buffered instruction executed");
+    msg!("Agent: {}", ctx.accounts.agent_account.key());
+    msg!("Program: {}", ctx.accounts.target_program.key());
+    msg!("Data: {} bytes", ctx.accounts.execution_buffer.total_len);
+
+    Ok(())
+}
@@ -0,0 +1,318 @@
+use anchor_lang::prelude::*;
+use crate::agent_cpi;
+use crate::auth::{Caller, Capability};
+use crate::errors::DimmError;
+use crate::events::{ExecutionReceipt, PlanStepExecuted, SoftLimitExceeded};
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ExecutePlanStep<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        seeds = [PROTOCOL_SEED, agent_account.main_wallet.as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [PLAN_SEED, agent_account.key().as_ref(), &plan.plan_id.to_le_bytes()],
+        bump = plan.bump,
+        constraint = plan.agent == agent_account.key() @ DimmError::Unauthorized
+    )]
+    pub plan: Box<Account<'info, Plan>>,
+
+    /// Main wallet or the agent's own signer key
+    pub authority: Signer<'info>,
+
+    /// CHECK: Must match the step's destination
+    #[account(mut)]
+    pub destination: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Must match the step's target program
+    pub target_program: Option<UncheckedAccount<'info>>,
+
+    /// Required when the agent enforces destination whitelisting
+    #[account(
+        seeds = [
+            WHITELIST_SEED,
+            agent_account.key().as_ref(),
+            &WhitelistType::Destinations.seed()
+        ],
+        bump = destination_whitelist.bump
+    )]
+    pub destination_whitelist: Option<Box<Account<'info, Whitelist>>>,
+
+    /// Programs the agent may invoke; required for program-call steps
+    #[account(
+        seeds = [
+            WHITELIST_SEED,
+            agent_account.key().as_ref(),
+            &WhitelistType::Programs.seed()
+        ],
+        bump = program_whitelist.bump
+    )]
+    pub program_whitelist: Option<Box<Account<'info, Whitelist>>>,
+
+    /// CHECK: Protocol's program registry; may be uninitialized
+    #[account(
+        seeds = [PROGRAM_REGISTRY_SEED, protocol_config.key().as_ref()],
+        bump
+    )]
+    pub program_registry: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's scoped program permissions; may be uninitialized
+    #[account(
+        seeds = [SCOPED_PERMISSIONS_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub scoped_permissions: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's destination blacklist; may be uninitialized
+    #[account(
+        seeds = [BLACKLIST_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet's emergency state; may be uninitialized
+    #[account(
+        seeds = [EMERGENCY_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub emergency_state: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's activity schedule; may be uninitialized
+    #[account(
+        seeds = [SCHEDULE_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub schedule: UncheckedAccount<'info>,
+
+    /// CHECK: Protocol's Pyth SOL/USD price account; required on the first
+    /// step if the agent has USD limits
+    pub price_feed: Option<UncheckedAccount<'info>>,
+}
+
+/// Execute the plan's next step and checkpoint it. The first step charges
+/// the plan's whole notional to the daily limit. Accounts for a program
+/// call are passed as remaining accounts, in order.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ExecutePlanStep<'info>>) -> Result<()> {
+    Caller::authorize(
+        ctx.accounts.authority.key(),
+        &ctx.accounts.agent_account.main_wallet,
+        Some(&*ctx.accounts.agent_account),
+        None,
+        Capability::Execute,
+    )?;
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
+    EmergencyState::require_not_paused(&ctx.accounts.emergency_state)?;
+
+    let clock = Clock::get()?;
+    let agent_account = &mut ctx.accounts.agent_account;
+
+    require!(!agent_account.revoked, DimmError::AgentRevoked);
+    require!(
+        !agent_account.is_frozen(clock.unix_timestamp),
+        DimmError::AgentFrozen
+    );
+    ActivitySchedule::require_active(&ctx.accounts.schedule, clock.unix_timestamp)?;
+    require!(
+        agent_account.is_within_active_hours(clock.unix_timestamp),
+        DimmError::OutsideActiveHours
+    );
+    require!(
+        !agent_account.soft_limit_pending_ack,
+        DimmError::SoftLimitNotAcknowledged
+    );
+
+    let plan = &ctx.accounts.plan;
+    require!(plan.is_active(), DimmError::PlanNotActive);
+    let step = plan.steps[plan.next_step as usize].clone();
+
+    // A timed grant that lapses mid-plan stops the remaining steps
+    agent_account.expire_permissions(clock.unix_timestamp);
+    if let Some(required_permission) = step.activity_type.required_permission() {
+        require!(
+            agent_account.has_permission(&required_permission),
+            DimmError::InsufficientPermissions
+        );
+    }
+
+    // The plan is budgeted as one spend of its aggregate notional
+    if plan.status == PlanStatus::Pending {
+        let total_notional = plan.total_notional;
+        // Bands may have been set since the plan was created
+        require!(
+            agent_account.required_co_signers(total_notional) == 0,
+            DimmError::CoSignersRequired
+        );
+        agent_account.check_and_reset_daily_limit(clock.unix_timestamp)?;
+        require!(
+            agent_account.can_spend(total_notional)?,
+            DimmError::ExceedsDailyLimit
+        );
+        require!(
+            agent_account.within_trial_limits(total_notional, clock.unix_timestamp)?,
+            DimmError::TrialLimitExceeded
+        );
+        require!(
+            agent_account.within_hourly_limit(total_notional)?,
+            DimmError::ExceedsHourlyLimit
+        );
+        ctx.accounts
+            .protocol_config
+            .check_cold_start(agent_account, total_notional, clock.unix_timestamp)?;
+        if agent_account.has_usd_limits() {
+            let price_feed = ctx.accounts.price_feed.as_ref().ok_or(DimmError::PriceFeedRequired)?;
+            let price = SolUsdPrice::load(
+                price_feed,
+                &ctx.accounts.protocol_config.sol_usd_price_feed,
+                clock.unix_timestamp,
+            )?;
+            agent_account.record_usd_spend(&price, total_notional)?;
+        }
+
+        let spent_before = agent_account.spent_today;
+        agent_account.record_spend(total_notional)?;
+        if agent_account.record_soft_limit_crossing(spent_before) {
+            emit!(SoftLimitExceeded {
+                agent: agent_account.key(),
+                soft_daily_limit: agent_account.soft_daily_limit,
+                spent_today: agent_account.spent_today,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+    }
+
+    let destination_info = match step.destination {
+        Some(destination) => {
+            let destination_info = ctx
+                .accounts
+                .destination
+                .as_ref()
+                .ok_or(DimmError::PlanStepMismatch)?;
+            require_keys_eq!(destination_info.key(), destination, DimmError::PlanStepMismatch);
+
+            Blacklist::require_not_blacklisted(&ctx.accounts.blacklist, &destination)?;
+            if agent_account.require_destination_whitelist {
+                let whitelist = ctx
+                    .accounts
+                    .destination_whitelist
+                    .as_ref()
+                    .ok_or(DimmError::WhitelistRequired)?;
+                require!(
+                    whitelist.is_whitelisted(&destination),
+                    DimmError::DestinationNotWhitelisted
+                );
+            }
+            Some(destination_info.to_account_info())
+        }
+        None => None,
+    };
+
+    match step.target_program {
+        Some(program_id) => {
+            let target_program = ctx
+                .accounts
+                .target_program
+                .as_ref()
+                .ok_or(DimmError::PlanStepMismatch)?;
+            require_keys_eq!(target_program.key(), program_id, DimmError::PlanStepMismatch);
+
+            let program_whitelist = ctx
+                .accounts
+                .program_whitelist
+                .as_ref()
+                .ok_or(DimmError::WhitelistRequired)?;
+            program_whitelist.require_program(&program_id)?;
+            ProgramRegistry::require_labeled(
+                &ctx.accounts.program_registry,
+                ctx.accounts.protocol_config.restrict_unlabeled_programs,
+                &program_id,
+            )?;
+            ScopedPermissions::require_scope(
+                &ctx.accounts.scoped_permissions,
+                &program_id,
+                step.amount,
+            )?;
+
+            // The call may only spend what the step declared
+            let balance_before = agent_account.to_account_info().lamports();
+            agent_cpi::invoke_as_agent(
+                agent_account,
+                &target_program.to_account_info(),
+                step.instruction_data.clone(),
+                ctx.remaining_accounts,
+            )?;
+            let balance_after = agent_account.to_account_info().lamports();
+            require!(
+                balance_before.saturating_sub(balance_after) <= step.amount,
+                DimmError::PlanStepOverspent
+            );
+        }
+        None => {
+            // Validated at creation: transfer steps always have a destination
+            let destination_info = destination_info.ok_or(DimmError::PlanStepMismatch)?;
+            let required_balance = step
+                .amount
+                .checked_add(MIN_AGENT_BALANCE)
+                .and_then(|v| v.checked_add(agent_account.fee_tank))
+                .ok_or(DimmError::NumericalOverflow)?;
+            require!(
+                agent_account.to_account_info().lamports() >= required_balance,
+                DimmError::InsufficientAgentBalance
+            );
+
+            // The agent account is owned by this program, so lamports move directly
+            **agent_account.to_account_info().try_borrow_mut_lamports()? -= step.amount;
+            **destination_info.try_borrow_mut_lamports()? += step.amount;
+        }
+    }
+
+    let agent_balance = agent_account.to_account_info().lamports();
+    let plan = &mut ctx.accounts.plan;
+    let step_index = plan.next_step;
+    plan.checkpoint(agent_balance, clock.unix_timestamp)?;
+
+    agent_account.last_used_at = clock.unix_timestamp;
+
+    emit!(ExecutionReceipt {
+        agent: agent_account.key(),
+        sequence: agent_account.next_receipt()?,
+        activity_type: step.activity_type,
+        amount: step.amount,
+        mint: None,
+        destination: step.destination,
+        fee: 0,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(PlanStepExecuted {
+        agent: agent_account.key(),
+        plan_id: plan.plan_id,
+        step: step_index,
+        amount: step.amount,
+        checkpoint_balance: agent_balance,
+        completed: plan.status == PlanStatus::Completed,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Plan step executed");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Plan ID: {}", plan.plan_id);
+    msg!("Step: {}/{}", plan.next_step, plan.steps.len());
+
+    Ok(())
+}
@@ -0,0 +1,376 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use crate::errors::DimmError;
+use crate::events::{AnomalyDetected, ExecutionReceipt, SoftLimitExceeded};
+use crate::hooks::{self, ExecutionSummary, HookAction};
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+#[instruction(intent: SignedIntent)]
+pub struct ExecuteSignedIntent<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        constraint = agent_account.key() == intent.agent @ DimmError::InvalidIntentSignature
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        seeds = [PROTOCOL_SEED, agent_account.main_wallet.as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Destinations the agent may send to; required if whitelisting is enabled
+    #[account(
+        seeds = [
+            WHITELIST_SEED,
+            agent_account.key().as_ref(),
+            &WhitelistType::Destinations.seed()
+        ],
+        bump = destination_whitelist.bump
+    )]
+    pub destination_whitelist: Option<Account<'info, Whitelist>>,
+
+    /// CHECK: Agent's destination blacklist; may be uninitialized
+    #[account(
+        seeds = [BLACKLIST_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet's emergency state; may be uninitialized
+    #[account(
+        seeds = [EMERGENCY_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub emergency_state: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's activity schedule; may be uninitialized
+    #[account(
+        seeds = [SCHEDULE_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub schedule: UncheckedAccount<'info>,
+
+    /// CHECK: Protocol's Pyth SOL/USD price account; required if the agent has USD limits
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Must match the intent's destination
+    #[account(
+        mut,
+        address = intent.destination @ DimmError::InvalidIntentSignature
+    )]
+    pub destination: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet's treasury, collecting protocol fees; may be uninitialized
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's policy hook registry; may be uninitialized
+    #[account(
+        seeds = [HOOK_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub hook_registry: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's stats; may be uninitialized
+    #[account(
+        mut,
+        seeds = [STATS_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet's budget across all agents; may be uninitialized
+    #[account(
+        mut,
+        seeds = [WALLET_BUDGET_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub wallet_budget: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's address book of risk tiers; may be uninitialized
+    #[account(
+        mut,
+        seeds = [ADDRESS_BOOK_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub address_book: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's limits towards `destination`; may be uninitialized
+    #[account(
+        mut,
+        seeds = [DESTINATION_LIMIT_SEED, agent_account.key().as_ref(), destination.key().as_ref()],
+        bump
+    )]
+    pub destination_limit: UncheckedAccount<'info>,
+
+    /// Runtime holding the agent's execution lease; required at or above the lease threshold
+    pub lease_holder: Option<Signer<'info>>,
+
+    /// Anyone submitting the intent; pays the transaction fee only
+    pub relayer: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, read for the Ed25519 verification
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+/// Must directly follow an Ed25519 program instruction verifying the agent
+/// signer's signature over `intent.message()`. The transfer is an ordinary
+/// spend against the agent's limits and policies; amounts needing approval
+/// or co-signers must go through the approval queue instead. Programs
+/// registered as hooks must be passed as remaining accounts.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteSignedIntent<'info>>,
+    intent: SignedIntent,
+) -> Result<()> {
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
+    EmergencyState::require_not_paused(&ctx.accounts.emergency_state)?;
+    require!(intent.amount > 0, DimmError::InvalidAmount);
+
+    let agent_account = &mut ctx.accounts.agent_account;
+    let clock = Clock::get()?;
+
+    require!(
+        agent_account.agent_signer != Pubkey::default(),
+        DimmError::InvalidIntentSignature
+    );
+    intent.verify(&ctx.accounts.instructions, &agent_account.agent_signer)?;
+    require!(clock.unix_timestamp < intent.expires_at, DimmError::IntentExpired);
+    require!(
+        intent.nonce == agent_account.intent_nonce,
+        DimmError::InvalidIntentNonce
+    );
+
+    require!(!agent_account.revoked, DimmError::AgentRevoked);
+    require!(
+        !agent_account.is_frozen(clock.unix_timestamp),
+        DimmError::AgentFrozen
+    );
+    ActivitySchedule::require_active(&ctx.accounts.schedule, clock.unix_timestamp)?;
+    require!(
+        agent_account.is_within_active_hours(clock.unix_timestamp),
+        DimmError::OutsideActiveHours
+    );
+
+    // An anomalous intent freezes the agent instead of running. It must
+    // return success, or the freeze would be rolled back with it.
+    let stats = AgentStats::load(&ctx.accounts.stats)?;
+    if let Some(kind) = agent_account.detect_anomaly(stats.as_ref(), intent.amount, clock.unix_timestamp) {
+        agent_account.frozen_until = i64::MAX;
+        emit!(AnomalyDetected {
+            agent: agent_account.key(),
+            kind,
+            amount: intent.amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Anomaly detected; agent frozen until the main wallet unfreezes it");
+        msg!("Agent: {}", agent_account.key());
+        msg!("Rule: {:?}", kind);
+        return Ok(());
+    }
+
+    agent_account.expire_permissions(clock.unix_timestamp);
+    require!(
+        agent_account.has_permission(&AgentPermission::TransferSol),
+        DimmError::InsufficientPermissions
+    );
+
+    if agent_account.require_destination_whitelist {
+        let whitelist = ctx
+            .accounts
+            .destination_whitelist
+            .as_ref()
+            .ok_or(DimmError::WhitelistRequired)?;
+        require!(
+            whitelist.is_whitelisted(&intent.destination),
+            DimmError::DestinationNotWhitelisted
+        );
+    }
+    Blacklist::require_not_blacklisted(&ctx.accounts.blacklist, &intent.destination)?;
+    require!(
+        intent.amount >= agent_account.min_transfer_amount()?,
+        DimmError::BelowMinTransferAmount
+    );
+
+    // Intents cannot carry a proposal, so approval and co-signing rule them out
+    require!(
+        !agent_account.requires_approval(intent.amount),
+        DimmError::ApprovalRequired
+    );
+    require!(
+        agent_account.required_co_signers(intent.amount) == 0,
+        DimmError::CoSignersRequired
+    );
+    agent_account.require_lease(
+        ctx.accounts.lease_holder.as_ref().map(|holder| holder.key()),
+        intent.amount,
+        clock.unix_timestamp,
+    )?;
+
+    let mut treasury = Treasury::load(&ctx.accounts.treasury)?;
+    let fee = match &treasury {
+        Some(treasury) => treasury.calculate_fee(intent.amount)?,
+        None => 0,
+    };
+
+    let usd_price = match &ctx.accounts.price_feed {
+        Some(price_feed) => Some(SolUsdPrice::load(
+            price_feed,
+            &ctx.accounts.protocol_config.sol_usd_price_feed,
+            clock.unix_timestamp,
+        )?),
+        None => None,
+    };
+    let soft_limit_crossed = agent_account.charge_spend(
+        &ctx.accounts.protocol_config,
+        usd_price.as_ref(),
+        intent.amount,
+        clock.unix_timestamp,
+    )?;
+    if soft_limit_crossed {
+        emit!(SoftLimitExceeded {
+            agent: agent_account.key(),
+            soft_daily_limit: agent_account.soft_daily_limit,
+            spent_today: agent_account.spent_today,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    WalletBudget::charge(&ctx.accounts.wallet_budget, intent.amount, clock.unix_timestamp)?;
+
+    let mut address_book = AddressBook::load(&ctx.accounts.address_book)?;
+    if let Some(address_book) = address_book.as_mut() {
+        address_book.record_spend(&intent.destination, intent.amount, clock.unix_timestamp)?;
+    }
+    let mut destination_limit = DestinationLimit::load(&ctx.accounts.destination_limit)?;
+    if let Some(destination_limit) = destination_limit.as_mut() {
+        destination_limit.record_spend(intent.amount, clock.unix_timestamp)?;
+    }
+
+    // Let the owner's policy programs approve the transfer before funds move
+    let hook_registry = HookRegistry::load(&ctx.accounts.hook_registry)?;
+    if let Some(hook_registry) = &hook_registry {
+        let action = HookAction {
+            agent: agent_account.key(),
+            main_wallet: agent_account.main_wallet,
+            activity_type: ActivityType::Transfer,
+            amount: intent.amount,
+            destination: Some(intent.destination),
+            spent_today: agent_account.spent_today,
+            timestamp: clock.unix_timestamp,
+        };
+
+        for hook in hook_registry.approval_hooks() {
+            let hook_program = hooks::find_hook_program(ctx.remaining_accounts, &hook.program_id)?;
+            hooks::invoke_approval_hook(
+                &hook_program,
+                &agent_account.to_account_info(),
+                &ctx.accounts.destination.to_account_info(),
+                &action,
+            )?;
+        }
+    }
+
+    let agent_info = agent_account.to_account_info();
+    let required_balance = intent.amount
+        .checked_add(fee)
+        .and_then(|v| v.checked_add(MIN_AGENT_BALANCE))
+        .and_then(|v| v.checked_add(agent_account.fee_tank))
+        .ok_or(DimmError::NumericalOverflow)?;
+    require!(
+        agent_info.lamports() >= required_balance,
+        DimmError::InsufficientAgentBalance
+    );
+
+    // The agent account is owned by this program, so lamports move directly
+    **agent_info.try_borrow_mut_lamports()? -= intent.amount;
+    **ctx.accounts.destination.to_account_info().try_borrow_mut_lamports()? += intent.amount;
+
+    if let Some(treasury) = &mut treasury {
+        if fee > 0 {
+            **agent_info.try_borrow_mut_lamports()? -= fee;
+            **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? += fee;
+        }
+
+        treasury.record_fee(fee, clock.unix_timestamp)?;
+        treasury.store(&ctx.accounts.treasury)?;
+    }
+
+    if let Some(mut stats) = stats {
+        stats.record_activity_time(clock.unix_timestamp);
+        stats.record_transaction(intent.amount, true, &ActivityType::Transfer)?;
+        stats.record_destination(&intent.destination)?;
+        stats.record_limit_hits(intent.amount, agent_account)?;
+        stats.store(&ctx.accounts.stats)?;
+    }
+    if let Some(address_book) = &address_book {
+        address_book.store(&ctx.accounts.address_book)?;
+    }
+    if let Some(destination_limit) = &destination_limit {
+        destination_limit.store(&ctx.accounts.destination_limit)?;
+    }
+
+    agent_account.intent_nonce = agent_account
+        .intent_nonce
+        .checked_add(1)
+        .ok_or(DimmError::NumericalOverflow)?;
+    agent_account.last_used_at = clock.unix_timestamp;
+
+    if let Some(hook_registry) = &hook_registry {
+        let summary = ExecutionSummary {
+            agent: agent_account.key(),
+            main_wallet: agent_account.main_wallet,
+            activity_type: ActivityType::Transfer,
+            amount: intent.amount,
+            destination: Some(intent.destination),
+            spent_today: agent_account.spent_today,
+            total_spent: agent_account.total_spent,
+            total_transactions: agent_account.total_transactions,
+            timestamp: clock.unix_timestamp,
+        };
+
+        for (program_id, fatal) in hook_registry.post_execution_hooks() {
+            let hook_program = hooks::find_hook_program(ctx.remaining_accounts, program_id)?;
+            hooks::invoke_post_execution_hook(
+                &hook_program,
+                &agent_account.to_account_info(),
+                &ctx.accounts.destination.to_account_info(),
+                &summary,
+                fatal,
+            )?;
+        }
+    }
+
+    emit!(ExecutionReceipt {
+        agent: agent_account.key(),
+        sequence: agent_account.next_receipt()?,
+        activity_type: ActivityType::Transfer,
+        amount: intent.amount,
+        mint: None,
+        destination: Some(intent.destination),
+        fee,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Signed intent executed");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Relayer: {}", ctx.accounts.relayer.key());
+    msg!("Nonce: {}", intent.nonce);
+    msg!("Amount: {} lamports", intent.amount);
+    msg!("Fee: {} lamports", fee);
+
+    Ok(())
+}
@@ -0,0 +1,242 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::agent_cpi;
+use crate::auth::{Caller, Capability};
+use crate::errors::DimmError;
+use crate::events::SwapExecuted;
+use crate::state::*;
+use crate::constants::*;
+
+/// Jupiter's exact-in route instructions: Anchor discriminator and the
+/// positions of the user's source and destination token accounts
+const JUPITER_ROUTES: [([u8; 8], usize, usize); 2] = [
+    ([229, 23, 203, 151, 122, 227, 173, 42], 2, 3),  // route
+    ([193, 32, 155, 51, 65, 214, 156, 129], 3, 6),   // shared_accounts_route
+];
+
+/// Trailing arguments of an exact-in route: in_amount (u64),
+/// quoted_out_amount (u64), slippage_bps (u16), platform_fee_bps (u8)
+const JUPITER_ROUTE_TAIL_LEN: usize = 8 + 8 + 2 + 1;
+
+#[derive(Accounts)]
+pub struct ExecuteSwap<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        seeds = [PROTOCOL_SEED, agent_account.main_wallet.as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Main wallet or the agent's own signer key
+    pub authority: Signer<'info>,
+
+    /// Mints the agent may swap between
+    #[account(
+        seeds = [
+            WHITELIST_SEED,
+            agent_account.key().as_ref(),
+            &WhitelistType::Tokens.seed()
+        ],
+        bump = token_whitelist.bump
+    )]
+    pub token_whitelist: Box<Account<'info, Whitelist>>,
+
+    /// Limits for the input mint
+    #[account(
+        mut,
+        seeds = [TOKEN_LIMIT_SEED, agent_account.key().as_ref(), source_token_account.mint.as_ref()],
+        bump = token_limit.bump
+    )]
+    pub token_limit: Box<Account<'info, TokenLimit>>,
+
+    /// Agent's token account of the input mint
+    #[account(
+        mut,
+        constraint = source_token_account.owner == agent_account.key() @ DimmError::InvalidTokenTransfer
+    )]
+    pub source_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Agent's token account of the output mint
+    #[account(
+        mut,
+        constraint = destination_token_account.owner == agent_account.key() @ DimmError::InvalidTokenTransfer
+    )]
+    pub destination_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Jupiter aggregator program
+    #[account(address = JUPITER_PROGRAM_ID @ DimmError::InvalidProgram)]
+    pub jupiter_program: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's scoped program permissions; may be uninitialized
+    #[account(
+        seeds = [SCOPED_PERMISSIONS_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub scoped_permissions: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet's emergency state; may be uninitialized
+    #[account(
+        seeds = [EMERGENCY_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub emergency_state: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's activity schedule; may be uninitialized
+    #[account(
+        seeds = [SCHEDULE_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub schedule: UncheckedAccount<'info>,
+}
+
+/// Swap through Jupiter with the agent PDA as authority. `route_data` is a
+/// Jupiter `route` or `shared_accounts_route` instruction and its accounts
+/// are passed as remaining accounts, in order. The route's slippage must be
+/// within the agent's maximum, and the swap is checked against the route's
+/// amounts once it returns.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteSwap<'info>>,
+    route_data: Vec<u8>,
+) -> Result<()> {
+    Caller::authorize(
+        ctx.accounts.authority.key(),
+        &ctx.accounts.agent_account.main_wallet,
+        Some(&*ctx.accounts.agent_account),
+        None,
+        Capability::Execute,
+    )?;
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
+    EmergencyState::require_not_paused(&ctx.accounts.emergency_state)?;
+
+    let clock = Clock::get()?;
+    let agent_account = &mut ctx.accounts.agent_account;
+
+    require!(!agent_account.revoked, DimmError::AgentRevoked);
+    require!(
+        !agent_account.is_frozen(clock.unix_timestamp),
+        DimmError::AgentFrozen
+    );
+    ActivitySchedule::require_active(&ctx.accounts.schedule, clock.unix_timestamp)?;
+    require!(
+        agent_account.is_within_active_hours(clock.unix_timestamp),
+        DimmError::OutsideActiveHours
+    );
+    agent_account.expire_permissions(clock.unix_timestamp);
+    require!(
+        agent_account.has_permission(&AgentPermission::SwapTokens),
+        DimmError::InsufficientPermissions
+    );
+
+    let (in_amount, quoted_out_amount, slippage_bps, source_index, destination_index) =
+        parse_route(&route_data)?;
+    require!(
+        slippage_bps <= agent_account.max_slippage_bps,
+        DimmError::ExceedsMaxSlippage
+    );
+
+    let input_mint = ctx.accounts.source_token_account.mint;
+    let output_mint = ctx.accounts.destination_token_account.mint;
+    require_keys_neq!(input_mint, output_mint, DimmError::InvalidTokenTransfer);
+    ctx.accounts.token_whitelist.require_token(&input_mint)?;
+    ctx.accounts.token_whitelist.require_token(&output_mint)?;
+    // The route must debit and credit the accounts whose balances are checked
+    let route_account = |index: usize| ctx.remaining_accounts.get(index).map(|info| info.key());
+    require!(
+        route_account(source_index) == Some(ctx.accounts.source_token_account.key())
+            && route_account(destination_index) == Some(ctx.accounts.destination_token_account.key()),
+        DimmError::InvalidSwapRoute
+    );
+    ScopedPermissions::require_scope(&ctx.accounts.scoped_permissions, &JUPITER_PROGRAM_ID, 0)?;
+
+    let token_limit = &mut ctx.accounts.token_limit;
+    token_limit.check_and_reset_daily_limit(clock.unix_timestamp)?;
+    require!(
+        in_amount <= token_limit.max_per_transaction,
+        DimmError::ExceedsTransactionLimit
+    );
+    require!(
+        token_limit.can_spend(in_amount)?,
+        DimmError::ExceedsDailyLimit
+    );
+
+    let source_before = ctx.accounts.source_token_account.amount;
+    let destination_before = ctx.accounts.destination_token_account.amount;
+
+    agent_cpi::invoke_as_agent(
+        &ctx.accounts.agent_account,
+        &ctx.accounts.jupiter_program.to_account_info(),
+        route_data,
+        ctx.remaining_accounts,
+    )?;
+
+    ctx.accounts.source_token_account.reload()?;
+    ctx.accounts.destination_token_account.reload()?;
+    let spent = source_before.saturating_sub(ctx.accounts.source_token_account.amount);
+    let received = ctx
+        .accounts
+        .destination_token_account
+        .amount
+        .saturating_sub(destination_before);
+
+    let min_out_amount = (quoted_out_amount as u128)
+        .checked_mul(10_000 - slippage_bps as u128)
+        .map(|v| v / 10_000)
+        .ok_or(DimmError::NumericalOverflow)? as u64;
+    require!(
+        spent <= in_amount && received >= min_out_amount,
+        DimmError::SwapOutputTooLow
+    );
+
+    ctx.accounts.token_limit.record_spend(spent)?;
+
+    let agent_account = &mut ctx.accounts.agent_account;
+    agent_account.total_transactions = agent_account
+        .total_transactions
+        .checked_add(1)
+        .ok_or(DimmError::NumericalOverflow)?;
+    agent_account.last_used_at = clock.unix_timestamp;
+
+    emit!(SwapExecuted {
+        agent: agent_account.key(),
+        input_mint,
+        output_mint,
+        in_amount: spent,
+        out_amount: received,
+        slippage_bps,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Swap executed");
+    msg!("Agent: {}", agent_account.key());
+    msg!("In: {} of {}", spent, input_mint);
+    msg!("Out: {} of {}", received, output_mint);
+
+    Ok(())
+}
+
+/// Read `(in_amount, quoted_out_amount, slippage_bps, source_index,
+/// destination_index)` from an exact-in Jupiter route instruction
+fn parse_route(data: &[u8]) -> Result<(u64, u64, u16, usize, usize)> {
+    require!(data.len() >= 8 + JUPITER_ROUTE_TAIL_LEN, DimmError::InvalidSwapRoute);
+    let (_, source_index, destination_index) = JUPITER_ROUTES
+        .iter()
+        .find(|(discriminator, _, _)| data[..8] == discriminator[..])
+        .ok_or(DimmError::InvalidSwapRoute)?;
+
+    let tail = &data[data.len() - JUPITER_ROUTE_TAIL_LEN..];
+    let in_amount = u64::from_le_bytes(tail[0..8].try_into().unwrap());
+    let quoted_out_amount = u64::from_le_bytes(tail[8..16].try_into().unwrap());
+    let slippage_bps = u16::from_le_bytes(tail[16..18].try_into().unwrap());
+
+    Ok((in_amount, quoted_out_amount, slippage_bps, *source_index, *destination_index))
+}
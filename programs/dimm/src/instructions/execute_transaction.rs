@@ -1,6 +1,10 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::agent_cpi;
+use crate::auth::{Caller, Capability, Role};
 use crate::errors::DimmError;
+use crate::events::{ui_amount, AnomalyDetected, ExecutionReceipt, SoftLimitExceeded, WatchtowerAlert};
+use crate::hooks::{self, ExecutionSummary, HookAction};
 use crate::state::*;
 use crate::constants::*;
 
@@ -17,105 +21,843 @@ pub struct ExecuteTransaction<'info> {
     )]
     pub agent_account: Account<'info, AgentAccount>,
 
+    #[account(
+        seeds = [PROTOCOL_SEED, agent_account.main_wallet.as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     /// CHECK: Destination can be any account
     #[account(mut)]
     pub destination: UncheckedAccount<'info>,
 
-    /// The signer must be authorized (for demo, we allow the main wallet)
-    #[account(mut, address = agent_account.main_wallet)]
+    /// Main wallet or the agent's own signer key
+    #[account(mut)]
     pub authority: Signer<'info>,
 
+    /// CHECK: Wallet's watchtower; may be uninitialized
+    #[account(
+        seeds = [WATCHTOWER_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub watchtower: UncheckedAccount<'info>,
+
+    /// Required when the agent enforces destination whitelisting
+    #[account(
+        seeds = [
+            WHITELIST_SEED,
+            agent_account.key().as_ref(),
+            &WhitelistType::Destinations.seed()
+        ],
+        bump = destination_whitelist.bump
+    )]
+    pub destination_whitelist: Option<Account<'info, Whitelist>>,
+
+    /// CHECK: Agent's destination blacklist; may be uninitialized
+    #[account(
+        seeds = [BLACKLIST_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+
+    /// Programs the agent may invoke; required for `Other` executions
+    #[account(
+        seeds = [
+            WHITELIST_SEED,
+            agent_account.key().as_ref(),
+            &WhitelistType::Programs.seed()
+        ],
+        bump = program_whitelist.bump
+    )]
+    pub program_whitelist: Option<Box<Account<'info, Whitelist>>>,
+
+    /// CHECK: Program invoked for `Other` executions; checked against the program whitelist
+    pub target_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: SPL Memo program; required when the execution carries a memo
+    #[account(address = MEMO_PROGRAM_ID @ DimmError::InvalidProgram)]
+    pub memo_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Protocol's program registry; may be uninitialized
+    #[account(
+        seeds = [PROGRAM_REGISTRY_SEED, protocol_config.key().as_ref()],
+        bump
+    )]
+    pub program_registry: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's scoped program permissions; may be uninitialized
+    #[account(
+        seeds = [SCOPED_PERMISSIONS_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub scoped_permissions: UncheckedAccount<'info>,
+
+    /// Agent's critical destinations and categories, if any are tagged
+    #[account(
+        seeds = [PRIORITY_LANES_SEED, agent_account.key().as_ref()],
+        bump = priority_lanes.bump
+    )]
+    pub priority_lanes: Option<Account<'info, PriorityLanes>>,
+
+    /// CHECK: Wallet's emergency state; may be uninitialized
+    #[account(
+        seeds = [EMERGENCY_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub emergency_state: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's activity schedule; may be uninitialized
+    #[account(
+        seeds = [SCHEDULE_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub schedule: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet's treasury, collecting protocol fees; may be uninitialized
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's policy hook registry; may be uninitialized
+    #[account(
+        seeds = [HOOK_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub hook_registry: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's stats; may be uninitialized
+    #[account(
+        mut,
+        seeds = [STATS_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet's activity digest; may be uninitialized
+    #[account(
+        mut,
+        seeds = [DIGEST_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub digest: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet's budget across all agents; may be uninitialized
+    #[account(
+        mut,
+        seeds = [WALLET_BUDGET_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub wallet_budget: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's address book of risk tiers; may be uninitialized
+    #[account(
+        mut,
+        seeds = [ADDRESS_BOOK_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub address_book: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's limits towards `destination`; may be uninitialized
+    #[account(
+        mut,
+        seeds = [DESTINATION_LIMIT_SEED, agent_account.key().as_ref(), destination.key().as_ref()],
+        bump
+    )]
+    pub destination_limit: UncheckedAccount<'info>,
+
+    /// Runtime holding the agent's execution lease; required at or above the lease threshold
+    pub lease_holder: Option<Signer<'info>>,
+
+    /// CHECK: Protocol's Pyth SOL/USD price account; required if the agent has USD limits
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Agent's snapshot for the current day, opened with `crank_snapshot`
+    #[account(mut)]
+    pub snapshot: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Protocol-wide stats; optional since every execution passing it
+    /// write-locks the same account
+    #[account(mut, seeds = [GLOBAL_STATS_SEED], bump)]
+    pub global_stats: Option<UncheckedAccount<'info>>,
+
+    /// Agent's escrow; required while escrow funding is on
+    #[account(
+        mut,
+        seeds = [AGENT_ESCROW_SEED, agent_account.key().as_ref()],
+        bump = agent_escrow.bump
+    )]
+    pub agent_escrow: Option<Box<Account<'info, AgentEscrow>>>,
+
+    /// Limits for `params.mint`; required for token transfers
+    #[account(
+        mut,
+        seeds = [TOKEN_LIMIT_SEED, agent_account.key().as_ref(), token_limit.mint.as_ref()],
+        bump = token_limit.bump
+    )]
+    pub token_limit: Option<Box<Account<'info, TokenLimit>>>,
+
+    /// Agent's token account the transfer is paid from
+    #[account(mut)]
+    pub source_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Token account owned by `destination`
+    #[account(mut)]
+    pub destination_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// Approved proposal; required above the agent's approval threshold.
+    /// Closed to `authority` only once the execution goes through, so an
+    /// anomaly freeze leaves it in place
+    #[account(
+        mut,
+        seeds = [
+            PENDING_TRANSACTION_SEED,
+            agent_account.key().as_ref(),
+            &pending_transaction.proposal_id.to_le_bytes()
+        ],
+        bump = pending_transaction.bump,
+        constraint = pending_transaction.proposer == authority.key() @ DimmError::Unauthorized
+    )]
+    pub pending_transaction: Option<Box<Account<'info, PendingTransaction>>>,
+
+    /// Agent's co-signers; required when the amount falls in a co-signed band
+    #[account(
+        seeds = [CO_SIGNER_SET_SEED, agent_account.key().as_ref()],
+        bump = co_signer_set.bump
+    )]
+    pub co_signer_set: Option<Box<Account<'info, CoSignerSet>>>,
+
+    /// Session the authority signs with; required when it is a session key
+    #[account(
+        mut,
+        seeds = [SESSION_KEY_SEED, agent_account.key().as_ref(), session_key.session_key.as_ref()],
+        bump = session_key.bump
+    )]
+    pub session_key: Option<Box<Account<'info, SessionKey>>>,
+
+    /// Relayer paying the network fee, if not the authority
+    #[account(mut)]
+    pub fee_payer: Option<Signer<'info>>,
+
+    /// Wallet's allowed relayers; required when a separate fee payer signs
+    #[account(
+        seeds = [RELAYER_REGISTRY_SEED, agent_account.main_wallet.as_ref()],
+        bump = relayer_registry.bump
+    )]
+    pub relayer_registry: Option<Box<Account<'info, RelayerRegistry>>>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
-    ctx: Context<ExecuteTransaction>,
+/// Programs registered as hooks must be passed as remaining accounts.
+/// `Other` executions with instruction data invoke `target_program` with
+/// the remaining accounts, in order, signed by the agent PDA.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteTransaction<'info>>,
     params: ExecuteTransactionParams,
 ) -> Result<()> {
-    let agent_account = &mut ctx.accounts.agent_account;
     let clock = Clock::get()?;
+    let caller = Caller::resolve_with_session(
+        ctx.accounts.authority.key(),
+        &ctx.accounts.agent_account.main_wallet,
+        Some(&*ctx.accounts.agent_account),
+        ctx.accounts.session_key.as_deref().map(|session| &**session),
+        clock.unix_timestamp,
+    )?;
+    caller.require(Capability::Execute)?;
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
+    EmergencyState::require_not_paused(&ctx.accounts.emergency_state)?;
+
+    let agent_account = &mut ctx.accounts.agent_account;
 
     // Validate agent is not revoked
     require!(!agent_account.revoked, DimmError::AgentRevoked);
+    require!(
+        !agent_account.is_frozen(clock.unix_timestamp),
+        DimmError::AgentFrozen
+    );
+    ActivitySchedule::require_active(&ctx.accounts.schedule, clock.unix_timestamp)?;
+    require!(
+        agent_account.is_within_active_hours(clock.unix_timestamp),
+        DimmError::OutsideActiveHours
+    );
 
-    // Check permissions based on activity type
-    let required_permission = match params.activity_type {
-        ActivityType::Transfer => AgentPermission::TransferSol,
-        ActivityType::Swap => AgentPermission::SwapTokens,
-        ActivityType::NftOperation => AgentPermission::NftOperations,
-        ActivityType::Staking => AgentPermission::Staking,
-        ActivityType::Governance => AgentPermission::Governance,
-        ActivityType::DefiInteraction => AgentPermission::DefiProtocols,
-        _ => AgentPermission::ExecutePrograms,
-    };
+    // An anomalous execution freezes the agent instead of running. It must
+    // return success, or the freeze would be rolled back with it, so any
+    // proposal is left open for after the main wallet unfreezes the agent.
+    let stats = AgentStats::load(&ctx.accounts.stats)?;
+    if let Some(kind) = agent_account.detect_anomaly(stats.as_ref(), params.lamports(), clock.unix_timestamp) {
+        agent_account.frozen_until = i64::MAX;
+        emit!(AnomalyDetected {
+            agent: agent_account.key(),
+            kind,
+            amount: params.lamports(),
+            timestamp: clock.unix_timestamp,
+        });
 
+        msg!("Anomaly detected; agent frozen until the main wallet unfreezes it");
+        msg!("Agent: {}", agent_account.key());
+        msg!("Rule: {:?}", kind);
+        return Ok(());
+    }
     require!(
-        agent_account.has_permission(&required_permission),
-        DimmError::InsufficientPermissions
+        !agent_account.soft_limit_pending_ack,
+        DimmError::SoftLimitNotAcknowledged
     );
 
-    // Check and reset daily limit if needed
-    agent_account.check_and_reset_daily_limit(clock.unix_timestamp)?;
+    // Every execution advances the nonce, checked if the caller expects one
+    agent_account.consume_nonce(params.expected_nonce)?;
 
-    // Validate spending limits
-    if params.amount > 0 {
+    // Lapsed timed grants no longer count
+    agent_account.expire_permissions(clock.unix_timestamp);
+
+    // Check permissions based on activity type
+    if let Some(required_permission) = params.required_permission() {
         require!(
-            params.amount <= agent_account.max_sol_per_transaction,
-            DimmError::ExceedsTransactionLimit
+            agent_account.has_permission(&required_permission),
+            DimmError::InsufficientPermissions
+        );
+    }
+
+    // The named destination is the account paid; lanes, hooks, alerts and
+    // receipts all report it
+    if let Some(destination) = params.destination {
+        require_keys_eq!(
+            destination,
+            ctx.accounts.destination.key(),
+            DimmError::DestinationMismatch
+        );
+    }
+
+    // Funds may only leave towards whitelisted destinations
+    if agent_account.require_destination_whitelist && params.destination.is_some() {
+        let whitelist = ctx
+            .accounts
+            .destination_whitelist
+            .as_ref()
+            .ok_or(DimmError::WhitelistRequired)?;
+
+        require!(
+            whitelist.is_whitelisted(&ctx.accounts.destination.key()),
+            DimmError::DestinationNotWhitelisted
         );
+    }
 
+    // Blacklisted destinations are blocked even with whitelisting disabled
+    if params.destination.is_some() {
+        Blacklist::require_not_blacklisted(&ctx.accounts.blacklist, &ctx.accounts.destination.key())?;
+    }
+
+    if params.activity_type == ActivityType::Transfer && params.amount > 0 {
         require!(
-            agent_account.can_spend(params.amount)?,
-            DimmError::ExceedsDailyLimit
+            params.amount >= agent_account.min_transfer_amount()?,
+            DimmError::BelowMinTransferAmount
+        );
+    }
+
+    if let Some(memo) = &params.memo {
+        require!(memo.len() <= MAX_MEMO_LENGTH, DimmError::MemoTooLong);
+        require!(ctx.accounts.memo_program.is_some(), DimmError::InvalidProgram);
+    }
+
+    if params.mint.is_some() {
+        require!(
+            params.activity_type == ActivityType::Transfer,
+            DimmError::InvalidTokenTransfer
         );
+    }
+
+    // Token transfers are checked against the mint's limits, not the SOL budget
+    let lamports = params.lamports();
+
+    // Session keys spend only within their own activity types and budget
+    if caller.role == Role::SessionKey {
+        if let Some(session_key) = ctx.accounts.session_key.as_mut() {
+            session_key.record_execution(&params.activity_type, lamports)?;
+        }
+    }
+
+    // A separate fee payer must be an allowed relayer and may be reimbursed
+    let reimbursement = match &ctx.accounts.fee_payer {
+        Some(fee_payer) if fee_payer.key() != ctx.accounts.authority.key() => {
+            let relayer_registry = ctx
+                .accounts
+                .relayer_registry
+                .as_ref()
+                .ok_or(DimmError::RelayerNotAllowed)?;
+            require!(
+                relayer_registry.is_relayer(&fee_payer.key()),
+                DimmError::RelayerNotAllowed
+            );
+            relayer_registry.reimbursement_lamports
+        }
+        _ => 0,
+    };
+
+    // Large transactions run only as an approved proposal, consumed here
+    let requires_approval = agent_account.requires_approval(lamports);
+    let required_co_signers = agent_account.required_co_signers(lamports);
+    if requires_approval || required_co_signers > 0 {
+        let pending_transaction = ctx
+            .accounts
+            .pending_transaction
+            .as_ref()
+            .ok_or(DimmError::ApprovalRequired)?;
+
+        require!(
+            pending_transaction.matches(&params),
+            DimmError::ProposalMismatch
+        );
+        if requires_approval {
+            require!(
+                pending_transaction.status == ProposalStatus::Approved,
+                DimmError::ProposalNotApproved
+            );
+        }
+        if required_co_signers > 0 {
+            let co_signer_set = ctx
+                .accounts
+                .co_signer_set
+                .as_ref()
+                .ok_or(DimmError::CoSignersRequired)?;
+            require!(
+                pending_transaction.co_signer_approval_count(co_signer_set.generation)
+                    >= required_co_signers as u32,
+                DimmError::CoSignersRequired
+            );
+        }
+    }
 
-        // Check agent has sufficient balance
-        let agent_balance = ctx.accounts.agent_account.to_account_info().lamports();
-        let required_balance = params.amount
-            .checked_add(MIN_AGENT_BALANCE)
+    agent_account.require_lease(
+        ctx.accounts.lease_holder.as_ref().map(|holder| holder.key()),
+        lamports,
+        clock.unix_timestamp,
+    )?;
+
+    // Check and reset daily limit if needed
+    agent_account.check_and_reset_daily_limit(clock.unix_timestamp)?;
+
+    let is_maintenance = params.activity_type == ActivityType::Maintenance;
+    let is_donation = params.activity_type == ActivityType::Donation;
+
+    // Critical spends may use the reserved slice of the daily budget
+    let is_critical = ctx
+        .accounts
+        .priority_lanes
+        .as_ref()
+        .map(|lanes| lanes.is_critical(&params.activity_type, params.destination.as_ref()))
+        .unwrap_or(false);
+
+    // Every execution pays the protocol fee once the treasury exists
+    let mut treasury = Treasury::load(&ctx.accounts.treasury)?;
+    let fee = match &treasury {
+        Some(treasury) => treasury.calculate_fee(lamports)?,
+        None => 0,
+    };
+
+    // The fee tank covers the relayer's reimbursement, or otherwise refunds
+    // the signer's signature fee once per slot
+    let network_fee = if reimbursement > 0 {
+        reimbursement
+    } else if agent_account.signature_refund_due(clock.slot) {
+        LAMPORTS_PER_SIGNATURE
+    } else {
+        0
+    };
+    let tank_payment = network_fee.min(agent_account.fee_tank);
+
+    // Counterparties outside the trusted tier may have tighter caps
+    let mut address_book = AddressBook::load(&ctx.accounts.address_book)?;
+    let mut destination_limit = DestinationLimit::load(&ctx.accounts.destination_limit)?;
+
+    // Validate spending limits
+    if lamports > 0 || fee > 0 || reimbursement > 0 || tank_payment > 0 {
+        // Maintenance spends and donations are checked against their allowances when recorded
+        if lamports > 0 && !is_maintenance && !is_donation {
+            require!(
+                lamports <= agent_account.max_sol_per_transaction,
+                DimmError::ExceedsTransactionLimit
+            );
+
+            require!(
+                agent_account.can_spend_in_lane(lamports, is_critical)?,
+                DimmError::ExceedsDailyLimit
+            );
+
+            require!(
+                agent_account.within_trial_limits(lamports, clock.unix_timestamp)?,
+                DimmError::TrialLimitExceeded
+            );
+            require!(
+                agent_account.within_hourly_limit(lamports)?,
+                DimmError::ExceedsHourlyLimit
+            );
+            WalletBudget::charge(&ctx.accounts.wallet_budget, lamports, clock.unix_timestamp)?;
+
+            ctx.accounts
+                .protocol_config
+                .check_cold_start(agent_account, lamports, clock.unix_timestamp)?;
+
+            if agent_account.has_usd_limits() {
+                let price_feed = ctx.accounts.price_feed.as_ref().ok_or(DimmError::PriceFeedRequired)?;
+                let price = SolUsdPrice::load(
+                    price_feed,
+                    &ctx.accounts.protocol_config.sol_usd_price_feed,
+                    clock.unix_timestamp,
+                )?;
+                agent_account.record_usd_spend(&price, lamports)?;
+            }
+
+            if let (Some(address_book), true) = (address_book.as_mut(), params.destination.is_some()) {
+                address_book.record_spend(
+                    &ctx.accounts.destination.key(),
+                    lamports,
+                    clock.unix_timestamp,
+                )?;
+            }
+            if let (Some(destination_limit), true) =
+                (destination_limit.as_mut(), params.destination.is_some())
+            {
+                destination_limit.record_spend(lamports, clock.unix_timestamp)?;
+            }
+        }
+
+        // Pull exactly what this spend needs out of escrow
+        if agent_account.escrow_funding {
+            let agent_escrow = ctx.accounts.agent_escrow.as_ref().ok_or(DimmError::EscrowRequired)?;
+            let needed = lamports
+                .checked_add(fee)
+                .and_then(|v| v.checked_add(reimbursement))
+                .ok_or(DimmError::NumericalOverflow)?;
+            AgentEscrow::release(
+                &agent_escrow.to_account_info(),
+                &agent_account.to_account_info(),
+                needed,
+            )?;
+        }
+
+        // Check agent has sufficient balance, keeping the fee tank whole
+        // except for what it pays toward this transaction
+        let agent_balance = agent_account.to_account_info().lamports();
+        let required_balance = lamports
+            .checked_add(fee)
+            .and_then(|v| v.checked_add(reimbursement))
+            .and_then(|v| v.checked_add(MIN_AGENT_BALANCE))
+            .and_then(|v| v.checked_add(agent_account.fee_tank))
+            .and_then(|v| v.checked_sub(reimbursement.min(tank_payment)))
             .ok_or(DimmError::NumericalOverflow)?;
             
         require!(
             agent_balance >= required_balance,
             DimmError::InsufficientAgentBalance
         );
+    }
 
-        // Execute transfer if it's a simple SOL transfer
-        if params.activity_type == ActivityType::Transfer && params.destination.is_some() {
-            let agent_seeds = &[
-                AGENT_SEED,
-                agent_account.main_wallet.as_ref(),
-                &agent_account.agent_id.to_le_bytes(),
-                &[agent_account.bump],
-            ];
-            let signer_seeds = &[&agent_seeds[..]];
-
-            let cpi_context = CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.agent_account.to_account_info(),
-                    to: ctx.accounts.destination.to_account_info(),
-                },
-                signer_seeds,
-            );
+    // Let the owner's policy programs approve the action before funds move
+    let hook_registry = HookRegistry::load(&ctx.accounts.hook_registry)?;
+    if let Some(hook_registry) = &hook_registry {
+        let action = HookAction {
+            agent: agent_account.key(),
+            main_wallet: agent_account.main_wallet,
+            activity_type: params.activity_type.clone(),
+            amount: params.amount,
+            destination: params.destination,
+            spent_today: agent_account.spent_today,
+            timestamp: clock.unix_timestamp,
+        };
+
+        for hook in hook_registry.approval_hooks() {
+            let hook_program = hooks::find_hook_program(ctx.remaining_accounts, &hook.program_id)?;
+            hooks::invoke_approval_hook(
+                &hook_program,
+                &agent_account.to_account_info(),
+                &ctx.accounts.destination.to_account_info(),
+                &action,
+            )?;
+        }
+    }
 
-            transfer(cpi_context, params.amount)?;
+    if lamports > 0 {
+        // Execute transfer if it's a simple SOL transfer, a maintenance payment or a donation
+        let is_transfer =
+            params.activity_type == ActivityType::Transfer || is_maintenance || is_donation;
+        if is_transfer && params.destination.is_some() {
+            // The agent account is owned by this program, so lamports move directly
+            **agent_account.to_account_info().try_borrow_mut_lamports()? -= lamports;
+            **ctx.accounts.destination.to_account_info().try_borrow_mut_lamports()? += lamports;
         }
 
         // Record the spend
-        agent_account.record_spend(params.amount)?;
+        if is_maintenance {
+            agent_account.record_maintenance_spend(lamports)?;
+        } else if is_donation {
+            agent_account.record_donation_spend(lamports)?;
+        } else {
+            let spent_before = agent_account.spent_today;
+            agent_account.record_spend(lamports)?;
+            if agent_account.record_soft_limit_crossing(spent_before) {
+                emit!(SoftLimitExceeded {
+                    agent: agent_account.key(),
+                    soft_daily_limit: agent_account.soft_daily_limit,
+                    spent_today: agent_account.spent_today,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+        }
+    }
+
+    // Arbitrary CPI, signed by the agent PDA
+    if params.activity_type == ActivityType::Other && !params.instruction_data.is_empty() {
+        let target_program = ctx
+            .accounts
+            .target_program
+            .as_ref()
+            .ok_or(DimmError::InvalidProgram)?;
+        let program_whitelist = ctx
+            .accounts
+            .program_whitelist
+            .as_ref()
+            .ok_or(DimmError::WhitelistRequired)?;
+
+        program_whitelist.require_program(&target_program.key())?;
+        ProgramRegistry::require_labeled(
+            &ctx.accounts.program_registry,
+            ctx.accounts.protocol_config.restrict_unlabeled_programs,
+            &target_program.key(),
+        )?;
+        ScopedPermissions::require_scope(
+            &ctx.accounts.scoped_permissions,
+            &target_program.key(),
+            params.amount,
+        )?;
+
+        agent_cpi::invoke_as_agent(
+            agent_account,
+            &target_program.to_account_info(),
+            params.instruction_data.clone(),
+            ctx.remaining_accounts,
+        )?;
+    }
+
+    // SPL token transfer, signed by the agent PDA
+    let mut token_decimals = None;
+    if let Some(mint) = params.mint {
+        let (Some(token_limit), Some(source), Some(destination), Some(token_program)) = (
+            ctx.accounts.token_limit.as_mut(),
+            ctx.accounts.source_token_account.as_ref(),
+            ctx.accounts.destination_token_account.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+        ) else {
+            return err!(DimmError::TokenAccountsRequired);
+        };
+
+        require!(
+            token_limit.mint == mint
+                && source.mint == mint
+                && destination.mint == mint
+                && source.owner == agent_account.key()
+                && destination.owner == ctx.accounts.destination.key()
+                && params.destination == Some(destination.owner),
+            DimmError::InvalidTokenTransfer
+        );
+
+        token_limit.check_and_reset_daily_limit(clock.unix_timestamp)?;
+        require!(
+            params.amount <= token_limit.max_per_transaction,
+            DimmError::ExceedsTransactionLimit
+        );
+        require!(
+            token_limit.can_spend(params.amount)?,
+            DimmError::ExceedsDailyLimit
+        );
+
+        let agent_seeds = &[
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes(),
+            &[agent_account.bump],
+        ];
+        let signer_seeds = &[&agent_seeds[..]];
+
+        let cpi_context = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: source.to_account_info(),
+                to: destination.to_account_info(),
+                authority: agent_account.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(cpi_context, params.amount)?;
+
+        token_limit.record_spend(params.amount)?;
+        agent_account.total_transactions = agent_account
+            .total_transactions
+            .checked_add(1)
+            .ok_or(DimmError::NumericalOverflow)?;
+        token_decimals = Some((token_limit.decimals, token_limit.display_decimals));
+    }
+
+    // Memo goes in the same transaction as the transfer it describes
+    if let (Some(memo), Some(memo_program)) = (&params.memo, &ctx.accounts.memo_program) {
+        agent_cpi::memo_as_agent(agent_account, &memo_program.to_account_info(), memo)?;
+    }
+
+    if let Some(treasury) = &mut treasury {
+        if fee > 0 {
+            **agent_account.to_account_info().try_borrow_mut_lamports()? -= fee;
+            **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? += fee;
+        }
+
+        treasury.record_fee(fee, clock.unix_timestamp)?;
+        treasury.store(&ctx.accounts.treasury)?;
+    }
+
+    if reimbursement > 0 {
+        if let Some(fee_payer) = &ctx.accounts.fee_payer {
+            **agent_account.to_account_info().try_borrow_mut_lamports()? -= reimbursement;
+            **fee_payer.to_account_info().try_borrow_mut_lamports()? += reimbursement;
+        }
+    }
+
+    if tank_payment > 0 {
+        let paid = agent_account.draw_fee_tank(tank_payment);
+        // The relayer was reimbursed above; otherwise the signer gets its fee back
+        if reimbursement == 0 {
+            agent_account.fee_tank_refund_slot = clock.slot;
+            **agent_account.to_account_info().try_borrow_mut_lamports()? -= paid;
+            **ctx.accounts.authority.to_account_info().try_borrow_mut_lamports()? += paid;
+        }
+    }
+
+    // Keep the agent's stats current when it has them
+    if let Some(mut stats) = stats {
+        stats.record_activity_time(clock.unix_timestamp);
+        stats.record_transaction(lamports, true, &params.activity_type)?;
+        stats.record_gas(params.fee_lamports);
+        if params.destination.is_some() {
+            stats.record_destination(&ctx.accounts.destination.key())?;
+        }
+        if !is_maintenance && !is_donation {
+            stats.record_limit_hits(lamports, agent_account)?;
+        }
+        stats.store(&ctx.accounts.stats)?;
+    }
+
+    if let Some(snapshot_info) = &ctx.accounts.snapshot {
+        let mut snapshot =
+            DailySnapshot::load_current(snapshot_info, &agent_account.key(), clock.unix_timestamp)?;
+        snapshot.record_transaction(lamports)?;
+        snapshot.store(snapshot_info)?;
+    }
+
+    if let Some(global_stats_info) = &ctx.accounts.global_stats {
+        if let Some(mut global_stats) = GlobalStats::load(global_stats_info)? {
+            let first_this_epoch = agent_account.last_active_epoch != clock.epoch;
+            global_stats.record_execution(lamports, fee, clock.epoch, first_this_epoch)?;
+            global_stats.store(global_stats_info)?;
+        }
+    }
+    agent_account.last_active_epoch = clock.epoch;
+
+    if let Some(mut digest) = WalletDigest::load(&ctx.accounts.digest)? {
+        digest.record_execution(
+            agent_account.key(),
+            lamports,
+            fee,
+            params.mint.is_some(),
+            clock.unix_timestamp,
+        )?;
+        digest.store(&ctx.accounts.digest)?;
+    }
+
+    if let Some(address_book) = &address_book {
+        address_book.store(&ctx.accounts.address_book)?;
+    }
+    if let Some(destination_limit) = &destination_limit {
+        destination_limit.store(&ctx.accounts.destination_limit)?;
     }
 
     // Update last used timestamp
     agent_account.last_used_at = clock.unix_timestamp;
+    let receipt_sequence = agent_account.next_receipt()?;
+
+    // Hand the execution summary to accounting/logging hooks
+    if let Some(hook_registry) = &hook_registry {
+        let summary = ExecutionSummary {
+            agent: agent_account.key(),
+            main_wallet: agent_account.main_wallet,
+            activity_type: params.activity_type.clone(),
+            amount: params.amount,
+            destination: params.destination,
+            spent_today: agent_account.spent_today,
+            total_spent: agent_account.total_spent,
+            total_transactions: agent_account.total_transactions,
+            timestamp: clock.unix_timestamp,
+        };
+
+        for (program_id, fatal) in hook_registry.post_execution_hooks() {
+            let hook_program = hooks::find_hook_program(ctx.remaining_accounts, program_id)?;
+            hooks::invoke_post_execution_hook(
+                &hook_program,
+                &agent_account.to_account_info(),
+                &ctx.accounts.destination.to_account_info(),
+                &summary,
+                fatal,
+            )?;
+        }
+    }
+
+    if let Some(watchtower) = Watchtower::load(&ctx.accounts.watchtower)? {
+        let (decimals, display_decimals) = token_decimals.unwrap_or((SOL_DECIMALS, SOL_DECIMALS));
+        emit!(WatchtowerAlert {
+            watchtower: watchtower.watchtower,
+            main_wallet: agent_account.main_wallet,
+            agent: agent_account.key(),
+            receipt_sequence,
+            activity_type: params.activity_type.clone(),
+            amount: params.amount,
+            decimals,
+            ui_amount: ui_amount(params.amount, decimals, display_decimals),
+            destination: params.destination,
+            spent_today: agent_account.spent_today,
+            heartbeat_overdue: watchtower.is_overdue(clock.unix_timestamp),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    emit!(ExecutionReceipt {
+        agent: agent_account.key(),
+        sequence: receipt_sequence,
+        activity_type: params.activity_type.clone(),
+        amount: params.amount,
+        mint: params.mint,
+        destination: params.destination,
+        fee,
+        timestamp: clock.unix_timestamp,
+    });
 
     msg!("Transaction executed successfully");
-    msg!("Agent: {}", ctx.accounts.agent_account.key());
+    msg!("Agent: {}", agent_account.key());
     msg!("Type: {:?}", params.activity_type);
-    msg!("Amount: {} lamports", params.amount);
+    msg!("Critical: {}", is_critical);
+    match params.mint {
+        Some(mint) => msg!("Amount: {} (mint {})", params.amount, mint),
+        None => msg!("Amount: {} lamports", params.amount),
+    }
+    msg!("Fee: {} lamports", fee);
     msg!("Total spent today: {} lamports", agent_account.spent_today);
     msg!("Total transactions: {}", agent_account.total_transactions);
 
+    // The proposal is spent only by an execution that went through
+    if let Some(pending_transaction) = &ctx.accounts.pending_transaction {
+        pending_transaction.close(ctx.accounts.authority.to_account_info())?;
+    }
+
     Ok(())
 }
 
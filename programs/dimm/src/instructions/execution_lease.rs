@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use crate::auth::{Caller, Capability};
+use crate::errors::DimmError;
+use crate::events::{ExecutionLeaseAcquired, ExecutionLeaseReleased};
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct AcquireExecutionLease<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Main wallet or the agent's own signer key
+    pub authority: Signer<'info>,
+
+    /// Per-runtime key that will hold the lease
+    pub holder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseExecutionLease<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Current lease holder or the main wallet
+    pub authority: Signer<'info>,
+}
+
+/// Acquire the lease if it is free or has lapsed, or renew it if `holder`
+/// already holds it
+pub fn acquire_handler(ctx: Context<AcquireExecutionLease>, duration_seconds: i64) -> Result<()> {
+    Caller::authorize(
+        ctx.accounts.authority.key(),
+        &ctx.accounts.agent_account.main_wallet,
+        Some(&*ctx.accounts.agent_account),
+        None,
+        Capability::Execute,
+    )?;
+    require!(
+        duration_seconds > 0 && duration_seconds <= MAX_EXECUTION_LEASE_SECONDS,
+        DimmError::InvalidLeaseDuration
+    );
+
+    let agent_account = &mut ctx.accounts.agent_account;
+    let holder = ctx.accounts.holder.key();
+    let clock = Clock::get()?;
+
+    require!(!agent_account.revoked, DimmError::AgentRevoked);
+    require!(
+        !agent_account.is_lease_active(clock.unix_timestamp)
+            || agent_account.lease_holder == holder,
+        DimmError::ExecutionLeaseHeld
+    );
+
+    agent_account.lease_holder = holder;
+    agent_account.lease_expires_at = clock
+        .unix_timestamp
+        .checked_add(duration_seconds)
+        .ok_or(DimmError::NumericalOverflow)?;
+
+    emit!(ExecutionLeaseAcquired {
+        agent: agent_account.key(),
+        holder,
+        expires_at: agent_account.lease_expires_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Execution lease acquired");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Holder: {}", holder);
+    msg!("Expires at: {}", agent_account.lease_expires_at);
+
+    Ok(())
+}
+
+/// Release the lease early so another runtime can take over
+pub fn release_handler(ctx: Context<ReleaseExecutionLease>) -> Result<()> {
+    let agent_account = &mut ctx.accounts.agent_account;
+    let authority = ctx.accounts.authority.key();
+
+    require!(
+        authority == agent_account.lease_holder || authority == agent_account.main_wallet,
+        DimmError::Unauthorized
+    );
+
+    let holder = agent_account.lease_holder;
+    agent_account.lease_holder = Pubkey::default();
+    agent_account.lease_expires_at = 0;
+
+    emit!(ExecutionLeaseReleased {
+        agent: agent_account.key(),
+        holder,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Execution lease released");
+    msg!("Agent: {}", agent_account.key());
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::events::PermissionsExpired;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ExpirePermissions<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+}
+
+/// Revoke lapsed timed grants. Permissionless: executions already ignore
+/// lapsed grants, this only brings the stored bitmask up to date.
+pub fn handler(ctx: Context<ExpirePermissions>) -> Result<()> {
+    let agent_account = &mut ctx.accounts.agent_account;
+    let clock = Clock::get()?;
+
+    let expired = agent_account.expire_permissions(clock.unix_timestamp);
+    if expired != 0 {
+        emit!(PermissionsExpired {
+            agent: agent_account.key(),
+            permissions: expired,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    msg!("Agent permissions expired");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Revoked: {:#b}", expired);
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use crate::events::AgentConfigExported;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ExportAgentConfig<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        seeds = [
+            WHITELIST_SEED,
+            agent_account.key().as_ref(),
+            &WhitelistType::Destinations.seed()
+        ],
+        bump = destination_whitelist.bump
+    )]
+    pub destination_whitelist: Option<Box<Account<'info, Whitelist>>>,
+
+    #[account(
+        seeds = [
+            WHITELIST_SEED,
+            agent_account.key().as_ref(),
+            &WhitelistType::Programs.seed()
+        ],
+        bump = program_whitelist.bump
+    )]
+    pub program_whitelist: Option<Box<Account<'info, Whitelist>>>,
+
+    #[account(
+        seeds = [SCHEDULE_SEED, agent_account.key().as_ref()],
+        bump = schedule.bump
+    )]
+    pub schedule: Option<Box<Account<'info, ActivitySchedule>>>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+/// Emit the agent's configuration and its hash. Pass the whitelists and
+/// schedule the agent has; omitted ones export as empty.
+pub fn handler(ctx: Context<ExportAgentConfig>) -> Result<()> {
+    let clock = Clock::get()?;
+    let mut config = AgentConfig::from_agent(&ctx.accounts.agent_account);
+
+    if let Some(whitelist) = &ctx.accounts.destination_whitelist {
+        config.destination_whitelist = whitelist.addresses.clone();
+    }
+    if let Some(whitelist) = &ctx.accounts.program_whitelist {
+        config.program_whitelist = whitelist.addresses.clone();
+    }
+    if let Some(schedule) = &ctx.accounts.schedule {
+        config.blackouts = schedule
+            .blackouts
+            .iter()
+            .filter(|window| window.end > clock.unix_timestamp)
+            .copied()
+            .collect();
+    }
+
+    let config_hash = config.hash()?;
+
+    emit!(AgentConfigExported {
+        agent: ctx.accounts.agent_account.key(),
+        config_hash,
+        config,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Agent configuration exported");
+    msg!("Agent: {}", ctx.accounts.agent_account.key());
+
+    Ok(())
+}
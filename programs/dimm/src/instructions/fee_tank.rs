@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetFeeTankCap<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FundFeeTank<'info> {
+    #[account(
+        seeds = [PROTOCOL_SEED, main_wallet.key().as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Set the most the agent's fee tank may hold. Lowering the cap below the
+/// tank's balance releases the excess into the agent's spendable balance.
+pub fn set_cap_handler(ctx: Context<SetFeeTankCap>, cap: u64) -> Result<()> {
+    let agent_account = &mut ctx.accounts.agent_account;
+    agent_account.fee_tank_cap = cap;
+    agent_account.fee_tank = agent_account.fee_tank.min(cap);
+
+    msg!("Fee tank cap updated");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Cap: {} lamports", cap);
+    msg!("Fee tank: {} lamports", agent_account.fee_tank);
+
+    Ok(())
+}
+
+/// Move SOL from the main wallet into the agent's fee tank. The lamports sit
+/// in the agent account but only pay network fees, never spends.
+pub fn fund_handler(ctx: Context<FundFeeTank>, amount: u64) -> Result<()> {
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
+    require!(amount > 0, DimmError::InvalidAmount);
+
+    let fee_tank = ctx
+        .accounts
+        .agent_account
+        .fee_tank
+        .checked_add(amount)
+        .ok_or(DimmError::NumericalOverflow)?;
+    require!(
+        fee_tank <= ctx.accounts.agent_account.fee_tank_cap,
+        DimmError::ExceedsFeeTankCap
+    );
+
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.main_wallet.to_account_info(),
+            to: ctx.accounts.agent_account.to_account_info(),
+        },
+    );
+    transfer(cpi_context, amount)?;
+
+    let agent_account = &mut ctx.accounts.agent_account;
+    agent_account.fee_tank = fee_tank;
+
+    msg!("Fee tank funded");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Amount: {} lamports", amount);
+    msg!("Fee tank: {} lamports", agent_account.fee_tank);
+
+    Ok(())
+}
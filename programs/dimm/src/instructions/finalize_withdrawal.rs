@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::events::WithdrawalFinalized;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct FinalizeWithdrawal<'info> {
+    #[account(
+        seeds = [PROTOCOL_SEED, main_wallet.key().as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        seeds = [WITHDRAWAL_SEED, agent_account.key().as_ref()],
+        bump = pending_withdrawal.bump,
+        has_one = main_wallet,
+        close = main_wallet
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    /// CHECK: Wallet's verified withdrawal address book; may be uninitialized
+    #[account(
+        seeds = [WITHDRAWAL_ADDRESSES_SEED, main_wallet.key().as_ref()],
+        bump
+    )]
+    pub withdrawal_addresses: UncheckedAccount<'info>,
+
+    /// CHECK: Validated against the withdrawal address book
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<FinalizeWithdrawal>) -> Result<()> {
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
+    let clock = Clock::get()?;
+    let amount = ctx.accounts.pending_withdrawal.amount;
+
+    require!(
+        ctx.accounts.pending_withdrawal.is_executable(clock.unix_timestamp),
+        DimmError::WithdrawalStillLocked
+    );
+
+    WithdrawalAddressBook::verify_destination(
+        &ctx.accounts.withdrawal_addresses.to_account_info(),
+        &ctx.accounts.main_wallet.key(),
+        &ctx.accounts.destination.key(),
+        clock.unix_timestamp,
+    )?;
+
+    let agent_info = ctx.accounts.agent_account.to_account_info();
+    let agent_balance = agent_info.lamports();
+
+    // Ensure we keep minimum balance for rent, and the fee tank until its cap is lowered
+    let available_balance = agent_balance
+        .checked_sub(MIN_AGENT_BALANCE)
+        .and_then(|v| v.checked_sub(ctx.accounts.agent_account.fee_tank))
+        .ok_or(DimmError::InsufficientBalance)?;
+
+    require!(
+        amount <= available_balance,
+        DimmError::InsufficientBalance
+    );
+
+    **agent_info.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.destination.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    emit!(WithdrawalFinalized {
+        agent: ctx.accounts.agent_account.key(),
+        destination: ctx.accounts.destination.key(),
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Withdrawal finalized");
+    msg!("Agent: {}", ctx.accounts.agent_account.key());
+    msg!("Destination: {}", ctx.accounts.destination.key());
+    msg!("Amount: {} lamports", amount);
+    msg!("Remaining balance: {} lamports", agent_balance - amount);
+
+    Ok(())
+}
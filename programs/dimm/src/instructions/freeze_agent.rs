@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::events::AgentFrozen;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct FreezeAgent<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<FreezeAgent>, duration_seconds: i64) -> Result<()> {
+    require!(duration_seconds > 0, DimmError::InvalidFreezeDuration);
+
+    let agent_account = &mut ctx.accounts.agent_account;
+    let clock = Clock::get()?;
+
+    // Replaces any current freeze, including one set by the watchtower
+    agent_account.frozen_until = clock
+        .unix_timestamp
+        .checked_add(duration_seconds)
+        .ok_or(DimmError::NumericalOverflow)?;
+
+    emit!(AgentFrozen {
+        agent: agent_account.key(),
+        frozen_until: agent_account.frozen_until,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Agent frozen");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Frozen until: {}", agent_account.frozen_until);
+
+    Ok(())
+}
@@ -6,6 +6,12 @@ use crate::constants::*;
 
 #[derive(Accounts)]
 pub struct FundAgent<'info> {
+    #[account(
+        seeds = [PROTOCOL_SEED, main_wallet.key().as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     #[account(
         mut,
         seeds = [
@@ -21,18 +27,36 @@ pub struct FundAgent<'info> {
     #[account(mut)]
     pub main_wallet: Signer<'info>,
 
+    /// Agent's escrow; required while escrow funding is on
+    #[account(
+        mut,
+        seeds = [AGENT_ESCROW_SEED, agent_account.key().as_ref()],
+        bump = agent_escrow.bump
+    )]
+    pub agent_escrow: Option<Account<'info, AgentEscrow>>,
+
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<FundAgent>, amount: u64) -> Result<()> {
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
     require!(amount > 0, DimmError::InvalidAmount);
 
-    // Transfer SOL from main wallet to agent account
+    // Transfer SOL from main wallet to the agent, or to its escrow while escrow funding is on
+    let recipient = if ctx.accounts.agent_account.escrow_funding {
+        ctx.accounts
+            .agent_escrow
+            .as_ref()
+            .ok_or(DimmError::EscrowRequired)?
+            .to_account_info()
+    } else {
+        ctx.accounts.agent_account.to_account_info()
+    };
     let cpi_context = CpiContext::new(
         ctx.accounts.system_program.to_account_info(),
         Transfer {
             from: ctx.accounts.main_wallet.to_account_info(),
-            to: ctx.accounts.agent_account.to_account_info(),
+            to: recipient,
         },
     );
 
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct FundAgentsBatch<'info> {
+    #[account(
+        seeds = [PROTOCOL_SEED, main_wallet.key().as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Agents to fund are passed as writable remaining accounts, in the same
+/// order as `amounts`; each must belong to the main wallet.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FundAgentsBatch<'info>>,
+    amounts: Vec<u64>,
+) -> Result<()> {
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
+    require!(
+        !amounts.is_empty() && amounts.len() == ctx.remaining_accounts.len(),
+        DimmError::FundingBatchMismatch
+    );
+
+    let main_wallet = ctx.accounts.main_wallet.key();
+    let mut total: u64 = 0;
+
+    for (info, amount) in ctx.remaining_accounts.iter().zip(amounts.iter()) {
+        require!(*amount > 0, DimmError::InvalidAmount);
+
+        let agent_account = Account::<AgentAccount>::try_from(info)?;
+        require_keys_eq!(agent_account.main_wallet, main_wallet, DimmError::Unauthorized);
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.main_wallet.to_account_info(),
+                to: info.clone(),
+            },
+        );
+        transfer(cpi_context, *amount)?;
+
+        total = total
+            .checked_add(*amount)
+            .ok_or(DimmError::NumericalOverflow)?;
+    }
+
+    msg!("Agents funded successfully");
+    msg!("Agents: {}", amounts.len());
+    msg!("Total: {} lamports ({} SOL)", total, total as f64 / 1_000_000_000.0);
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct FundRentPool<'info> {
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, authority.key().as_ref()],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = RentPool::LEN,
+        seeds = [RENT_POOL_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub rent_pool: Account<'info, RentPool>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves collected fees from the treasury into the rent pool
+pub fn handler(ctx: Context<FundRentPool>, amount: u64) -> Result<()> {
+    require!(amount > 0, DimmError::InvalidAmount);
+
+    // Keep the treasury rent exempt
+    let treasury_info = ctx.accounts.treasury.to_account_info();
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(Treasury::LEN);
+    let available_balance = treasury_info
+        .lamports()
+        .checked_sub(rent_exempt_minimum)
+        .ok_or(DimmError::InsufficientTreasuryBalance)?;
+
+    require!(
+        amount <= available_balance,
+        DimmError::InsufficientTreasuryBalance
+    );
+
+    **treasury_info.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.rent_pool.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    let rent_pool = &mut ctx.accounts.rent_pool;
+    rent_pool.authority = ctx.accounts.authority.key();
+    rent_pool.total_funded = rent_pool
+        .total_funded
+        .checked_add(amount)
+        .ok_or(DimmError::NumericalOverflow)?;
+    rent_pool.bump = ctx.bumps.rent_pool;
+
+    msg!("Rent pool funded");
+    msg!("Authority: {}", rent_pool.authority);
+    msg!("Amount: {} lamports", amount);
+    msg!("Total funded: {} lamports", rent_pool.total_funded);
+
+    Ok(())
+}
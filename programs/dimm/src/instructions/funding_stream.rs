@@ -0,0 +1,203 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::auth::{Capability, Caller};
+use crate::constants::*;
+use crate::errors::DimmError;
+use crate::events::StreamWithdrawn;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CreateFundingStream<'info> {
+    #[account(
+        seeds = [PROTOCOL_SEED, main_wallet.key().as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        init,
+        payer = main_wallet,
+        space = FundingStream::LEN,
+        seeds = [FUNDING_STREAM_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub funding_stream: Account<'info, FundingStream>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawStreamed<'info> {
+    #[account(
+        seeds = [PROTOCOL_SEED, agent_account.main_wallet.as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        seeds = [FUNDING_STREAM_SEED, agent_account.key().as_ref()],
+        bump = funding_stream.bump,
+        constraint = funding_stream.agent == agent_account.key() @ DimmError::Unauthorized
+    )]
+    pub funding_stream: Account<'info, FundingStream>,
+
+    /// Agent signer or main wallet
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelFundingStream<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        close = main_wallet,
+        seeds = [FUNDING_STREAM_SEED, agent_account.key().as_ref()],
+        bump = funding_stream.bump,
+        has_one = main_wallet
+    )]
+    pub funding_stream: Account<'info, FundingStream>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+}
+
+/// Open a stream to the agent, escrowing everything it will release
+pub fn create_handler(ctx: Context<CreateFundingStream>, params: FundingStreamParams) -> Result<()> {
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
+    require!(!ctx.accounts.agent_account.revoked, DimmError::AgentRevoked);
+    require!(params.rate > 0, DimmError::InvalidAmount);
+    require!(
+        params.start_at <= params.cliff_at
+            && params.cliff_at <= params.end_at
+            && params.start_at < params.end_at,
+        DimmError::InvalidStreamSchedule
+    );
+
+    let funding_stream = &mut ctx.accounts.funding_stream;
+    funding_stream.agent = ctx.accounts.agent_account.key();
+    funding_stream.main_wallet = ctx.accounts.main_wallet.key();
+    funding_stream.rate = params.rate;
+    funding_stream.start_at = params.start_at;
+    funding_stream.cliff_at = params.cliff_at;
+    funding_stream.end_at = params.end_at;
+    funding_stream.withdrawn = 0;
+    funding_stream.bump = ctx.bumps.funding_stream;
+
+    let total = funding_stream.total()?;
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.main_wallet.to_account_info(),
+            to: ctx.accounts.funding_stream.to_account_info(),
+        },
+    );
+    transfer(cpi_context, total)?;
+
+    msg!("Funding stream created");
+    msg!("Agent: {}", ctx.accounts.agent_account.key());
+    msg!("Rate: {} lamports/sec", params.rate);
+    msg!("Total: {} lamports", total);
+
+    Ok(())
+}
+
+/// Move everything vested so far from the stream into the agent
+pub fn withdraw_handler(ctx: Context<WithdrawStreamed>) -> Result<()> {
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
+    let agent_account = &ctx.accounts.agent_account;
+    require!(!agent_account.revoked, DimmError::AgentRevoked);
+    Caller::authorize(
+        ctx.accounts.authority.key(),
+        &agent_account.main_wallet,
+        Some(agent_account),
+        None,
+        Capability::Execute,
+    )?;
+
+    let clock = Clock::get()?;
+    let funding_stream = &mut ctx.accounts.funding_stream;
+    let amount = funding_stream.withdrawable(clock.unix_timestamp)?;
+    require!(amount > 0, DimmError::NothingVested);
+
+    **funding_stream.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.agent_account.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    funding_stream.withdrawn = funding_stream
+        .withdrawn
+        .checked_add(amount)
+        .ok_or(DimmError::NumericalOverflow)?;
+
+    emit!(StreamWithdrawn {
+        agent: funding_stream.agent,
+        amount,
+        withdrawn: funding_stream.withdrawn,
+        remaining: funding_stream.total()?.saturating_sub(funding_stream.withdrawn),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Streamed funds withdrawn");
+    msg!("Agent: {}", funding_stream.agent);
+    msg!("Amount: {} lamports", amount);
+
+    Ok(())
+}
+
+/// Stop the stream: pay the agent what has vested and return the rest of the
+/// escrow and the rent to the main wallet
+pub fn cancel_handler(ctx: Context<CancelFundingStream>) -> Result<()> {
+    let clock = Clock::get()?;
+    let funding_stream = &mut ctx.accounts.funding_stream;
+    let vested = funding_stream.withdrawable(clock.unix_timestamp)?;
+
+    if vested > 0 {
+        **funding_stream.to_account_info().try_borrow_mut_lamports()? -= vested;
+        **ctx.accounts.agent_account.to_account_info().try_borrow_mut_lamports()? += vested;
+        funding_stream.withdrawn = funding_stream
+            .withdrawn
+            .checked_add(vested)
+            .ok_or(DimmError::NumericalOverflow)?;
+    }
+
+    msg!("Funding stream cancelled");
+    msg!("Agent: {}", funding_stream.agent);
+    msg!("Paid to agent: {} lamports", vested);
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct GrantPermission<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+/// Grant one permission, lapsing at `expires_at` if given
+pub fn handler(
+    ctx: Context<GrantPermission>,
+    permission: AgentPermission,
+    expires_at: Option<i64>,
+) -> Result<()> {
+    let agent_account = &mut ctx.accounts.agent_account;
+    let clock = Clock::get()?;
+
+    // New permissions wait out the change delay through update_permissions
+    require!(
+        !agent_account.config_locked || agent_account.permissions.has(permission),
+        DimmError::ConfigLocked
+    );
+    require!(
+        agent_account.change_delay_seconds == 0 || agent_account.permissions.has(permission),
+        DimmError::ChangeDelayActive
+    );
+    agent_account.grant_permission(permission, expires_at, clock.unix_timestamp)?;
+
+    msg!("Agent permission granted");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Permission: {:?}", permission);
+    if let Some(expires_at) = expires_at {
+        msg!("Expires at: {}", expires_at);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,231 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::events::AgentConfigImported;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ImportAgentConfig<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED, main_wallet.key().as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Wallet's protocol stake; may be uninitialized
+    #[account(
+        seeds = [STAKE_SEED, main_wallet.key().as_ref()],
+        bump
+    )]
+    pub wallet_stake: UncheckedAccount<'info>,
+
+    /// Required if the configuration has destination whitelist entries
+    #[account(
+        mut,
+        seeds = [
+            WHITELIST_SEED,
+            agent_account.key().as_ref(),
+            &WhitelistType::Destinations.seed()
+        ],
+        bump = destination_whitelist.bump
+    )]
+    pub destination_whitelist: Option<Box<Account<'info, Whitelist>>>,
+
+    /// Required if the configuration has program whitelist entries
+    #[account(
+        mut,
+        seeds = [
+            WHITELIST_SEED,
+            agent_account.key().as_ref(),
+            &WhitelistType::Programs.seed()
+        ],
+        bump = program_whitelist.bump
+    )]
+    pub program_whitelist: Option<Box<Account<'info, Whitelist>>>,
+
+    /// Required if the configuration has blackout windows; created on first use
+    #[account(
+        init_if_needed,
+        payer = main_wallet,
+        space = ActivitySchedule::LEN,
+        seeds = [SCHEDULE_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub schedule: Option<Box<Account<'info, ActivitySchedule>>>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Apply an exported configuration to the agent. `expected_hash` is the
+/// `config_hash` from the export event, so a mistyped or altered
+/// configuration is rejected instead of half-applied.
+pub fn handler(
+    ctx: Context<ImportAgentConfig>,
+    config: AgentConfig,
+    expected_hash: [u8; 32],
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let config_hash = config.hash()?;
+    require!(config_hash == expected_hash, DimmError::ConfigHashMismatch);
+    config.validate()?;
+    let permissions = PermissionSet::from_bits(config.permissions)?;
+
+    let agent_key = ctx.accounts.agent_account.key();
+    let agent_account = &mut ctx.accounts.agent_account;
+    require!(!agent_account.revoked, DimmError::AgentRevoked);
+    require!(
+        config.daily_limit >= agent_account.critical_reserve,
+        DimmError::InvalidLimitConfiguration
+    );
+
+    // Lists are replaced wholesale; their accounts must be passed to carry entries
+    if let Some(whitelist) = ctx.accounts.destination_whitelist.as_mut() {
+        whitelist.addresses.clear();
+        for address in &config.destination_whitelist {
+            whitelist.add_address(*address)?;
+        }
+        whitelist.last_updated = clock.unix_timestamp;
+    } else {
+        require!(
+            config.destination_whitelist.is_empty() && !config.require_destination_whitelist,
+            DimmError::WhitelistRequired
+        );
+    }
+
+    if let Some(whitelist) = ctx.accounts.program_whitelist.as_mut() {
+        whitelist.addresses.clear();
+        for program in &config.program_whitelist {
+            whitelist.add_address(*program)?;
+        }
+        whitelist.last_updated = clock.unix_timestamp;
+    } else {
+        require!(config.program_whitelist.is_empty(), DimmError::WhitelistRequired);
+    }
+
+    if let Some(schedule) = ctx.accounts.schedule.as_mut() {
+        schedule.agent = agent_key;
+        schedule.bump = ctx.bumps.schedule;
+        schedule.blackouts.clear();
+        // Windows that ended since the export are dropped rather than rejected
+        for window in config.blackouts.iter().filter(|w| w.end > clock.unix_timestamp) {
+            schedule.add_blackout(*window, clock.unix_timestamp)?;
+        }
+        schedule.last_updated = clock.unix_timestamp;
+    } else {
+        require!(
+            config.blackouts.iter().all(|w| w.end <= clock.unix_timestamp),
+            DimmError::ScheduleRequired
+        );
+    }
+
+    // USD limits are priced by this deployment's own feed
+    require!(
+        (config.daily_limit_usd == 0 && config.max_usd_per_transaction == 0)
+            || ctx.accounts.protocol_config.sol_usd_price_feed != Pubkey::default(),
+        DimmError::InvalidPriceFeed
+    );
+
+    // A lock or change delay can't be sidestepped by importing looser settings
+    let limits = UpdateLimitsParams {
+        max_sol_per_transaction: Some(config.max_sol_per_transaction),
+        daily_limit: Some(config.daily_limit),
+        maintenance_allowance: Some(config.maintenance_allowance),
+        approval_threshold: Some(config.approval_threshold),
+        donation_allowance: Some(config.donation_allowance),
+        soft_daily_limit: Some(config.soft_daily_limit),
+        lease_threshold: Some(config.lease_threshold),
+        daily_limit_usd: Some(config.daily_limit_usd),
+        max_usd_per_transaction: Some(config.max_usd_per_transaction),
+        max_slippage_bps: Some(config.max_slippage_bps),
+        max_sol_per_hour: Some(config.max_sol_per_hour),
+    };
+    let (_, loosened) = agent_account.split_limit_changes(&limits);
+    let loosens = loosened.is_some() || config.permissions & !agent_account.permissions.bits != 0;
+    require!(!(loosens && agent_account.config_locked), DimmError::ConfigLocked);
+    require!(
+        !(loosens && agent_account.change_delay_seconds > 0),
+        DimmError::ChangeDelayActive
+    );
+    agent_account.supersede_pending_limits(&limits);
+    agent_account.pending_permissions &= config.permissions;
+
+    let previous_daily_limit = agent_account.daily_limit;
+
+    agent_account.permissions = permissions;
+    agent_account.max_sol_per_transaction = config.max_sol_per_transaction;
+    agent_account.daily_limit = config.daily_limit;
+    agent_account.soft_daily_limit = config.soft_daily_limit;
+    agent_account.max_sol_per_hour = config.max_sol_per_hour;
+    agent_account.maintenance_allowance = config.maintenance_allowance;
+    agent_account.donation_allowance = config.donation_allowance;
+    agent_account.approval_threshold = config.approval_threshold;
+    agent_account.lease_threshold = config.lease_threshold;
+    agent_account.daily_limit_usd = config.daily_limit_usd;
+    agent_account.max_usd_per_transaction = config.max_usd_per_transaction;
+    agent_account.max_slippage_bps = config.max_slippage_bps;
+    // The withdrawal timelock can only be weakened through the staged path
+    require!(
+        !agent_account.loosens_withdrawal_policy(&WithdrawalPolicyParams {
+            threshold: config.withdrawal_threshold,
+            delay_seconds: config.withdrawal_delay,
+        }),
+        DimmError::WithdrawalPolicyLoosened
+    );
+    agent_account.withdrawal_threshold = config.withdrawal_threshold;
+    agent_account.withdrawal_delay = config.withdrawal_delay;
+    agent_account.retention_max_records = config.retention_max_records;
+    agent_account.retention_max_age = config.retention_max_age;
+    agent_account.require_destination_whitelist = config.require_destination_whitelist;
+
+    // A higher daily limit must stay within the wallet's stake quota, as in update_limits
+    let protocol_config = &mut ctx.accounts.protocol_config;
+    protocol_config.check_tier_limits(
+        agent_account.trust_level,
+        agent_account.max_sol_per_transaction,
+        agent_account.daily_limit,
+    )?;
+    protocol_config.check_limit_caps(agent_account.max_sol_per_transaction, agent_account.daily_limit)?;
+    let aggregate_daily_limit = protocol_config
+        .aggregate_daily_limit
+        .saturating_sub(previous_daily_limit)
+        .checked_add(agent_account.daily_limit)
+        .ok_or(DimmError::NumericalOverflow)?;
+    if agent_account.daily_limit > previous_daily_limit {
+        let effective_stake =
+            WalletStake::load_effective(&ctx.accounts.wallet_stake, clock.epoch)?;
+        protocol_config.check_stake_quotas(
+            effective_stake,
+            protocol_config.active_agents,
+            aggregate_daily_limit,
+        )?;
+    }
+    protocol_config.aggregate_daily_limit = aggregate_daily_limit;
+
+    emit!(AgentConfigImported {
+        agent: agent_key,
+        config_hash,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Agent configuration imported");
+    msg!("Agent: {}", agent_key);
+    msg!("Daily limit: {} lamports", agent_account.daily_limit);
+
+    Ok(())
+}
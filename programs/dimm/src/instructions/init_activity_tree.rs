@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use spl_account_compression::cpi::{self as compression, accounts::Initialize};
+use spl_account_compression::{program::SplAccountCompression, Noop};
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct InitActivityTree<'info> {
+    #[account(
+        init,
+        payer = main_wallet,
+        space = ActivityTree::LEN,
+        seeds = [ACTIVITY_TREE_SEED, main_wallet.key().as_ref()],
+        bump
+    )]
+    pub activity_tree: Account<'info, ActivityTree>,
+
+    /// CHECK: This account is initialized by the account compression program
+    #[account(zero)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitActivityTree>, params: InitializeParams) -> Result<()> {
+    let capacity = 1u64
+        .checked_shl(params.max_depth)
+        .ok_or(DimmError::NumericalOverflow)?;
+
+    let main_wallet = ctx.accounts.main_wallet.key();
+    let bump = ctx.bumps.activity_tree;
+    let tree_seeds = &[ACTIVITY_TREE_SEED, main_wallet.as_ref(), &[bump]];
+    let signer_seeds = &[&tree_seeds[..]];
+
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.compression_program.to_account_info(),
+        Initialize {
+            merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+            authority: ctx.accounts.activity_tree.to_account_info(),
+            noop: ctx.accounts.log_wrapper.to_account_info(),
+        },
+        signer_seeds,
+    );
+    compression::init_empty_merkle_tree(cpi_context, params.max_depth, params.max_buffer_size)?;
+
+    let activity_tree = &mut ctx.accounts.activity_tree;
+    activity_tree.main_wallet = main_wallet;
+    activity_tree.merkle_tree = ctx.accounts.merkle_tree.key();
+    activity_tree.leaf_count = 0;
+    activity_tree.capacity = capacity;
+    activity_tree.bump = bump;
+    activity_tree.reserved = [0; 32];
+
+    msg!("Activity tree initialized");
+    msg!("Main wallet: {}", main_wallet);
+    msg!("Merkle Tree: {}", activity_tree.merkle_tree);
+    msg!("Capacity: {} records", capacity);
+
+    Ok(())
+}
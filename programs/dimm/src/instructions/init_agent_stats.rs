@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct InitAgentStats<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        init,
+        payer = main_wallet,
+        space = AgentStats::LEN,
+        seeds = [STATS_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub stats: Account<'info, AgentStats>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitAgentStats>) -> Result<()> {
+    let stats = &mut ctx.accounts.stats;
+
+    stats.agent = ctx.accounts.agent_account.key();
+    stats.successful_transactions = 0;
+    stats.failed_transactions = 0;
+    stats.sol_spent_transfers = 0;
+    stats.sol_spent_swaps = 0;
+    stats.sol_spent_nfts = 0;
+    stats.sol_spent_staking = 0;
+    stats.sol_spent_governance = 0;
+    stats.sol_spent_defi = 0;
+    stats.sol_spent_donations = 0;
+    stats.avg_transaction_size = 0;
+    stats.largest_transaction = 0;
+    stats.daily_limit_hits = 0;
+    stats.tx_limit_hits = 0;
+    stats.total_gas_paid = 0;
+    stats.last_activity = 0;
+    stats.longest_inactive_period = 0;
+    stats.unique_destinations = 0;
+    stats.destination_filter = [0; AgentStats::DESTINATION_FILTER_BYTES];
+    stats.version = AGENT_STATS_VERSION;
+    stats.bump = ctx.bumps.stats;
+    stats.reserved = [0; 64];
+
+    msg!("Agent stats initialized");
+    msg!("Agent: {}", stats.agent);
+
+    Ok(())
+}
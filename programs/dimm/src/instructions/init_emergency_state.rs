@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct InitEmergencyState<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = EmergencyState::LEN,
+        seeds = [EMERGENCY_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub emergency_state: Account<'info, EmergencyState>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitEmergencyState>, emergency_contacts: Vec<Pubkey>) -> Result<()> {
+    require!(
+        emergency_contacts.len() <= EmergencyState::MAX_EMERGENCY_CONTACTS,
+        DimmError::TooManyEmergencyContacts
+    );
+
+    let emergency_state = &mut ctx.accounts.emergency_state;
+
+    emergency_state.authority = ctx.accounts.authority.key();
+    emergency_state.paused = false;
+    emergency_state.pause_reason = String::new();
+    emergency_state.paused_at = 0;
+    emergency_state.paused_by = Pubkey::default();
+    emergency_state.emergency_contacts = emergency_contacts;
+    emergency_state.pause_count = 0;
+    emergency_state.bump = ctx.bumps.emergency_state;
+
+    msg!("Emergency state initialized");
+    msg!("Authority: {}", emergency_state.authority);
+    msg!("Emergency contacts: {}", emergency_state.emergency_contacts.len());
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct InitGlobalStats<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = GlobalStats::LEN,
+        seeds = [GLOBAL_STATS_SEED],
+        bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the protocol-wide stats singleton. Permissionless; activity before
+/// this point is not counted.
+pub fn handler(ctx: Context<InitGlobalStats>) -> Result<()> {
+    let clock = Clock::get()?;
+    let global_stats = &mut ctx.accounts.global_stats;
+
+    global_stats.total_agents = 0;
+    global_stats.revoked_agents = 0;
+    global_stats.total_volume = 0;
+    global_stats.total_fees = 0;
+    global_stats.epoch = clock.epoch;
+    global_stats.active_agents = 0;
+    global_stats.bump = ctx.bumps.global_stats;
+    global_stats.reserved = [0u8; 32];
+
+    msg!("Global stats initialized");
+    msg!("Epoch: {}", clock.epoch);
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct InitTreasury<'info> {
+    #[account(
+        seeds = [PROTOCOL_SEED, authority.key().as_ref()],
+        bump = protocol_config.bump,
+        has_one = authority
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Treasury::LEN,
+        seeds = [TREASURY_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitTreasury>, params: InitTreasuryParams) -> Result<()> {
+    require!(params.fee_bps <= 10000, DimmError::InvalidFeeConfiguration);
+
+    let treasury = &mut ctx.accounts.treasury;
+    let clock = Clock::get()?;
+
+    treasury.authority = ctx.accounts.authority.key();
+    treasury.total_fees_collected = 0;
+    treasury.total_distributed = 0;
+    treasury.total_withdrawn = 0;
+    treasury.active_agents = 0;
+    treasury.fee_bps = params.fee_bps;
+    treasury.min_fee = params.min_fee;
+    treasury.last_fee_collection = clock.unix_timestamp;
+    treasury.bump = ctx.bumps.treasury;
+
+    msg!("Treasury initialized");
+    msg!("Authority: {}", treasury.authority);
+    msg!("Fee: {} bps (min {} lamports)", treasury.fee_bps, treasury.min_fee);
+
+    Ok(())
+}
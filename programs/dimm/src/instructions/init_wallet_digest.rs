@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct InitWalletDigest<'info> {
+    #[account(
+        init,
+        payer = main_wallet,
+        space = WalletDigest::LEN,
+        seeds = [DIGEST_SEED, main_wallet.key().as_ref()],
+        bump
+    )]
+    pub digest: Account<'info, WalletDigest>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitWalletDigest>) -> Result<()> {
+    let digest = &mut ctx.accounts.digest;
+    let clock = Clock::get()?;
+
+    digest.main_wallet = ctx.accounts.main_wallet.key();
+    digest.total_transactions = 0;
+    digest.token_transfers = 0;
+    digest.total_volume = 0;
+    digest.total_fees = 0;
+    digest.volume_today = 0;
+    digest.transactions_today = 0;
+    digest.window_start = clock.unix_timestamp;
+    digest.last_agent = Pubkey::default();
+    digest.last_execution_at = 0;
+    digest.bump = ctx.bumps.digest;
+    digest.reserved = [0; 64];
+
+    msg!("Wallet digest initialized");
+    msg!("Main wallet: {}", digest.main_wallet);
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+#[instruction(whitelist_type: WhitelistType)]
+pub struct InitWhitelist<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        init,
+        payer = main_wallet,
+        space = Whitelist::LEN,
+        seeds = [
+            WHITELIST_SEED,
+            agent_account.key().as_ref(),
+            &whitelist_type.seed()
+        ],
+        bump
+    )]
+    pub whitelist: Account<'info, Whitelist>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitWhitelist>, whitelist_type: WhitelistType) -> Result<()> {
+    let agent_account = &mut ctx.accounts.agent_account;
+    let whitelist = &mut ctx.accounts.whitelist;
+    let clock = Clock::get()?;
+
+    whitelist.owner = agent_account.key();
+    whitelist.addresses = Vec::new();
+    whitelist.enabled = true;
+    whitelist.whitelist_type = whitelist_type;
+    whitelist.last_updated = clock.unix_timestamp;
+    whitelist.bump = ctx.bumps.whitelist;
+
+    if whitelist_type == WhitelistType::Destinations {
+        agent_account.require_destination_whitelist = true;
+    }
+
+    msg!("Whitelist initialized");
+    msg!("Agent: {}", whitelist.owner);
+    msg!("Type: {:?}", whitelist.whitelist_type);
+
+    Ok(())
+}
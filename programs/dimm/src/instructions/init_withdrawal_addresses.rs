@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct InitWithdrawalAddresses<'info> {
+    #[account(
+        init,
+        payer = main_wallet,
+        space = WithdrawalAddressBook::LEN,
+        seeds = [WITHDRAWAL_ADDRESSES_SEED, main_wallet.key().as_ref()],
+        bump
+    )]
+    pub withdrawal_addresses: Account<'info, WithdrawalAddressBook>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitWithdrawalAddresses>) -> Result<()> {
+    let withdrawal_addresses = &mut ctx.accounts.withdrawal_addresses;
+    let clock = Clock::get()?;
+
+    withdrawal_addresses.main_wallet = ctx.accounts.main_wallet.key();
+    withdrawal_addresses.entries = Vec::new();
+    withdrawal_addresses.last_updated = clock.unix_timestamp;
+    withdrawal_addresses.bump = ctx.bumps.withdrawal_addresses;
+
+    msg!("Verified withdrawal addresses enabled");
+    msg!("Main Wallet: {}", withdrawal_addresses.main_wallet);
+
+    Ok(())
+}
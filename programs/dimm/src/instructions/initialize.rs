@@ -37,6 +37,12 @@ pub struct Initialize<'info> {
 }
 
 pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()> {
+    LimitCapParams {
+        max_agent_daily_limit: params.max_agent_daily_limit,
+        max_agent_tx_limit: params.max_agent_tx_limit,
+    }
+    .validate()?;
+
     let protocol_config = &mut ctx.accounts.protocol_config;
     
     protocol_config.authority = ctx.accounts.authority.key();
@@ -45,6 +51,20 @@ pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()>
     protocol_config.version = 1;
     protocol_config.paused = false;
     protocol_config.bump = ctx.bumps.protocol_config;
+    protocol_config.agents_per_staked_sol = 0;
+    protocol_config.daily_limit_per_staked_sol = 0;
+    protocol_config.aggregate_daily_limit = 0;
+    protocol_config.cold_start_threshold = 0;
+    protocol_config.cold_start_min_age = 0;
+    protocol_config.cold_start_min_transactions = 0;
+    protocol_config.restrict_unlabeled_programs = false;
+    protocol_config.sol_usd_price_feed = Pubkey::default();
+    protocol_config.trust_tiers = [TierConfig::default(); 4];
+    protocol_config.max_agent_daily_limit = params.max_agent_daily_limit;
+    protocol_config.max_agent_tx_limit = params.max_agent_tx_limit;
+    protocol_config.merkle_trees = vec![protocol_config.merkle_tree];
+    protocol_config.current_tree = 0;
+    protocol_config.active_agents = 0;
 
     msg!("DIMM Protocol initialized");
     msg!("Authority: {}", protocol_config.authority);
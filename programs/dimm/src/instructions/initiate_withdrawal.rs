@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::events::WithdrawalInitiated;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct InitiateWithdrawal<'info> {
+    #[account(
+        seeds = [PROTOCOL_SEED, main_wallet.key().as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        init,
+        payer = main_wallet,
+        space = PendingWithdrawal::LEN,
+        seeds = [WITHDRAWAL_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+
+    /// CHECK: Wallet's emergency state; may be uninitialized
+    #[account(
+        seeds = [EMERGENCY_SEED, main_wallet.key().as_ref()],
+        bump
+    )]
+    pub emergency_state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitiateWithdrawal>, amount: u64) -> Result<()> {
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
+    EmergencyState::require_not_paused(&ctx.accounts.emergency_state)?;
+    require!(!ctx.accounts.agent_account.revoked, DimmError::AgentRevoked);
+    require!(amount > 0, DimmError::InvalidAmount);
+
+    let agent_account = &ctx.accounts.agent_account;
+
+    // Refuse what `finalize_withdrawal` could never pay out, so a bogus
+    // request cannot hold the withdrawal slot for the whole delay
+    let available_balance = agent_account
+        .to_account_info()
+        .lamports()
+        .checked_sub(MIN_AGENT_BALANCE)
+        .and_then(|v| v.checked_sub(agent_account.fee_tank))
+        .ok_or(DimmError::InsufficientBalance)?;
+
+    require!(
+        amount <= available_balance,
+        DimmError::InsufficientBalance
+    );
+
+    let pending_withdrawal = &mut ctx.accounts.pending_withdrawal;
+    let clock = Clock::get()?;
+
+    pending_withdrawal.agent = agent_account.key();
+    pending_withdrawal.main_wallet = agent_account.main_wallet;
+    pending_withdrawal.amount = amount;
+    pending_withdrawal.initiated_at = clock.unix_timestamp;
+    pending_withdrawal.executable_at = clock
+        .unix_timestamp
+        .checked_add(agent_account.withdrawal_delay)
+        .ok_or(DimmError::NumericalOverflow)?;
+    pending_withdrawal.bump = ctx.bumps.pending_withdrawal;
+
+    emit!(WithdrawalInitiated {
+        agent: pending_withdrawal.agent,
+        main_wallet: pending_withdrawal.main_wallet,
+        amount,
+        executable_at: pending_withdrawal.executable_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Withdrawal initiated");
+    msg!("Agent: {}", pending_withdrawal.agent);
+    msg!("Amount: {} lamports", amount);
+    msg!("Executable at: {}", pending_withdrawal.executable_at);
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use mpl_bubblegum::program::Bubblegum;
+use spl_account_compression::{program::SplAccountCompression, Noop};
+use crate::cnft::{self, TreeAccounts};
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ListAgentForSale<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet,
+        has_one = merkle_tree
+    )]
+    pub agent_account: Box<Account<'info, AgentAccount>>,
+
+    #[account(
+        init,
+        payer = main_wallet,
+        space = AgentListing::LEN,
+        seeds = [LISTING_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub listing: Box<Account<'info, AgentListing>>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    /// CHECK: Tree authority PDA
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = bubblegum_program.key()
+    )]
+    pub tree_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the agent's merkle tree
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    pub bubblegum_program: Program<'info, Bubblegum>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub system_program: Program<'info, System>,
+}
+
+/// The cNFT's merkle proof must be passed as remaining accounts.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ListAgentForSale<'info>>,
+    price: u64,
+    leaf: AgentLeafArgs,
+) -> Result<()> {
+    let agent_account = &ctx.accounts.agent_account;
+    let clock = Clock::get()?;
+
+    require!(price > 0, DimmError::InvalidAmount);
+    require!(!agent_account.revoked, DimmError::AgentRevoked);
+    require!(leaf.index == agent_account.leaf_index, DimmError::InvalidMerkleProof);
+
+    // The listing escrow becomes the cNFT delegate so a buyer can settle without the seller
+    let tree = TreeAccounts {
+        tree_authority: ctx.accounts.tree_authority.to_account_info(),
+        merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+        bubblegum_program: ctx.accounts.bubblegum_program.to_account_info(),
+        compression_program: ctx.accounts.compression_program.to_account_info(),
+        log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+    };
+    cnft::delegate_leaf(
+        &tree,
+        &ctx.accounts.main_wallet.to_account_info(),
+        &ctx.accounts.main_wallet.to_account_info(),
+        &ctx.accounts.listing.to_account_info(),
+        &leaf,
+        ctx.remaining_accounts,
+    )?;
+
+    let listing = &mut ctx.accounts.listing;
+    listing.agent = agent_account.key();
+    listing.seller = ctx.accounts.main_wallet.key();
+    listing.price = price;
+    listing.listed_at = clock.unix_timestamp;
+    listing.bump = ctx.bumps.listing;
+
+    msg!("Agent listed for sale");
+    msg!("Agent: {}", listing.agent);
+    msg!("Seller: {}", listing.seller);
+    msg!("Price: {} lamports", listing.price);
+
+    Ok(())
+}
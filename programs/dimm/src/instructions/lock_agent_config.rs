@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::events::AgentConfigLocked;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct LockAgentConfig<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+/// Permanently lock the agent's permissions and limits against increases.
+/// They can still be lowered and the agent revoked, but never raised or
+/// reactivated, so counterparties can rely on its authority never growing.
+/// Anything staged behind the change delay is dropped.
+pub fn handler(ctx: Context<LockAgentConfig>) -> Result<()> {
+    let clock = Clock::get()?;
+    let agent = ctx.accounts.agent_account.key();
+    let agent_account = &mut ctx.accounts.agent_account;
+    require!(!agent_account.config_locked, DimmError::ConfigLocked);
+
+    agent_account.config_locked = true;
+    agent_account.clear_pending_changes();
+
+    emit!(AgentConfigLocked {
+        agent,
+        permissions: agent_account.permissions.bits,
+        max_sol_per_transaction: agent_account.max_sol_per_transaction,
+        daily_limit: agent_account.daily_limit,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Agent configuration locked");
+    msg!("Agent: {}", agent);
+
+    Ok(())
+}
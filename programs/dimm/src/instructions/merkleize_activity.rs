@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::events::ActivityMerkleized;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct MerkleizeActivity<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+}
+
+/// Permissionless crank. Activity records are passed as remaining accounts
+/// in sequence order, starting at `merkleized_through`. Each leaf commits to
+/// a record's chain hash, which in turn commits to every record before it,
+/// so the latest root covers the whole history. Records should be
+/// merkleized before the retention policy overwrites them.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MerkleizeActivity<'info>>,
+) -> Result<()> {
+    require!(!ctx.remaining_accounts.is_empty(), DimmError::EmptyActivityBatch);
+
+    let clock = Clock::get()?;
+    let agent_account = &mut ctx.accounts.agent_account;
+    let agent_key = agent_account.key();
+    let first_sequence = agent_account.merkleized_through;
+
+    let mut next_sequence = first_sequence;
+    let mut head = agent_account.merkleized_head;
+    let mut leaves = Vec::with_capacity(ctx.remaining_accounts.len());
+
+    for info in ctx.remaining_accounts.iter() {
+        let record = Account::<AgentActivity>::try_from(info)?;
+        require_keys_eq!(record.agent, agent_key, DimmError::Unauthorized);
+        require!(
+            record.sequence == next_sequence && record.prev_hash == head,
+            DimmError::ActivityChainBroken
+        );
+
+        head = record.chain_hash()?;
+        leaves.push(activity_leaf(&head));
+        next_sequence = next_sequence
+            .checked_add(1)
+            .ok_or(DimmError::NumericalOverflow)?;
+    }
+
+    let root = activity_merkle_root(&leaves);
+    agent_account.activity_root = root;
+    agent_account.merkleized_through = next_sequence;
+    agent_account.merkleized_head = head;
+
+    emit!(ActivityMerkleized {
+        agent: agent_key,
+        first_sequence,
+        count: leaves.len() as u64,
+        root,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Activity merkleized");
+    msg!("Agent: {}", agent_key);
+    msg!("Records: {} to {}", first_sequence, next_sequence - 1);
+
+    Ok(())
+}
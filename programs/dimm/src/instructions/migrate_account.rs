@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+use crate::constants::*;
+use crate::errors::DimmError;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: Program account to upgrade; owner and discriminator checked in the handler
+    #[account(mut)]
+    pub account: UncheckedAccount<'info>,
+
+    /// Agent's protocol config; required to migrate an `AgentAccount`
+    pub protocol_config: Option<Box<Account<'info, ProtocolConfig>>>,
+
+    /// Pays rent for the added space
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrade an `AgentAccount`, `AgentStats` or `RateLimit` laid out before
+/// it was versioned to the current layout, in place. Permissionless; the
+/// conversion is fixed, so the caller only chooses who pays the rent.
+pub fn handler(ctx: Context<MigrateAccount>) -> Result<()> {
+    let account = ctx.accounts.account.to_account_info();
+    require_keys_eq!(*account.owner, crate::ID, DimmError::Unauthorized);
+
+    let data = account.try_borrow_data()?;
+    require!(data.len() >= 8, DimmError::UnsupportedAccountType);
+    let discriminator = &data[..8];
+    let legacy = &mut &data[8..];
+
+    if discriminator == AgentAccount::DISCRIMINATOR {
+        require!(data.len() == AgentAccountV0::LEN, DimmError::AccountAlreadyMigrated);
+        let agent = AgentAccountV0::deserialize(legacy)?;
+        let protocol_config = ctx
+            .accounts
+            .protocol_config
+            .as_ref()
+            .ok_or(DimmError::ProtocolConfigRequired)?;
+        let expected_config = Pubkey::create_program_address(
+            &[PROTOCOL_SEED, agent.main_wallet.as_ref(), &[protocol_config.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| DimmError::Unauthorized)?;
+        require_keys_eq!(protocol_config.key(), expected_config, DimmError::Unauthorized);
+
+        let agent = agent.into_current(protocol_config.merkle_tree);
+        drop(data);
+        rewrite(&ctx, &agent, AgentAccount::LEN)?;
+        msg!("Agent ID: {}", agent.agent_id);
+    } else if discriminator == AgentStats::DISCRIMINATOR {
+        require!(data.len() == AgentStatsV0::LEN, DimmError::AccountAlreadyMigrated);
+        let stats = AgentStatsV0::deserialize(legacy)?.into_current();
+        drop(data);
+        rewrite(&ctx, &stats, AgentStats::LEN)?;
+    } else if discriminator == RateLimit::DISCRIMINATOR {
+        require!(data.len() == RateLimitV0::LEN, DimmError::AccountAlreadyMigrated);
+        let rate_limit = RateLimitV0::deserialize(legacy)?.into_current();
+        drop(data);
+        rewrite(&ctx, &rate_limit, RateLimit::LEN)?;
+    } else {
+        return err!(DimmError::UnsupportedAccountType);
+    }
+
+    msg!("Account migrated");
+    msg!("Account: {}", account.key());
+
+    Ok(())
+}
+
+/// Grow the account to `size` bytes, topping up its rent from the payer,
+/// and write `value` in the current layout
+fn rewrite<T: AccountSerialize>(ctx: &Context<MigrateAccount>, value: &T, size: usize) -> Result<()> {
+    let account = ctx.accounts.account.to_account_info();
+    let current_size = account.data_len();
+
+    let rent = Rent::get()?;
+    let top_up = rent
+        .minimum_balance(size)
+        .saturating_sub(rent.minimum_balance(current_size));
+    if top_up > 0 {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: account.clone(),
+            },
+        );
+        transfer(cpi_context, top_up)?;
+    }
+
+    if size > current_size {
+        account.realloc(size, true)?;
+    }
+    let mut data = account.try_borrow_mut_data()?;
+    value.try_serialize(&mut &mut data[..])
+}
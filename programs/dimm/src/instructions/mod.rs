@@ -6,8 +6,142 @@ pub mod execute_transaction;
 pub mod update_permissions;
 pub mod revoke_agent;
 pub mod withdraw_from_agent;
+pub mod fee_tank;
 pub mod update_limits;
 pub mod record_activity;
+pub mod init_treasury;
+pub mod approve_credit_line;
+pub mod draw_credit;
+pub mod repay_credit;
+pub mod update_retention_policy;
+pub mod register_watchtower;
+pub mod remove_watchtower;
+pub mod watchtower_heartbeat;
+pub mod watchtower_freeze_agent;
+pub mod set_withdrawal_policy;
+pub mod initiate_withdrawal;
+pub mod finalize_withdrawal;
+pub mod cancel_withdrawal;
+pub mod init_withdrawal_addresses;
+pub mod add_withdrawal_address;
+pub mod remove_withdrawal_address;
+pub mod register_hook;
+pub mod remove_hook;
+pub mod pause_protocol;
+pub mod unpause_protocol;
+pub mod configure_priority_lanes;
+pub mod set_token_limit;
+pub mod init_whitelist;
+pub mod add_to_whitelist;
+pub mod remove_from_whitelist;
+pub mod set_whitelist_enabled;
+pub mod list_agent_for_sale;
+pub mod buy_agent;
+pub mod cancel_listing;
+pub mod set_reporting_key;
+pub mod withdraw_treasury;
+pub mod write_execution_buffer;
+pub mod execute_from_buffer;
+pub mod init_emergency_state;
+pub mod emergency_pause;
+pub mod emergency_unpause;
+pub mod update_emergency_contacts;
+pub mod rotate_agent_signer;
+pub mod quote_execution;
+pub mod approve_spender;
+pub mod revoke_spender;
+pub mod spend_on_behalf;
+pub mod verify_invariants;
+pub mod propose_transaction;
+pub mod approve_transaction;
+pub mod reject_transaction;
+pub mod cancel_proposal;
+pub mod fund_rent_pool;
+pub mod set_rent_sponsorship;
+pub mod add_program_to_whitelist;
+pub mod add_blackout_window;
+pub mod remove_blackout_window;
+pub mod stake_protocol;
+pub mod unstake_protocol;
+pub mod set_stake_quotas;
+pub mod set_agent_extension;
+pub mod reactivate_agent;
+pub mod set_cold_start_policy;
+pub mod freeze_agent;
+pub mod unfreeze_agent;
+pub mod close_agent;
+pub mod create_bounty;
+pub mod claim_bounty;
+pub mod approve_bounty;
+pub mod reject_bounty_claim;
+pub mod cancel_bounty;
+pub mod merkleize_activity;
+pub mod init_agent_stats;
+pub mod copy_whitelist;
+pub mod publish_whitelist_template;
+pub mod close_activity;
+pub mod set_activity_dispute;
+pub mod init_activity_tree;
+pub mod record_activity_compressed;
+pub mod verify_activity;
+pub mod acknowledge_soft_limit;
+pub mod execute_batch;
+pub mod init_wallet_digest;
+pub mod fund_agents_batch;
+pub mod execution_lease;
+pub mod set_destination_tier;
+pub mod set_tier_limit;
+pub mod set_destination_limit;
+pub mod add_to_blacklist;
+pub mod remove_from_blacklist;
+pub mod register_program;
+pub mod whitelist_registered_programs;
+pub mod set_program_policy;
+pub mod create_plan;
+pub mod execute_plan_step;
+pub mod abort_plan;
+pub mod set_scoped_permission;
+pub mod export_agent_config;
+pub mod import_agent_config;
+pub mod grant_permission;
+pub mod expire_permissions;
+pub mod set_price_feed;
+pub mod execute_swap;
+pub mod native_stake;
+pub mod deposit_stake_pool;
+pub mod cast_governance_vote;
+pub mod agent_token_account;
+pub mod crank_snapshot;
+pub mod init_global_stats;
+pub mod update_agent_metadata;
+pub mod resize_agent_account;
+pub mod migrate_account;
+pub mod allowance;
+pub mod funding_stream;
+pub mod set_escrow_funding;
+pub mod approve_funding_request;
+pub mod deny_funding_request;
+pub mod set_inactivity_policy;
+pub mod enforce_inactivity;
+pub mod set_co_signers;
+pub mod set_amount_bands;
+pub mod approve_band_transaction;
+pub mod session_key;
+pub mod execute_signed_intent;
+pub mod set_relayer_registry;
+pub mod set_active_hours;
+pub mod set_wallet_budget;
+pub mod set_tier_config;
+pub mod promote_agent;
+pub mod set_anomaly_rules;
+pub mod set_change_delay;
+pub mod apply_pending_changes;
+pub mod lock_agent_config;
+pub mod set_limit_caps;
+pub mod update_protocol_config;
+pub mod add_merkle_tree;
+pub mod verify_agent_leaf;
+pub mod sync_agent_metadata;
 
 pub use initialize::*;
 pub use create_agent::*;
@@ -17,7 +151,141 @@ pub use execute_transaction::*;
 pub use update_permissions::*;
 pub use revoke_agent::*;
 pub use withdraw_from_agent::*;
+pub use fee_tank::*;
 pub use update_limits::*;
 pub use record_activity::*;
+pub use init_treasury::*;
+pub use approve_credit_line::*;
+pub use draw_credit::*;
+pub use repay_credit::*;
+pub use update_retention_policy::*;
+pub use register_watchtower::*;
+pub use remove_watchtower::*;
+pub use watchtower_heartbeat::*;
+pub use watchtower_freeze_agent::*;
+pub use set_withdrawal_policy::*;
+pub use initiate_withdrawal::*;
+pub use finalize_withdrawal::*;
+pub use cancel_withdrawal::*;
+pub use init_withdrawal_addresses::*;
+pub use add_withdrawal_address::*;
+pub use remove_withdrawal_address::*;
+pub use register_hook::*;
+pub use remove_hook::*;
+pub use pause_protocol::*;
+pub use unpause_protocol::*;
+pub use configure_priority_lanes::*;
+pub use set_token_limit::*;
+pub use init_whitelist::*;
+pub use add_to_whitelist::*;
+pub use remove_from_whitelist::*;
+pub use set_whitelist_enabled::*;
+pub use list_agent_for_sale::*;
+pub use buy_agent::*;
+pub use cancel_listing::*;
+pub use set_reporting_key::*;
+pub use withdraw_treasury::*;
+pub use write_execution_buffer::*;
+pub use execute_from_buffer::*;
+pub use init_emergency_state::*;
+pub use emergency_pause::*;
+pub use emergency_unpause::*;
+pub use update_emergency_contacts::*;
+pub use rotate_agent_signer::*;
+pub use quote_execution::*;
+pub use approve_spender::*;
+pub use revoke_spender::*;
+pub use spend_on_behalf::*;
+pub use verify_invariants::*;
+pub use propose_transaction::*;
+pub use approve_transaction::*;
+pub use reject_transaction::*;
+pub use cancel_proposal::*;
+pub use fund_rent_pool::*;
+pub use set_rent_sponsorship::*;
+pub use add_program_to_whitelist::*;
+pub use add_blackout_window::*;
+pub use remove_blackout_window::*;
+pub use stake_protocol::*;
+pub use unstake_protocol::*;
+pub use set_stake_quotas::*;
+pub use set_agent_extension::*;
+pub use reactivate_agent::*;
+pub use set_cold_start_policy::*;
+pub use freeze_agent::*;
+pub use unfreeze_agent::*;
+pub use close_agent::*;
+pub use create_bounty::*;
+pub use claim_bounty::*;
+pub use approve_bounty::*;
+pub use reject_bounty_claim::*;
+pub use cancel_bounty::*;
+pub use merkleize_activity::*;
+pub use init_agent_stats::*;
+pub use copy_whitelist::*;
+pub use publish_whitelist_template::*;
+pub use close_activity::*;
+pub use set_activity_dispute::*;
+pub use init_activity_tree::*;
+pub use record_activity_compressed::*;
+pub use verify_activity::*;
+pub use acknowledge_soft_limit::*;
+pub use execute_batch::*;
+pub use init_wallet_digest::*;
+pub use fund_agents_batch::*;
+pub use execution_lease::*;
+pub use set_destination_tier::*;
+pub use set_tier_limit::*;
+pub use set_destination_limit::*;
+pub use add_to_blacklist::*;
+pub use remove_from_blacklist::*;
+pub use register_program::*;
+pub use whitelist_registered_programs::*;
+pub use set_program_policy::*;
+pub use create_plan::*;
+pub use execute_plan_step::*;
+pub use abort_plan::*;
+pub use set_scoped_permission::*;
+pub use export_agent_config::*;
+pub use import_agent_config::*;
+pub use grant_permission::*;
+pub use expire_permissions::*;
+pub use set_price_feed::*;
+pub use execute_swap::*;
+pub use native_stake::*;
+pub use deposit_stake_pool::*;
+pub use cast_governance_vote::*;
+pub use agent_token_account::*;
+pub use crank_snapshot::*;
+pub use init_global_stats::*;
+pub use update_agent_metadata::*;
+pub use resize_agent_account::*;
+pub use migrate_account::*;
+pub use allowance::*;
+pub use funding_stream::*;
+pub use set_escrow_funding::*;
+pub use approve_funding_request::*;
+pub use deny_funding_request::*;
+pub use set_inactivity_policy::*;
+pub use enforce_inactivity::*;
+pub use set_co_signers::*;
+pub use set_amount_bands::*;
+pub use approve_band_transaction::*;
+pub use session_key::*;
+pub use execute_signed_intent::*;
+pub use set_relayer_registry::*;
+pub use set_active_hours::*;
+pub use set_wallet_budget::*;
+pub use set_tier_config::*;
+pub use promote_agent::*;
+pub use set_anomaly_rules::*;
+pub use set_change_delay::*;
+pub use apply_pending_changes::*;
+pub use lock_agent_config::*;
+pub use set_limit_caps::*;
+pub use update_protocol_config::*;
+pub use add_merkle_tree::*;
+pub use verify_agent_leaf::*;
+pub use sync_agent_metadata::*;
 
 
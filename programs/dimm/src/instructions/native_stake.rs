@@ -0,0 +1,420 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::stake::{
+    self,
+    instruction as stake_instruction,
+    state::{Authorized, Lockup, StakeStateV2},
+};
+use anchor_lang::solana_program::{system_instruction, sysvar};
+use crate::auth::{Caller, Capability};
+use crate::errors::DimmError;
+use crate::events::{SoftLimitExceeded, StakeDeactivated, StakeDelegated, StakeWithdrawn};
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+#[instruction(stake_id: u64)]
+pub struct StakeSol<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        seeds = [PROTOCOL_SEED, agent_account.main_wallet.as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Main wallet or the agent's own signer key; pays the stake account's rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: New stake account, created here with the agent PDA as staker and withdrawer
+    #[account(
+        mut,
+        seeds = [STAKE_ACCOUNT_SEED, agent_account.key().as_ref(), &stake_id.to_le_bytes()],
+        bump
+    )]
+    pub stake_account: UncheckedAccount<'info>,
+
+    /// Validators the agent may delegate to
+    #[account(
+        seeds = [
+            WHITELIST_SEED,
+            agent_account.key().as_ref(),
+            &WhitelistType::Validators.seed()
+        ],
+        bump = validator_whitelist.bump
+    )]
+    pub validator_whitelist: Box<Account<'info, Whitelist>>,
+
+    /// CHECK: Validator vote account, checked against the whitelist and by the stake program
+    pub vote_account: UncheckedAccount<'info>,
+
+    /// CHECK: Protocol's Pyth SOL/USD price account; required if the agent has USD limits
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Wallet's emergency state; may be uninitialized
+    #[account(
+        seeds = [EMERGENCY_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub emergency_state: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's activity schedule; may be uninitialized
+    #[account(
+        seeds = [SCHEDULE_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub schedule: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's stats; may be uninitialized
+    #[account(
+        mut,
+        seeds = [STATS_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+
+    /// CHECK: Stake config, required by delegation
+    #[allow(deprecated)]
+    #[account(address = stake::config::ID)]
+    pub stake_config: UncheckedAccount<'info>,
+
+    /// CHECK: Stake history sysvar
+    #[account(address = sysvar::stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+
+    /// CHECK: Native stake program
+    #[account(address = stake::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DeactivateStake<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Main wallet or the agent's own signer key
+    pub authority: Signer<'info>,
+
+    /// CHECK: Stake account the agent PDA is staker of; checked by the stake program
+    #[account(mut, owner = stake::program::ID)]
+    pub stake_account: UncheckedAccount<'info>,
+
+    /// CHECK: Native stake program
+    #[account(address = stake::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Main wallet or the agent's own signer key
+    pub authority: Signer<'info>,
+
+    /// CHECK: Stake account the agent PDA is withdrawer of; checked by the stake program
+    #[account(mut, owner = stake::program::ID)]
+    pub stake_account: UncheckedAccount<'info>,
+
+    /// CHECK: Stake history sysvar
+    #[account(address = sysvar::stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+
+    /// CHECK: Native stake program
+    #[account(address = stake::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+/// Move `amount` lamports from the agent into a new stake account and
+/// delegate it to a whitelisted validator. The amount is an ordinary spend
+/// against the agent's limits; the stake account's rent is paid by the caller.
+pub fn stake_handler(ctx: Context<StakeSol>, stake_id: u64, amount: u64) -> Result<()> {
+    Caller::authorize(
+        ctx.accounts.authority.key(),
+        &ctx.accounts.agent_account.main_wallet,
+        Some(&*ctx.accounts.agent_account),
+        None,
+        Capability::Execute,
+    )?;
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
+    EmergencyState::require_not_paused(&ctx.accounts.emergency_state)?;
+    require!(amount > 0, DimmError::InvalidAmount);
+
+    let clock = Clock::get()?;
+    let usd_price = match &ctx.accounts.price_feed {
+        Some(price_feed) => Some(SolUsdPrice::load(
+            price_feed,
+            &ctx.accounts.protocol_config.sol_usd_price_feed,
+            clock.unix_timestamp,
+        )?),
+        None => None,
+    };
+
+    let agent_account = &mut ctx.accounts.agent_account;
+    require!(!agent_account.revoked, DimmError::AgentRevoked);
+    require!(
+        !agent_account.is_frozen(clock.unix_timestamp),
+        DimmError::AgentFrozen
+    );
+    ActivitySchedule::require_active(&ctx.accounts.schedule, clock.unix_timestamp)?;
+    require!(
+        agent_account.is_within_active_hours(clock.unix_timestamp),
+        DimmError::OutsideActiveHours
+    );
+    agent_account.expire_permissions(clock.unix_timestamp);
+    require!(
+        agent_account.has_permission(&AgentPermission::Staking),
+        DimmError::InsufficientPermissions
+    );
+    ctx.accounts
+        .validator_whitelist
+        .require_validator(&ctx.accounts.vote_account.key())?;
+
+    let soft_limit_crossed = agent_account.charge_spend(
+        &ctx.accounts.protocol_config,
+        usd_price.as_ref(),
+        amount,
+        clock.unix_timestamp,
+    )?;
+    if soft_limit_crossed {
+        emit!(SoftLimitExceeded {
+            agent: agent_account.key(),
+            soft_daily_limit: agent_account.soft_daily_limit,
+            spent_today: agent_account.spent_today,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    let required_balance = amount
+        .checked_add(MIN_AGENT_BALANCE)
+        .and_then(|v| v.checked_add(agent_account.fee_tank))
+        .ok_or(DimmError::NumericalOverflow)?;
+    require!(
+        agent_account.to_account_info().lamports() >= required_balance,
+        DimmError::InsufficientAgentBalance
+    );
+
+    // Create the stake account at its PDA, rent paid by the caller
+    let agent_key = agent_account.key();
+    let stake_key = ctx.accounts.stake_account.key();
+    let stake_seeds = &[
+        STAKE_ACCOUNT_SEED,
+        agent_key.as_ref(),
+        &stake_id.to_le_bytes(),
+        &[ctx.bumps.stake_account],
+    ];
+    let space = StakeStateV2::size_of();
+    invoke_signed(
+        &system_instruction::create_account(
+            &ctx.accounts.authority.key(),
+            &stake_key,
+            ctx.accounts.rent.minimum_balance(space),
+            space as u64,
+            &stake::program::ID,
+        ),
+        &[
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.stake_account.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        &[&stake_seeds[..]],
+    )?;
+
+    // The agent account is owned by this program, so lamports move directly
+    **agent_account.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.stake_account.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    invoke(
+        &stake_instruction::initialize(
+            &stake_key,
+            &Authorized {
+                staker: agent_key,
+                withdrawer: agent_key,
+            },
+            &Lockup::default(),
+        ),
+        &[
+            ctx.accounts.stake_account.to_account_info(),
+            ctx.accounts.rent.to_account_info(),
+            ctx.accounts.stake_program.to_account_info(),
+        ],
+    )?;
+
+    let agent_seeds = &[
+        AGENT_SEED,
+        agent_account.main_wallet.as_ref(),
+        &agent_account.agent_id.to_le_bytes(),
+        &[agent_account.bump],
+    ];
+    invoke_signed(
+        &stake_instruction::delegate_stake(
+            &stake_key,
+            &agent_key,
+            &ctx.accounts.vote_account.key(),
+        ),
+        &[
+            ctx.accounts.stake_account.to_account_info(),
+            ctx.accounts.vote_account.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.stake_history.to_account_info(),
+            ctx.accounts.stake_config.to_account_info(),
+            agent_account.to_account_info(),
+            ctx.accounts.stake_program.to_account_info(),
+        ],
+        &[&agent_seeds[..]],
+    )?;
+
+    agent_account.total_transactions = agent_account
+        .total_transactions
+        .checked_add(1)
+        .ok_or(DimmError::NumericalOverflow)?;
+    agent_account.last_used_at = clock.unix_timestamp;
+
+    if let Some(mut stats) = AgentStats::load(&ctx.accounts.stats)? {
+        stats.record_activity_time(clock.unix_timestamp);
+        stats.record_transaction(amount, true, &ActivityType::Staking)?;
+        stats.store(&ctx.accounts.stats)?;
+    }
+
+    emit!(StakeDelegated {
+        agent: agent_key,
+        stake_account: stake_key,
+        vote_account: ctx.accounts.vote_account.key(),
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Stake delegated");
+    msg!("Agent: {}", agent_key);
+    msg!("Stake account: {}", stake_key);
+    msg!("Amount: {} lamports", amount);
+
+    Ok(())
+}
+
+/// Deactivate one of the agent's stake accounts so it can be withdrawn
+/// once the cooldown epoch passes
+pub fn deactivate_handler(ctx: Context<DeactivateStake>) -> Result<()> {
+    Caller::authorize(
+        ctx.accounts.authority.key(),
+        &ctx.accounts.agent_account.main_wallet,
+        Some(&*ctx.accounts.agent_account),
+        None,
+        Capability::Execute,
+    )?;
+
+    let agent_account = &ctx.accounts.agent_account;
+    let agent_key = agent_account.key();
+    let agent_seeds = &[
+        AGENT_SEED,
+        agent_account.main_wallet.as_ref(),
+        &agent_account.agent_id.to_le_bytes(),
+        &[agent_account.bump],
+    ];
+    invoke_signed(
+        &stake_instruction::deactivate_stake(&ctx.accounts.stake_account.key(), &agent_key),
+        &[
+            ctx.accounts.stake_account.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            agent_account.to_account_info(),
+            ctx.accounts.stake_program.to_account_info(),
+        ],
+        &[&agent_seeds[..]],
+    )?;
+
+    emit!(StakeDeactivated {
+        agent: agent_key,
+        stake_account: ctx.accounts.stake_account.key(),
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+
+    msg!("Stake deactivated");
+    msg!("Agent: {}", agent_key);
+    msg!("Stake account: {}", ctx.accounts.stake_account.key());
+
+    Ok(())
+}
+
+/// Withdraw `lamports` from an inactive stake account back into the agent.
+/// Withdrawing the full balance closes the stake account.
+pub fn withdraw_handler(ctx: Context<WithdrawStake>, lamports: u64) -> Result<()> {
+    Caller::authorize(
+        ctx.accounts.authority.key(),
+        &ctx.accounts.agent_account.main_wallet,
+        Some(&*ctx.accounts.agent_account),
+        None,
+        Capability::Execute,
+    )?;
+
+    let agent_account = &ctx.accounts.agent_account;
+    let agent_key = agent_account.key();
+    let agent_seeds = &[
+        AGENT_SEED,
+        agent_account.main_wallet.as_ref(),
+        &agent_account.agent_id.to_le_bytes(),
+        &[agent_account.bump],
+    ];
+    invoke_signed(
+        &stake_instruction::withdraw(
+            &ctx.accounts.stake_account.key(),
+            &agent_key,
+            &agent_key,
+            lamports,
+            None,
+        ),
+        &[
+            ctx.accounts.stake_account.to_account_info(),
+            agent_account.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.stake_history.to_account_info(),
+            ctx.accounts.stake_program.to_account_info(),
+        ],
+        &[&agent_seeds[..]],
+    )?;
+
+    emit!(StakeWithdrawn {
+        agent: agent_key,
+        stake_account: ctx.accounts.stake_account.key(),
+        amount: lamports,
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+
+    msg!("Stake withdrawn");
+    msg!("Agent: {}", agent_key);
+    msg!("Amount: {} lamports", lamports);
+
+    Ok(())
+}
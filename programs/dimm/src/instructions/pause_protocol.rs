@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct PauseProtocol<'info> {
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED, authority.key().as_ref()],
+        bump = protocol_config.bump,
+        has_one = authority
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<PauseProtocol>) -> Result<()> {
+    let protocol_config = &mut ctx.accounts.protocol_config;
+
+    protocol_config.paused = true;
+
+    msg!("DIMM Protocol paused");
+    msg!("Authority: {}", protocol_config.authority);
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::events::AgentPromoted;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct PromoteAgent<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        seeds = [PROTOCOL_SEED, agent_account.main_wallet.as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Agent's stats; may be uninitialized
+    #[account(
+        seeds = [STATS_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+
+    /// Main wallet, or anyone once the agent has earned the next tier
+    pub authority: Signer<'info>,
+}
+
+/// Raise the agent one trust tier. The main wallet may promote at any time;
+/// anyone else only once the agent's stats show the next tier's successful
+/// transactions and no failures.
+pub fn handler(ctx: Context<PromoteAgent>) -> Result<()> {
+    let agent_account = &mut ctx.accounts.agent_account;
+    require!(!agent_account.revoked, DimmError::AgentRevoked);
+
+    let next_level = agent_account.trust_level + 1;
+    let next_tier = ctx
+        .accounts
+        .protocol_config
+        .trust_tiers
+        .get(next_level as usize)
+        .ok_or(DimmError::PromotionNotEarned)?;
+    let automatic = ctx.accounts.authority.key() != agent_account.main_wallet;
+    if automatic {
+        let stats = AgentStats::load(&ctx.accounts.stats)?.ok_or(DimmError::PromotionNotEarned)?;
+        require!(
+            next_tier.promote_after_transactions > 0
+                && stats.successful_transactions >= next_tier.promote_after_transactions
+                && stats.failed_transactions == 0,
+            DimmError::PromotionNotEarned
+        );
+    }
+
+    agent_account.trust_level = next_level;
+    let clock = Clock::get()?;
+
+    emit!(AgentPromoted {
+        agent: agent_account.key(),
+        trust_level: next_level,
+        automatic,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Agent promoted");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Trust level: {}", next_level);
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use crate::auth::{Caller, Capability};
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ProposeTransaction<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = PendingTransaction::LEN,
+        seeds = [
+            PENDING_TRANSACTION_SEED,
+            agent_account.key().as_ref(),
+            &agent_account.proposal_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub pending_transaction: Account<'info, PendingTransaction>,
+
+    /// Main wallet or the agent's own signer key
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ProposeTransaction>, params: ExecuteTransactionParams) -> Result<()> {
+    Caller::authorize(
+        ctx.accounts.proposer.key(),
+        &ctx.accounts.agent_account.main_wallet,
+        Some(&*ctx.accounts.agent_account),
+        None,
+        Capability::Execute,
+    )?;
+    require!(!ctx.accounts.agent_account.revoked, DimmError::AgentRevoked);
+    let clock = Clock::get()?;
+    let agent_key = ctx.accounts.agent_account.key();
+    let agent_account = &mut ctx.accounts.agent_account;
+    let pending_transaction = &mut ctx.accounts.pending_transaction;
+
+    pending_transaction.agent = agent_key;
+    pending_transaction.main_wallet = agent_account.main_wallet;
+    pending_transaction.proposer = ctx.accounts.proposer.key();
+    pending_transaction.proposal_id = agent_account.proposal_count;
+    pending_transaction.activity_type = params.activity_type;
+    pending_transaction.amount = params.amount;
+    pending_transaction.destination = params.destination;
+    pending_transaction.mint = params.mint;
+    pending_transaction.status = ProposalStatus::Pending;
+    pending_transaction.proposed_at = clock.unix_timestamp;
+    pending_transaction.approved_at = 0;
+    pending_transaction.co_signer_approvals = 0;
+    pending_transaction.co_signer_generation = 0;
+    pending_transaction.bump = ctx.bumps.pending_transaction;
+
+    agent_account.proposal_count = agent_account
+        .proposal_count
+        .checked_add(1)
+        .ok_or(DimmError::NumericalOverflow)?;
+
+    msg!("Transaction proposed");
+    msg!("Agent: {}", agent_key);
+    msg!("Proposal ID: {}", pending_transaction.proposal_id);
+    msg!("Amount: {}", pending_transaction.amount);
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+#[instruction(template_id: u64)]
+pub struct PublishWhitelistTemplate<'info> {
+    #[account(
+        seeds = [PROTOCOL_SEED, authority.key().as_ref()],
+        bump = protocol_config.bump,
+        has_one = authority
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Templates are immutable once published, so copies can be trusted
+    #[account(
+        init,
+        payer = authority,
+        space = Whitelist::LEN,
+        seeds = [
+            WHITELIST_SEED,
+            protocol_config.key().as_ref(),
+            &template_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub template: Account<'info, Whitelist>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<PublishWhitelistTemplate>,
+    template_id: u64,
+    whitelist_type: WhitelistType,
+    addresses: Vec<Pubkey>,
+) -> Result<()> {
+    let template = &mut ctx.accounts.template;
+    let clock = Clock::get()?;
+
+    template.owner = ctx.accounts.protocol_config.key();
+    template.set_addresses(addresses)?;
+    template.enabled = true;
+    template.whitelist_type = whitelist_type;
+    template.last_updated = clock.unix_timestamp;
+    template.bump = ctx.bumps.template;
+
+    msg!("Whitelist template published");
+    msg!("Template ID: {}", template_id);
+    msg!("Type: {:?}", template.whitelist_type);
+    msg!("Addresses: {}", template.addresses.len());
+
+    Ok(())
+}
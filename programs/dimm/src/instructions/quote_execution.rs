@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct QuoteExecution<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// CHECK: Wallet's treasury, collecting protocol fees; may be uninitialized
+    #[account(
+        seeds = [TREASURY_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Agent's critical destinations and categories, if any are tagged
+    #[account(
+        seeds = [PRIORITY_LANES_SEED, agent_account.key().as_ref()],
+        bump = priority_lanes.bump
+    )]
+    pub priority_lanes: Option<Account<'info, PriorityLanes>>,
+}
+
+/// Read-only: mirrors the fee and limit checks of `execute_transaction`
+/// without moving funds. The quote is returned as return data.
+pub fn handler(ctx: Context<QuoteExecution>, params: ExecuteTransactionParams) -> Result<ExecutionQuote> {
+    let clock = Clock::get()?;
+
+    // Quote against the budget as it will be after any pending daily reset
+    let mut agent_account = (*ctx.accounts.agent_account).clone();
+    agent_account.check_and_reset_daily_limit(clock.unix_timestamp)?;
+
+    let fee = match Treasury::load(&ctx.accounts.treasury)? {
+        Some(treasury) => treasury.calculate_fee(params.lamports())?,
+        None => 0,
+    };
+
+    // Token transfers only need lamports for the fee; their limits live in `TokenLimit`
+    let lamports = params.lamports();
+    let required_balance = lamports
+        .checked_add(fee)
+        .and_then(|v| v.checked_add(MIN_AGENT_BALANCE))
+        .and_then(|v| v.checked_add(agent_account.fee_tank))
+        .ok_or(DimmError::NumericalOverflow)?;
+
+    let is_critical = ctx
+        .accounts
+        .priority_lanes
+        .as_ref()
+        .map(|lanes| lanes.is_critical(&params.activity_type, params.destination.as_ref()))
+        .unwrap_or(false);
+
+    let (within_limits, allowance_after) = if params.activity_type == ActivityType::Maintenance {
+        let remaining = agent_account
+            .maintenance_allowance
+            .saturating_sub(agent_account.maintenance_spent_today);
+        (lamports <= remaining, remaining.saturating_sub(lamports))
+    } else if params.activity_type == ActivityType::Donation {
+        let remaining = agent_account
+            .donation_allowance
+            .saturating_sub(agent_account.donation_spent_today);
+        (lamports <= remaining, remaining.saturating_sub(lamports))
+    } else {
+        let daily_budget = if is_critical {
+            agent_account.daily_limit
+        } else {
+            agent_account.daily_limit.saturating_sub(agent_account.critical_reserve)
+        };
+        let remaining = daily_budget.saturating_sub(agent_account.spent_today);
+        (
+            agent_account.can_spend_in_lane(lamports, is_critical)?
+                && agent_account.within_trial_limits(lamports, clock.unix_timestamp)?
+                && agent_account.within_hourly_limit(lamports)?,
+            remaining.saturating_sub(lamports),
+        )
+    };
+
+    let quote = ExecutionQuote {
+        fee,
+        reserve_requirement: MIN_AGENT_BALANCE.saturating_add(agent_account.fee_tank),
+        required_balance,
+        agent_balance: ctx.accounts.agent_account.to_account_info().lamports(),
+        allowance_after,
+        within_limits,
+    };
+
+    msg!("Execution quoted");
+    msg!("Agent: {}", ctx.accounts.agent_account.key());
+    msg!("Fee: {} lamports", quote.fee);
+    msg!("Required balance: {} lamports", quote.required_balance);
+    msg!("Allowance after: {} lamports", quote.allowance_after);
+
+    Ok(quote)
+}
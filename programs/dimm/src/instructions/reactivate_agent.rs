@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::events::AgentReactivated;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ReactivateAgent<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet,
+        constraint = agent_account.revoked @ DimmError::AgentNotRevoked
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED, main_wallet.key().as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Wallet's protocol stake; may be uninitialized
+    #[account(
+        seeds = [STAKE_SEED, main_wallet.key().as_ref()],
+        bump
+    )]
+    pub wallet_stake: UncheckedAccount<'info>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ReactivateAgent>, reset_daily_counters: bool) -> Result<()> {
+    let clock = Clock::get()?;
+    let agent = ctx.accounts.agent_account.key();
+    let agent_account = &mut ctx.accounts.agent_account;
+    // Revoking a locked agent is final
+    require!(!agent_account.config_locked, DimmError::ConfigLocked);
+    // So is burning its cNFT
+    require!(!agent_account.cnft_burned, DimmError::AgentCnftBurned);
+
+    // The agent counts against the stake quota again, as a new agent would
+    let protocol_config = &mut ctx.accounts.protocol_config;
+    let agent_count = protocol_config
+        .active_agents
+        .checked_add(1)
+        .ok_or(DimmError::NumericalOverflow)?;
+    let aggregate_daily_limit = protocol_config
+        .aggregate_daily_limit
+        .checked_add(agent_account.daily_limit)
+        .ok_or(DimmError::NumericalOverflow)?;
+    let effective_stake = WalletStake::load_effective(&ctx.accounts.wallet_stake, clock.epoch)?;
+    protocol_config.check_stake_quotas(effective_stake, agent_count, aggregate_daily_limit)?;
+    protocol_config.active_agents = agent_count;
+    protocol_config.aggregate_daily_limit = aggregate_daily_limit;
+
+    agent_account.revoked = false;
+    agent_account.inactivity_reset_at = clock.unix_timestamp;
+
+    // Start the agent on a fresh daily window instead of whatever it had
+    // spent before the incident
+    if reset_daily_counters {
+        agent_account.spent_today = 0;
+        agent_account.maintenance_spent_today = 0;
+        agent_account.donation_spent_today = 0;
+        agent_account.last_daily_reset = clock.unix_timestamp;
+    }
+
+    emit!(AgentReactivated {
+        agent,
+        main_wallet: agent_account.main_wallet,
+        revoked_at: agent_account.revoked_at,
+        revoked_for: clock.unix_timestamp.saturating_sub(agent_account.revoked_at),
+        counters_reset: reset_daily_counters,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Agent reactivated");
+    msg!("Agent: {}", agent);
+    msg!("Agent ID: {}", agent_account.agent_id);
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::auth::{Caller, Capability, Role};
 use crate::errors::DimmError;
 use crate::state::*;
 use crate::constants::*;
@@ -7,6 +8,7 @@ use crate::constants::*;
 #[instruction(params: ActivityParams)]
 pub struct RecordActivity<'info> {
     #[account(
+        mut,
         seeds = [
             AGENT_SEED,
             agent_account.main_wallet.as_ref(),
@@ -16,35 +18,87 @@ pub struct RecordActivity<'info> {
     )]
     pub agent_account: Account<'info, AgentAccount>,
 
+    /// Reuses the oldest slot once the agent's retention limit is reached
     #[account(
-        init,
+        init_if_needed,
         payer = payer,
         space = AgentActivity::LEN,
         seeds = [
-            ACTIVITY_SEED,
+            ACTIVITY_SLOT_SEED,
             agent_account.key().as_ref(),
-            &agent_account.total_transactions.to_le_bytes()
+            &agent_account.next_activity_slot().to_le_bytes()
         ],
         bump
     )]
     pub activity: Account<'info, AgentActivity>,
 
+    /// Main wallet or the agent's own signer key
     #[account(mut)]
     pub payer: Signer<'info>,
 
+    /// Wallet's rent pool; pays new records of sponsored agents
+    #[account(
+        mut,
+        seeds = [RENT_POOL_SEED, agent_account.main_wallet.as_ref()],
+        bump = rent_pool.bump
+    )]
+    pub rent_pool: Option<Account<'info, RentPool>>,
+
+    /// CHECK: Agent's snapshot for the current day; counts reported failures
+    #[account(mut)]
+    pub snapshot: Option<UncheckedAccount<'info>>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<RecordActivity>, params: ActivityParams) -> Result<()> {
-    let activity = &mut ctx.accounts.activity;
+/// Activity records past the agent's `retention_max_age` may be passed as
+/// remaining accounts; they are closed and their rent returned to the agent,
+/// or to the rent pool if it paid for them. Disputed records are skipped.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RecordActivity<'info>>,
+    params: ActivityParams,
+) -> Result<()> {
     let clock = Clock::get()?;
 
-    require!(
-        params.reason.len() <= MAX_REASON_LENGTH,
-        DimmError::ReasonTooLong
-    );
+    // Slots are reused, so only the agent's own keys may write them
+    let caller = Caller::authorize(
+        ctx.accounts.payer.key(),
+        &ctx.accounts.agent_account.main_wallet,
+        Some(&*ctx.accounts.agent_account),
+        None,
+        Capability::Execute,
+    )?;
+
+    params.validate(ctx.accounts.agent_account.reporting_key.is_some())?;
+
+    let agent_key = ctx.accounts.agent_account.key();
+    let is_new_record = ctx.accounts.activity.agent == Pubkey::default();
+
+    // A disputed record is kept as evidence, not overwritten
+    require!(!ctx.accounts.activity.disputed, DimmError::ActivityDisputed);
+
+    // Refund the payer from the rent pool for new records of sponsored agents.
+    // The refund goes to the payer, so it is kept to the agent's own keys.
+    let mut sponsored = false;
+    let sponsorable_payer = matches!(caller.role, Role::MainWallet | Role::AgentSigner);
+    if is_new_record && sponsorable_payer && ctx.accounts.agent_account.rent_sponsored {
+        if let Some(rent_pool) = ctx.accounts.rent_pool.as_mut() {
+            let rent = Rent::get()?.minimum_balance(AgentActivity::LEN);
+            let pool_info = rent_pool.to_account_info();
 
-    activity.agent = ctx.accounts.agent_account.key();
+            if RentPool::available(&pool_info)? >= rent {
+                **pool_info.try_borrow_mut_lamports()? -= rent;
+                **ctx.accounts.payer.to_account_info().try_borrow_mut_lamports()? += rent;
+                rent_pool.record_sponsorship(rent)?;
+                sponsored = true;
+            }
+        }
+    }
+
+    let failure_category = params.resolved_failure_category();
+    let activity = &mut ctx.accounts.activity;
+
+    activity.agent = agent_key;
     activity.activity_type = params.activity_type;
     activity.amount = params.amount;
     activity.destination = params.destination;
@@ -52,15 +106,68 @@ pub fn handler(ctx: Context<RecordActivity>, params: ActivityParams) -> Result<(
     activity.timestamp = clock.unix_timestamp;
     activity.signature = params.signature;
     activity.success = params.success;
+    activity.error_code = params.error_code;
+    activity.failure_category = failure_category;
+    activity.encrypted_payload = params.encrypted_payload;
+    activity.sequence = ctx.accounts.agent_account.activity_sequence;
+    activity.prev_hash = ctx.accounts.agent_account.activity_hash;
+    if is_new_record {
+        activity.rent_sponsored = sponsored;
+        activity.payer = ctx.accounts.payer.key();
+    }
     activity.bump = ctx.bumps.activity;
 
+    let agent_account = &mut ctx.accounts.agent_account;
+    agent_account.activity_hash = activity.chain_hash()?;
+    agent_account.activity_sequence = agent_account
+        .activity_sequence
+        .checked_add(1)
+        .ok_or(DimmError::NumericalOverflow)?;
+
+    // Reported failures count towards the agent's anomaly rules
+    let agent_account = &mut ctx.accounts.agent_account;
+    if !ctx.accounts.activity.success {
+        agent_account.record_failure(clock.unix_timestamp);
+    }
+
+    if let (Some(snapshot_info), false) = (&ctx.accounts.snapshot, ctx.accounts.activity.success) {
+        let mut snapshot = DailySnapshot::load_current(snapshot_info, &agent_key, clock.unix_timestamp)?;
+        snapshot.record_failure()?;
+        snapshot.store(snapshot_info)?;
+    }
+
+    // Prune expired records, refunding their rent to the agent
+    let mut pruned: u32 = 0;
+    for info in ctx.remaining_accounts.iter() {
+        let expired = Account::<AgentActivity>::try_from(info)?;
+        require_keys_eq!(expired.agent, agent_key, DimmError::Unauthorized);
+
+        if !expired.disputed
+            && agent_account.is_activity_expired(expired.timestamp, clock.unix_timestamp)
+        {
+            if expired.rent_sponsored {
+                let rent_pool = ctx
+                    .accounts
+                    .rent_pool
+                    .as_mut()
+                    .ok_or(DimmError::RentPoolRequired)?;
+                rent_pool.record_reclaim(info.lamports())?;
+                expired.close(rent_pool.to_account_info())?;
+            } else {
+                expired.close(agent_account.to_account_info())?;
+            }
+            pruned += 1;
+        }
+    }
+
     msg!("Activity recorded");
-    msg!("Agent: {}", activity.agent);
-    msg!("Type: {:?}", activity.activity_type);
-    msg!("Amount: {} lamports", activity.amount);
-    msg!("Success: {}", activity.success);
+    msg!("Agent: {}", agent_key);
+    msg!("Type: {:?}", ctx.accounts.activity.activity_type);
+    msg!("Amount: {} lamports", ctx.accounts.activity.amount);
+    msg!("Success: {}", ctx.accounts.activity.success);
+    if pruned > 0 {
+        msg!("Pruned {} expired activity records", pruned);
+    }
 
     Ok(())
 }
-
-
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use spl_account_compression::cpi::{self as compression, accounts::Modify};
+use spl_account_compression::{program::SplAccountCompression, wrap_application_data_v1, Noop};
+use crate::auth::{Caller, Capability};
+use crate::errors::DimmError;
+use crate::events::CompressedActivityRecorded;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct RecordActivityCompressed<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        seeds = [ACTIVITY_TREE_SEED, agent_account.main_wallet.as_ref()],
+        bump = activity_tree.bump,
+        has_one = merkle_tree
+    )]
+    pub activity_tree: Account<'info, ActivityTree>,
+
+    /// CHECK: Checked against the activity tree; owned by the compression program
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// Main wallet or the agent's own signer key
+    pub payer: Signer<'info>,
+
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub log_wrapper: Program<'info, Noop>,
+}
+
+/// Same record as `record_activity`, but appended as a leaf to the wallet's
+/// activity tree instead of stored in its own PDA. The full record is
+/// logged through the noop program for indexers; the leaf is its chain hash,
+/// so compressed and account-backed records share the agent's one chain.
+pub fn handler(ctx: Context<RecordActivityCompressed>, params: ActivityParams) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Only the agent's own keys may extend its activity chain
+    Caller::authorize(
+        ctx.accounts.payer.key(),
+        &ctx.accounts.agent_account.main_wallet,
+        Some(&*ctx.accounts.agent_account),
+        None,
+        Capability::Execute,
+    )?;
+
+    params.validate(ctx.accounts.agent_account.reporting_key.is_some())?;
+
+    let agent_account = &mut ctx.accounts.agent_account;
+    let agent_key = agent_account.key();
+
+    let failure_category = params.resolved_failure_category();
+    let record = AgentActivity {
+        agent: agent_key,
+        activity_type: params.activity_type,
+        amount: params.amount,
+        destination: params.destination,
+        reason: params.reason,
+        timestamp: clock.unix_timestamp,
+        signature: params.signature,
+        success: params.success,
+        encrypted_payload: params.encrypted_payload,
+        sequence: agent_account.activity_sequence,
+        prev_hash: agent_account.activity_hash,
+        rent_sponsored: false,
+        payer: ctx.accounts.payer.key(),
+        disputed: false,
+        error_code: params.error_code,
+        failure_category,
+        bump: 0,
+    };
+    let leaf = record.chain_hash()?;
+
+    wrap_application_data_v1(record.try_to_vec()?, &ctx.accounts.log_wrapper)?;
+
+    let activity_tree = &mut ctx.accounts.activity_tree;
+    let leaf_index = activity_tree.next_leaf()?;
+    let main_wallet = activity_tree.main_wallet;
+    let tree_seeds = &[ACTIVITY_TREE_SEED, main_wallet.as_ref(), &[activity_tree.bump]];
+    let signer_seeds = &[&tree_seeds[..]];
+
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.compression_program.to_account_info(),
+        Modify {
+            merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+            authority: activity_tree.to_account_info(),
+            noop: ctx.accounts.log_wrapper.to_account_info(),
+        },
+        signer_seeds,
+    );
+    compression::append(cpi_context, leaf)?;
+
+    agent_account.activity_hash = leaf;
+    agent_account.activity_sequence = agent_account
+        .activity_sequence
+        .checked_add(1)
+        .ok_or(DimmError::NumericalOverflow)?;
+
+    emit!(CompressedActivityRecorded {
+        agent: agent_key,
+        merkle_tree: activity_tree.merkle_tree,
+        leaf_index,
+        sequence: record.sequence,
+        leaf,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Compressed activity recorded");
+    msg!("Agent: {}", agent_key);
+    msg!("Type: {:?}", record.activity_type);
+    msg!("Leaf index: {}", leaf_index);
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct RegisterHook<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = main_wallet,
+        space = HookRegistry::LEN,
+        seeds = [HOOK_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub hook_registry: Account<'info, HookRegistry>,
+
+    /// CHECK: Only required to be an executable program
+    #[account(executable)]
+    pub hook_program: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterHook>, kind: HookKind) -> Result<()> {
+    let hook_registry = &mut ctx.accounts.hook_registry;
+    let program_id = ctx.accounts.hook_program.key();
+    let clock = Clock::get()?;
+
+    require!(program_id != crate::ID, DimmError::InvalidHook);
+    require!(
+        !hook_registry.hooks.iter().any(|hook| hook.program_id == program_id),
+        DimmError::InvalidHook
+    );
+    require!(
+        hook_registry.hooks.len() < HookRegistry::MAX_HOOKS,
+        DimmError::MaxHooksReached
+    );
+
+    hook_registry.agent = ctx.accounts.agent_account.key();
+    hook_registry.hooks.push(PolicyHook { program_id, kind });
+    hook_registry.last_updated = clock.unix_timestamp;
+    hook_registry.bump = ctx.bumps.hook_registry;
+
+    msg!("Policy hook registered");
+    msg!("Agent: {}", hook_registry.agent);
+    msg!("Hook program: {}", program_id);
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct RegisterProgram<'info> {
+    #[account(
+        seeds = [PROTOCOL_SEED, authority.key().as_ref()],
+        bump = protocol_config.bump,
+        has_one = authority
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Created on first use
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ProgramRegistry::LEN,
+        seeds = [PROGRAM_REGISTRY_SEED, protocol_config.key().as_ref()],
+        bump
+    )]
+    pub program_registry: Account<'info, ProgramRegistry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnregisterProgram<'info> {
+    #[account(
+        seeds = [PROTOCOL_SEED, authority.key().as_ref()],
+        bump = protocol_config.bump,
+        has_one = authority
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [PROGRAM_REGISTRY_SEED, protocol_config.key().as_ref()],
+        bump = program_registry.bump
+    )]
+    pub program_registry: Account<'info, ProgramRegistry>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Add a program to the registry, or relabel an existing id
+pub fn register_handler(ctx: Context<RegisterProgram>, entry: RegisteredProgram) -> Result<()> {
+    require_keys_neq!(entry.program_id, crate::ID, DimmError::InvalidProgram);
+
+    let protocol_config_key = ctx.accounts.protocol_config.key();
+    let program_registry = &mut ctx.accounts.program_registry;
+
+    if program_registry.protocol_config == Pubkey::default() {
+        program_registry.protocol_config = protocol_config_key;
+        program_registry.entries = Vec::new();
+        program_registry.bump = ctx.bumps.program_registry;
+    }
+
+    program_registry.upsert(entry)?;
+    program_registry.last_updated = Clock::get()?.unix_timestamp;
+
+    msg!("Program registered");
+    msg!("ID: {}", entry.id);
+    msg!("Program: {}", entry.program_id);
+    msg!("Category: {:?}", entry.category);
+    msg!("Risk: {:?}", entry.risk);
+
+    Ok(())
+}
+
+/// Remove a program from the registry. Whitelists that copied it keep it.
+pub fn unregister_handler(ctx: Context<UnregisterProgram>, id: u16) -> Result<()> {
+    let program_registry = &mut ctx.accounts.program_registry;
+
+    program_registry.remove(id);
+    program_registry.last_updated = Clock::get()?.unix_timestamp;
+
+    msg!("Program unregistered");
+    msg!("ID: {}", id);
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct RegisterWatchtower<'info> {
+    /// Re-registering replaces the monitoring key
+    #[account(
+        init_if_needed,
+        payer = main_wallet,
+        space = Watchtower::LEN,
+        seeds = [WATCHTOWER_SEED, main_wallet.key().as_ref()],
+        bump
+    )]
+    pub watchtower: Account<'info, Watchtower>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterWatchtower>, params: RegisterWatchtowerParams) -> Result<()> {
+    require!(params.heartbeat_interval >= 0, DimmError::InvalidActivityWindow);
+
+    let watchtower = &mut ctx.accounts.watchtower;
+    let clock = Clock::get()?;
+
+    watchtower.main_wallet = ctx.accounts.main_wallet.key();
+    watchtower.watchtower = params.watchtower;
+    watchtower.heartbeat_interval = params.heartbeat_interval;
+    watchtower.last_heartbeat = clock.unix_timestamp;
+    watchtower.registered_at = clock.unix_timestamp;
+    watchtower.freeze_count = 0;
+    watchtower.bump = ctx.bumps.watchtower;
+
+    msg!("Watchtower registered");
+    msg!("Main Wallet: {}", watchtower.main_wallet);
+    msg!("Watchtower: {}", watchtower.watchtower);
+    msg!("Heartbeat interval: {} seconds", watchtower.heartbeat_interval);
+
+    Ok(())
+}
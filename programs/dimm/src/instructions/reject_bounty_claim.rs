@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct RejectBountyClaim<'info> {
+    #[account(
+        mut,
+        seeds = [BOUNTY_SEED, authority.key().as_ref(), &bounty.bounty_id.to_le_bytes()],
+        bump = bounty.bump,
+        has_one = authority
+    )]
+    pub bounty: Account<'info, Bounty>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RejectBountyClaim>) -> Result<()> {
+    let bounty = &mut ctx.accounts.bounty;
+
+    require!(bounty.status == BountyStatus::Claimed, DimmError::BountyNotClaimed);
+
+    msg!("Bounty claim rejected");
+    msg!("Bounty ID: {}", bounty.bounty_id);
+    msg!("Claimant: {:?}", bounty.claimant);
+
+    // Reopen for other researchers
+    bounty.claimant = None;
+    bounty.report_hash = [0; 32];
+    bounty.status = BountyStatus::Open;
+    bounty.claimed_at = 0;
+
+    Ok(())
+}
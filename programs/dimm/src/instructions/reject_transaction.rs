@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct RejectTransaction<'info> {
+    #[account(
+        mut,
+        seeds = [
+            PENDING_TRANSACTION_SEED,
+            pending_transaction.agent.as_ref(),
+            &pending_transaction.proposal_id.to_le_bytes()
+        ],
+        bump = pending_transaction.bump,
+        has_one = main_wallet,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub pending_transaction: Account<'info, PendingTransaction>,
+
+    pub main_wallet: Signer<'info>,
+
+    /// CHECK: Receives the proposal rent; checked against the proposal
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<RejectTransaction>) -> Result<()> {
+    let pending_transaction = &ctx.accounts.pending_transaction;
+
+    require!(
+        pending_transaction.status == ProposalStatus::Pending,
+        DimmError::ProposalNotPending
+    );
+
+    msg!("Transaction rejected");
+    msg!("Agent: {}", pending_transaction.agent);
+    msg!("Proposal ID: {}", pending_transaction.proposal_id);
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct RemoveBlackoutWindow<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        seeds = [SCHEDULE_SEED, agent_account.key().as_ref()],
+        bump = schedule.bump
+    )]
+    pub schedule: Account<'info, ActivitySchedule>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RemoveBlackoutWindow>, window: BlackoutWindow) -> Result<()> {
+    let schedule = &mut ctx.accounts.schedule;
+    let clock = Clock::get()?;
+
+    schedule.blackouts.retain(|existing| existing != &window);
+    schedule.prune_expired(clock.unix_timestamp);
+    schedule.last_updated = clock.unix_timestamp;
+
+    msg!("Blackout window removed");
+    msg!("Agent: {}", schedule.agent);
+    msg!("Remaining windows: {}", schedule.blackouts.len());
+
+    Ok(())
+}
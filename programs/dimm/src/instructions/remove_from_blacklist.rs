@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct RemoveFromBlacklist<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        seeds = [BLACKLIST_SEED, agent_account.key().as_ref()],
+        bump = blacklist.bump
+    )]
+    pub blacklist: Account<'info, Blacklist>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RemoveFromBlacklist>, address: Pubkey) -> Result<()> {
+    let blacklist = &mut ctx.accounts.blacklist;
+    let clock = Clock::get()?;
+
+    blacklist.remove_address(&address);
+    blacklist.last_updated = clock.unix_timestamp;
+
+    msg!("Address removed from blacklist");
+    msg!("Agent: {}", blacklist.agent);
+    msg!("Address: {}", address);
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct RemoveHook<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        seeds = [HOOK_SEED, agent_account.key().as_ref()],
+        bump = hook_registry.bump
+    )]
+    pub hook_registry: Account<'info, HookRegistry>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RemoveHook>, program_id: Pubkey) -> Result<()> {
+    let hook_registry = &mut ctx.accounts.hook_registry;
+    let clock = Clock::get()?;
+
+    hook_registry.hooks.retain(|hook| hook.program_id != program_id);
+    hook_registry.last_updated = clock.unix_timestamp;
+
+    msg!("Policy hook removed");
+    msg!("Agent: {}", hook_registry.agent);
+    msg!("Hook program: {}", program_id);
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct RemoveWatchtower<'info> {
+    #[account(
+        mut,
+        seeds = [WATCHTOWER_SEED, main_wallet.key().as_ref()],
+        bump = watchtower.bump,
+        has_one = main_wallet,
+        close = main_wallet
+    )]
+    pub watchtower: Account<'info, Watchtower>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RemoveWatchtower>) -> Result<()> {
+    msg!("Watchtower removed");
+    msg!("Main Wallet: {}", ctx.accounts.main_wallet.key());
+    msg!("Watchtower: {}", ctx.accounts.watchtower.watchtower);
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct RemoveWithdrawalAddress<'info> {
+    #[account(
+        mut,
+        seeds = [WITHDRAWAL_ADDRESSES_SEED, main_wallet.key().as_ref()],
+        bump = withdrawal_addresses.bump,
+        has_one = main_wallet
+    )]
+    pub withdrawal_addresses: Account<'info, WithdrawalAddressBook>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RemoveWithdrawalAddress>, address: Pubkey) -> Result<()> {
+    let withdrawal_addresses = &mut ctx.accounts.withdrawal_addresses;
+    let clock = Clock::get()?;
+
+    // Removal only narrows the set, so it takes effect immediately
+    withdrawal_addresses.entries.retain(|entry| entry.address != address);
+    withdrawal_addresses.last_updated = clock.unix_timestamp;
+
+    msg!("Withdrawal address removed");
+    msg!("Address: {}", address);
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct RepayCredit<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        seeds = [CREDIT_LINE_SEED, agent_account.key().as_ref()],
+        bump = credit_line.bump,
+        constraint = credit_line.agent == agent_account.key() @ DimmError::Unauthorized,
+        constraint = credit_line.treasury == treasury.key() @ DimmError::Unauthorized
+    )]
+    pub credit_line: Account<'info, CreditLine>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, main_wallet.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RepayCredit>, amount: u64) -> Result<()> {
+    require!(amount > 0, DimmError::InvalidAmount);
+
+    let clock = Clock::get()?;
+    let credit_line = &mut ctx.accounts.credit_line;
+
+    // A repayment landing after the due date still counts as missed
+    credit_line.check_repayment_schedule(clock.unix_timestamp)?;
+    credit_line.record_repayment(amount)?;
+
+    // Repay from the agent's inflows while keeping the minimum balance and fee tank
+    let agent_info = ctx.accounts.agent_account.to_account_info();
+    let available_balance = agent_info
+        .lamports()
+        .checked_sub(MIN_AGENT_BALANCE)
+        .and_then(|v| v.checked_sub(ctx.accounts.agent_account.fee_tank))
+        .ok_or(DimmError::InsufficientAgentBalance)?;
+
+    require!(
+        amount <= available_balance,
+        DimmError::InsufficientAgentBalance
+    );
+
+    **agent_info.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    msg!("Credit repaid");
+    msg!("Agent: {}", ctx.accounts.agent_account.key());
+    msg!("Amount: {} lamports", amount);
+    msg!("Outstanding: {} lamports", ctx.accounts.credit_line.outstanding);
+    msg!("Frozen: {}", ctx.accounts.credit_line.frozen);
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program::{transfer, Transfer};
 use crate::errors::DimmError;
+use crate::events::FundingRequested;
 use crate::state::*;
 use crate::constants::*;
 
@@ -10,21 +10,36 @@ pub struct RequestSol<'info> {
         mut,
         seeds = [
             AGENT_SEED,
-            main_wallet.key().as_ref(),
+            agent_account.main_wallet.as_ref(),
             &agent_account.agent_id.to_le_bytes()
         ],
         bump = agent_account.bump,
-        has_one = main_wallet
+        constraint = agent_account.agent_signer == requester.key() @ DimmError::Unauthorized
     )]
     pub agent_account: Account<'info, AgentAccount>,
 
+    #[account(
+        init,
+        payer = requester,
+        space = FundingRequest::LEN,
+        seeds = [
+            FUNDING_REQUEST_SEED,
+            agent_account.key().as_ref(),
+            &agent_account.funding_request_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub funding_request: Account<'info, FundingRequest>,
+
+    /// The agent's own signer key
     #[account(mut)]
-    pub main_wallet: Signer<'info>,
+    pub requester: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<RequestSol>, amount: u64, reason: String) -> Result<()> {
+    let agent_key = ctx.accounts.agent_account.key();
     let agent_account = &mut ctx.accounts.agent_account;
     let clock = Clock::get()?;
 
@@ -36,29 +51,40 @@ pub fn handler(ctx: Context<RequestSol>, amount: u64, reason: String) -> Result<
     // Check and reset daily limit if needed
     agent_account.check_and_reset_daily_limit(clock.unix_timestamp)?;
 
-    // Check if agent can spend this amount
+    // An agent can't ask for more than it could spend
     require!(
         agent_account.can_spend(amount)?,
         DimmError::ExceedsDailyLimit
     );
 
-    // Transfer SOL from main wallet to agent
-    let cpi_context = CpiContext::new(
-        ctx.accounts.system_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.main_wallet.to_account_info(),
-            to: ctx.accounts.agent_account.to_account_info(),
-        },
-    );
+    let funding_request = &mut ctx.accounts.funding_request;
+    funding_request.agent = agent_key;
+    funding_request.main_wallet = agent_account.main_wallet;
+    funding_request.requester = ctx.accounts.requester.key();
+    funding_request.request_id = agent_account.funding_request_count;
+    funding_request.amount = amount;
+    funding_request.reason = reason;
+    funding_request.requested_at = clock.unix_timestamp;
+    funding_request.bump = ctx.bumps.funding_request;
+
+    agent_account.funding_request_count = agent_account
+        .funding_request_count
+        .checked_add(1)
+        .ok_or(DimmError::NumericalOverflow)?;
 
-    transfer(cpi_context, amount)?;
+    emit!(FundingRequested {
+        agent: agent_key,
+        request_id: funding_request.request_id,
+        amount,
+        reason: funding_request.reason.clone(),
+        timestamp: clock.unix_timestamp,
+    });
 
-    msg!("SOL requested and transferred to agent");
-    msg!("Agent: {}", ctx.accounts.agent_account.key());
+    msg!("SOL requested");
+    msg!("Agent: {}", agent_key);
+    msg!("Request ID: {}", funding_request.request_id);
     msg!("Amount: {} lamports", amount);
-    msg!("Reason: {}", reason);
+    msg!("Reason: {}", funding_request.reason);
 
     Ok(())
 }
-
-
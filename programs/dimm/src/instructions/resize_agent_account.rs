@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+use crate::constants::*;
+use crate::errors::DimmError;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct ResizeAgentAccount<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Pays rent for the added space
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grow the agent account to `new_size` bytes, zero-filling the new space,
+/// and stamp it with the current layout version
+pub fn handler(ctx: Context<ResizeAgentAccount>, new_size: u32) -> Result<()> {
+    let agent_info = ctx.accounts.agent_account.to_account_info();
+    let current_size = agent_info.data_len();
+    let new_size = new_size as usize;
+    require!(
+        new_size > current_size && new_size - current_size <= MAX_PERMITTED_DATA_INCREASE,
+        DimmError::InvalidAccountSize
+    );
+
+    let rent = Rent::get()?;
+    let top_up = rent
+        .minimum_balance(new_size)
+        .saturating_sub(rent.minimum_balance(current_size));
+    if top_up > 0 {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.main_wallet.to_account_info(),
+                to: agent_info.clone(),
+            },
+        );
+        transfer(cpi_context, top_up)?;
+    }
+
+    agent_info.realloc(new_size, true)?;
+
+    let agent_account = &mut ctx.accounts.agent_account;
+    let previous_version = agent_account.schema_version;
+    agent_account.schema_version = AGENT_SCHEMA_VERSION;
+
+    msg!("Agent account resized");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Size: {} -> {}", current_size, new_size);
+    msg!("Schema version: {} -> {}", previous_version, agent_account.schema_version);
+
+    Ok(())
+}
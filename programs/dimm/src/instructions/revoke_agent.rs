@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use mpl_bubblegum::program::Bubblegum;
+use spl_account_compression::{program::SplAccountCompression, Noop};
+use crate::cnft::{self, TreeAccounts};
 use crate::state::*;
 use crate::constants::*;
 
@@ -12,23 +15,94 @@ pub struct RevokeAgent<'info> {
             &agent_account.agent_id.to_le_bytes()
         ],
         bump = agent_account.bump,
-        has_one = main_wallet
+        has_one = main_wallet,
+        has_one = merkle_tree
     )]
-    pub agent_account: Account<'info, AgentAccount>,
+    pub agent_account: Box<Account<'info, AgentAccount>>,
 
     pub main_wallet: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED, main_wallet.key().as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: Protocol-wide stats; may be uninitialized
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATS_SEED],
+        bump
+    )]
+    pub global_stats: UncheckedAccount<'info>,
+
+    /// CHECK: Current delegate of the agent's cNFT, hashed into its leaf;
+    /// the main wallet unless the agent is listed
+    pub leaf_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: Tree authority PDA
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = bubblegum_program.key()
+    )]
+    pub tree_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the agent's merkle tree
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    pub bubblegum_program: Program<'info, Bubblegum>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<RevokeAgent>) -> Result<()> {
+/// Revoke the agent and burn its cNFT so it no longer shows as a live
+/// credential. The cNFT's merkle proof must be passed as remaining accounts;
+/// an agent whose cNFT is already burned needs none.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RevokeAgent<'info>>,
+    leaf: AgentLeafArgs,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let agent = ctx.accounts.agent_account.key();
     let agent_account = &mut ctx.accounts.agent_account;
 
+    if !agent_account.revoked {
+        ctx.accounts.protocol_config.release_agent(agent_account.daily_limit);
+        if let Some(mut global_stats) = GlobalStats::load(&ctx.accounts.global_stats)? {
+            global_stats.record_agent_revoked()?;
+            global_stats.store(&ctx.accounts.global_stats)?;
+        }
+    }
+
+    if !agent_account.cnft_burned {
+        let tree = TreeAccounts {
+            tree_authority: ctx.accounts.tree_authority.to_account_info(),
+            merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+            bubblegum_program: ctx.accounts.bubblegum_program.to_account_info(),
+            compression_program: ctx.accounts.compression_program.to_account_info(),
+            log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        cnft::burn_leaf(
+            &tree,
+            &ctx.accounts.main_wallet.to_account_info(),
+            &ctx.accounts.leaf_delegate.to_account_info(),
+            &leaf,
+            ctx.remaining_accounts,
+        )?;
+        agent_account.cnft_burned = true;
+    }
+
     agent_account.revoked = true;
+    agent_account.revoked_at = clock.unix_timestamp;
 
     msg!("Agent revoked");
-    msg!("Agent: {}", ctx.accounts.agent_account.key());
+    msg!("Agent: {}", agent);
     msg!("Agent ID: {}", agent_account.agent_id);
 
     Ok(())
 }
-
-
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct RevokeSpender<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            SPEND_APPROVAL_SEED,
+            agent_account.key().as_ref(),
+            spend_approval.spender.as_ref()
+        ],
+        bump = spend_approval.bump,
+        close = main_wallet
+    )]
+    pub spend_approval: Account<'info, SpendApproval>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RevokeSpender>) -> Result<()> {
+    msg!("Spender revoked");
+    msg!("Agent: {}", ctx.accounts.agent_account.key());
+    msg!("Spender: {}", ctx.accounts.spend_approval.spender);
+
+    Ok(())
+}
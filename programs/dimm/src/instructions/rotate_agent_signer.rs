@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct RotateAgentSigner<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RotateAgentSigner>, new_agent_signer: Pubkey) -> Result<()> {
+    let agent_account = &mut ctx.accounts.agent_account;
+    let previous_agent_signer = agent_account.agent_signer;
+
+    agent_account.agent_signer = new_agent_signer;
+
+    msg!("Agent signer rotated");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Previous signer: {}", previous_agent_signer);
+    msg!("New signer: {}", agent_account.agent_signer);
+
+    Ok(())
+}
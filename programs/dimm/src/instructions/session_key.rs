@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use crate::auth::{Capability, Caller};
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+#[instruction(params: CreateSessionKeyParams)]
+pub struct CreateSessionKey<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = SessionKey::LEN,
+        seeds = [SESSION_KEY_SEED, agent_account.key().as_ref(), params.session_key.as_ref()],
+        bump
+    )]
+    pub session_key: Account<'info, SessionKey>,
+
+    /// Main wallet or the agent's own signer key
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeSessionKey<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        seeds = [SESSION_KEY_SEED, agent_account.key().as_ref(), session_key.session_key.as_ref()],
+        bump = session_key.bump,
+        has_one = created_by,
+        close = created_by
+    )]
+    pub session_key: Account<'info, SessionKey>,
+
+    /// Main wallet or the agent's own signer key
+    pub authority: Signer<'info>,
+
+    /// CHECK: Receives the session rent; checked against the session
+    #[account(mut)]
+    pub created_by: UncheckedAccount<'info>,
+}
+
+/// Open a session key for the agent's runtime
+pub fn create_handler(ctx: Context<CreateSessionKey>, params: CreateSessionKeyParams) -> Result<()> {
+    let agent_account = &ctx.accounts.agent_account;
+    Caller::authorize(
+        ctx.accounts.authority.key(),
+        &agent_account.main_wallet,
+        Some(agent_account),
+        None,
+        Capability::Execute,
+    )?;
+    require!(!agent_account.revoked, DimmError::AgentRevoked);
+
+    let clock = Clock::get()?;
+    require!(
+        params.expires_at > clock.unix_timestamp
+            && params.expires_at - clock.unix_timestamp <= MAX_SESSION_DURATION_SECONDS,
+        DimmError::InvalidSessionExpiry
+    );
+    require!(
+        !params.allowed_activity_types.is_empty()
+            && params.allowed_activity_types.len() <= MAX_SESSION_ACTIVITY_TYPES,
+        DimmError::InvalidSessionActivityTypes
+    );
+
+    let session_key = &mut ctx.accounts.session_key;
+    session_key.agent = agent_account.key();
+    session_key.session_key = params.session_key;
+    session_key.created_by = ctx.accounts.authority.key();
+    session_key.expires_at = params.expires_at;
+    session_key.max_total_spend = params.max_total_spend;
+    session_key.spent = 0;
+    session_key.allowed_activity_types = params.allowed_activity_types;
+    session_key.bump = ctx.bumps.session_key;
+
+    msg!("Session key created");
+    msg!("Agent: {}", session_key.agent);
+    msg!("Session key: {}", session_key.session_key);
+    msg!("Expires at: {}", session_key.expires_at);
+    msg!("Max total spend: {} lamports", session_key.max_total_spend);
+
+    Ok(())
+}
+
+/// Close a session key before it expires, or clean one up after
+pub fn revoke_handler(ctx: Context<RevokeSessionKey>) -> Result<()> {
+    let agent_account = &ctx.accounts.agent_account;
+    Caller::authorize(
+        ctx.accounts.authority.key(),
+        &agent_account.main_wallet,
+        Some(agent_account),
+        None,
+        Capability::Execute,
+    )?;
+
+    msg!("Session key revoked");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Session key: {}", ctx.accounts.session_key.session_key);
+
+    Ok(())
+}
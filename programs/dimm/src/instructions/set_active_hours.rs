@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetActiveHours<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+/// Restrict the agent to a daily UTC window on the given weekdays. A window
+/// ending before it starts runs past midnight; equal ends allow the whole day.
+pub fn handler(ctx: Context<SetActiveHours>, params: ActiveHoursParams) -> Result<()> {
+    require!(
+        (params.start as i64) < SECONDS_PER_DAY
+            && (params.end as i64) < SECONDS_PER_DAY
+            && params.weekdays != 0
+            && params.weekdays <= ALL_WEEKDAYS,
+        DimmError::InvalidActiveHours
+    );
+
+    let agent_account = &mut ctx.accounts.agent_account;
+    agent_account.active_hours_start = params.start;
+    agent_account.active_hours_end = params.end;
+    agent_account.active_weekdays = params.weekdays;
+
+    msg!("Active hours updated");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Hours: {} - {} (UTC seconds of day)", params.start, params.end);
+    msg!("Weekdays: {:#09b}", params.weekdays);
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetActivityDispute<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        constraint = activity.agent == agent_account.key() @ DimmError::Unauthorized
+    )]
+    pub activity: Account<'info, AgentActivity>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetActivityDispute>, disputed: bool) -> Result<()> {
+    let activity = &mut ctx.accounts.activity;
+
+    activity.disputed = disputed;
+
+    msg!("Activity dispute flag updated");
+    msg!("Agent: {}", activity.agent);
+    msg!("Sequence: {}", activity.sequence);
+    msg!("Disputed: {}", disputed);
+
+    Ok(())
+}
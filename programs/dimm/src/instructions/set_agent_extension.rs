@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetAgentExtension<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+pub fn set_handler(ctx: Context<SetAgentExtension>, value: ExtensionValue) -> Result<()> {
+    let agent_account = &mut ctx.accounts.agent_account;
+
+    agent_account.set_extension(&value)?;
+
+    msg!("Agent extension set");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Extension: {:?}", value);
+
+    Ok(())
+}
+
+pub fn clear_handler(ctx: Context<SetAgentExtension>, extension_type: ExtensionType) -> Result<()> {
+    let agent_account = &mut ctx.accounts.agent_account;
+
+    agent_account.clear_extension(extension_type)?;
+
+    msg!("Agent extension cleared");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Extension: {:?}", extension_type);
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetAmountBands<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+/// Replace the agent's amount bands. An empty list lets the agent spend
+/// without co-signers.
+pub fn handler(ctx: Context<SetAmountBands>, bands: Vec<AmountBand>) -> Result<()> {
+    require!(bands.len() <= MAX_AMOUNT_BANDS, DimmError::InvalidAmountBands);
+    require!(
+        bands
+            .iter()
+            .all(|band| band.co_signers > 0 && band.co_signers as usize <= MAX_CO_SIGNERS),
+        DimmError::InvalidAmountBands
+    );
+    require!(
+        bands
+            .windows(2)
+            .all(|pair| pair[0].above < pair[1].above && pair[0].co_signers < pair[1].co_signers),
+        DimmError::InvalidAmountBands
+    );
+
+    let agent_account = &mut ctx.accounts.agent_account;
+    agent_account.amount_bands = bands;
+
+    msg!("Amount bands updated");
+    msg!("Agent: {}", agent_account.key());
+    for band in &agent_account.amount_bands {
+        msg!("Above {} lamports: {} co-signers", band.above, band.co_signers);
+    }
+
+    Ok(())
+}
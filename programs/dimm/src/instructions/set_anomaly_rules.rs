@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetAnomalyRules<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+/// Set the rules that freeze the agent when an execution looks anomalous
+pub fn handler(ctx: Context<SetAnomalyRules>, rules: AnomalyRules) -> Result<()> {
+    let agent_account = &mut ctx.accounts.agent_account;
+    agent_account.anomaly_rules = rules;
+
+    msg!("Anomaly rules updated");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Max multiple of average: {}", rules.max_multiple_of_average);
+    msg!("Max failures per hour: {}", rules.max_failures_per_hour);
+
+    Ok(())
+}
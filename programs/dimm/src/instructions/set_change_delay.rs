@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetChangeDelay<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+/// Set how long increases to the agent's permissions and limits wait before
+/// they can be applied. Lengthening the delay is immediate; shortening it is
+/// itself staged behind the current delay.
+pub fn handler(ctx: Context<SetChangeDelay>, delay_seconds: i64) -> Result<()> {
+    require!(
+        (0..=MAX_CHANGE_DELAY_SECONDS).contains(&delay_seconds),
+        DimmError::InvalidChangeDelay
+    );
+
+    let clock = Clock::get()?;
+    let agent_account = &mut ctx.accounts.agent_account;
+
+    if delay_seconds >= agent_account.change_delay_seconds {
+        agent_account.change_delay_seconds = delay_seconds;
+        agent_account.pending_change_delay = None;
+        msg!("Change delay updated");
+    } else {
+        agent_account.stage_change_delay(delay_seconds, clock.unix_timestamp)?;
+        msg!("Change delay staged until {}", agent_account.pending_effective_at);
+    }
+
+    msg!("Agent: {}", agent_account.key());
+    msg!("Delay: {} seconds", delay_seconds);
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetCoSigners<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = main_wallet,
+        space = CoSignerSet::LEN,
+        seeds = [CO_SIGNER_SET_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub co_signer_set: Account<'info, CoSignerSet>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Replace the agent's co-signers. Approvals given to the previous set no
+/// longer count.
+pub fn handler(ctx: Context<SetCoSigners>, co_signers: Vec<Pubkey>) -> Result<()> {
+    require!(co_signers.len() <= MAX_CO_SIGNERS, DimmError::TooManyCoSigners);
+    for (i, co_signer) in co_signers.iter().enumerate() {
+        require!(!co_signers[..i].contains(co_signer), DimmError::DuplicateCoSigner);
+    }
+
+    let co_signer_set = &mut ctx.accounts.co_signer_set;
+    co_signer_set.agent = ctx.accounts.agent_account.key();
+    co_signer_set.co_signers = co_signers;
+    co_signer_set.generation = co_signer_set.generation.wrapping_add(1);
+    co_signer_set.bump = ctx.bumps.co_signer_set;
+
+    msg!("Co-signers updated");
+    msg!("Agent: {}", co_signer_set.agent);
+    msg!("Co-signers: {}", co_signer_set.co_signers.len());
+
+    Ok(())
+}
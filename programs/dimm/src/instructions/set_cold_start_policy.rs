@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetColdStartPolicy<'info> {
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED, authority.key().as_ref()],
+        bump = protocol_config.bump,
+        has_one = authority
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetColdStartPolicy>, params: ColdStartParams) -> Result<()> {
+    require!(params.min_age >= 0, DimmError::InvalidColdStartPolicy);
+
+    let protocol_config = &mut ctx.accounts.protocol_config;
+
+    protocol_config.cold_start_threshold = params.threshold;
+    protocol_config.cold_start_min_age = params.min_age;
+    protocol_config.cold_start_min_transactions = params.min_transactions;
+
+    msg!("Cold-start policy updated");
+    msg!("Authority: {}", protocol_config.authority);
+    msg!("Threshold: {} lamports", protocol_config.cold_start_threshold);
+    msg!("Minimum age: {} seconds", protocol_config.cold_start_min_age);
+    msg!("Minimum transactions: {}", protocol_config.cold_start_min_transactions);
+
+    Ok(())
+}
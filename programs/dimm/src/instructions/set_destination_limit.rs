@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+#[instruction(destination: Pubkey)]
+pub struct SetDestinationLimit<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Created on first use
+    #[account(
+        init_if_needed,
+        payer = main_wallet,
+        space = DestinationLimit::LEN,
+        seeds = [DESTINATION_LIMIT_SEED, agent_account.key().as_ref(), destination.as_ref()],
+        bump
+    )]
+    pub destination_limit: Account<'info, DestinationLimit>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveDestinationLimit<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            DESTINATION_LIMIT_SEED,
+            agent_account.key().as_ref(),
+            destination_limit.destination.as_ref()
+        ],
+        bump = destination_limit.bump,
+        close = main_wallet
+    )]
+    pub destination_limit: Account<'info, DestinationLimit>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+}
+
+/// Set the agent's limits towards `destination`. Spend already recorded
+/// today is kept.
+pub fn set_handler(
+    ctx: Context<SetDestinationLimit>,
+    destination: Pubkey,
+    max_per_transaction: u64,
+    daily_limit: u64,
+) -> Result<()> {
+    require!(
+        daily_limit >= max_per_transaction,
+        DimmError::InvalidLimitConfiguration
+    );
+
+    let agent_key = ctx.accounts.agent_account.key();
+    let destination_limit = &mut ctx.accounts.destination_limit;
+
+    if destination_limit.agent == Pubkey::default() {
+        destination_limit.agent = agent_key;
+        destination_limit.destination = destination;
+        destination_limit.last_daily_reset = Clock::get()?.unix_timestamp;
+        destination_limit.bump = ctx.bumps.destination_limit;
+    }
+
+    destination_limit.max_per_transaction = max_per_transaction;
+    destination_limit.daily_limit = daily_limit;
+
+    msg!("Destination limit set");
+    msg!("Agent: {}", agent_key);
+    msg!("Destination: {}", destination);
+    msg!("Max per transaction: {} lamports", max_per_transaction);
+    msg!("Daily limit: {} lamports", daily_limit);
+
+    Ok(())
+}
+
+/// Drop the limits, leaving only the agent's own towards the destination
+pub fn remove_handler(ctx: Context<RemoveDestinationLimit>) -> Result<()> {
+    msg!("Destination limit removed");
+    msg!("Agent: {}", ctx.accounts.agent_account.key());
+    msg!("Destination: {}", ctx.accounts.destination_limit.destination);
+
+    Ok(())
+}
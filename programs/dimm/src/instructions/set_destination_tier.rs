@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetDestinationTier<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Created on first use
+    #[account(
+        init_if_needed,
+        payer = main_wallet,
+        space = AddressBook::LEN,
+        seeds = [ADDRESS_BOOK_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub address_book: Account<'info, AddressBook>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Classify `destination` into a risk tier, or drop it from the address
+/// book with `None` so it counts as high-risk again
+pub fn handler(
+    ctx: Context<SetDestinationTier>,
+    destination: Pubkey,
+    tier: Option<RiskTier>,
+) -> Result<()> {
+    let agent_key = ctx.accounts.agent_account.key();
+    let address_book = &mut ctx.accounts.address_book;
+    let clock = Clock::get()?;
+
+    if address_book.agent == Pubkey::default() {
+        address_book.agent = agent_key;
+        address_book.entries = Vec::new();
+        address_book.last_daily_reset = clock.unix_timestamp;
+        address_book.bump = ctx.bumps.address_book;
+    }
+
+    address_book.set_tier(destination, tier)?;
+    address_book.last_updated = clock.unix_timestamp;
+
+    msg!("Destination tier updated");
+    msg!("Agent: {}", agent_key);
+    msg!("Destination: {}", destination);
+    msg!("Tier: {:?}", tier);
+
+    Ok(())
+}
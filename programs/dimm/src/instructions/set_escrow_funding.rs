@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetEscrowFunding<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = main_wallet,
+        space = AgentEscrow::LEN,
+        seeds = [AGENT_ESCROW_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub agent_escrow: Account<'info, AgentEscrow>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Turn escrow funding on or off. Turning it on sweeps the agent's balance
+/// above its rent, `MIN_AGENT_BALANCE` and fee tank into the escrow; turning it off
+/// moves the escrow back into the agent.
+pub fn handler(ctx: Context<SetEscrowFunding>, enabled: bool) -> Result<()> {
+    let agent_info = ctx.accounts.agent_account.to_account_info();
+    let escrow_info = ctx.accounts.agent_escrow.to_account_info();
+
+    let agent_escrow = &mut ctx.accounts.agent_escrow;
+    agent_escrow.agent = agent_info.key();
+    agent_escrow.bump = ctx.bumps.agent_escrow;
+
+    let moved = if enabled {
+        let reserve = Rent::get()?
+            .minimum_balance(agent_info.data_len())
+            .saturating_add(MIN_AGENT_BALANCE)
+            .saturating_add(ctx.accounts.agent_account.fee_tank);
+        let idle = agent_info.lamports().saturating_sub(reserve);
+        **agent_info.try_borrow_mut_lamports()? -= idle;
+        **escrow_info.try_borrow_mut_lamports()? += idle;
+        idle
+    } else {
+        let held = AgentEscrow::available(&escrow_info)?;
+        AgentEscrow::release(&escrow_info, &agent_info, held)?;
+        held
+    };
+
+    ctx.accounts.agent_account.escrow_funding = enabled;
+
+    msg!("Escrow funding {}", if enabled { "enabled" } else { "disabled" });
+    msg!("Agent: {}", agent_info.key());
+    msg!("Moved: {} lamports", moved);
+
+    Ok(())
+}
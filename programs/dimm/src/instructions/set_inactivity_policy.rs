@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetInactivityPolicy<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+/// Set the agent's dead-man switch. Setting it also restarts the
+/// inactivity clock.
+pub fn handler(ctx: Context<SetInactivityPolicy>, params: InactivityPolicyParams) -> Result<()> {
+    require!(params.timeout_seconds >= 0, DimmError::InvalidInactivityPolicy);
+
+    let agent_account = &mut ctx.accounts.agent_account;
+    let clock = Clock::get()?;
+
+    agent_account.inactivity_timeout_seconds = params.timeout_seconds;
+    agent_account.inactivity_action = params.action;
+    agent_account.inactivity_keeper_reward = params.keeper_reward;
+    agent_account.inactivity_reset_at = clock.unix_timestamp;
+
+    msg!("Inactivity policy updated");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Timeout: {} seconds", agent_account.inactivity_timeout_seconds);
+    msg!("Action: {:?}", agent_account.inactivity_action);
+    msg!("Keeper reward: {} lamports", agent_account.inactivity_keeper_reward);
+
+    Ok(())
+}
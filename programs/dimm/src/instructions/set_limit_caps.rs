@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::events::LimitCapsUpdated;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetLimitCaps<'info> {
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED, authority.key().as_ref()],
+        bump = protocol_config.bump,
+        has_one = authority
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Set the caps on what limits any agent may be given. Agents already above
+/// a new cap keep their limits until they are next changed.
+pub fn handler(ctx: Context<SetLimitCaps>, params: LimitCapParams) -> Result<()> {
+    params.validate()?;
+
+    let protocol_config = &mut ctx.accounts.protocol_config;
+    protocol_config.max_agent_daily_limit = params.max_agent_daily_limit;
+    protocol_config.max_agent_tx_limit = params.max_agent_tx_limit;
+
+    emit!(LimitCapsUpdated {
+        authority: protocol_config.authority,
+        max_agent_daily_limit: params.max_agent_daily_limit,
+        max_agent_tx_limit: params.max_agent_tx_limit,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Agent limit caps updated");
+    msg!("Authority: {}", protocol_config.authority);
+    msg!("Max agent daily limit: {} lamports", protocol_config.max_agent_daily_limit);
+    msg!("Max agent transaction limit: {} lamports", protocol_config.max_agent_tx_limit);
+
+    Ok(())
+}
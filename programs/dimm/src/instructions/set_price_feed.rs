@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetPriceFeed<'info> {
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED, authority.key().as_ref()],
+        bump = protocol_config.bump,
+        has_one = authority
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Pyth SOL/USD price account; parsed to reject anything else
+    pub price_feed: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Set the Pyth SOL/USD price account agents' USD limits are priced with.
+/// Feeds differ per cluster, so each deployment configures its own.
+pub fn handler(ctx: Context<SetPriceFeed>) -> Result<()> {
+    SolUsdPrice::parse(&ctx.accounts.price_feed.try_borrow_data()?)?;
+
+    let protocol_config = &mut ctx.accounts.protocol_config;
+    protocol_config.sol_usd_price_feed = ctx.accounts.price_feed.key();
+
+    msg!("Price feed updated");
+    msg!("Authority: {}", protocol_config.authority);
+    msg!("SOL/USD feed: {}", protocol_config.sol_usd_price_feed);
+
+    Ok(())
+}
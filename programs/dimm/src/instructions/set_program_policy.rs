@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetProgramPolicy<'info> {
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED, authority.key().as_ref()],
+        bump = protocol_config.bump,
+        has_one = authority
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetProgramPolicy>, restrict_unlabeled_programs: bool) -> Result<()> {
+    let protocol_config = &mut ctx.accounts.protocol_config;
+
+    protocol_config.restrict_unlabeled_programs = restrict_unlabeled_programs;
+
+    msg!("Program policy updated");
+    msg!("Authority: {}", protocol_config.authority);
+    msg!("Restrict unlabeled programs: {}", restrict_unlabeled_programs);
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetRelayerRegistry<'info> {
+    /// Created on first use
+    #[account(
+        init_if_needed,
+        payer = main_wallet,
+        space = RelayerRegistry::LEN,
+        seeds = [RELAYER_REGISTRY_SEED, main_wallet.key().as_ref()],
+        bump
+    )]
+    pub relayer_registry: Account<'info, RelayerRegistry>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Replace the relayers allowed to sponsor the wallet's executions and the
+/// reimbursement each sponsored execution pays them
+pub fn handler(
+    ctx: Context<SetRelayerRegistry>,
+    relayers: Vec<Pubkey>,
+    reimbursement_lamports: u64,
+) -> Result<()> {
+    require!(relayers.len() <= MAX_RELAYERS, DimmError::TooManyRelayers);
+    for (i, relayer) in relayers.iter().enumerate() {
+        require!(
+            !relayers[..i].contains(relayer),
+            DimmError::DuplicateRelayer
+        );
+    }
+    require!(
+        reimbursement_lamports <= MAX_RELAYER_REIMBURSEMENT,
+        DimmError::ReimbursementTooHigh
+    );
+
+    let relayer_registry = &mut ctx.accounts.relayer_registry;
+    relayer_registry.main_wallet = ctx.accounts.main_wallet.key();
+    relayer_registry.relayers = relayers;
+    relayer_registry.reimbursement_lamports = reimbursement_lamports;
+    relayer_registry.bump = ctx.bumps.relayer_registry;
+
+    msg!("Relayer registry updated");
+    msg!("Main wallet: {}", relayer_registry.main_wallet);
+    msg!("Relayers: {}", relayer_registry.relayers.len());
+    msg!("Reimbursement: {} lamports", reimbursement_lamports);
+
+    Ok(())
+}
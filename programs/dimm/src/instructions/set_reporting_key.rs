@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetReportingKey<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetReportingKey>, reporting_key: Option<[u8; 32]>) -> Result<()> {
+    let agent_account = &mut ctx.accounts.agent_account;
+
+    agent_account.reporting_key = reporting_key;
+
+    msg!("Reporting key updated");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Encrypted payloads: {}", if reporting_key.is_some() { "enabled" } else { "disabled" });
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetScopedPermission<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Created on first use
+    #[account(
+        init_if_needed,
+        payer = main_wallet,
+        space = ScopedPermissions::LEN,
+        seeds = [SCOPED_PERMISSIONS_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub scoped_permissions: Account<'info, ScopedPermissions>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveScopedPermission<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        seeds = [SCOPED_PERMISSIONS_SEED, agent_account.key().as_ref()],
+        bump = scoped_permissions.bump
+    )]
+    pub scoped_permissions: Account<'info, ScopedPermissions>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+/// Allow calls to `scope.program_id` up to `scope.max_amount`. Once an agent
+/// has any scope, program calls outside its scopes are rejected.
+pub fn set_handler(ctx: Context<SetScopedPermission>, scope: ScopedPermission) -> Result<()> {
+    require_keys_neq!(scope.program_id, crate::ID, DimmError::InvalidProgram);
+
+    let agent_key = ctx.accounts.agent_account.key();
+    let scoped_permissions = &mut ctx.accounts.scoped_permissions;
+
+    if scoped_permissions.agent == Pubkey::default() {
+        scoped_permissions.agent = agent_key;
+        scoped_permissions.scopes = Vec::new();
+        scoped_permissions.bump = ctx.bumps.scoped_permissions;
+    }
+
+    scoped_permissions.upsert(scope)?;
+    scoped_permissions.last_updated = Clock::get()?.unix_timestamp;
+
+    msg!("Scoped permission set");
+    msg!("Agent: {}", agent_key);
+    msg!("Program: {}", scope.program_id);
+    msg!("Max amount: {}", scope.max_amount);
+
+    Ok(())
+}
+
+/// Drop a program's scope. Removing the last scope leaves the agent with
+/// no callable programs, not with unrestricted `ExecutePrograms`.
+pub fn remove_handler(ctx: Context<RemoveScopedPermission>, program_id: Pubkey) -> Result<()> {
+    let scoped_permissions = &mut ctx.accounts.scoped_permissions;
+
+    scoped_permissions.remove(&program_id);
+    scoped_permissions.last_updated = Clock::get()?.unix_timestamp;
+
+    msg!("Scoped permission removed");
+    msg!("Agent: {}", scoped_permissions.agent);
+    msg!("Program: {}", program_id);
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetStakeQuotas<'info> {
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED, authority.key().as_ref()],
+        bump = protocol_config.bump,
+        has_one = authority
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// The main wallet; the caps only bind its own agents
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetStakeQuotas>, params: StakeQuotaParams) -> Result<()> {
+    let protocol_config = &mut ctx.accounts.protocol_config;
+
+    protocol_config.agents_per_staked_sol = params.agents_per_staked_sol;
+    protocol_config.daily_limit_per_staked_sol = params.daily_limit_per_staked_sol;
+
+    msg!("Stake quotas updated");
+    msg!("Authority: {}", protocol_config.authority);
+    msg!("Agents per staked SOL: {}", protocol_config.agents_per_staked_sol);
+    msg!("Daily limit per staked SOL: {} lamports", protocol_config.daily_limit_per_staked_sol);
+
+    Ok(())
+}
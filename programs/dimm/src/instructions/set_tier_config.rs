@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetTierConfig<'info> {
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED, authority.key().as_ref()],
+        bump = protocol_config.bump,
+        has_one = authority
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Set the limit ceilings and promotion requirements of every trust tier.
+/// Tiers must not loosen going down, so promotion never lowers a ceiling.
+pub fn handler(ctx: Context<SetTierConfig>, tiers: [TierConfig; 4]) -> Result<()> {
+    for tier in tiers.iter() {
+        require!(
+            tier.max_sol_per_transaction == 0
+                || tier.daily_limit == 0
+                || tier.daily_limit >= tier.max_sol_per_transaction,
+            DimmError::InvalidTierConfig
+        );
+    }
+    for pair in tiers.windows(2) {
+        require!(
+            pair[1].loosens(&pair[0])
+                && pair[1].promote_after_transactions >= pair[0].promote_after_transactions,
+            DimmError::InvalidTierConfig
+        );
+    }
+
+    let protocol_config = &mut ctx.accounts.protocol_config;
+    protocol_config.trust_tiers = tiers;
+
+    msg!("Trust tiers updated");
+    msg!("Authority: {}", protocol_config.authority);
+    for (level, tier) in protocol_config.trust_tiers.iter().enumerate() {
+        msg!(
+            "Tier {}: {} lamports per transaction, {} lamports daily, after {} transactions",
+            level,
+            tier.max_sol_per_transaction,
+            tier.daily_limit,
+            tier.promote_after_transactions
+        );
+    }
+
+    Ok(())
+}
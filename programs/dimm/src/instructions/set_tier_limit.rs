@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetTierLimit<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Created on first use
+    #[account(
+        init_if_needed,
+        payer = main_wallet,
+        space = AddressBook::LEN,
+        seeds = [ADDRESS_BOOK_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub address_book: Account<'info, AddressBook>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Set a risk tier's caps (0 = no cap beyond the agent's own limits).
+/// Spend already recorded today towards the tier is kept.
+pub fn handler(
+    ctx: Context<SetTierLimit>,
+    tier: RiskTier,
+    max_per_transaction: u64,
+    daily_limit: u64,
+) -> Result<()> {
+    let agent_key = ctx.accounts.agent_account.key();
+    let address_book = &mut ctx.accounts.address_book;
+    let clock = Clock::get()?;
+
+    if address_book.agent == Pubkey::default() {
+        address_book.agent = agent_key;
+        address_book.entries = Vec::new();
+        address_book.last_daily_reset = clock.unix_timestamp;
+        address_book.bump = ctx.bumps.address_book;
+    }
+
+    let limit = &mut address_book.tier_limits[tier as usize];
+    limit.max_per_transaction = max_per_transaction;
+    limit.daily_limit = daily_limit;
+    address_book.last_updated = clock.unix_timestamp;
+
+    msg!("Tier limit updated");
+    msg!("Agent: {}", agent_key);
+    msg!("Tier: {:?}", tier);
+    msg!("Max per transaction: {} lamports", max_per_transaction);
+    msg!("Daily limit: {} lamports", daily_limit);
+
+    Ok(())
+}
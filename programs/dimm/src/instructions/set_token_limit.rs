@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetTokenLimit<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = main_wallet,
+        space = TokenLimit::LEN,
+        seeds = [TOKEN_LIMIT_SEED, agent_account.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub token_limit: Account<'info, TokenLimit>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetTokenLimit>, params: TokenLimitParams) -> Result<()> {
+    require!(
+        params.daily_limit >= params.max_per_transaction,
+        DimmError::InvalidLimitConfiguration
+    );
+
+    let decimals = ctx.accounts.mint.decimals;
+    require!(
+        params.display_decimals <= decimals,
+        DimmError::InvalidDisplayDecimals
+    );
+
+    let token_limit = &mut ctx.accounts.token_limit;
+    let clock = Clock::get()?;
+
+    // First configuration starts a fresh daily window
+    if token_limit.agent == Pubkey::default() {
+        token_limit.agent = ctx.accounts.agent_account.key();
+        token_limit.mint = ctx.accounts.mint.key();
+        token_limit.spent_today = 0;
+        token_limit.last_daily_reset = clock.unix_timestamp;
+        token_limit.total_spent = 0;
+        token_limit.bump = ctx.bumps.token_limit;
+    }
+
+    token_limit.decimals = decimals;
+    token_limit.display_decimals = params.display_decimals;
+    token_limit.max_per_transaction = params.max_per_transaction;
+    token_limit.daily_limit = params.daily_limit;
+
+    msg!("Token limit configured");
+    msg!("Agent: {}", token_limit.agent);
+    msg!("Mint: {} ({} decimals)", token_limit.mint, token_limit.decimals);
+    msg!(
+        "Max per transaction: {}",
+        token_limit.ui_amount(token_limit.max_per_transaction)
+    );
+    msg!("Daily limit: {}", token_limit.ui_amount(token_limit.daily_limit));
+
+    Ok(())
+}
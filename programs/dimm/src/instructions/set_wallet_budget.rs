@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetWalletBudget<'info> {
+    /// Created on first use
+    #[account(
+        init_if_needed,
+        payer = main_wallet,
+        space = WalletBudget::LEN,
+        seeds = [WALLET_BUDGET_SEED, main_wallet.key().as_ref()],
+        bump
+    )]
+    pub wallet_budget: Account<'info, WalletBudget>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Set the daily cap across all of the wallet's agents (0 = no cap).
+/// Spend already recorded in the current window is kept.
+pub fn handler(ctx: Context<SetWalletBudget>, daily_cap: u64) -> Result<()> {
+    let wallet_budget = &mut ctx.accounts.wallet_budget;
+
+    if wallet_budget.main_wallet == Pubkey::default() {
+        wallet_budget.main_wallet = ctx.accounts.main_wallet.key();
+        wallet_budget.spent_today = 0;
+        wallet_budget.window_start = Clock::get()?.unix_timestamp;
+        wallet_budget.bump = ctx.bumps.wallet_budget;
+    }
+    wallet_budget.daily_cap = daily_cap;
+
+    msg!("Wallet budget updated");
+    msg!("Main wallet: {}", wallet_budget.main_wallet);
+    msg!("Daily cap: {} lamports", daily_cap);
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetWhitelistEnabled<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            WHITELIST_SEED,
+            agent_account.key().as_ref(),
+            &whitelist.whitelist_type.seed()
+        ],
+        bump = whitelist.bump,
+        constraint = whitelist.owner == agent_account.key() @ crate::errors::DimmError::Unauthorized
+    )]
+    pub whitelist: Account<'info, Whitelist>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetWhitelistEnabled>, enabled: bool) -> Result<()> {
+    let agent_account = &mut ctx.accounts.agent_account;
+    let whitelist = &mut ctx.accounts.whitelist;
+    let clock = Clock::get()?;
+
+    whitelist.enabled = enabled;
+    whitelist.last_updated = clock.unix_timestamp;
+
+    if whitelist.whitelist_type == WhitelistType::Destinations {
+        agent_account.require_destination_whitelist = enabled;
+    }
+
+    msg!("Whitelist {}", if enabled { "enabled" } else { "disabled" });
+    msg!("Agent: {}", whitelist.owner);
+    msg!("Type: {:?}", whitelist.whitelist_type);
+
+    Ok(())
+}
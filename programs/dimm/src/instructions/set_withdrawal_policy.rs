@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetWithdrawalPolicy<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+/// Tightening the policy takes effect at once. Raising or disabling the
+/// threshold, or shortening the delay, is staged for `apply_pending_changes`
+/// so a compromised main wallet can't drop the timelock and drain the agent.
+pub fn handler(ctx: Context<SetWithdrawalPolicy>, params: WithdrawalPolicyParams) -> Result<()> {
+    require!(
+        params.delay_seconds >= 0 && params.delay_seconds <= MAX_WITHDRAWAL_DELAY_SECONDS,
+        DimmError::InvalidWithdrawalPolicy
+    );
+
+    let clock = Clock::get()?;
+    let agent_account = &mut ctx.accounts.agent_account;
+    let loosens = agent_account.loosens_withdrawal_policy(&params);
+
+    if !agent_account.loosens_withdrawal_threshold(params.threshold) {
+        agent_account.withdrawal_threshold = params.threshold;
+    }
+    if params.delay_seconds >= agent_account.withdrawal_delay {
+        agent_account.withdrawal_delay = params.delay_seconds;
+    }
+
+    if loosens {
+        agent_account.stage_withdrawal_policy(params, clock.unix_timestamp)?;
+        msg!("Withdrawal policy loosening staged");
+        msg!("Effective at: {}", agent_account.pending_effective_at);
+    } else {
+        agent_account.pending_withdrawal_policy = None;
+    }
+
+    msg!("Withdrawal policy updated");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Threshold: {} lamports", agent_account.withdrawal_threshold);
+    msg!("Delay: {} seconds", agent_account.withdrawal_delay);
+
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+use crate::errors::DimmError;
+use crate::events::{ExecutionReceipt, SoftLimitExceeded};
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SpendOnBehalf<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        seeds = [PROTOCOL_SEED, agent_account.main_wallet.as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Wallet's emergency state; may be uninitialized
+    #[account(
+        seeds = [EMERGENCY_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub emergency_state: UncheckedAccount<'info>,
+
+    /// CHECK: Agent's activity schedule; may be uninitialized
+    #[account(
+        seeds = [SCHEDULE_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub schedule: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet's activity digest; may be uninitialized
+    #[account(
+        mut,
+        seeds = [DIGEST_SEED, agent_account.main_wallet.as_ref()],
+        bump
+    )]
+    pub digest: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            SPEND_APPROVAL_SEED,
+            agent_account.key().as_ref(),
+            spender.key().as_ref()
+        ],
+        bump = spend_approval.bump,
+        has_one = spender,
+        constraint = spend_approval.agent == agent_account.key() @ DimmError::Unauthorized
+    )]
+    pub spend_approval: Account<'info, SpendApproval>,
+
+    /// Approved spender, usually a PDA signing through `invoke_signed`
+    pub spender: Signer<'info>,
+
+    /// CHECK: Receives the pulled funds; chosen by the spender
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+}
+
+/// Only callable from another program. Pulls count against the agent's
+/// per-transaction and daily limits like any other spend.
+pub fn handler(ctx: Context<SpendOnBehalf>, amount: u64) -> Result<()> {
+    require!(
+        get_stack_height() > TRANSACTION_LEVEL_STACK_HEIGHT,
+        DimmError::SpendRequiresCpi
+    );
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
+    EmergencyState::require_not_paused(&ctx.accounts.emergency_state)?;
+    require!(amount > 0, DimmError::InvalidAmount);
+
+    let agent_account = &mut ctx.accounts.agent_account;
+    let spend_approval = &mut ctx.accounts.spend_approval;
+    let clock = Clock::get()?;
+
+    require!(!agent_account.revoked, DimmError::AgentRevoked);
+    require!(
+        !agent_account.is_frozen(clock.unix_timestamp),
+        DimmError::AgentFrozen
+    );
+    ActivitySchedule::require_active(&ctx.accounts.schedule, clock.unix_timestamp)?;
+    require!(
+        agent_account.is_within_active_hours(clock.unix_timestamp),
+        DimmError::OutsideActiveHours
+    );
+    require!(
+        !agent_account.soft_limit_pending_ack,
+        DimmError::SoftLimitNotAcknowledged
+    );
+    require!(
+        !spend_approval.is_expired(clock.unix_timestamp),
+        DimmError::SpendApprovalExpired
+    );
+
+    spend_approval.record_spend(amount)?;
+
+    // Band-sized spends need co-signers, which only execute_transaction collects
+    require!(
+        agent_account.required_co_signers(amount) == 0,
+        DimmError::CoSignersRequired
+    );
+
+    agent_account.check_and_reset_daily_limit(clock.unix_timestamp)?;
+    require!(
+        agent_account.can_spend(amount)?,
+        DimmError::ExceedsDailyLimit
+    );
+    require!(
+        agent_account.within_trial_limits(amount, clock.unix_timestamp)?,
+        DimmError::TrialLimitExceeded
+    );
+    require!(
+        agent_account.within_hourly_limit(amount)?,
+        DimmError::ExceedsHourlyLimit
+    );
+
+    let agent_info = agent_account.to_account_info();
+    let required_balance = amount
+        .checked_add(MIN_AGENT_BALANCE)
+        .and_then(|v| v.checked_add(agent_account.fee_tank))
+        .ok_or(DimmError::NumericalOverflow)?;
+    require!(
+        agent_info.lamports() >= required_balance,
+        DimmError::InsufficientAgentBalance
+    );
+
+    // The agent account is owned by this program, so lamports move directly
+    **agent_info.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.destination.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    let spent_before = agent_account.spent_today;
+    agent_account.record_spend(amount)?;
+    if agent_account.record_soft_limit_crossing(spent_before) {
+        emit!(SoftLimitExceeded {
+            agent: agent_account.key(),
+            soft_daily_limit: agent_account.soft_daily_limit,
+            spent_today: agent_account.spent_today,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    agent_account.last_used_at = clock.unix_timestamp;
+    spend_approval.last_updated = clock.unix_timestamp;
+
+    if let Some(mut digest) = WalletDigest::load(&ctx.accounts.digest)? {
+        digest.record_execution(agent_account.key(), amount, 0, false, clock.unix_timestamp)?;
+        digest.store(&ctx.accounts.digest)?;
+    }
+
+    emit!(ExecutionReceipt {
+        agent: agent_account.key(),
+        sequence: agent_account.next_receipt()?,
+        activity_type: ActivityType::Transfer,
+        amount,
+        mint: None,
+        destination: Some(ctx.accounts.destination.key()),
+        fee: 0,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Spent on behalf of agent");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Spender: {}", spend_approval.spender);
+    msg!("Amount: {} lamports", amount);
+    msg!("Allowance remaining: {} lamports", spend_approval.remaining());
+
+    Ok(())
+}
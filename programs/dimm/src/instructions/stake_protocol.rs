@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct StakeProtocol<'info> {
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = WalletStake::LEN,
+        seeds = [STAKE_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub wallet_stake: Account<'info, WalletStake>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// New stake counts towards quotas from the next epoch
+pub fn handler(ctx: Context<StakeProtocol>, amount: u64) -> Result<()> {
+    require!(amount > 0, DimmError::InvalidAmount);
+    let clock = Clock::get()?;
+
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.owner.to_account_info(),
+            to: ctx.accounts.wallet_stake.to_account_info(),
+        },
+    );
+    transfer(cpi_context, amount)?;
+
+    let wallet_stake = &mut ctx.accounts.wallet_stake;
+    wallet_stake.owner = ctx.accounts.owner.key();
+    wallet_stake.bump = ctx.bumps.wallet_stake;
+    wallet_stake.settle(clock.epoch);
+    wallet_stake.activating = wallet_stake
+        .activating
+        .checked_add(amount)
+        .ok_or(DimmError::NumericalOverflow)?;
+    wallet_stake.activation_epoch = clock
+        .epoch
+        .checked_add(1)
+        .ok_or(DimmError::NumericalOverflow)?;
+
+    msg!("Stake deposited");
+    msg!("Owner: {}", wallet_stake.owner);
+    msg!("Amount: {} lamports", amount);
+    msg!("Active from epoch: {}", wallet_stake.activation_epoch);
+
+    Ok(())
+}
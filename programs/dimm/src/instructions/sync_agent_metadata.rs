@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use mpl_bubblegum::program::Bubblegum;
+use spl_account_compression::{program::SplAccountCompression, Noop};
+use crate::cnft::{self, TreeAccounts};
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SyncAgentMetadata<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet,
+        has_one = merkle_tree
+    )]
+    pub agent_account: Box<Account<'info, AgentAccount>>,
+
+    /// Leaf owner, and creator or delegate of the agent's tree
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    /// CHECK: Current delegate of the agent's cNFT, hashed into its leaf;
+    /// the main wallet unless the agent is listed
+    pub leaf_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: Tree authority PDA
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = bubblegum_program.key()
+    )]
+    pub tree_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the agent's merkle tree
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Token Metadata program
+    #[account(address = TOKEN_METADATA_PROGRAM_ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+
+    pub bubblegum_program: Program<'info, Bubblegum>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Bring the agent's cNFT metadata in line with its current name,
+/// permissions and limits, after `update_permissions`, `update_limits` or any
+/// other change to them. `current_name` and `current_uri` are the cNFT's
+/// metadata as it stands; Bubblegum checks them against the leaf. The cNFT's
+/// merkle proof must be passed as remaining accounts.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SyncAgentMetadata<'info>>,
+    leaf: AgentLeafArgs,
+    current_name: String,
+    current_uri: String,
+) -> Result<()> {
+    let agent = ctx.accounts.agent_account.key();
+    let agent_account = &ctx.accounts.agent_account;
+    require!(!agent_account.cnft_burned, DimmError::AgentCnftBurned);
+
+    let updated = cnft::agent_metadata(agent_account, &agent);
+    if updated.name == current_name && updated.uri == current_uri {
+        msg!("Agent metadata already in sync");
+        return Ok(());
+    }
+
+    let tree = TreeAccounts {
+        tree_authority: ctx.accounts.tree_authority.to_account_info(),
+        merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+        bubblegum_program: ctx.accounts.bubblegum_program.to_account_info(),
+        compression_program: ctx.accounts.compression_program.to_account_info(),
+        log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+    };
+    cnft::update_leaf_metadata(
+        &tree,
+        &ctx.accounts.main_wallet.to_account_info(),
+        &ctx.accounts.main_wallet.to_account_info(),
+        &ctx.accounts.leaf_delegate.to_account_info(),
+        &ctx.accounts.token_metadata_program.to_account_info(),
+        &leaf,
+        cnft::metadata_args(current_name, current_uri, agent_account.main_wallet),
+        &updated,
+        ctx.remaining_accounts,
+    )?;
+
+    msg!("Agent metadata synced");
+    msg!("Agent: {}", agent);
+    msg!("URI: {}", updated.uri);
+
+    Ok(())
+}
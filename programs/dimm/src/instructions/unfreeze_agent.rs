@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::events::AgentUnfrozen;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct UnfreezeAgent<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UnfreezeAgent>) -> Result<()> {
+    let agent_account = &mut ctx.accounts.agent_account;
+    let clock = Clock::get()?;
+
+    agent_account.frozen_until = 0;
+    agent_account.inactivity_reset_at = clock.unix_timestamp;
+    agent_account.failures_this_hour = 0;
+
+    emit!(AgentUnfrozen {
+        agent: agent_account.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Agent unfrozen");
+    msg!("Agent: {}", agent_account.key());
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct UnstakeProtocol<'info> {
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, owner.key().as_ref()],
+        bump = wallet_stake.bump,
+        has_one = owner
+    )]
+    pub wallet_stake: Account<'info, WalletStake>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Withdraws activating stake first. Quotas are only checked on create and
+/// limit updates, so existing agents keep running after an unstake.
+pub fn handler(ctx: Context<UnstakeProtocol>, amount: u64) -> Result<()> {
+    require!(amount > 0, DimmError::InvalidAmount);
+    let clock = Clock::get()?;
+    let wallet_stake = &mut ctx.accounts.wallet_stake;
+
+    wallet_stake.settle(clock.epoch);
+
+    let from_activating = amount.min(wallet_stake.activating);
+    let from_staked = amount - from_activating;
+    require!(
+        from_staked <= wallet_stake.staked,
+        DimmError::InsufficientStake
+    );
+
+    wallet_stake.activating -= from_activating;
+    wallet_stake.staked -= from_staked;
+
+    // The stake account is owned by this program, so lamports move directly
+    **wallet_stake.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.owner.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    msg!("Stake withdrawn");
+    msg!("Owner: {}", wallet_stake.owner);
+    msg!("Amount: {} lamports", amount);
+    msg!("Remaining stake: {} lamports", wallet_stake.staked + wallet_stake.activating);
+
+    Ok(())
+}
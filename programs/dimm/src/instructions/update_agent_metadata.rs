@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::DimmError;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(params: UpdateAgentMetadataParams)]
+pub struct UpdateAgentMetadata<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet,
+        realloc = agent_account.space_with_metadata(
+            agent_account.to_account_info().data_len(),
+            &params
+        ),
+        realloc::payer = main_wallet,
+        realloc::zero = false
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Pays for a larger account, and is refunded when it shrinks
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Replace the agent's name and optional metadata, resizing the account
+/// to fit
+pub fn handler(ctx: Context<UpdateAgentMetadata>, params: UpdateAgentMetadataParams) -> Result<()> {
+    #[allow(clippy::unnecessary_map_or)]
+    let fits = |value: &Option<String>, max: usize| value.as_ref().map_or(true, |v| v.len() <= max);
+
+    require!(params.name.len() <= MAX_AGENT_NAME_LENGTH, DimmError::AgentNameTooLong);
+    require!(
+        fits(&params.description, MAX_AGENT_DESCRIPTION_LENGTH),
+        DimmError::DescriptionTooLong
+    );
+    require!(
+        fits(&params.metadata_uri, MAX_METADATA_URI_LENGTH),
+        DimmError::MetadataUriTooLong
+    );
+    require!(fits(&params.model_id, MAX_MODEL_ID_LENGTH), DimmError::ModelIdTooLong);
+
+    let agent_account = &mut ctx.accounts.agent_account;
+    agent_account.name = params.name;
+    agent_account.description = params.description;
+    agent_account.metadata_uri = params.metadata_uri;
+    agent_account.model_id = params.model_id;
+
+    msg!("Agent metadata updated");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Name: {}", agent_account.name);
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct UpdateEmergencyContacts<'info> {
+    #[account(
+        mut,
+        seeds = [EMERGENCY_SEED, authority.key().as_ref()],
+        bump = emergency_state.bump,
+        has_one = authority
+    )]
+    pub emergency_state: Account<'info, EmergencyState>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UpdateEmergencyContacts>, emergency_contacts: Vec<Pubkey>) -> Result<()> {
+    require!(
+        emergency_contacts.len() <= EmergencyState::MAX_EMERGENCY_CONTACTS,
+        DimmError::TooManyEmergencyContacts
+    );
+
+    let emergency_state = &mut ctx.accounts.emergency_state;
+
+    emergency_state.emergency_contacts = emergency_contacts;
+
+    msg!("Emergency contacts updated");
+    msg!("Authority: {}", emergency_state.authority);
+    msg!("Emergency contacts: {}", emergency_state.emergency_contacts.len());
+
+    Ok(())
+}
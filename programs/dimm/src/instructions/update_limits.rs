@@ -17,30 +17,67 @@ pub struct UpdateLimits<'info> {
     )]
     pub agent_account: Account<'info, AgentAccount>,
 
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED, main_wallet.key().as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Wallet's protocol stake; may be uninitialized
+    #[account(
+        seeds = [STAKE_SEED, main_wallet.key().as_ref()],
+        bump
+    )]
+    pub wallet_stake: UncheckedAccount<'info>,
+
     pub main_wallet: Signer<'info>,
 }
 
+/// Set the given limits. With a change delay configured, changes that loosen
+/// a limit are staged for `apply_pending_changes` and the rest apply now. A
+/// locked agent accepts only changes that tighten.
 pub fn handler(ctx: Context<UpdateLimits>, params: UpdateLimitsParams) -> Result<()> {
+    let clock = Clock::get()?;
     let agent_account = &mut ctx.accounts.agent_account;
+    let previous_daily_limit = agent_account.daily_limit;
 
-    if let Some(max_sol_per_transaction) = params.max_sol_per_transaction {
-        agent_account.max_sol_per_transaction = max_sol_per_transaction;
-    }
-
-    if let Some(daily_limit) = params.daily_limit {
-        agent_account.daily_limit = daily_limit;
-    }
-
-    // Validate the configuration
+    let (tightening, loosening) = agent_account.split_limit_changes(&params);
     require!(
-        agent_account.daily_limit >= agent_account.max_sol_per_transaction,
-        DimmError::InvalidLimitConfiguration
+        !agent_account.config_locked || loosening.is_none(),
+        DimmError::ConfigLocked
     );
+    let (immediate, staged) = if agent_account.change_delay_seconds > 0 {
+        (tightening, loosening)
+    } else {
+        (params, None)
+    };
+    agent_account.supersede_pending_limits(&immediate);
+    agent_account.apply_limits(&immediate)?;
+    ctx.accounts.protocol_config.commit_agent_limits(
+        agent_account,
+        previous_daily_limit,
+        &ctx.accounts.wallet_stake,
+    )?;
+
+    if let Some(staged) = staged {
+        agent_account.stage_limits(staged, clock.unix_timestamp)?;
+        msg!("Limit increases staged until {}", agent_account.pending_effective_at);
+    }
 
     msg!("Agent limits updated");
-    msg!("Agent: {}", ctx.accounts.agent_account.key());
+    msg!("Agent: {}", agent_account.key());
     msg!("Max per transaction: {} lamports", agent_account.max_sol_per_transaction);
     msg!("Daily limit: {} lamports", agent_account.daily_limit);
+    msg!("Maintenance allowance: {} lamports", agent_account.maintenance_allowance);
+    msg!("Approval threshold: {} lamports", agent_account.approval_threshold);
+    msg!("Donation allowance: {} lamports", agent_account.donation_allowance);
+    msg!("Soft daily limit: {} lamports", agent_account.soft_daily_limit);
+    msg!("Hourly limit: {} lamports", agent_account.max_sol_per_hour);
+    msg!("Lease threshold: {} lamports", agent_account.lease_threshold);
+    msg!("Daily limit: {} USD cents", agent_account.daily_limit_usd);
+    msg!("Max per transaction: {} USD cents", agent_account.max_usd_per_transaction);
+    msg!("Max slippage: {} bps", agent_account.max_slippage_bps);
 
     Ok(())
 }
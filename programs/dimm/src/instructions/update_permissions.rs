@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::errors::DimmError;
 use crate::state::*;
 use crate::constants::*;
 
@@ -19,19 +20,34 @@ pub struct UpdatePermissions<'info> {
     pub main_wallet: Signer<'info>,
 }
 
-pub fn handler(
-    ctx: Context<UpdatePermissions>,
-    new_permissions: Vec<AgentPermission>,
-) -> Result<()> {
+/// Replace the agent's permissions with a bitmask of `AgentPermission::bit()`.
+/// Timed grants of permissions that stay in the set keep their expiry. With a
+/// change delay configured, added permissions are staged for
+/// `apply_pending_changes` while removals apply now. A locked agent can only
+/// lose permissions.
+pub fn handler(ctx: Context<UpdatePermissions>, new_permissions: u64) -> Result<()> {
+    let clock = Clock::get()?;
     let agent_account = &mut ctx.accounts.agent_account;
-
-    agent_account.permissions = new_permissions;
+    PermissionSet::from_bits(new_permissions)?;
+
+    let added = new_permissions & !agent_account.permissions.bits;
+    require!(!agent_account.config_locked || added == 0, DimmError::ConfigLocked);
+    let granted_now = if agent_account.change_delay_seconds > 0 { 0 } else { added };
+    agent_account.permissions =
+        PermissionSet::from_bits(new_permissions & (agent_account.permissions.bits | granted_now))?;
+    agent_account
+        .timed_permissions
+        .retain(|grant| new_permissions & grant.permission.bit() != 0);
+    agent_account.pending_permissions &= new_permissions;
+
+    if added != granted_now {
+        agent_account.stage_permissions(added, clock.unix_timestamp)?;
+        msg!("Added permissions staged until {}", agent_account.pending_effective_at);
+    }
 
     msg!("Agent permissions updated");
-    msg!("Agent: {}", ctx.accounts.agent_account.key());
-    msg!("New permissions: {:?}", agent_account.permissions);
+    msg!("Agent: {}", agent_account.key());
+    msg!("New permissions: {:#b}", agent_account.permissions.bits);
 
     Ok(())
 }
-
-
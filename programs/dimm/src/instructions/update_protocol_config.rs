@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use crate::cnft;
+use crate::errors::DimmError;
+use crate::events::{LimitCapsUpdated, MerkleTreeRotated, ProtocolFeeUpdated, ProtocolPauseUpdated};
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct UpdateProtocolConfig<'info> {
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED, authority.key().as_ref()],
+        bump = protocol_config.bump,
+        has_one = authority
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Required to change fee parameters
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, authority.key().as_ref()],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Option<Account<'info, Treasury>>,
+
+    /// CHECK: Tree new agents are minted into from now on; checked in the handler
+    pub new_merkle_tree: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Bubblegum tree config of `new_merkle_tree`; checked in the handler
+    pub new_tree_authority: Option<UncheckedAccount<'info>>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Change the protocol settings given in `params`, and rotate new agents
+/// onto `new_merkle_tree` when it is passed. Each change emits an event.
+pub fn handler(ctx: Context<UpdateProtocolConfig>, params: UpdateProtocolConfigParams) -> Result<()> {
+    let clock = Clock::get()?;
+    let authority = ctx.accounts.authority.key();
+    let protocol_config = &mut ctx.accounts.protocol_config;
+
+    if let Some(paused) = params.paused {
+        protocol_config.paused = paused;
+        emit!(ProtocolPauseUpdated {
+            authority,
+            paused,
+            timestamp: clock.unix_timestamp,
+        });
+        msg!("Paused: {}", paused);
+    }
+
+    if params.fee_bps.is_some() || params.min_fee.is_some() {
+        let treasury = ctx.accounts.treasury.as_mut().ok_or(DimmError::TreasuryRequired)?;
+        if let Some(fee_bps) = params.fee_bps {
+            require!(fee_bps <= 10000, DimmError::InvalidFeeConfiguration);
+            treasury.fee_bps = fee_bps;
+        }
+        if let Some(min_fee) = params.min_fee {
+            treasury.min_fee = min_fee;
+        }
+        emit!(ProtocolFeeUpdated {
+            authority,
+            fee_bps: treasury.fee_bps,
+            min_fee: treasury.min_fee,
+            timestamp: clock.unix_timestamp,
+        });
+        msg!("Fee: {} bps (min {} lamports)", treasury.fee_bps, treasury.min_fee);
+    }
+
+    if params.max_agent_daily_limit.is_some() || params.max_agent_tx_limit.is_some() {
+        let caps = LimitCapParams {
+            max_agent_daily_limit: params
+                .max_agent_daily_limit
+                .unwrap_or(protocol_config.max_agent_daily_limit),
+            max_agent_tx_limit: params
+                .max_agent_tx_limit
+                .unwrap_or(protocol_config.max_agent_tx_limit),
+        };
+        caps.validate()?;
+        protocol_config.max_agent_daily_limit = caps.max_agent_daily_limit;
+        protocol_config.max_agent_tx_limit = caps.max_agent_tx_limit;
+        emit!(LimitCapsUpdated {
+            authority,
+            max_agent_daily_limit: caps.max_agent_daily_limit,
+            max_agent_tx_limit: caps.max_agent_tx_limit,
+            timestamp: clock.unix_timestamp,
+        });
+        msg!("Max agent daily limit: {} lamports", caps.max_agent_daily_limit);
+        msg!("Max agent transaction limit: {} lamports", caps.max_agent_tx_limit);
+    }
+
+    match (&ctx.accounts.new_merkle_tree, &ctx.accounts.new_tree_authority) {
+        (Some(new_merkle_tree), Some(new_tree_authority)) => {
+            cnft::check_tree(new_merkle_tree, new_tree_authority)?;
+            let previous_tree = protocol_config.merkle_tree;
+            protocol_config.rotate_merkle_tree(new_merkle_tree.key())?;
+            emit!(MerkleTreeRotated {
+                authority,
+                previous_tree,
+                merkle_tree: protocol_config.merkle_tree,
+                timestamp: clock.unix_timestamp,
+            });
+            msg!("Merkle Tree: {}", protocol_config.merkle_tree);
+        }
+        (None, None) => {}
+        _ => return err!(DimmError::InvalidMerkleTree),
+    }
+
+    msg!("Protocol config updated");
+    msg!("Authority: {}", authority);
+
+    Ok(())
+}
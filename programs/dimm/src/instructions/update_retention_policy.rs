@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct UpdateRetentionPolicy<'info> {
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    pub main_wallet: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UpdateRetentionPolicy>, params: RetentionPolicyParams) -> Result<()> {
+    require!(
+        params.max_records <= MAX_RETENTION_RECORDS,
+        DimmError::InvalidRetentionPolicy
+    );
+    require!(params.max_age >= 0, DimmError::InvalidRetentionPolicy);
+
+    let agent_account = &mut ctx.accounts.agent_account;
+
+    agent_account.retention_max_records = params.max_records;
+    agent_account.retention_max_age = params.max_age;
+
+    msg!("Activity retention policy updated");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Max records: {}", agent_account.retention_max_records);
+    msg!("Max age: {} seconds", agent_account.retention_max_age);
+
+    Ok(())
+}
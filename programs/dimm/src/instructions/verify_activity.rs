@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use spl_account_compression::cpi::{self as compression, accounts::VerifyLeaf};
+use spl_account_compression::program::SplAccountCompression;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct VerifyActivity<'info> {
+    #[account(
+        seeds = [ACTIVITY_TREE_SEED, activity_tree.main_wallet.as_ref()],
+        bump = activity_tree.bump,
+        has_one = merkle_tree
+    )]
+    pub activity_tree: Account<'info, ActivityTree>,
+
+    /// CHECK: Checked against the activity tree; owned by the compression program
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    pub compression_program: Program<'info, SplAccountCompression>,
+}
+
+/// Prove a compressed activity record is in the wallet's activity tree.
+/// The proof nodes are passed as remaining accounts; fails if the record
+/// does not hash to the leaf at `leaf_index` under `root`.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, VerifyActivity<'info>>,
+    root: [u8; 32],
+    record: AgentActivity,
+    leaf_index: u32,
+) -> Result<()> {
+    let leaf = record.chain_hash()?;
+
+    let cpi_context = CpiContext::new(
+        ctx.accounts.compression_program.to_account_info(),
+        VerifyLeaf {
+            merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+        },
+    )
+    .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    compression::verify_leaf(cpi_context, root, leaf, leaf_index)?;
+
+    msg!("Activity verified");
+    msg!("Agent: {}", record.agent);
+    msg!("Sequence: {}", record.sequence);
+    msg!("Leaf index: {}", leaf_index);
+
+    Ok(())
+}
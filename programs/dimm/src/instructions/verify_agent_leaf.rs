@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use spl_account_compression::cpi::{self as compression, accounts::VerifyLeaf};
+use spl_account_compression::program::SplAccountCompression;
+use crate::cnft;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct VerifyAgentLeaf<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = merkle_tree
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// CHECK: Current delegate of the agent's cNFT, hashed into its leaf;
+    /// the main wallet unless the agent is listed
+    pub leaf_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the agent's merkle tree
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    pub compression_program: Program<'info, SplAccountCompression>,
+}
+
+/// Prove the agent's cNFT is live and matches its `AgentAccount`: the leaf
+/// at its `leaf_index` under `root` must hash from the agent's name, owner,
+/// permissions and limits. The proof nodes are passed as remaining accounts.
+/// Other programs can CPI into this to accept the cNFT as an agent credential.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, VerifyAgentLeaf<'info>>,
+    root: [u8; 32],
+) -> Result<()> {
+    let agent = ctx.accounts.agent_account.key();
+    let agent_account = &ctx.accounts.agent_account;
+    require!(!agent_account.revoked, DimmError::AgentRevoked);
+    require!(!agent_account.cnft_burned, DimmError::AgentCnftBurned);
+
+    let leaf = cnft::agent_leaf_hash(agent_account, &agent, ctx.accounts.leaf_delegate.key)?;
+
+    let cpi_context = CpiContext::new(
+        ctx.accounts.compression_program.to_account_info(),
+        VerifyLeaf {
+            merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+        },
+    )
+    .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    compression::verify_leaf(cpi_context, root, leaf, agent_account.leaf_index)?;
+
+    msg!("Agent leaf verified");
+    msg!("Agent: {}", agent);
+    msg!("Owner: {}", agent_account.main_wallet);
+    msg!("Leaf index: {}", agent_account.leaf_index);
+
+    Ok(())
+}
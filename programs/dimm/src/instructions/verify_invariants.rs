@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::events::InvariantReport;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct VerifyInvariants<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+}
+
+/// Activity records are passed as remaining accounts in sequence order,
+/// ending with the most recent. The chain is replayed and compared against
+/// the agent's live state; divergence is reported, not treated as an error.
+/// Counters are only compared when the history starts at sequence 0.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, VerifyInvariants<'info>>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let agent_account = &ctx.accounts.agent_account;
+    let agent_key = agent_account.key();
+
+    let mut first_sequence = None;
+    let mut next_sequence = 0u64;
+    let mut head = [0u8; 32];
+    let mut spent: u64 = 0;
+    let mut transactions: u64 = 0;
+
+    for info in ctx.remaining_accounts.iter() {
+        let record = Account::<AgentActivity>::try_from(info)?;
+        require_keys_eq!(record.agent, agent_key, DimmError::Unauthorized);
+
+        match first_sequence {
+            None => {
+                require!(
+                    record.sequence > 0 || record.prev_hash == [0; 32],
+                    DimmError::ActivityChainBroken
+                );
+                first_sequence = Some(record.sequence);
+            }
+            Some(_) => {
+                require!(
+                    record.sequence == next_sequence && record.prev_hash == head,
+                    DimmError::ActivityChainBroken
+                );
+            }
+        }
+
+        if record.is_spend() {
+            spent = spent
+                .checked_add(record.amount)
+                .ok_or(DimmError::NumericalOverflow)?;
+            transactions = transactions
+                .checked_add(1)
+                .ok_or(DimmError::NumericalOverflow)?;
+        }
+
+        head = record.chain_hash()?;
+        next_sequence = record
+            .sequence
+            .checked_add(1)
+            .ok_or(DimmError::NumericalOverflow)?;
+    }
+
+    let first_sequence = first_sequence.unwrap_or(agent_account.activity_sequence);
+    let records_checked = next_sequence.saturating_sub(first_sequence);
+    let head_matches = if records_checked == 0 {
+        agent_account.activity_sequence == 0
+    } else {
+        next_sequence == agent_account.activity_sequence && head == agent_account.activity_hash
+    };
+
+    let full_history = first_sequence == 0 && head_matches;
+    let spent_matches = full_history.then(|| spent == agent_account.total_spent);
+    let transactions_match =
+        full_history.then(|| transactions == agent_account.total_transactions);
+
+    emit!(InvariantReport {
+        agent: agent_key,
+        first_sequence,
+        records_checked,
+        head_matches,
+        spent_matches,
+        transactions_match,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Invariants verified");
+    msg!("Agent: {}", agent_key);
+    msg!("Records checked: {}", records_checked);
+    msg!("Head matches: {}", head_matches);
+    if let (Some(spent_matches), Some(transactions_match)) = (spent_matches, transactions_match) {
+        msg!("Spent matches: {}", spent_matches);
+        msg!("Transactions match: {}", transactions_match);
+    }
+
+    Ok(())
+}
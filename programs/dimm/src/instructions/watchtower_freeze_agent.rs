@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::events::AgentFrozenByWatchtower;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct WatchtowerFreezeAgent<'info> {
+    #[account(
+        mut,
+        seeds = [WATCHTOWER_SEED, watchtower.main_wallet.as_ref()],
+        bump = watchtower.bump,
+        constraint = watchtower.watchtower == watchtower_key.key() @ DimmError::Unauthorized
+    )]
+    pub watchtower: Account<'info, Watchtower>,
+
+    /// The watchtower may only freeze agents of the wallet it monitors
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            watchtower.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        constraint = agent_account.main_wallet == watchtower.main_wallet @ DimmError::Unauthorized
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    pub watchtower_key: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<WatchtowerFreezeAgent>, duration_seconds: i64) -> Result<()> {
+    require!(
+        duration_seconds > 0 && duration_seconds <= MAX_WATCHTOWER_FREEZE_SECONDS,
+        DimmError::InvalidFreezeDuration
+    );
+
+    let agent_account = &mut ctx.accounts.agent_account;
+    let watchtower = &mut ctx.accounts.watchtower;
+    let clock = Clock::get()?;
+
+    let frozen_until = clock
+        .unix_timestamp
+        .checked_add(duration_seconds)
+        .ok_or(DimmError::NumericalOverflow)?;
+
+    // Never shortens a freeze the main wallet already imposed
+    agent_account.frozen_until = agent_account.frozen_until.max(frozen_until);
+
+    watchtower.freeze_count = watchtower
+        .freeze_count
+        .checked_add(1)
+        .ok_or(DimmError::NumericalOverflow)?;
+
+    emit!(AgentFrozenByWatchtower {
+        watchtower: watchtower.watchtower,
+        agent: agent_account.key(),
+        frozen_until: agent_account.frozen_until,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Agent frozen by watchtower");
+    msg!("Agent: {}", agent_account.key());
+    msg!("Frozen until: {}", agent_account.frozen_until);
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::events::HeartbeatReceived;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct WatchtowerHeartbeat<'info> {
+    #[account(
+        mut,
+        seeds = [WATCHTOWER_SEED, watchtower.main_wallet.as_ref()],
+        bump = watchtower.bump,
+        constraint = watchtower.watchtower == watchtower_key.key() @ crate::errors::DimmError::Unauthorized
+    )]
+    pub watchtower: Account<'info, Watchtower>,
+
+    pub watchtower_key: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<WatchtowerHeartbeat>) -> Result<()> {
+    let watchtower = &mut ctx.accounts.watchtower;
+    let clock = Clock::get()?;
+
+    watchtower.last_heartbeat = clock.unix_timestamp;
+
+    emit!(HeartbeatReceived {
+        watchtower: watchtower.watchtower,
+        main_wallet: watchtower.main_wallet,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct WhitelistRegisteredPrograms<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            main_wallet.key().as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+        has_one = main_wallet
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        seeds = [PROTOCOL_SEED, main_wallet.key().as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        seeds = [PROGRAM_REGISTRY_SEED, protocol_config.key().as_ref()],
+        bump = program_registry.bump
+    )]
+    pub program_registry: Account<'info, ProgramRegistry>,
+
+    /// Created on first use
+    #[account(
+        init_if_needed,
+        payer = main_wallet,
+        space = Whitelist::LEN,
+        seeds = [
+            WHITELIST_SEED,
+            agent_account.key().as_ref(),
+            &WhitelistType::Programs.seed()
+        ],
+        bump
+    )]
+    pub program_whitelist: Account<'info, Whitelist>,
+
+    #[account(mut)]
+    pub main_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Add registry entries to the agent's program whitelist by id
+pub fn handler(ctx: Context<WhitelistRegisteredPrograms>, ids: Vec<u16>) -> Result<()> {
+    let agent_key = ctx.accounts.agent_account.key();
+    let program_registry = &ctx.accounts.program_registry;
+    let program_whitelist = &mut ctx.accounts.program_whitelist;
+    let clock = Clock::get()?;
+
+    if program_whitelist.owner == Pubkey::default() {
+        program_whitelist.owner = agent_key;
+        program_whitelist.addresses = Vec::new();
+        program_whitelist.enabled = true;
+        program_whitelist.whitelist_type = WhitelistType::Programs;
+        program_whitelist.bump = ctx.bumps.program_whitelist;
+    }
+
+    for id in ids.iter() {
+        let entry = program_registry
+            .get(*id)
+            .ok_or(DimmError::ProgramNotRegistered)?;
+        program_whitelist.add_address(entry.program_id)?;
+    }
+    program_whitelist.last_updated = clock.unix_timestamp;
+
+    msg!("Registered programs whitelisted");
+    msg!("Agent: {}", agent_key);
+    msg!("Programs: {}", ids.len());
+
+    Ok(())
+}
@@ -5,6 +5,12 @@ use crate::constants::*;
 
 #[derive(Accounts)]
 pub struct WithdrawFromAgent<'info> {
+    #[account(
+        seeds = [PROTOCOL_SEED, main_wallet.key().as_ref()],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     #[account(
         mut,
         seeds = [
@@ -20,18 +26,45 @@ pub struct WithdrawFromAgent<'info> {
     #[account(mut)]
     pub main_wallet: Signer<'info>,
 
+    /// CHECK: Wallet's verified withdrawal address book; may be uninitialized
+    #[account(
+        seeds = [WITHDRAWAL_ADDRESSES_SEED, main_wallet.key().as_ref()],
+        bump
+    )]
+    pub withdrawal_addresses: UncheckedAccount<'info>,
+
+    /// CHECK: Validated against the withdrawal address book
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<WithdrawFromAgent>, amount: u64) -> Result<()> {
+    require!(!ctx.accounts.protocol_config.paused, DimmError::ProtocolPaused);
     require!(amount > 0, DimmError::InvalidAmount);
 
     let agent_account = &ctx.accounts.agent_account;
+    let clock = Clock::get()?;
+
+    require!(
+        !agent_account.requires_timelocked_withdrawal(amount, clock.unix_timestamp),
+        DimmError::WithdrawalRequiresTimelock
+    );
+
+    WithdrawalAddressBook::verify_destination(
+        &ctx.accounts.withdrawal_addresses.to_account_info(),
+        &agent_account.main_wallet,
+        &ctx.accounts.destination.key(),
+        clock.unix_timestamp,
+    )?;
+
     let agent_balance = agent_account.to_account_info().lamports();
 
-    // Ensure we keep minimum balance for rent
+    // Ensure we keep minimum balance for rent, and the fee tank until its cap is lowered
     let available_balance = agent_balance
         .checked_sub(MIN_AGENT_BALANCE)
+        .and_then(|v| v.checked_sub(agent_account.fee_tank))
         .ok_or(DimmError::InsufficientBalance)?;
 
     require!(
@@ -39,12 +72,17 @@ pub fn handler(ctx: Context<WithdrawFromAgent>, amount: u64) -> Result<()> {
         DimmError::InsufficientBalance
     );
 
-    // Transfer from agent to main wallet
+    ctx.accounts
+        .agent_account
+        .record_direct_withdrawal(amount, clock.unix_timestamp)?;
+
+    // Transfer from agent to the verified destination
     **ctx.accounts.agent_account.to_account_info().try_borrow_mut_lamports()? -= amount;
-    **ctx.accounts.main_wallet.to_account_info().try_borrow_mut_lamports()? += amount;
+    **ctx.accounts.destination.to_account_info().try_borrow_mut_lamports()? += amount;
 
     msg!("Withdrawal successful");
     msg!("Agent: {}", ctx.accounts.agent_account.key());
+    msg!("Destination: {}", ctx.accounts.destination.key());
     msg!("Amount: {} lamports", amount);
     msg!("Remaining balance: {} lamports", agent_balance - amount);
 
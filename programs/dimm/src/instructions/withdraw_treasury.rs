@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, authority.key().as_ref()],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+    require!(amount > 0, DimmError::InvalidAmount);
+
+    // Keep the treasury rent exempt
+    let treasury_info = ctx.accounts.treasury.to_account_info();
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(Treasury::LEN);
+    let available_balance = treasury_info
+        .lamports()
+        .checked_sub(rent_exempt_minimum)
+        .ok_or(DimmError::InsufficientTreasuryBalance)?;
+
+    require!(
+        amount <= available_balance,
+        DimmError::InsufficientTreasuryBalance
+    );
+
+    // The treasury is owned by this program, so lamports move directly
+    **treasury_info.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.authority.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    msg!("Treasury withdrawal");
+    msg!("Authority: {}", ctx.accounts.authority.key());
+    msg!("Amount: {} lamports", amount);
+    msg!("Remaining balance: {} lamports", treasury_info.lamports());
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use crate::auth::{Caller, Capability};
+use crate::errors::DimmError;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+#[instruction(params: WriteExecutionBufferParams)]
+pub struct WriteExecutionBuffer<'info> {
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            agent_account.main_wallet.as_ref(),
+            &agent_account.agent_id.to_le_bytes()
+        ],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ExecutionBuffer::space(params.total_len),
+        seeds = [EXECUTION_BUFFER_SEED, agent_account.key().as_ref()],
+        bump
+    )]
+    pub execution_buffer: Account<'info, ExecutionBuffer>,
+
+    /// Main wallet or the agent's own signer key
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<WriteExecutionBuffer>, params: WriteExecutionBufferParams) -> Result<()> {
+    Caller::authorize(
+        ctx.accounts.authority.key(),
+        &ctx.accounts.agent_account.main_wallet,
+        Some(&*ctx.accounts.agent_account),
+        None,
+        Capability::Execute,
+    )?;
+    require!(
+        params.total_len <= MAX_EXECUTION_BUFFER_SIZE,
+        DimmError::ExecutionBufferTooLarge
+    );
+
+    let agent_key = ctx.accounts.agent_account.key();
+    let execution_buffer = &mut ctx.accounts.execution_buffer;
+
+    // First chunk opens the buffer
+    if execution_buffer.agent == Pubkey::default() {
+        let clock = Clock::get()?;
+        execution_buffer.agent = agent_key;
+        execution_buffer.target_program = params.target_program;
+        execution_buffer.total_len = params.total_len;
+        execution_buffer.data = Vec::with_capacity(params.total_len as usize);
+        execution_buffer.created_at = clock.unix_timestamp;
+        execution_buffer.bump = ctx.bumps.execution_buffer;
+    }
+
+    require!(
+        execution_buffer.target_program == params.target_program
+            && execution_buffer.total_len == params.total_len,
+        DimmError::ExecutionBufferMismatch
+    );
+
+    let end = (params.offset as usize)
+        .checked_add(params.chunk.len())
+        .ok_or(DimmError::NumericalOverflow)?;
+    require!(
+        params.offset as usize == execution_buffer.data.len()
+            && end <= execution_buffer.total_len as usize,
+        DimmError::InvalidExecutionBufferWrite
+    );
+
+    execution_buffer.data.extend_from_slice(&params.chunk);
+
+    msg!("Execution buffer written");
+    msg!("Agent: {}", agent_key);
+    msg!("Written: {}/{} bytes", execution_buffer.data.len(), execution_buffer.total_len);
+
+    Ok(())
+}
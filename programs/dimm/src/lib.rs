@@ -1,19 +1,34 @@
 use anchor_lang::prelude::*;
+#[cfg(feature = "program")]
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+#[cfg(feature = "program")]
 use mpl_bubblegum::program::Bubblegum;
+#[cfg(feature = "program")]
 use spl_account_compression::{program::SplAccountCompression, Noop};
 
 declare_id!("DimmProgram11111111111111111111111111111111");
 
+#[cfg(feature = "program")]
+pub mod agent_cpi;
+pub mod auth;
+#[cfg(feature = "program")]
+pub mod cnft;
 pub mod constants;
 pub mod errors;
+pub mod events;
+pub mod hooks;
+#[cfg(feature = "program")]
 pub mod instructions;
 pub mod state;
 
+#[cfg(feature = "program")]
 use errors::*;
+#[cfg(feature = "program")]
 use instructions::*;
+#[cfg(feature = "program")]
 use state::*;
 
+#[cfg(feature = "program")]
 #[program]
 pub mod dimm {
     use super::*;
@@ -33,14 +48,14 @@ pub mod dimm {
         instructions::fund_agent::handler(ctx, amount)
     }
 
-    /// Request SOL from main wallet to agent SubAccount
+    /// Ask the main wallet for SOL, signed by the agent signer
     pub fn request_sol(ctx: Context<RequestSol>, amount: u64, reason: String) -> Result<()> {
         instructions::request_sol::handler(ctx, amount, reason)
     }
 
     /// Execute a transaction through an agent SubAccount
-    pub fn execute_transaction(
-        ctx: Context<ExecuteTransaction>,
+    pub fn execute_transaction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteTransaction<'info>>,
         params: ExecuteTransactionParams,
     ) -> Result<()> {
         instructions::execute_transaction::handler(ctx, params)
@@ -49,14 +64,17 @@ pub mod dimm {
     /// Update agent permissions
     pub fn update_permissions(
         ctx: Context<UpdatePermissions>,
-        new_permissions: Vec<AgentPermission>,
+        new_permissions: u64,
     ) -> Result<()> {
         instructions::update_permissions::handler(ctx, new_permissions)
     }
 
     /// Revoke agent access
-    pub fn revoke_agent(ctx: Context<RevokeAgent>) -> Result<()> {
-        instructions::revoke_agent::handler(ctx)
+    pub fn revoke_agent<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RevokeAgent<'info>>,
+        leaf: AgentLeafArgs,
+    ) -> Result<()> {
+        instructions::revoke_agent::handler(ctx, leaf)
     }
 
     /// Withdraw remaining SOL from agent back to main wallet
@@ -64,15 +82,948 @@ pub mod dimm {
         instructions::withdraw_from_agent::handler(ctx, amount)
     }
 
+    /// Cap the agent's fee tank
+    pub fn set_fee_tank_cap(ctx: Context<SetFeeTankCap>, cap: u64) -> Result<()> {
+        instructions::fee_tank::set_cap_handler(ctx, cap)
+    }
+
+    /// Fund the agent's fee tank from the main wallet
+    pub fn fund_fee_tank(ctx: Context<FundFeeTank>, amount: u64) -> Result<()> {
+        instructions::fee_tank::fund_handler(ctx, amount)
+    }
+
     /// Update agent limits
     pub fn update_limits(ctx: Context<UpdateLimits>, params: UpdateLimitsParams) -> Result<()> {
         instructions::update_limits::handler(ctx, params)
     }
 
     /// Record agent activity (called automatically by execute_transaction)
-    pub fn record_activity(ctx: Context<RecordActivity>, params: ActivityParams) -> Result<()> {
+    pub fn record_activity<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RecordActivity<'info>>,
+        params: ActivityParams,
+    ) -> Result<()> {
         instructions::record_activity::handler(ctx, params)
     }
-}
 
+    /// Initialize the protocol treasury
+    pub fn init_treasury(ctx: Context<InitTreasury>, params: InitTreasuryParams) -> Result<()> {
+        instructions::init_treasury::handler(ctx, params)
+    }
+
+    /// Approve a treasury credit line for an agent
+    pub fn approve_credit_line(
+        ctx: Context<ApproveCreditLine>,
+        params: CreditLineParams,
+    ) -> Result<()> {
+        instructions::approve_credit_line::handler(ctx, params)
+    }
+
+    /// Borrow SOL from the treasury against an agent's credit line
+    pub fn draw_credit(ctx: Context<DrawCredit>, amount: u64) -> Result<()> {
+        instructions::draw_credit::handler(ctx, amount)
+    }
+
+    /// Repay an agent's credit line from its balance
+    pub fn repay_credit(ctx: Context<RepayCredit>, amount: u64) -> Result<()> {
+        instructions::repay_credit::handler(ctx, amount)
+    }
+
+    /// Configure how many activity records an agent keeps and for how long
+    pub fn update_retention_policy(
+        ctx: Context<UpdateRetentionPolicy>,
+        params: RetentionPolicyParams,
+    ) -> Result<()> {
+        instructions::update_retention_policy::handler(ctx, params)
+    }
+
+    /// Attach a monitoring service key to the main wallet
+    pub fn register_watchtower(
+        ctx: Context<RegisterWatchtower>,
+        params: RegisterWatchtowerParams,
+    ) -> Result<()> {
+        instructions::register_watchtower::handler(ctx, params)
+    }
+
+    /// Detach the main wallet's watchtower
+    pub fn remove_watchtower(ctx: Context<RemoveWatchtower>) -> Result<()> {
+        instructions::remove_watchtower::handler(ctx)
+    }
+
+    /// Record a liveness heartbeat from the watchtower
+    pub fn watchtower_heartbeat(ctx: Context<WatchtowerHeartbeat>) -> Result<()> {
+        instructions::watchtower_heartbeat::handler(ctx)
+    }
+
+    /// Temporarily freeze an agent on behalf of the watchtower
+    pub fn watchtower_freeze_agent(
+        ctx: Context<WatchtowerFreezeAgent>,
+        duration_seconds: i64,
+    ) -> Result<()> {
+        instructions::watchtower_freeze_agent::handler(ctx, duration_seconds)
+    }
+
+    /// Configure the timelock threshold and delay for large withdrawals
+    pub fn set_withdrawal_policy(
+        ctx: Context<SetWithdrawalPolicy>,
+        params: WithdrawalPolicyParams,
+    ) -> Result<()> {
+        instructions::set_withdrawal_policy::handler(ctx, params)
+    }
+
+    /// Start a timelocked withdrawal from agent to main wallet
+    pub fn initiate_withdrawal(ctx: Context<InitiateWithdrawal>, amount: u64) -> Result<()> {
+        instructions::initiate_withdrawal::handler(ctx, amount)
+    }
+
+    /// Complete a timelocked withdrawal once its delay has elapsed
+    pub fn finalize_withdrawal(ctx: Context<FinalizeWithdrawal>) -> Result<()> {
+        instructions::finalize_withdrawal::handler(ctx)
+    }
+
+    /// Cancel a pending withdrawal (main wallet or emergency contact)
+    pub fn cancel_withdrawal(ctx: Context<CancelWithdrawal>) -> Result<()> {
+        instructions::cancel_withdrawal::handler(ctx)
+    }
+
+    /// Restrict agent withdrawals to a verified address book
+    pub fn init_withdrawal_addresses(ctx: Context<InitWithdrawalAddresses>) -> Result<()> {
+        instructions::init_withdrawal_addresses::handler(ctx)
+    }
+
+    /// Add a verified withdrawal address (active after a timelock)
+    pub fn add_withdrawal_address(
+        ctx: Context<AddWithdrawalAddress>,
+        address: Pubkey,
+        label: String,
+    ) -> Result<()> {
+        instructions::add_withdrawal_address::handler(ctx, address, label)
+    }
+
+    /// Remove a verified withdrawal address
+    pub fn remove_withdrawal_address(
+        ctx: Context<RemoveWithdrawalAddress>,
+        address: Pubkey,
+    ) -> Result<()> {
+        instructions::remove_withdrawal_address::handler(ctx, address)
+    }
+
+    /// Register an external policy program for an agent
+    pub fn register_hook(ctx: Context<RegisterHook>, kind: HookKind) -> Result<()> {
+        instructions::register_hook::handler(ctx, kind)
+    }
+
+    /// Remove a policy program from an agent
+    pub fn remove_hook(ctx: Context<RemoveHook>, program_id: Pubkey) -> Result<()> {
+        instructions::remove_hook::handler(ctx, program_id)
+    }
+
+    /// Pause all agent fund movements (authority only)
+    pub fn pause_protocol(ctx: Context<PauseProtocol>) -> Result<()> {
+        instructions::pause_protocol::handler(ctx)
+    }
+
+    /// Resume a paused protocol (authority only)
+    pub fn unpause_protocol(ctx: Context<UnpauseProtocol>) -> Result<()> {
+        instructions::unpause_protocol::handler(ctx)
+    }
+
+    /// Reserve part of the daily budget for critical destinations and categories
+    pub fn configure_priority_lanes(
+        ctx: Context<ConfigurePriorityLanes>,
+        params: PriorityLaneParams,
+    ) -> Result<()> {
+        instructions::configure_priority_lanes::handler(ctx, params)
+    }
+
+    /// Configure per-mint limits for an agent, caching the mint's decimals
+    pub fn set_token_limit(ctx: Context<SetTokenLimit>, params: TokenLimitParams) -> Result<()> {
+        instructions::set_token_limit::handler(ctx, params)
+    }
+
+    /// Create a whitelist for an agent
+    pub fn init_whitelist(
+        ctx: Context<InitWhitelist>,
+        whitelist_type: WhitelistType,
+    ) -> Result<()> {
+        instructions::init_whitelist::handler(ctx, whitelist_type)
+    }
+
+    /// Add an address to an agent whitelist
+    pub fn add_to_whitelist(ctx: Context<AddToWhitelist>, address: Pubkey) -> Result<()> {
+        instructions::add_to_whitelist::handler(ctx, address)
+    }
+
+    /// Remove an address from an agent whitelist
+    pub fn remove_from_whitelist(ctx: Context<RemoveFromWhitelist>, address: Pubkey) -> Result<()> {
+        instructions::remove_from_whitelist::handler(ctx, address)
+    }
+
+    /// Turn enforcement of an agent whitelist on or off
+    pub fn set_whitelist_enabled(ctx: Context<SetWhitelistEnabled>, enabled: bool) -> Result<()> {
+        instructions::set_whitelist_enabled::handler(ctx, enabled)
+    }
+
+    /// List an agent for sale, delegating its cNFT to the listing escrow
+    pub fn list_agent_for_sale<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ListAgentForSale<'info>>,
+        price: u64,
+        leaf: AgentLeafArgs,
+    ) -> Result<()> {
+        instructions::list_agent_for_sale::handler(ctx, price, leaf)
+    }
+
+    /// Buy a listed agent, paying the seller through the listing escrow
+    pub fn buy_agent<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyAgent<'info>>,
+        max_price: u64,
+        leaf: AgentLeafArgs,
+    ) -> Result<()> {
+        instructions::buy_agent::handler(ctx, max_price, leaf)
+    }
+
+    /// Cancel an agent listing and take back the cNFT delegation
+    pub fn cancel_listing<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelListing<'info>>,
+        leaf: AgentLeafArgs,
+    ) -> Result<()> {
+        instructions::cancel_listing::handler(ctx, leaf)
+    }
+
+    /// Register or clear an agent's X25519 reporting key
+    pub fn set_reporting_key(
+        ctx: Context<SetReportingKey>,
+        reporting_key: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::set_reporting_key::handler(ctx, reporting_key)
+    }
+
+    /// Withdraw collected fees from the treasury
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        instructions::withdraw_treasury::handler(ctx, amount)
+    }
+
+    /// Write a chunk of oversized instruction data to the staging buffer
+    pub fn write_execution_buffer(
+        ctx: Context<WriteExecutionBuffer>,
+        params: WriteExecutionBufferParams,
+    ) -> Result<()> {
+        instructions::write_execution_buffer::handler(ctx, params)
+    }
+
+    /// Execute the instruction staged in the buffer, then close it
+    pub fn execute_from_buffer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteFromBuffer<'info>>,
+    ) -> Result<()> {
+        instructions::execute_from_buffer::handler(ctx)
+    }
+
+    /// Set up the wallet's emergency pause and contacts
+    pub fn init_emergency_state(
+        ctx: Context<InitEmergencyState>,
+        emergency_contacts: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::init_emergency_state::handler(ctx, emergency_contacts)
+    }
+
+    /// Pause agent spending in an emergency
+    pub fn emergency_pause(ctx: Context<EmergencyPause>, reason: String) -> Result<()> {
+        instructions::emergency_pause::handler(ctx, reason)
+    }
+
+    /// Lift an emergency pause
+    pub fn emergency_unpause(ctx: Context<EmergencyUnpause>) -> Result<()> {
+        instructions::emergency_unpause::handler(ctx)
+    }
+
+    /// Replace the emergency contacts
+    pub fn update_emergency_contacts(
+        ctx: Context<UpdateEmergencyContacts>,
+        emergency_contacts: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::update_emergency_contacts::handler(ctx, emergency_contacts)
+    }
+
+    /// Rotate the agent's signer key
+    pub fn rotate_agent_signer(
+        ctx: Context<RotateAgentSigner>,
+        new_agent_signer: Pubkey,
+    ) -> Result<()> {
+        instructions::rotate_agent_signer::handler(ctx, new_agent_signer)
+    }
+
+    /// Quote the fee, reserve and remaining allowance for an execution
+    pub fn quote_execution(
+        ctx: Context<QuoteExecution>,
+        params: ExecuteTransactionParams,
+    ) -> Result<ExecutionQuote> {
+        instructions::quote_execution::handler(ctx, params)
+    }
+
+    /// Approve an external protocol to pull funds from an agent
+    pub fn approve_spender(
+        ctx: Context<ApproveSpender>,
+        params: ApproveSpenderParams,
+    ) -> Result<()> {
+        instructions::approve_spender::handler(ctx, params)
+    }
+
+    /// Revoke an external protocol's spend approval
+    pub fn revoke_spender(ctx: Context<RevokeSpender>) -> Result<()> {
+        instructions::revoke_spender::handler(ctx)
+    }
+
+    /// Pull approved funds from an agent; CPI only
+    pub fn spend_on_behalf(ctx: Context<SpendOnBehalf>, amount: u64) -> Result<()> {
+        instructions::spend_on_behalf::handler(ctx, amount)
+    }
+
+    /// Replay an agent's activity chain and report divergence from live state
+    pub fn verify_invariants<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyInvariants<'info>>,
+    ) -> Result<()> {
+        instructions::verify_invariants::handler(ctx)
+    }
+
+    /// Propose a transaction above the agent's approval threshold
+    pub fn propose_transaction(
+        ctx: Context<ProposeTransaction>,
+        params: ExecuteTransactionParams,
+    ) -> Result<()> {
+        instructions::propose_transaction::handler(ctx, params)
+    }
+
+    /// Approve a proposed transaction; main wallet only
+    pub fn approve_transaction(ctx: Context<ApproveTransaction>) -> Result<()> {
+        instructions::approve_transaction::handler(ctx)
+    }
+
+    /// Reject a proposed transaction, returning its rent to the proposer
+    pub fn reject_transaction(ctx: Context<RejectTransaction>) -> Result<()> {
+        instructions::reject_transaction::handler(ctx)
+    }
+
+    /// Withdraw a proposal; proposer only
+    pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+        instructions::cancel_proposal::handler(ctx)
+    }
+
+    /// Move treasury fees into the activity rent pool
+    pub fn fund_rent_pool(ctx: Context<FundRentPool>, amount: u64) -> Result<()> {
+        instructions::fund_rent_pool::handler(ctx, amount)
+    }
+
+    /// Opt an agent in or out of activity rent sponsorship
+    pub fn set_rent_sponsorship(ctx: Context<SetRentSponsorship>, enabled: bool) -> Result<()> {
+        instructions::set_rent_sponsorship::handler(ctx, enabled)
+    }
+
+    /// Whitelist a program the agent may invoke, creating the whitelist if needed
+    pub fn add_program_to_whitelist(
+        ctx: Context<AddProgramToWhitelist>,
+        program: Pubkey,
+    ) -> Result<()> {
+        instructions::add_program_to_whitelist::handler(ctx, program)
+    }
+
+    /// Block an agent from executing during a time window
+    pub fn add_blackout_window(
+        ctx: Context<AddBlackoutWindow>,
+        window: BlackoutWindow,
+    ) -> Result<()> {
+        instructions::add_blackout_window::handler(ctx, window)
+    }
+
+    /// Remove one of an agent's blackout windows
+    pub fn remove_blackout_window(
+        ctx: Context<RemoveBlackoutWindow>,
+        window: BlackoutWindow,
+    ) -> Result<()> {
+        instructions::remove_blackout_window::handler(ctx, window)
+    }
+
+    /// Stake SOL in the protocol to earn agent quotas
+    pub fn stake_protocol(ctx: Context<StakeProtocol>, amount: u64) -> Result<()> {
+        instructions::stake_protocol::handler(ctx, amount)
+    }
+
+    /// Withdraw staked SOL
+    pub fn unstake_protocol(ctx: Context<UnstakeProtocol>, amount: u64) -> Result<()> {
+        instructions::unstake_protocol::handler(ctx, amount)
+    }
+
+    /// Cap the wallet's own agent count and aggregate limit by its staked SOL
+    pub fn set_stake_quotas(ctx: Context<SetStakeQuotas>, params: StakeQuotaParams) -> Result<()> {
+        instructions::set_stake_quotas::handler(ctx, params)
+    }
+
+    /// Set an optional agent setting stored in its extension region
+    pub fn set_agent_extension(ctx: Context<SetAgentExtension>, value: ExtensionValue) -> Result<()> {
+        instructions::set_agent_extension::set_handler(ctx, value)
+    }
+
+    /// Clear an optional agent setting
+    pub fn clear_agent_extension(
+        ctx: Context<SetAgentExtension>,
+        extension_type: ExtensionType,
+    ) -> Result<()> {
+        instructions::set_agent_extension::clear_handler(ctx, extension_type)
+    }
+
+    /// Reactivate a revoked agent, optionally starting a fresh daily window
+    pub fn reactivate_agent(ctx: Context<ReactivateAgent>, reset_daily_counters: bool) -> Result<()> {
+        instructions::reactivate_agent::handler(ctx, reset_daily_counters)
+    }
+
+    /// Require agents to age and prove themselves before spending above a threshold
+    pub fn set_cold_start_policy(ctx: Context<SetColdStartPolicy>, params: ColdStartParams) -> Result<()> {
+        instructions::set_cold_start_policy::handler(ctx, params)
+    }
+
+    /// Suspend an agent for a fixed window without revoking it
+    pub fn freeze_agent(ctx: Context<FreezeAgent>, duration_seconds: i64) -> Result<()> {
+        instructions::freeze_agent::handler(ctx, duration_seconds)
+    }
+
+    /// Lift an agent's freeze early
+    pub fn unfreeze_agent(ctx: Context<UnfreezeAgent>) -> Result<()> {
+        instructions::unfreeze_agent::handler(ctx)
+    }
+
+    /// Close a retired agent and its companion accounts, sweeping lamports to the main wallet
+    pub fn close_agent(ctx: Context<CloseAgent>) -> Result<()> {
+        instructions::close_agent::handler(ctx)
+    }
+
+    /// Escrow a bug bounty reward from the treasury
+    pub fn create_bounty(ctx: Context<CreateBounty>, params: CreateBountyParams) -> Result<()> {
+        instructions::create_bounty::handler(ctx, params)
+    }
+
+    /// Claim an open bounty with a hash of the researcher's report
+    pub fn claim_bounty(ctx: Context<ClaimBounty>, report_hash: [u8; 32]) -> Result<()> {
+        instructions::claim_bounty::handler(ctx, report_hash)
+    }
+
+    /// Pay a claimed bounty to the researcher
+    pub fn approve_bounty(ctx: Context<ApproveBounty>) -> Result<()> {
+        instructions::approve_bounty::handler(ctx)
+    }
+
+    /// Reject a bounty claim and reopen the bounty
+    pub fn reject_bounty_claim(ctx: Context<RejectBountyClaim>) -> Result<()> {
+        instructions::reject_bounty_claim::handler(ctx)
+    }
+
+    /// Cancel an open bounty, returning the reward to the treasury
+    pub fn cancel_bounty(ctx: Context<CancelBounty>) -> Result<()> {
+        instructions::cancel_bounty::handler(ctx)
+    }
+
+    /// Commit a batch of activity records to a merkle root on the agent
+    pub fn merkleize_activity<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MerkleizeActivity<'info>>,
+    ) -> Result<()> {
+        instructions::merkleize_activity::handler(ctx)
+    }
+
+    /// Create the agent's stats account; executions keep it up to date
+    pub fn init_agent_stats(ctx: Context<InitAgentStats>) -> Result<()> {
+        instructions::init_agent_stats::handler(ctx)
+    }
+
+    /// Clone another agent's whitelist or a published template into a new whitelist
+    pub fn copy_whitelist(ctx: Context<CopyWhitelist>, whitelist_type: WhitelistType) -> Result<()> {
+        instructions::copy_whitelist::handler(ctx, whitelist_type)
+    }
+
+    /// Publish an immutable whitelist template agents can copy from
+    pub fn publish_whitelist_template(
+        ctx: Context<PublishWhitelistTemplate>,
+        template_id: u64,
+        whitelist_type: WhitelistType,
+        addresses: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::publish_whitelist_template::handler(ctx, template_id, whitelist_type, addresses)
+    }
+
+    /// Close an activity record past the retention period, returning its rent
+    pub fn close_activity(ctx: Context<CloseActivity>) -> Result<()> {
+        instructions::close_activity::handler(ctx)
+    }
+
+    /// Flag or clear an activity record as disputed, protecting it from closure
+    pub fn set_activity_dispute(ctx: Context<SetActivityDispute>, disputed: bool) -> Result<()> {
+        instructions::set_activity_dispute::handler(ctx, disputed)
+    }
+
+    /// Create the wallet's merkle tree for compressed activity records
+    pub fn init_activity_tree(ctx: Context<InitActivityTree>, params: InitializeParams) -> Result<()> {
+        instructions::init_activity_tree::handler(ctx, params)
+    }
+
+    /// Record agent activity as a leaf in the wallet's activity tree
+    pub fn record_activity_compressed(
+        ctx: Context<RecordActivityCompressed>,
+        params: ActivityParams,
+    ) -> Result<()> {
+        instructions::record_activity_compressed::handler(ctx, params)
+    }
+
+    /// Verify a compressed activity record against the activity tree
+    pub fn verify_activity<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyActivity<'info>>,
+        root: [u8; 32],
+        record: AgentActivity,
+        leaf_index: u32,
+    ) -> Result<()> {
+        instructions::verify_activity::handler(ctx, root, record, leaf_index)
+    }
+
+    /// Acknowledge a crossed soft limit so the agent may spend again
+    pub fn acknowledge_soft_limit(ctx: Context<AcknowledgeSoftLimit>) -> Result<()> {
+        instructions::acknowledge_soft_limit::handler(ctx)
+    }
+
+    /// Execute several SOL transactions atomically, checked against the limits as one
+    pub fn execute_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteBatch<'info>>,
+        legs: Vec<ExecuteTransactionParams>,
+    ) -> Result<()> {
+        instructions::execute_batch::handler(ctx, legs)
+    }
+
+    /// Create the wallet's digest aggregating activity across its agents
+    pub fn init_wallet_digest(ctx: Context<InitWalletDigest>) -> Result<()> {
+        instructions::init_wallet_digest::handler(ctx)
+    }
+
+    /// Fund several agents from the main wallet in one instruction
+    pub fn fund_agents_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FundAgentsBatch<'info>>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        instructions::fund_agents_batch::handler(ctx, amounts)
+    }
+
+    /// Acquire or renew an agent's execution lease for one runtime
+    pub fn acquire_execution_lease(
+        ctx: Context<AcquireExecutionLease>,
+        duration_seconds: i64,
+    ) -> Result<()> {
+        instructions::execution_lease::acquire_handler(ctx, duration_seconds)
+    }
+
+    /// Release an agent's execution lease
+    pub fn release_execution_lease(ctx: Context<ReleaseExecutionLease>) -> Result<()> {
+        instructions::execution_lease::release_handler(ctx)
+    }
+
+    /// Classify a destination into a risk tier in the agent's address book
+    pub fn set_destination_tier(
+        ctx: Context<SetDestinationTier>,
+        destination: Pubkey,
+        tier: Option<RiskTier>,
+    ) -> Result<()> {
+        instructions::set_destination_tier::handler(ctx, destination, tier)
+    }
+
+    /// Set the per-transaction and daily caps of a risk tier
+    pub fn set_tier_limit(
+        ctx: Context<SetTierLimit>,
+        tier: RiskTier,
+        max_per_transaction: u64,
+        daily_limit: u64,
+    ) -> Result<()> {
+        instructions::set_tier_limit::handler(ctx, tier, max_per_transaction, daily_limit)
+    }
+
+    /// Set an agent's spending limits towards one destination
+    pub fn set_destination_limit(
+        ctx: Context<SetDestinationLimit>,
+        destination: Pubkey,
+        max_per_transaction: u64,
+        daily_limit: u64,
+    ) -> Result<()> {
+        instructions::set_destination_limit::set_handler(
+            ctx,
+            destination,
+            max_per_transaction,
+            daily_limit,
+        )
+    }
+
+    /// Remove an agent's spending limits towards one destination
+    pub fn remove_destination_limit(ctx: Context<RemoveDestinationLimit>) -> Result<()> {
+        instructions::set_destination_limit::remove_handler(ctx)
+    }
+
+    /// Block a destination for an agent, even with whitelisting disabled
+    pub fn add_to_blacklist(ctx: Context<AddToBlacklist>, address: Pubkey) -> Result<()> {
+        instructions::add_to_blacklist::handler(ctx, address)
+    }
+
+    /// Unblock a destination for an agent
+    pub fn remove_from_blacklist(ctx: Context<RemoveFromBlacklist>, address: Pubkey) -> Result<()> {
+        instructions::remove_from_blacklist::handler(ctx, address)
+    }
+
+    /// Add or relabel a well-known program in the protocol's registry
+    pub fn register_program(ctx: Context<RegisterProgram>, entry: RegisteredProgram) -> Result<()> {
+        instructions::register_program::register_handler(ctx, entry)
+    }
+
+    /// Remove a program from the protocol's registry
+    pub fn unregister_program(ctx: Context<UnregisterProgram>, id: u16) -> Result<()> {
+        instructions::register_program::unregister_handler(ctx, id)
+    }
+
+    /// Add registered programs to an agent's program whitelist by id
+    pub fn whitelist_registered_programs(
+        ctx: Context<WhitelistRegisteredPrograms>,
+        ids: Vec<u16>,
+    ) -> Result<()> {
+        instructions::whitelist_registered_programs::handler(ctx, ids)
+    }
+
+    /// Restrict agents to programs labeled in the registry
+    pub fn set_program_policy(
+        ctx: Context<SetProgramPolicy>,
+        restrict_unlabeled_programs: bool,
+    ) -> Result<()> {
+        instructions::set_program_policy::handler(ctx, restrict_unlabeled_programs)
+    }
+
+    /// Create a multi-step execution plan for an agent
+    pub fn create_plan(ctx: Context<CreatePlan>, plan_id: u64, steps: Vec<PlanStep>) -> Result<()> {
+        instructions::create_plan::handler(ctx, plan_id, steps)
+    }
+
+    /// Execute the next step of a plan
+    pub fn execute_plan_step<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecutePlanStep<'info>>,
+    ) -> Result<()> {
+        instructions::execute_plan_step::handler(ctx)
+    }
+
+    /// Abort a plan's remaining steps and close it
+    pub fn abort_plan(ctx: Context<AbortPlan>) -> Result<()> {
+        instructions::abort_plan::handler(ctx)
+    }
+
+    /// Allow an agent to call one program up to an amount cap
+    pub fn set_scoped_permission(
+        ctx: Context<SetScopedPermission>,
+        scope: ScopedPermission,
+    ) -> Result<()> {
+        instructions::set_scoped_permission::set_handler(ctx, scope)
+    }
+
+    /// Remove an agent's scoped permission for a program
+    pub fn remove_scoped_permission(
+        ctx: Context<RemoveScopedPermission>,
+        program_id: Pubkey,
+    ) -> Result<()> {
+        instructions::set_scoped_permission::remove_handler(ctx, program_id)
+    }
+
+    /// Emit an agent's configuration and its hash for import elsewhere
+    pub fn export_agent_config(ctx: Context<ExportAgentConfig>) -> Result<()> {
+        instructions::export_agent_config::handler(ctx)
+    }
+
+    /// Apply an exported configuration to an agent
+    pub fn import_agent_config(
+        ctx: Context<ImportAgentConfig>,
+        config: AgentConfig,
+        expected_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::import_agent_config::handler(ctx, config, expected_hash)
+    }
+
+    /// Grant an agent one permission, optionally until an expiry time
+    pub fn grant_permission(
+        ctx: Context<GrantPermission>,
+        permission: AgentPermission,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        instructions::grant_permission::handler(ctx, permission, expires_at)
+    }
+
+    /// Revoke an agent's lapsed timed permissions
+    pub fn expire_permissions(ctx: Context<ExpirePermissions>) -> Result<()> {
+        instructions::expire_permissions::handler(ctx)
+    }
+
+    /// Set the Pyth SOL/USD price account used for USD-denominated limits
+    pub fn set_price_feed(ctx: Context<SetPriceFeed>) -> Result<()> {
+        instructions::set_price_feed::handler(ctx)
+    }
+
+    /// Swap tokens through Jupiter with the agent PDA as authority
+    pub fn execute_swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteSwap<'info>>,
+        route_data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::execute_swap::handler(ctx, route_data)
+    }
+
+    /// Delegate agent SOL to a whitelisted validator from a new stake account
+    pub fn stake_sol(ctx: Context<StakeSol>, stake_id: u64, amount: u64) -> Result<()> {
+        instructions::native_stake::stake_handler(ctx, stake_id, amount)
+    }
+
+    /// Deactivate one of the agent's stake accounts
+    pub fn deactivate_stake(ctx: Context<DeactivateStake>) -> Result<()> {
+        instructions::native_stake::deactivate_handler(ctx)
+    }
+
+    /// Withdraw lamports from an inactive stake account back into the agent
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>, lamports: u64) -> Result<()> {
+        instructions::native_stake::withdraw_handler(ctx, lamports)
+    }
+
+    /// Deposit agent SOL into an SPL stake pool for its liquid staking token
+    pub fn deposit_stake_pool(
+        ctx: Context<DepositStakePool>,
+        amount: u64,
+        min_pool_tokens: u64,
+    ) -> Result<()> {
+        instructions::deposit_stake_pool::handler(ctx, amount, min_pool_tokens)
+    }
+
+    /// Vote on an SPL governance proposal in a whitelisted realm
+    pub fn cast_governance_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CastGovernanceVote<'info>>,
+        vote: GovernanceVote,
+    ) -> Result<()> {
+        instructions::cast_governance_vote::handler(ctx, vote)
+    }
+
+    /// Create the agent's associated token account for a mint, paid from its balance
+    pub fn create_agent_token_account(ctx: Context<CreateAgentTokenAccount>) -> Result<()> {
+        instructions::agent_token_account::create_handler(ctx)
+    }
+
+    /// Close an empty agent token account, returning its rent to the agent
+    pub fn close_agent_token_account(ctx: Context<CloseAgentTokenAccount>) -> Result<()> {
+        instructions::agent_token_account::close_handler(ctx)
+    }
 
+    /// Open an agent's daily snapshot for the current day
+    pub fn crank_snapshot(ctx: Context<CrankSnapshot>, day_index: u64) -> Result<()> {
+        instructions::crank_snapshot::handler(ctx, day_index)
+    }
+
+    /// Initialize the protocol-wide stats singleton
+    pub fn init_global_stats(ctx: Context<InitGlobalStats>) -> Result<()> {
+        instructions::init_global_stats::handler(ctx)
+    }
+
+    /// Update the agent's name and optional metadata
+    pub fn update_agent_metadata(
+        ctx: Context<UpdateAgentMetadata>,
+        params: UpdateAgentMetadataParams,
+    ) -> Result<()> {
+        instructions::update_agent_metadata::handler(ctx, params)
+    }
+
+    /// Grow the agent account and stamp the current layout version
+    pub fn resize_agent_account(ctx: Context<ResizeAgentAccount>, new_size: u32) -> Result<()> {
+        instructions::resize_agent_account::handler(ctx, new_size)
+    }
+
+    /// Upgrade an agent, stats or rate limit account to the current layout
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        instructions::migrate_account::handler(ctx)
+    }
+
+    /// Set an agent's recurring allowance and escrow lamports for it
+    pub fn set_allowance(
+        ctx: Context<SetAllowance>,
+        amount_per_period: u64,
+        period_seconds: i64,
+        deposit: u64,
+    ) -> Result<()> {
+        instructions::allowance::set_handler(ctx, amount_per_period, period_seconds, deposit)
+    }
+
+    /// Pay an agent's allowance for the current period from escrow
+    pub fn claim_allowance(ctx: Context<ClaimAllowance>) -> Result<()> {
+        instructions::allowance::claim_handler(ctx)
+    }
+
+    /// Close an agent's allowance, returning the escrow to the main wallet
+    pub fn close_allowance(ctx: Context<CloseAllowance>) -> Result<()> {
+        instructions::allowance::close_handler(ctx)
+    }
+
+    /// Open a funding stream to an agent, escrowing its full amount
+    pub fn create_funding_stream(
+        ctx: Context<CreateFundingStream>,
+        params: FundingStreamParams,
+    ) -> Result<()> {
+        instructions::funding_stream::create_handler(ctx, params)
+    }
+
+    /// Move an agent's vested stream funds into the agent
+    pub fn withdraw_streamed(ctx: Context<WithdrawStreamed>) -> Result<()> {
+        instructions::funding_stream::withdraw_handler(ctx)
+    }
+
+    /// Cancel a funding stream, settling vested funds with the agent
+    pub fn cancel_funding_stream(ctx: Context<CancelFundingStream>) -> Result<()> {
+        instructions::funding_stream::cancel_handler(ctx)
+    }
+
+    /// Turn escrow funding for an agent on or off
+    pub fn set_escrow_funding(ctx: Context<SetEscrowFunding>, enabled: bool) -> Result<()> {
+        instructions::set_escrow_funding::handler(ctx, enabled)
+    }
+
+    /// Approve an agent's funding request, paying it from the main wallet
+    pub fn approve_funding_request(ctx: Context<ApproveFundingRequest>) -> Result<()> {
+        instructions::approve_funding_request::handler(ctx)
+    }
+
+    /// Deny an agent's funding request
+    pub fn deny_funding_request(ctx: Context<DenyFundingRequest>) -> Result<()> {
+        instructions::deny_funding_request::handler(ctx)
+    }
+
+    /// Set an agent's inactivity timeout, action and keeper reward
+    pub fn set_inactivity_policy(
+        ctx: Context<SetInactivityPolicy>,
+        params: InactivityPolicyParams,
+    ) -> Result<()> {
+        instructions::set_inactivity_policy::handler(ctx, params)
+    }
+
+    /// Revoke or freeze an agent inactive past its timeout
+    pub fn enforce_inactivity(ctx: Context<EnforceInactivity>) -> Result<()> {
+        instructions::enforce_inactivity::handler(ctx)
+    }
+
+    /// Replace an agent's co-signer set
+    pub fn set_co_signers(ctx: Context<SetCoSigners>, co_signers: Vec<Pubkey>) -> Result<()> {
+        instructions::set_co_signers::handler(ctx, co_signers)
+    }
+
+    /// Replace an agent's co-signed amount bands
+    pub fn set_amount_bands(ctx: Context<SetAmountBands>, bands: Vec<AmountBand>) -> Result<()> {
+        instructions::set_amount_bands::handler(ctx, bands)
+    }
+
+    /// Co-sign a proposed transaction
+    pub fn approve_band_transaction(ctx: Context<ApproveBandTransaction>) -> Result<()> {
+        instructions::approve_band_transaction::handler(ctx)
+    }
+
+    /// Open a short-lived session key that may execute for an agent
+    pub fn create_session_key(
+        ctx: Context<CreateSessionKey>,
+        params: CreateSessionKeyParams,
+    ) -> Result<()> {
+        instructions::session_key::create_handler(ctx, params)
+    }
+
+    /// Close an agent's session key
+    pub fn revoke_session_key(ctx: Context<RevokeSessionKey>) -> Result<()> {
+        instructions::session_key::revoke_handler(ctx)
+    }
+
+    /// Settle a transfer signed off-chain by the agent signer, submitted by any relayer
+    pub fn execute_signed_intent<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteSignedIntent<'info>>,
+        intent: SignedIntent,
+    ) -> Result<()> {
+        instructions::execute_signed_intent::handler(ctx, intent)
+    }
+
+    /// Set the relayers allowed to pay fees for the wallet's agents
+    pub fn set_relayer_registry(
+        ctx: Context<SetRelayerRegistry>,
+        relayers: Vec<Pubkey>,
+        reimbursement_lamports: u64,
+    ) -> Result<()> {
+        instructions::set_relayer_registry::handler(ctx, relayers, reimbursement_lamports)
+    }
+
+    /// Restrict an agent to daily active hours on chosen weekdays
+    pub fn set_active_hours(ctx: Context<SetActiveHours>, params: ActiveHoursParams) -> Result<()> {
+        instructions::set_active_hours::handler(ctx, params)
+    }
+
+    /// Set the daily budget shared by all of a wallet's agents
+    pub fn set_wallet_budget(ctx: Context<SetWalletBudget>, daily_cap: u64) -> Result<()> {
+        instructions::set_wallet_budget::handler(ctx, daily_cap)
+    }
+
+    /// Set the trust tier ceilings and promotion requirements
+    pub fn set_tier_config(
+        ctx: Context<SetTierConfig>,
+        tiers: [TierConfig; 4],
+    ) -> Result<()> {
+        instructions::set_tier_config::handler(ctx, tiers)
+    }
+
+    /// Raise an agent one trust tier
+    pub fn promote_agent(ctx: Context<PromoteAgent>) -> Result<()> {
+        instructions::promote_agent::handler(ctx)
+    }
+
+    /// Set the anomaly rules that freeze an agent
+    pub fn set_anomaly_rules(ctx: Context<SetAnomalyRules>, rules: AnomalyRules) -> Result<()> {
+        instructions::set_anomaly_rules::handler(ctx, rules)
+    }
+
+    /// Set the delay before permission and limit increases take effect
+    pub fn set_change_delay(ctx: Context<SetChangeDelay>, delay_seconds: i64) -> Result<()> {
+        instructions::set_change_delay::handler(ctx, delay_seconds)
+    }
+
+    /// Apply permission and limit changes staged behind the change delay
+    pub fn apply_pending_changes(ctx: Context<ApplyPendingChanges>) -> Result<()> {
+        instructions::apply_pending_changes::handler(ctx)
+    }
+
+    /// Permanently prevent increases to an agent's permissions and limits
+    pub fn lock_agent_config(ctx: Context<LockAgentConfig>) -> Result<()> {
+        instructions::lock_agent_config::handler(ctx)
+    }
+
+    /// Cap the limits any agent may be given
+    pub fn set_limit_caps(ctx: Context<SetLimitCaps>, params: LimitCapParams) -> Result<()> {
+        instructions::set_limit_caps::handler(ctx, params)
+    }
+
+    /// Change protocol settings after initialize
+    pub fn update_protocol_config(
+        ctx: Context<UpdateProtocolConfig>,
+        params: UpdateProtocolConfigParams,
+    ) -> Result<()> {
+        instructions::update_protocol_config::handler(ctx, params)
+    }
+
+    /// Queue a merkle tree for new agents to roll onto
+    pub fn add_merkle_tree(ctx: Context<AddMerkleTree>) -> Result<()> {
+        instructions::add_merkle_tree::handler(ctx)
+    }
+
+    /// Prove an agent's cNFT leaf matches its on-chain account
+    pub fn verify_agent_leaf<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyAgentLeaf<'info>>,
+        root: [u8; 32],
+    ) -> Result<()> {
+        instructions::verify_agent_leaf::handler(ctx, root)
+    }
+
+    /// Update an agent's cNFT metadata to match its current permissions and limits
+    pub fn sync_agent_metadata<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SyncAgentMetadata<'info>>,
+        leaf: AgentLeafArgs,
+        current_name: String,
+        current_uri: String,
+    ) -> Result<()> {
+        instructions::sync_agent_metadata::handler(ctx, leaf, current_name, current_uri)
+    }
+}
@@ -0,0 +1,46 @@
+use anchor_lang::solana_program::hash::hashv;
+
+/// Leaf committing to an activity record's chain hash
+pub fn activity_leaf(chain_hash: &[u8; 32]) -> [u8; 32] {
+    hashv(&[&[0u8], chain_hash]).to_bytes()
+}
+
+fn node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    hashv(&[&[1u8], left, right]).to_bytes()
+}
+
+/// Merkle root over `leaves`; an odd node at any level is paired with itself
+pub fn activity_merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    if leaves.is_empty() {
+        return [0; 32];
+    }
+
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| node(&pair[0], pair.get(1).unwrap_or(&pair[0])))
+            .collect();
+    }
+
+    level[0]
+}
+
+/// Check that `leaf` sits at `index` under `root`, given its sibling hashes
+/// from the bottom of the tree up. Runs off-chain for auditors and
+/// counterparties holding a root from an `ActivityMerkleized` event.
+#[allow(clippy::manual_is_multiple_of)]
+pub fn verify_activity_proof(root: &[u8; 32], leaf: &[u8; 32], index: u64, proof: &[[u8; 32]]) -> bool {
+    let mut hash = *leaf;
+    let mut index = index;
+    for sibling in proof {
+        hash = if index % 2 == 0 {
+            node(&hash, sibling)
+        } else {
+            node(sibling, &hash)
+        };
+        index /= 2;
+    }
+
+    index == 0 && hash == *root
+}
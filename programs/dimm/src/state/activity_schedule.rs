@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+
+/// When an agent is allowed to execute
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ActivitySchedule {
+    /// Agent this schedule belongs to
+    pub agent: Pubkey,
+    
+    /// Windows during which the agent cannot execute, whatever its other settings
+    pub blackouts: Vec<BlackoutWindow>,
+    
+    /// Last updated timestamp
+    pub last_updated: i64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+    
+    /// Reserved space
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
+    pub reserved: [u8; 64],
+}
+
+impl ActivitySchedule {
+    pub const MAX_BLACKOUTS: usize = 16;
+    
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        4 + (BlackoutWindow::LEN * Self::MAX_BLACKOUTS) + // blackouts
+        8 +  // last_updated
+        1 +  // bump
+        64;  // reserved
+
+    /// Check if any blackout window covers `current_time`
+    pub fn is_blacked_out(&self, current_time: i64) -> bool {
+        self.blackouts.iter().any(|window| window.contains(current_time))
+    }
+
+    /// Drop windows that have already ended
+    pub fn prune_expired(&mut self, current_time: i64) {
+        self.blackouts.retain(|window| window.end > current_time);
+    }
+
+    /// Add a blackout window, pruning ended ones to make room
+    pub fn add_blackout(&mut self, window: BlackoutWindow, current_time: i64) -> Result<()> {
+        require!(
+            window.start < window.end && window.end > current_time,
+            crate::errors::DimmError::InvalidBlackoutWindow
+        );
+
+        self.prune_expired(current_time);
+        require!(
+            self.blackouts.len() < Self::MAX_BLACKOUTS,
+            crate::errors::DimmError::TooManyBlackouts
+        );
+
+        self.blackouts.push(window);
+        Ok(())
+    }
+
+    /// Load the agent's schedule if it has one.
+    /// Returns `None` when the PDA has not been initialized.
+    pub fn load(schedule: &AccountInfo) -> Result<Option<ActivitySchedule>> {
+        if schedule.data_is_empty() {
+            return Ok(None);
+        }
+
+        require_keys_eq!(*schedule.owner, crate::ID, crate::errors::DimmError::Unauthorized);
+        let data = schedule.try_borrow_data()?;
+        Ok(Some(ActivitySchedule::try_deserialize(&mut &data[..])?))
+    }
+
+    /// Fail if the agent's schedule blocks execution right now
+    pub fn require_active(schedule: &AccountInfo, current_time: i64) -> Result<()> {
+        if let Some(schedule) = Self::load(schedule)? {
+            require!(
+                !schedule.is_blacked_out(current_time),
+                crate::errors::DimmError::AgentBlackedOut
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Half-open interval `[start, end)` of unix timestamps
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlackoutWindow {
+    /// Window start (inclusive)
+    pub start: i64,
+    
+    /// Window end (exclusive)
+    pub end: i64,
+}
+
+impl BlackoutWindow {
+    pub const LEN: usize = 8 + 8;
+
+    /// Check if the window covers `current_time`
+    pub fn contains(&self, current_time: i64) -> bool {
+        current_time >= self.start && current_time < self.end
+    }
+}
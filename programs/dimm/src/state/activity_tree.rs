@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+/// Wallet's concurrent merkle tree of compressed activity records. The PDA
+/// is the tree's authority.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ActivityTree {
+    /// Main wallet whose agents log into the tree
+    pub main_wallet: Pubkey,
+    
+    /// Concurrent merkle tree account
+    pub merkle_tree: Pubkey,
+    
+    /// Leaves appended so far, i.e. the index of the next leaf
+    pub leaf_count: u64,
+    
+    /// Leaves the tree can hold (2^max_depth)
+    pub capacity: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+    
+    /// Reserved space
+    pub reserved: [u8; 32],
+}
+
+impl ActivityTree {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // main_wallet
+        32 + // merkle_tree
+        8 +  // leaf_count
+        8 +  // capacity
+        1 +  // bump
+        32;  // reserved
+
+    /// Reserve the next leaf index
+    pub fn next_leaf(&mut self) -> Result<u32> {
+        require!(
+            self.leaf_count < self.capacity,
+            crate::errors::DimmError::ActivityTreeFull
+        );
+        let index = u32::try_from(self.leaf_count)
+            .map_err(|_| error!(crate::errors::DimmError::NumericalOverflow))?;
+        self.leaf_count += 1;
+        Ok(index)
+    }
+}
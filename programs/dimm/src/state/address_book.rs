@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use crate::constants::DAILY_WINDOW_SECONDS;
+
+/// Agent's counterparties classified into risk tiers, each with its own
+/// per-transaction and daily caps on top of the agent's limits
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AddressBook {
+    /// Agent this address book belongs to
+    pub agent: Pubkey,
+    
+    /// Classified destinations, kept sorted by address for binary search
+    pub entries: Vec<AddressBookEntry>,
+    
+    /// Caps and daily spend per tier, indexed by `RiskTier`
+    pub tier_limits: [TierLimit; 3],
+    
+    /// Timestamp of last daily reset of the tier spends
+    pub last_daily_reset: i64,
+    
+    /// Last updated timestamp
+    pub last_updated: i64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+    
+    /// Reserved space
+    pub reserved: [u8; 32],
+}
+
+impl AddressBook {
+    pub const MAX_ENTRIES: usize = 64;
+    
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        4 + (AddressBookEntry::LEN * Self::MAX_ENTRIES) + // entries
+        (TierLimit::LEN * 3) + // tier_limits
+        8 +  // last_daily_reset
+        8 +  // last_updated
+        1 +  // bump
+        32;  // reserved
+
+    /// Load the agent's address book; `None` if it was never initialized
+    pub fn load(address_book: &AccountInfo) -> Result<Option<AddressBook>> {
+        if address_book.data_is_empty() {
+            return Ok(None);
+        }
+
+        require_keys_eq!(*address_book.owner, crate::ID, crate::errors::DimmError::Unauthorized);
+        let data = address_book.try_borrow_data()?;
+        Ok(Some(AddressBook::try_deserialize(&mut &data[..])?))
+    }
+
+    /// Write the address book back after recording spends
+    pub fn store(&self, address_book: &AccountInfo) -> Result<()> {
+        let mut data = address_book.try_borrow_mut_data()?;
+        self.try_serialize(&mut &mut data[..])
+    }
+
+    /// Tier of a destination; unlisted counterparties are high-risk
+    pub fn tier_of(&self, destination: &Pubkey) -> RiskTier {
+        self.entries
+            .binary_search_by(|entry| entry.address.cmp(destination))
+            .map(|index| self.entries[index].tier)
+            .unwrap_or(RiskTier::HighRisk)
+    }
+
+    /// Classify a destination, or remove it with `None`, keeping entries sorted
+    pub fn set_tier(&mut self, address: Pubkey, tier: Option<RiskTier>) -> Result<()> {
+        match (self.entries.binary_search_by(|entry| entry.address.cmp(&address)), tier) {
+            (Ok(index), Some(tier)) => self.entries[index].tier = tier,
+            (Ok(index), None) => {
+                self.entries.remove(index);
+            }
+            (Err(index), Some(tier)) => {
+                require!(
+                    self.entries.len() < Self::MAX_ENTRIES,
+                    crate::errors::DimmError::AddressBookFull
+                );
+                self.entries.insert(index, AddressBookEntry { address, tier });
+            }
+            (Err(_), None) => {}
+        }
+        Ok(())
+    }
+
+    /// Check a spend towards `destination` against its tier's caps and
+    /// record it against the tier's daily spend
+    pub fn record_spend(&mut self, destination: &Pubkey, amount: u64, current_time: i64) -> Result<()> {
+        let time_since_reset = current_time
+            .checked_sub(self.last_daily_reset)
+            .ok_or(crate::errors::DimmError::InvalidActivityWindow)?;
+        if time_since_reset >= DAILY_WINDOW_SECONDS {
+            for limit in self.tier_limits.iter_mut() {
+                limit.spent_today = 0;
+            }
+            self.last_daily_reset = current_time;
+        }
+
+        let limit = &mut self.tier_limits[self.tier_of(destination) as usize];
+        let new_daily_total = limit
+            .spent_today
+            .checked_add(amount)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+
+        require!(
+            limit.max_per_transaction == 0 || amount <= limit.max_per_transaction,
+            crate::errors::DimmError::ExceedsTierTransactionLimit
+        );
+        require!(
+            limit.daily_limit == 0 || new_daily_total <= limit.daily_limit,
+            crate::errors::DimmError::ExceedsTierDailyLimit
+        );
+
+        limit.spent_today = new_daily_total;
+        Ok(())
+    }
+}
+
+/// A classified counterparty
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AddressBookEntry {
+    pub address: Pubkey,
+    pub tier: RiskTier,
+}
+
+impl AddressBookEntry {
+    pub const LEN: usize = 32 + 1;
+}
+
+/// How much an owner trusts a counterparty
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RiskTier {
+    /// Known counterparties, e.g. the owner's own exchange accounts
+    Trusted,
+    
+    /// Counterparties the owner has vetted
+    Normal,
+    
+    /// Explicitly risky or unlisted counterparties
+    HighRisk,
+}
+
+/// Caps of one risk tier (0 = no cap beyond the agent's own limits)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TierLimit {
+    /// Maximum lamports per transaction
+    pub max_per_transaction: u64,
+    
+    /// Daily limit (in lamports)
+    pub daily_limit: u64,
+    
+    /// Lamports spent today towards the tier
+    pub spent_today: u64,
+}
+
+impl TierLimit {
+    pub const LEN: usize = 8 + 8 + 8;
+}
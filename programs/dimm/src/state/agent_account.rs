@@ -1,8 +1,11 @@
 use anchor_lang::prelude::*;
 use crate::constants::*;
+use crate::state::extensions::{self, ExtensionType, ExtensionValue};
+use crate::state::{AgentStats, AmountBand, PermissionSet, SolUsdPrice, SpendPolicy, TimedPermission};
 
 /// Agent SubAccount state
 #[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AgentAccount {
     /// Main wallet that owns this agent
     pub main_wallet: Pubkey,
@@ -13,8 +16,11 @@ pub struct AgentAccount {
     /// Agent name
     pub name: String,
     
+    /// Key the agent signs with to execute on its own, within its limits
+    pub agent_signer: Pubkey,
+    
     /// Current permissions granted to this agent
-    pub permissions: Vec<AgentPermission>,
+    pub permissions: PermissionSet,
     
     /// Maximum SOL per transaction (in lamports)
     pub max_sol_per_transaction: u64,
@@ -46,10 +52,230 @@ pub struct AgentAccount {
     /// Merkle tree leaf index for cNFT
     pub leaf_index: u32,
     
+    /// Merkle tree holding the agent's cNFT
+    pub merkle_tree: Pubkey,
+    
+    /// Daily allowance for fee-only maintenance spends, outside the main budget
+    pub maintenance_allowance: u64,
+    
+    /// Maintenance SOL spent today (in lamports)
+    pub maintenance_spent_today: u64,
+    
+    /// Number of activity records written for this agent
+    pub activity_sequence: u64,
+    
+    /// Head of the hash chain over every activity record written
+    pub activity_hash: [u8; 32],
+    
+    /// Activity records kept before the oldest slot is overwritten (0 = unlimited)
+    pub retention_max_records: u32,
+    
+    /// Age in seconds after which activity records may be pruned (0 = unlimited)
+    pub retention_max_age: i64,
+    
+    /// Agent cannot execute until this timestamp (0 = not frozen)
+    pub frozen_until: i64,
+    
+    /// Withdrawals above this amount must go through the timelock (0 = disabled)
+    pub withdrawal_threshold: u64,
+    
+    /// Delay between initiating and finalizing a large withdrawal (in seconds)
+    pub withdrawal_delay: i64,
+    
+    /// Slice of the daily limit only critical spends may consume (in lamports)
+    pub critical_reserve: u64,
+    
+    /// Lamports held for network fees, kept out of the spend budget
+    pub fee_tank: u64,
+    
+    /// Most the fee tank may hold (0 = no fee tank)
+    pub fee_tank_cap: u64,
+    
+    /// Network fees paid out of the fee tank
+    pub fee_tank_spent: u64,
+    
+    /// Whether transfers must target a whitelisted destination
+    pub require_destination_whitelist: bool,
+    
+    /// X25519 public key activity payloads are sealed to
+    pub reporting_key: Option<[u8; 32]>,
+    
+    /// Transactions above this amount need main wallet approval (0 = disabled)
+    pub approval_threshold: u64,
+    
+    /// Number of transactions proposed for approval
+    pub proposal_count: u64,
+    
+    /// Whether the wallet's rent pool pays for this agent's activity records
+    pub rent_sponsored: bool,
+    
+    /// Trial limits apply until this timestamp, whatever the configured limits
+    pub trial_ends_at: i64,
+    
+    /// Daily allowance for burns and donations, outside the main budget
+    pub donation_allowance: u64,
+    
+    /// Burned or donated SOL today (in lamports)
+    pub donation_spent_today: u64,
+    
+    /// When the agent was last revoked (0 = never)
+    pub revoked_at: i64,
+    
+    /// Root over the most recent batch of merkleized activity records
+    pub activity_root: [u8; 32],
+    
+    /// Sequence of the next activity record to merkleize
+    pub merkleized_through: u64,
+    
+    /// Chain hash of the last merkleized activity record
+    pub merkleized_head: [u8; 32],
+    
+    /// Sequence of the latest execution receipt (0 = none yet)
+    pub receipt_sequence: u64,
+    
+    /// Spending past this much in a day needs acknowledgment (0 = disabled)
+    pub soft_daily_limit: u64,
+    
+    /// Whether a crossed soft limit awaits the main wallet's acknowledgment
+    pub soft_limit_pending_ack: bool,
+    
+    /// Key holding the execution lease (default = none)
+    pub lease_holder: Pubkey,
+    
+    /// When the execution lease lapses
+    pub lease_expires_at: i64,
+    
+    /// Executions moving at least this much need the lease (0 = disabled)
+    pub lease_threshold: u64,
+    
+    /// Permissions that lapse at a set time, at most one entry per permission
+    pub timed_permissions: Vec<TimedPermission>,
+    
+    /// Daily limit in USD cents, priced by the protocol's oracle (0 = none)
+    pub daily_limit_usd: u64,
+    
+    /// Max USD cents per transaction (0 = none)
+    pub max_usd_per_transaction: u64,
+    
+    /// USD cents spent in the current daily window
+    pub usd_spent_today: u64,
+    
+    /// Max slippage the agent may swap with (in basis points)
+    pub max_slippage_bps: u16,
+    
+    /// Token accounts opened through `create_agent_token_account` and not yet closed
+    pub open_token_accounts: u8,
+    
+    /// Epoch of the agent's last execution, for counting active agents
+    pub last_active_epoch: u64,
+    
+    /// Free-form description of the agent
+    pub description: Option<String>,
+    
+    /// URI of off-chain agent metadata
+    pub metadata_uri: Option<String>,
+    
+    /// Identifier of the model driving the agent
+    pub model_id: Option<String>,
+    
+    /// Layout version, raised by `resize_agent_account` and `migrate_account`
+    pub schema_version: u8,
+    
+    /// Whether funding lands in the agent's escrow rather than the agent itself
+    pub escrow_funding: bool,
+    
+    /// Funding requests made so far; the next request's identifier
+    pub funding_request_count: u64,
+    
+    /// Seconds without activity before anyone may enforce the inactivity action (0 = disabled)
+    pub inactivity_timeout_seconds: i64,
+    
+    /// What `enforce_inactivity` does to an inactive agent
+    pub inactivity_action: InactivityAction,
+    
+    /// Lamports paid from the agent to whoever enforces inactivity
+    pub inactivity_keeper_reward: u64,
+    
+    /// Last time the main wallet reset the inactivity clock
+    pub inactivity_reset_at: i64,
+    
+    /// Amount bands needing co-signer approval, ascending by `above`
+    pub amount_bands: Vec<AmountBand>,
+    
+    /// Next nonce a signed intent must carry
+    pub intent_nonce: u64,
+    
+    /// Executions so far; an execution may require its exact value
+    pub nonce: u64,
+    
+    /// Start of the daily active window (UTC seconds of day)
+    pub active_hours_start: u32,
+    
+    /// End of the daily active window (UTC seconds of day; equal to start = all day)
+    pub active_hours_end: u32,
+    
+    /// Weekdays the agent may execute on, bit 0 = Monday
+    pub active_weekdays: u8,
+    
+    /// Most the agent may spend per hourly window (0 = no hourly limit)
+    pub max_sol_per_hour: u64,
+    
+    /// Amount spent in the current hourly window
+    pub spent_this_hour: u64,
+    
+    /// Start of the current hourly window
+    pub last_hourly_reset: i64,
+    
+    /// Trust tier whose ceilings cap the agent's limits
+    pub trust_level: u8,
+    
+    /// Rules that freeze the agent on an anomalous execution
+    pub anomaly_rules: AnomalyRules,
+    
+    /// Failures reported in the current hourly window
+    pub failures_this_hour: u16,
+    
+    /// Start of the current failure window
+    pub failure_window_start: i64,
+    
+    /// Delay before increases to permissions and limits take effect (0 = immediate)
+    pub change_delay_seconds: i64,
+    
+    /// Permission bits staged to be granted once the change delay passes
+    pub pending_permissions: u64,
+    
+    /// Limit increases staged until the change delay passes
+    pub pending_limits: Option<UpdateLimitsParams>,
+    
+    /// Lowered change delay staged until the current delay passes
+    pub pending_change_delay: Option<i64>,
+    
+    /// When staged changes can be applied (0 = nothing staged)
+    pub pending_effective_at: i64,
+    
+    /// Whether permissions and limits can no longer be increased
+    pub config_locked: bool,
+    
+    /// Whether the agent's cNFT has been burned
+    pub cnft_burned: bool,
+    
+    /// Withdrawal policy loosening waiting out the delays
+    pub pending_withdrawal_policy: Option<WithdrawalPolicyParams>,
+    
+    /// Direct withdrawals in the current window, counted against the threshold
+    pub withdrawn_in_window: u64,
+    
+    /// Start of the current direct-withdrawal window
+    pub withdrawal_window_start: i64,
+    
+    /// Slot the fee tank last refunded a signature fee in
+    pub fee_tank_refund_slot: u64,
+    
     /// Bump seed for PDA
     pub bump: u8,
     
-    /// Reserved space for future upgrades
+    /// Reserved space; optional settings live here as extensions
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
     pub reserved: [u8; 128],
 }
 
@@ -58,7 +284,8 @@ impl AgentAccount {
         32 + // main_wallet
         8 +  // agent_id
         4 + MAX_AGENT_NAME_LENGTH + // name (String with length prefix)
-        4 + (1 * 20) + // permissions (Vec with max 20 permissions)
+        32 + // agent_signer
+        4 + 20 + // permissions (legacy Vec of up to 20; the bitmask takes 4 + 8)
         8 +  // max_sol_per_transaction
         8 +  // daily_limit
         8 +  // spent_today
@@ -69,36 +296,738 @@ impl AgentAccount {
         8 +  // created_at
         8 +  // last_used_at
         4 +  // leaf_index
+        32 + // merkle_tree
+        8 +  // maintenance_allowance
+        8 +  // maintenance_spent_today
+        8 +  // activity_sequence
+        32 + // activity_hash
+        4 +  // retention_max_records
+        8 +  // retention_max_age
+        8 +  // frozen_until
+        8 +  // withdrawal_threshold
+        8 +  // withdrawal_delay
+        8 +  // critical_reserve
+        8 +  // fee_tank
+        8 +  // fee_tank_cap
+        8 +  // fee_tank_spent
+        1 +  // require_destination_whitelist
+        1 + 32 + // reporting_key (Option<[u8; 32]>)
+        8 +  // approval_threshold
+        8 +  // proposal_count
+        1 +  // rent_sponsored
+        8 +  // trial_ends_at
+        8 +  // donation_allowance
+        8 +  // donation_spent_today
+        8 +  // revoked_at
+        32 + // activity_root
+        8 +  // merkleized_through
+        32 + // merkleized_head
+        8 +  // receipt_sequence
+        8 +  // soft_daily_limit
+        1 +  // soft_limit_pending_ack
+        32 + // lease_holder
+        8 +  // lease_expires_at
+        8 +  // lease_threshold
+        4 + (TimedPermission::LEN * TimedPermission::MAX_ENTRIES) + // timed_permissions
+        8 +  // daily_limit_usd
+        8 +  // max_usd_per_transaction
+        8 +  // usd_spent_today
+        2 +  // max_slippage_bps
+        1 +  // open_token_accounts
+        8 +  // last_active_epoch
+        1 +  // description (None; grown by update_agent_metadata)
+        1 +  // metadata_uri (None; grown by update_agent_metadata)
+        1 +  // model_id (None; grown by update_agent_metadata)
+        1 +  // schema_version
+        1 +  // escrow_funding
+        8 +  // funding_request_count
+        8 +  // inactivity_timeout_seconds
+        1 +  // inactivity_action
+        8 +  // inactivity_keeper_reward
+        8 +  // inactivity_reset_at
+        4 + (AmountBand::LEN * MAX_AMOUNT_BANDS) + // amount_bands
+        8 +  // intent_nonce
+        8 +  // nonce
+        4 +  // active_hours_start
+        4 +  // active_hours_end
+        1 +  // active_weekdays
+        8 +  // max_sol_per_hour
+        8 +  // spent_this_hour
+        8 +  // last_hourly_reset
+        1 +  // trust_level
+        AnomalyRules::LEN + // anomaly_rules
+        2 +  // failures_this_hour
+        8 +  // failure_window_start
+        8 +  // change_delay_seconds
+        8 +  // pending_permissions
+        1 + UpdateLimitsParams::LEN + // pending_limits
+        1 + 8 + // pending_change_delay
+        8 +  // pending_effective_at
+        1 +  // config_locked
+        1 +  // cnft_burned
+        1 + WithdrawalPolicyParams::LEN + // pending_withdrawal_policy
+        8 +  // withdrawn_in_window
+        8 +  // withdrawal_window_start
+        8 +  // fee_tank_refund_slot
         1 +  // bump
         128; // reserved
 
+    /// Bytes the optional metadata takes beyond the `None` counted in `LEN`
+    fn metadata_bytes(
+        description: &Option<String>,
+        metadata_uri: &Option<String>,
+        model_id: &Option<String>,
+    ) -> usize {
+        let field = |value: &Option<String>| value.as_ref().map_or(0, |v| 4 + v.len());
+        field(description) + field(metadata_uri) + field(model_id)
+    }
+
+    /// Size of an account currently `current_space` bytes once its metadata
+    /// is replaced with `params`; space added by `resize_agent_account` is kept
+    pub fn space_with_metadata(&self, current_space: usize, params: &UpdateAgentMetadataParams) -> usize {
+        current_space
+            .saturating_sub(Self::metadata_bytes(&self.description, &self.metadata_uri, &self.model_id))
+            + Self::metadata_bytes(&params.description, &params.metadata_uri, &params.model_id)
+    }
+
+    /// Activity PDA index for the next record under the retention policy
+    pub fn next_activity_slot(&self) -> u64 {
+        if self.retention_max_records == 0 {
+            self.activity_sequence
+        } else {
+            self.activity_sequence % self.retention_max_records as u64
+        }
+    }
+
+    /// Check if an activity record has outlived the retention policy
+    pub fn is_activity_expired(&self, recorded_at: i64, current_time: i64) -> bool {
+        if self.retention_max_age == 0 {
+            return false;
+        }
+        current_time.saturating_sub(recorded_at) > self.retention_max_age
+    }
+
+    /// Timestamp after which the agent counts as inactive, if a timeout is set
+    pub fn inactive_after(&self) -> Option<i64> {
+        if self.inactivity_timeout_seconds == 0 {
+            return None;
+        }
+        Some(
+            self.last_used_at
+                .max(self.inactivity_reset_at)
+                .saturating_add(self.inactivity_timeout_seconds),
+        )
+    }
+
+    /// Check if a withdrawal must go through initiate/finalize. The threshold
+    /// caps direct withdrawals over a daily window, so a large pull can't be
+    /// split into calls under it.
+    pub fn requires_timelocked_withdrawal(&self, amount: u64, current_time: i64) -> bool {
+        if self.withdrawal_threshold == 0 {
+            return false;
+        }
+
+        let withdrawn = if self.withdrawal_window_elapsed(current_time) {
+            0
+        } else {
+            self.withdrawn_in_window
+        };
+        withdrawn.saturating_add(amount) > self.withdrawal_threshold
+    }
+
+    /// Count a direct withdrawal towards the threshold's window
+    pub fn record_direct_withdrawal(&mut self, amount: u64, current_time: i64) -> Result<()> {
+        if self.withdrawal_window_elapsed(current_time) {
+            self.withdrawal_window_start = current_time;
+            self.withdrawn_in_window = 0;
+        }
+
+        self.withdrawn_in_window = self
+            .withdrawn_in_window
+            .checked_add(amount)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+        Ok(())
+    }
+
+    fn withdrawal_window_elapsed(&self, current_time: i64) -> bool {
+        current_time.saturating_sub(self.withdrawal_window_start) >= DAILY_WINDOW_SECONDS
+    }
+
+    /// Whether `params` would weaken the withdrawal timelock: a raised or
+    /// disabled threshold, or a shorter delay
+    pub fn loosens_withdrawal_policy(&self, params: &WithdrawalPolicyParams) -> bool {
+        self.loosens_withdrawal_threshold(params.threshold)
+            || params.delay_seconds < self.withdrawal_delay
+    }
+
+    /// Whether `threshold` would let more through without the timelock
+    pub fn loosens_withdrawal_threshold(&self, threshold: u64) -> bool {
+        self.withdrawal_threshold > 0
+            && (threshold == 0 || threshold > self.withdrawal_threshold)
+    }
+
+    /// Co-signer approvals a spend of `amount` needs under the amount bands
+    pub fn required_co_signers(&self, amount: u64) -> u8 {
+        self.amount_bands
+            .iter()
+            .filter(|band| amount > band.above)
+            .map(|band| band.co_signers)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Check if a transaction must be approved by the main wallet first
+    pub fn requires_approval(&self, amount: u64) -> bool {
+        self.approval_threshold > 0 && amount > self.approval_threshold
+    }
+
+    /// Flag the soft limit once a spend takes `spent_today` past it.
+    /// Returns whether this spend crossed it.
+    pub fn record_soft_limit_crossing(&mut self, spent_before: u64) -> bool {
+        let crossed = self.soft_daily_limit > 0
+            && spent_before <= self.soft_daily_limit
+            && self.spent_today > self.soft_daily_limit;
+        if crossed {
+            self.soft_limit_pending_ack = true;
+        }
+        crossed
+    }
+
+    /// Grant a permission, expiring at `expires_at` if given. A grant
+    /// without expiry replaces any timed grant of the same permission.
+    pub fn grant_permission(
+        &mut self,
+        permission: AgentPermission,
+        expires_at: Option<i64>,
+        current_time: i64,
+    ) -> Result<()> {
+        self.timed_permissions.retain(|grant| grant.permission != permission);
+        if let Some(expires_at) = expires_at {
+            require!(
+                expires_at > current_time,
+                crate::errors::DimmError::InvalidPermissionExpiry
+            );
+            self.timed_permissions.push(TimedPermission { permission, expires_at });
+        }
+        self.permissions.grant(permission);
+        Ok(())
+    }
+
+    /// Revoke timed grants that have lapsed by `current_time` and return
+    /// the bits revoked
+    pub fn expire_permissions(&mut self, current_time: i64) -> u64 {
+        let mut expired = 0;
+        for grant in self.timed_permissions.iter().filter(|grant| grant.is_expired(current_time)) {
+            expired |= grant.permission.bit();
+        }
+        self.timed_permissions.retain(|grant| !grant.is_expired(current_time));
+        self.permissions.bits &= !expired;
+        expired
+    }
+
+    /// Check if either USD-denominated limit is set
+    pub fn has_usd_limits(&self) -> bool {
+        self.daily_limit_usd > 0 || self.max_usd_per_transaction > 0
+    }
+
+    /// Price a SOL spend in USD cents and charge it against the USD limits.
+    /// Call after the daily window has been reset.
+    pub fn record_usd_spend(&mut self, price: &SolUsdPrice, lamports: u64) -> Result<()> {
+        let cents = price.lamports_to_usd_cents(lamports)?;
+
+        if self.max_usd_per_transaction > 0 {
+            require!(
+                cents <= self.max_usd_per_transaction,
+                crate::errors::DimmError::ExceedsUsdTransactionLimit
+            );
+        }
+
+        let usd_spent_today = self
+            .usd_spent_today
+            .checked_add(cents)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+        if self.daily_limit_usd > 0 {
+            require!(
+                usd_spent_today <= self.daily_limit_usd,
+                crate::errors::DimmError::ExceedsUsdDailyLimit
+            );
+        }
+
+        self.usd_spent_today = usd_spent_today;
+        Ok(())
+    }
+
+    /// Run the checks an ordinary SOL spend of `amount` goes through and
+    /// record it. `usd_price` is required when the agent has USD limits.
+    /// Returns whether the spend crossed the soft daily limit.
+    pub fn charge_spend(
+        &mut self,
+        protocol_config: &crate::state::ProtocolConfig,
+        usd_price: Option<&SolUsdPrice>,
+        amount: u64,
+        current_time: i64,
+    ) -> Result<bool> {
+        require!(
+            !self.soft_limit_pending_ack,
+            crate::errors::DimmError::SoftLimitNotAcknowledged
+        );
+        self.check_and_reset_daily_limit(current_time)?;
+        require!(
+            amount <= self.max_sol_per_transaction,
+            crate::errors::DimmError::ExceedsTransactionLimit
+        );
+        require!(self.can_spend(amount)?, crate::errors::DimmError::ExceedsDailyLimit);
+        require!(
+            self.within_trial_limits(amount, current_time)?,
+            crate::errors::DimmError::TrialLimitExceeded
+        );
+        require!(
+            self.within_hourly_limit(amount)?,
+            crate::errors::DimmError::ExceedsHourlyLimit
+        );
+        protocol_config.check_cold_start(self, amount, current_time)?;
+
+        if self.has_usd_limits() {
+            let price = usd_price.ok_or(crate::errors::DimmError::PriceFeedRequired)?;
+            self.record_usd_spend(price, amount)?;
+        }
+
+        let spent_before = self.spent_today;
+        self.record_spend(amount)?;
+        Ok(self.record_soft_limit_crossing(spent_before))
+    }
+
+    /// Check if `current_time` falls on an active weekday and in the daily
+    /// active window
+    pub fn is_within_active_hours(&self, current_time: i64) -> bool {
+        let days = current_time.div_euclid(SECONDS_PER_DAY);
+        // 1970-01-01 was a Thursday
+        let weekday = (days + 3).rem_euclid(7);
+        if self.active_weekdays & (1 << weekday) == 0 {
+            return false;
+        }
+
+        let second_of_day = current_time.rem_euclid(SECONDS_PER_DAY) as u32;
+        let (start, end) = (self.active_hours_start, self.active_hours_end);
+        if start == end {
+            true
+        } else if start < end {
+            second_of_day >= start && second_of_day < end
+        } else {
+            second_of_day >= start || second_of_day < end
+        }
+    }
+
+    /// Check an execution of `amount` against the anomaly rules, using the
+    /// agent's stats for its trailing average
+    pub fn detect_anomaly(&self, stats: Option<&AgentStats>, amount: u64, current_time: i64) -> Option<AnomalyKind> {
+        let rules = &self.anomaly_rules;
+        let in_failure_window =
+            current_time.saturating_sub(self.failure_window_start) < HOURLY_WINDOW_SECONDS;
+        if rules.max_failures_per_hour > 0
+            && in_failure_window
+            && self.failures_this_hour >= rules.max_failures_per_hour
+        {
+            return Some(AnomalyKind::FailureBurst);
+        }
+
+        if let (true, Some(stats)) = (rules.max_multiple_of_average > 0, stats) {
+            let ceiling = stats
+                .avg_transaction_size
+                .saturating_mul(rules.max_multiple_of_average as u64);
+            if stats.successful_transactions >= ANOMALY_MIN_HISTORY && ceiling > 0 && amount > ceiling {
+                return Some(AnomalyKind::LargeTransaction);
+            }
+        }
+
+        None
+    }
+
+    /// Count a reported failure towards the hourly failure rule
+    pub fn record_failure(&mut self, current_time: i64) {
+        if current_time.saturating_sub(self.failure_window_start) >= HOURLY_WINDOW_SECONDS {
+            self.failures_this_hour = 0;
+            self.failure_window_start = current_time;
+        }
+        self.failures_this_hour = self.failures_this_hour.saturating_add(1);
+    }
+
+    /// Split `params` into the changes that tighten or keep a limit and the
+    /// ones that loosen it. A cap of 0 that disables a check counts as the
+    /// loosest setting.
+    pub fn split_limit_changes(&self, params: &UpdateLimitsParams) -> (UpdateLimitsParams, Option<UpdateLimitsParams>) {
+        let raises = |current: u64, new: u64| new > current;
+        let relaxes = |current: u64, new: u64| new > current || (new == 0 && current != 0);
+        let split = |current: u64, new: Option<u64>, loosens: &dyn Fn(u64, u64) -> bool| match new {
+            Some(new) if loosens(current, new) => (None, Some(new)),
+            new => (new, None),
+        };
+
+        let (max_sol_per_transaction, staged_max_sol_per_transaction) =
+            split(self.max_sol_per_transaction, params.max_sol_per_transaction, &raises);
+        let (daily_limit, staged_daily_limit) = split(self.daily_limit, params.daily_limit, &raises);
+        let (maintenance_allowance, staged_maintenance_allowance) =
+            split(self.maintenance_allowance, params.maintenance_allowance, &raises);
+        let (approval_threshold, staged_approval_threshold) =
+            split(self.approval_threshold, params.approval_threshold, &relaxes);
+        let (donation_allowance, staged_donation_allowance) =
+            split(self.donation_allowance, params.donation_allowance, &raises);
+        let (soft_daily_limit, staged_soft_daily_limit) =
+            split(self.soft_daily_limit, params.soft_daily_limit, &relaxes);
+        let (lease_threshold, staged_lease_threshold) =
+            split(self.lease_threshold, params.lease_threshold, &relaxes);
+        let (daily_limit_usd, staged_daily_limit_usd) =
+            split(self.daily_limit_usd, params.daily_limit_usd, &relaxes);
+        let (max_usd_per_transaction, staged_max_usd_per_transaction) =
+            split(self.max_usd_per_transaction, params.max_usd_per_transaction, &relaxes);
+        let (max_slippage_bps, staged_max_slippage_bps) = match params.max_slippage_bps {
+            Some(bps) if bps > self.max_slippage_bps => (None, Some(bps)),
+            bps => (bps, None),
+        };
+        let (max_sol_per_hour, staged_max_sol_per_hour) =
+            split(self.max_sol_per_hour, params.max_sol_per_hour, &relaxes);
+
+        let immediate = UpdateLimitsParams {
+            max_sol_per_transaction,
+            daily_limit,
+            maintenance_allowance,
+            approval_threshold,
+            donation_allowance,
+            soft_daily_limit,
+            lease_threshold,
+            daily_limit_usd,
+            max_usd_per_transaction,
+            max_slippage_bps,
+            max_sol_per_hour,
+        };
+        let staged = UpdateLimitsParams {
+            max_sol_per_transaction: staged_max_sol_per_transaction,
+            daily_limit: staged_daily_limit,
+            maintenance_allowance: staged_maintenance_allowance,
+            approval_threshold: staged_approval_threshold,
+            donation_allowance: staged_donation_allowance,
+            soft_daily_limit: staged_soft_daily_limit,
+            lease_threshold: staged_lease_threshold,
+            daily_limit_usd: staged_daily_limit_usd,
+            max_usd_per_transaction: staged_max_usd_per_transaction,
+            max_slippage_bps: staged_max_slippage_bps,
+            max_sol_per_hour: staged_max_sol_per_hour,
+        };
+
+        (immediate, (!staged.is_empty()).then_some(staged))
+    }
+
+    /// Set the limits given in `params` and check the resulting configuration
+    pub fn apply_limits(&mut self, params: &UpdateLimitsParams) -> Result<()> {
+        if let Some(max_sol_per_transaction) = params.max_sol_per_transaction {
+            self.max_sol_per_transaction = max_sol_per_transaction;
+        }
+
+        if let Some(daily_limit) = params.daily_limit {
+            self.daily_limit = daily_limit;
+        }
+
+        if let Some(maintenance_allowance) = params.maintenance_allowance {
+            require!(
+                maintenance_allowance <= MAX_MAINTENANCE_ALLOWANCE,
+                crate::errors::DimmError::ExceedsMaintenanceAllowance
+            );
+            self.maintenance_allowance = maintenance_allowance;
+        }
+
+        if let Some(approval_threshold) = params.approval_threshold {
+            self.approval_threshold = approval_threshold;
+        }
+
+        if let Some(donation_allowance) = params.donation_allowance {
+            self.donation_allowance = donation_allowance;
+        }
+
+        if let Some(soft_daily_limit) = params.soft_daily_limit {
+            self.soft_daily_limit = soft_daily_limit;
+        }
+
+        if let Some(lease_threshold) = params.lease_threshold {
+            self.lease_threshold = lease_threshold;
+        }
+
+        if let Some(daily_limit_usd) = params.daily_limit_usd {
+            self.daily_limit_usd = daily_limit_usd;
+        }
+
+        if let Some(max_usd_per_transaction) = params.max_usd_per_transaction {
+            self.max_usd_per_transaction = max_usd_per_transaction;
+        }
+
+        if let Some(max_slippage_bps) = params.max_slippage_bps {
+            require!(max_slippage_bps <= 10_000, crate::errors::DimmError::InvalidSlippage);
+            self.max_slippage_bps = max_slippage_bps;
+        }
+
+        if let Some(max_sol_per_hour) = params.max_sol_per_hour {
+            self.max_sol_per_hour = max_sol_per_hour;
+        }
+
+        // Validate the configuration
+        require!(
+            self.daily_limit >= self.max_sol_per_transaction,
+            crate::errors::DimmError::InvalidLimitConfiguration
+        );
+
+        require!(
+            self.daily_limit >= self.critical_reserve,
+            crate::errors::DimmError::InvalidLimitConfiguration
+        );
+
+        require!(
+            self.max_sol_per_hour == 0
+                || (self.max_sol_per_hour >= self.max_sol_per_transaction
+                    && self.max_sol_per_hour <= self.daily_limit),
+            crate::errors::DimmError::InvalidLimitConfiguration
+        );
+
+        require!(
+            self.soft_daily_limit <= self.daily_limit,
+            crate::errors::DimmError::InvalidLimitConfiguration
+        );
+
+        require!(
+            self.daily_limit_usd == 0 || self.daily_limit_usd >= self.max_usd_per_transaction,
+            crate::errors::DimmError::InvalidLimitConfiguration
+        );
+
+        Ok(())
+    }
+
+    /// Stage permission bits to grant once the change delay passes. Anything
+    /// already staged is kept and its delay restarts.
+    pub fn stage_permissions(&mut self, bits: u64, current_time: i64) -> Result<()> {
+        self.pending_permissions |= bits;
+        self.restart_change_delay(current_time)
+    }
+
+    /// Stage limit increases until the change delay passes. Anything already
+    /// staged is kept unless `params` sets it again, and its delay restarts.
+    pub fn stage_limits(&mut self, params: UpdateLimitsParams, current_time: i64) -> Result<()> {
+        let staged = match self.pending_limits.take() {
+            Some(pending) => params.or(pending),
+            None => params,
+        };
+        self.pending_limits = Some(staged);
+        self.restart_change_delay(current_time)
+    }
+
+    /// Stage a lowered change delay until the current delay passes
+    pub fn stage_change_delay(&mut self, delay_seconds: i64, current_time: i64) -> Result<()> {
+        self.pending_change_delay = Some(delay_seconds);
+        self.restart_change_delay(current_time)
+    }
+
+    /// Stage a loosened withdrawal policy. It waits out the current
+    /// withdrawal delay as well as the change delay, giving the same window
+    /// to notice a compromised main wallet as a timelocked withdrawal.
+    pub fn stage_withdrawal_policy(&mut self, params: WithdrawalPolicyParams, current_time: i64) -> Result<()> {
+        self.pending_withdrawal_policy = Some(params);
+        self.restart_change_delay(current_time)
+    }
+
+    fn restart_change_delay(&mut self, current_time: i64) -> Result<()> {
+        let delay = if self.pending_withdrawal_policy.is_some() {
+            self.change_delay_seconds.max(self.withdrawal_delay)
+        } else {
+            self.change_delay_seconds
+        };
+        self.pending_effective_at = current_time
+            .checked_add(delay)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+        Ok(())
+    }
+
+    /// Whether any change is staged
+    pub fn has_pending_changes(&self) -> bool {
+        self.pending_permissions != 0
+            || self.pending_limits.is_some()
+            || self.pending_change_delay.is_some()
+            || self.pending_withdrawal_policy.is_some()
+    }
+
+    /// Drop staged limits that `params` sets directly, so a later apply
+    /// can't undo a change made since they were staged
+    pub fn supersede_pending_limits(&mut self, params: &UpdateLimitsParams) {
+        self.pending_limits = self
+            .pending_limits
+            .take()
+            .map(|pending| pending.without(params))
+            .filter(|pending| !pending.is_empty());
+    }
+
+    /// Drop everything staged
+    pub fn clear_pending_changes(&mut self) {
+        self.pending_permissions = 0;
+        self.pending_limits = None;
+        self.pending_change_delay = None;
+        self.pending_withdrawal_policy = None;
+        self.pending_effective_at = 0;
+    }
+
+    /// Check the nonce an execution expects, if any, and advance it. Off-chain
+    /// coordinators use this to make executions idempotent and ordered.
+    pub fn consume_nonce(&mut self, expected_nonce: Option<u64>) -> Result<()> {
+        if let Some(expected_nonce) = expected_nonce {
+            require!(
+                expected_nonce == self.nonce,
+                crate::errors::DimmError::NonceMismatch
+            );
+        }
+        self.nonce = self.nonce
+            .checked_add(1)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+        Ok(())
+    }
+
+    /// Check if the execution lease is held by anyone at `current_time`
+    pub fn is_lease_active(&self, current_time: i64) -> bool {
+        self.lease_holder != Pubkey::default() && current_time < self.lease_expires_at
+    }
+
+    /// Executions moving `amount` or more must be signed by the live lease
+    /// holder, so concurrent runtimes of one agent cannot both spend
+    pub fn require_lease(&self, holder: Option<Pubkey>, amount: u64, current_time: i64) -> Result<()> {
+        if self.lease_threshold == 0 || amount < self.lease_threshold {
+            return Ok(());
+        }
+
+        require!(
+            self.is_lease_active(current_time) && holder == Some(self.lease_holder),
+            crate::errors::DimmError::ExecutionLeaseRequired
+        );
+        Ok(())
+    }
+
+    /// Advance and return the receipt sequence for an execution
+    pub fn next_receipt(&mut self) -> Result<u64> {
+        self.receipt_sequence = self
+            .receipt_sequence
+            .checked_add(1)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+        Ok(self.receipt_sequence)
+    }
+
+    /// Decode an optional setting from the extension region
+    pub fn extension<T: AnchorDeserialize>(&self, extension_type: ExtensionType) -> Result<Option<T>> {
+        extensions::read_extension(&self.reserved, extension_type)?
+            .map(|value| T::try_from_slice(&value).map_err(|_| error!(crate::errors::DimmError::InvalidExtensionData)))
+            .transpose()
+    }
+
+    /// Set an optional setting in the extension region
+    pub fn set_extension(&mut self, value: &ExtensionValue) -> Result<()> {
+        extensions::write_extension(&mut self.reserved, value)
+    }
+
+    /// Clear an optional setting from the extension region
+    pub fn clear_extension(&mut self, extension_type: ExtensionType) -> Result<()> {
+        extensions::remove_extension(&mut self.reserved, extension_type)
+    }
+
+    /// Smallest transfer the agent may make (0 = no minimum)
+    pub fn min_transfer_amount(&self) -> Result<u64> {
+        Ok(self.extension(ExtensionType::MinTransferAmount)?.unwrap_or(0))
+    }
+
+    /// Whether the signer's signature fee may be refunded in `slot`. A
+    /// transaction pays one signature fee however many executions it
+    /// bundles, so the tank refunds it at most once per slot.
+    pub fn signature_refund_due(&self, slot: u64) -> bool {
+        self.fee_tank_refund_slot != slot
+    }
+
+    /// Pay up to `fee_lamports` of network fees from the fee tank and
+    /// return the amount paid
+    pub fn draw_fee_tank(&mut self, fee_lamports: u64) -> u64 {
+        let paid = fee_lamports.min(self.fee_tank);
+        self.fee_tank -= paid;
+        self.fee_tank_spent = self.fee_tank_spent.saturating_add(paid);
+        paid
+    }
+
+    /// Check a spend against the hourly velocity limit; always passes when
+    /// the agent has none
+    pub fn within_hourly_limit(&self, amount: u64) -> Result<bool> {
+        if self.max_sol_per_hour == 0 {
+            return Ok(true);
+        }
+
+        let new_hourly_total = self.spent_this_hour
+            .checked_add(amount)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+
+        Ok(new_hourly_total <= self.max_sol_per_hour)
+    }
+
+    /// Check if the agent is still in its trial period
+    pub fn is_in_trial(&self, current_time: i64) -> bool {
+        current_time < self.trial_ends_at
+    }
+
+    /// Check a spend against the protocol's trial limits; always passes
+    /// once the agent has graduated
+    pub fn within_trial_limits(&self, amount: u64, current_time: i64) -> Result<bool> {
+        if !self.is_in_trial(current_time) {
+            return Ok(true);
+        }
+
+        let new_daily_total = self.spent_today
+            .checked_add(amount)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+
+        Ok(amount <= TRIAL_TX_LIMIT && new_daily_total <= TRIAL_DAILY_LIMIT)
+    }
+}
+
+impl SpendPolicy for AgentAccount {
     /// Check if daily limit needs to be reset
-    pub fn check_and_reset_daily_limit(&mut self, current_time: i64) -> Result<()> {
+    fn check_and_reset_daily_limit(&mut self, current_time: i64) -> Result<()> {
         let time_since_reset = current_time
             .checked_sub(self.last_daily_reset)
             .ok_or(crate::errors::DimmError::InvalidActivityWindow)?;
 
         if time_since_reset >= DAILY_WINDOW_SECONDS {
             self.spent_today = 0;
+            self.maintenance_spent_today = 0;
+            self.donation_spent_today = 0;
+            self.usd_spent_today = 0;
             self.last_daily_reset = current_time;
         }
+
+        if current_time.saturating_sub(self.last_hourly_reset) >= HOURLY_WINDOW_SECONDS {
+            self.spent_this_hour = 0;
+            self.last_hourly_reset = current_time;
+        }
         
         Ok(())
     }
 
-    /// Check if agent can spend the specified amount
-    pub fn can_spend(&self, amount: u64) -> Result<bool> {
+    /// Check if agent can spend the specified amount; only critical
+    /// spends may dip into the reserved slice of the daily limit
+    fn can_spend_in_lane(&self, amount: u64, critical: bool) -> Result<bool> {
         // Check per-transaction limit
         if amount > self.max_sol_per_transaction {
             return Ok(false);
         }
 
+        let daily_budget = if critical {
+            self.daily_limit
+        } else {
+            self.daily_limit.saturating_sub(self.critical_reserve)
+        };
+
         // Check daily limit
         let new_daily_total = self.spent_today
             .checked_add(amount)
             .ok_or(crate::errors::DimmError::NumericalOverflow)?;
             
-        if new_daily_total > self.daily_limit {
+        if new_daily_total > daily_budget {
             return Ok(false);
         }
 
@@ -106,11 +1035,63 @@ impl AgentAccount {
     }
 
     /// Record a spend
-    pub fn record_spend(&mut self, amount: u64) -> Result<()> {
+    fn record_spend(&mut self, amount: u64) -> Result<()> {
         self.spent_today = self.spent_today
             .checked_add(amount)
             .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+
+        self.spent_this_hour = self.spent_this_hour
+            .checked_add(amount)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+            
+        self.total_spent = self.total_spent
+            .checked_add(amount)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
             
+        self.total_transactions = self.total_transactions
+            .checked_add(1)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+            
+        Ok(())
+    }
+
+    /// Record a fee-only maintenance spend against the separate allowance
+    fn record_maintenance_spend(&mut self, amount: u64) -> Result<()> {
+        let new_maintenance_total = self.maintenance_spent_today
+            .checked_add(amount)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+
+        require!(
+            new_maintenance_total <= self.maintenance_allowance,
+            crate::errors::DimmError::ExceedsMaintenanceAllowance
+        );
+
+        self.maintenance_spent_today = new_maintenance_total;
+
+        self.total_spent = self.total_spent
+            .checked_add(amount)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+            
+        self.total_transactions = self.total_transactions
+            .checked_add(1)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+            
+        Ok(())
+    }
+
+    /// Record a burn or donation against the separate allowance
+    fn record_donation_spend(&mut self, amount: u64) -> Result<()> {
+        let new_donation_total = self.donation_spent_today
+            .checked_add(amount)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+
+        require!(
+            new_donation_total <= self.donation_allowance,
+            crate::errors::DimmError::ExceedsDonationAllowance
+        );
+
+        self.donation_spent_today = new_donation_total;
+
         self.total_spent = self.total_spent
             .checked_add(amount)
             .ok_or(crate::errors::DimmError::NumericalOverflow)?;
@@ -122,14 +1103,20 @@ impl AgentAccount {
         Ok(())
     }
 
+    /// Check if agent is temporarily frozen
+    fn is_frozen(&self, current_time: i64) -> bool {
+        current_time < self.frozen_until
+    }
+
     /// Check if agent has a specific permission
-    pub fn has_permission(&self, permission: &AgentPermission) -> bool {
-        self.permissions.contains(permission)
+    fn has_permission(&self, permission: &AgentPermission) -> bool {
+        self.permissions.has(*permission)
     }
 }
 
 /// Permission types for agents
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AgentPermission {
     /// Transfer SOL to any address
     TransferSol,
@@ -154,15 +1141,73 @@ pub enum AgentPermission {
     
     /// Execute arbitrary programs (use with caution)
     ExecutePrograms,
+    
+    /// Burn or donate SOL within the donation allowance
+    Donations,
+    
+    /// Pay fees and rent from the maintenance allowance, outside the daily limit
+    Maintenance,
+}
+
+/// Conditions under which an execution freezes the agent instead of running
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnomalyRules {
+    /// Trips when an execution exceeds this multiple of the trailing
+    /// average transaction size (0 = off)
+    pub max_multiple_of_average: u16,
+    
+    /// Trips once this many failures are reported within an hour (0 = off)
+    pub max_failures_per_hour: u16,
+}
+
+impl AnomalyRules {
+    pub const LEN: usize = 2 + 2;
+}
+
+/// Anomaly rule an execution tripped
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AnomalyKind {
+    /// Execution far above the trailing average
+    LargeTransaction,
+    
+    /// Too many failures reported within an hour
+    FailureBurst,
+}
+
+/// What happens to an agent left inactive past its timeout
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InactivityAction {
+    /// Revoke the agent
+    Revoke,
+    
+    /// Freeze the agent until the main wallet unfreezes it
+    Freeze,
+}
+
+impl AgentPermission {
+    /// Mask of every defined permission
+    pub const ALL: u64 = (1 << 10) - 1;
+
+    /// Bit of this permission in a `PermissionSet`
+    pub fn bit(&self) -> u64 {
+        1 << (*self as u64)
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CreateAgentParams {
     /// Agent name
     pub name: String,
     
-    /// Initial permissions
-    pub permissions: Vec<AgentPermission>,
+    /// Key the agent signs with
+    pub agent_signer: Pubkey,
+    
+    /// Initial permissions, as a bitmask of `AgentPermission::bit()`
+    pub permissions: u64,
     
     /// Max SOL per transaction
     pub max_sol_per_transaction: u64,
@@ -172,12 +1217,252 @@ pub struct CreateAgentParams {
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UpdateAgentMetadataParams {
+    /// New agent name
+    pub name: String,
+    
+    /// New description (None clears it)
+    pub description: Option<String>,
+    
+    /// New metadata URI (None clears it)
+    pub metadata_uri: Option<String>,
+    
+    /// New model identifier (None clears it)
+    pub model_id: Option<String>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ActiveHoursParams {
+    /// Start of the daily window (UTC seconds of day)
+    pub start: u32,
+    
+    /// End of the daily window (UTC seconds of day)
+    pub end: u32,
+    
+    /// Weekday bitmask, bit 0 = Monday through bit 6 = Sunday
+    pub weekdays: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InactivityPolicyParams {
+    /// Seconds without activity before the action may be enforced (0 = disabled)
+    pub timeout_seconds: i64,
+    
+    /// What enforcing does to the agent
+    pub action: InactivityAction,
+    
+    /// Lamports paid to whoever enforces it
+    pub keeper_reward: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RetentionPolicyParams {
+    /// Activity records kept before overwriting the oldest (0 = unlimited)
+    pub max_records: u32,
+    
+    /// Age in seconds after which records may be pruned (0 = unlimited)
+    pub max_age: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WithdrawalPolicyParams {
+    /// Withdrawals above this amount need the timelock (0 = disabled)
+    pub threshold: u64,
+    
+    /// Delay before a large withdrawal can be finalized (in seconds)
+    pub delay_seconds: i64,
+}
+
+impl WithdrawalPolicyParams {
+    pub const LEN: usize = 8 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UpdateLimitsParams {
     /// New max SOL per transaction
     pub max_sol_per_transaction: Option<u64>,
     
     /// New daily limit
     pub daily_limit: Option<u64>,
+    
+    /// New daily maintenance allowance
+    pub maintenance_allowance: Option<u64>,
+    
+    /// New approval threshold (0 = disabled)
+    pub approval_threshold: Option<u64>,
+    
+    /// New daily burn/donation allowance
+    pub donation_allowance: Option<u64>,
+    
+    /// New soft daily limit (0 = disabled)
+    pub soft_daily_limit: Option<u64>,
+    
+    /// New execution lease threshold (0 = disabled)
+    pub lease_threshold: Option<u64>,
+    
+    /// Daily limit in USD cents (0 = none)
+    pub daily_limit_usd: Option<u64>,
+    
+    /// Max USD cents per transaction (0 = none)
+    pub max_usd_per_transaction: Option<u64>,
+    
+    /// Max swap slippage in basis points
+    pub max_slippage_bps: Option<u16>,
+    
+    /// Max lamports per hourly window (0 = no hourly limit)
+    pub max_sol_per_hour: Option<u64>,
 }
 
+impl UpdateLimitsParams {
+    /// Bytes the params take when every limit is set
+    pub const LEN: usize = 10 * (1 + 8) + (1 + 2);
+
+    /// Whether no limit is set
+    pub fn is_empty(&self) -> bool {
+        self.max_sol_per_transaction.is_none()
+            && self.daily_limit.is_none()
+            && self.maintenance_allowance.is_none()
+            && self.approval_threshold.is_none()
+            && self.donation_allowance.is_none()
+            && self.soft_daily_limit.is_none()
+            && self.lease_threshold.is_none()
+            && self.daily_limit_usd.is_none()
+            && self.max_usd_per_transaction.is_none()
+            && self.max_slippage_bps.is_none()
+            && self.max_sol_per_hour.is_none()
+    }
+
+    /// Drop each limit that `other` sets
+    pub fn without(self, other: &UpdateLimitsParams) -> UpdateLimitsParams {
+        fn unless<T>(value: Option<T>, set: &Option<T>) -> Option<T> {
+            value.filter(|_| set.is_none())
+        }
+        UpdateLimitsParams {
+            max_sol_per_transaction: unless(self.max_sol_per_transaction, &other.max_sol_per_transaction),
+            daily_limit: unless(self.daily_limit, &other.daily_limit),
+            maintenance_allowance: unless(self.maintenance_allowance, &other.maintenance_allowance),
+            approval_threshold: unless(self.approval_threshold, &other.approval_threshold),
+            donation_allowance: unless(self.donation_allowance, &other.donation_allowance),
+            soft_daily_limit: unless(self.soft_daily_limit, &other.soft_daily_limit),
+            lease_threshold: unless(self.lease_threshold, &other.lease_threshold),
+            daily_limit_usd: unless(self.daily_limit_usd, &other.daily_limit_usd),
+            max_usd_per_transaction: unless(self.max_usd_per_transaction, &other.max_usd_per_transaction),
+            max_slippage_bps: unless(self.max_slippage_bps, &other.max_slippage_bps),
+            max_sol_per_hour: unless(self.max_sol_per_hour, &other.max_sol_per_hour),
+        }
+    }
+
+    /// Take each limit from `self`, falling back to `other`
+    pub fn or(self, other: UpdateLimitsParams) -> UpdateLimitsParams {
+        UpdateLimitsParams {
+            max_sol_per_transaction: self.max_sol_per_transaction.or(other.max_sol_per_transaction),
+            daily_limit: self.daily_limit.or(other.daily_limit),
+            maintenance_allowance: self.maintenance_allowance.or(other.maintenance_allowance),
+            approval_threshold: self.approval_threshold.or(other.approval_threshold),
+            donation_allowance: self.donation_allowance.or(other.donation_allowance),
+            soft_daily_limit: self.soft_daily_limit.or(other.soft_daily_limit),
+            lease_threshold: self.lease_threshold.or(other.lease_threshold),
+            daily_limit_usd: self.daily_limit_usd.or(other.daily_limit_usd),
+            max_usd_per_transaction: self.max_usd_per_transaction.or(other.max_usd_per_transaction),
+            max_slippage_bps: self.max_slippage_bps.or(other.max_slippage_bps),
+            max_sol_per_hour: self.max_sol_per_hour.or(other.max_sol_per_hour),
+        }
+    }
+}
 
+/// Leaf data identifying an agent's cNFT in its merkle tree
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AgentLeafArgs {
+    /// Current root of the merkle tree
+    pub root: [u8; 32],
+    
+    /// Hash of the cNFT metadata
+    pub data_hash: [u8; 32],
+    
+    /// Hash of the cNFT creators
+    pub creator_hash: [u8; 32],
+    
+    /// Leaf nonce
+    pub nonce: u64,
+    
+    /// Leaf index in the tree
+    pub index: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{AgentAccountV0, AmountBand};
+
+    fn agent() -> AgentAccount {
+        AgentAccountV0 {
+            main_wallet: Pubkey::new_unique(),
+            agent_id: 0,
+            name: String::from("agent"),
+            permissions: PermissionSet::from_bits(0).unwrap(),
+            max_sol_per_transaction: 0,
+            daily_limit: 0,
+            spent_today: 0,
+            last_daily_reset: 0,
+            total_spent: 0,
+            total_transactions: 0,
+            revoked: false,
+            created_at: 0,
+            last_used_at: 0,
+            leaf_index: 0,
+            bump: 0,
+            reserved: [0; 128],
+        }
+        .into_current(Pubkey::default())
+    }
+
+    fn band(above: u64, co_signers: u8) -> AmountBand {
+        AmountBand { above, co_signers }
+    }
+
+    #[test]
+    fn no_bands_need_no_co_signers() {
+        assert_eq!(agent().required_co_signers(u64::MAX), 0);
+    }
+
+    #[test]
+    fn amount_takes_the_highest_band_it_exceeds() {
+        let mut agent = agent();
+        agent.amount_bands = vec![band(1_000, 1), band(10_000, 2), band(100_000, 3)];
+
+        assert_eq!(agent.required_co_signers(0), 0);
+        assert_eq!(agent.required_co_signers(1_000), 0);
+        assert_eq!(agent.required_co_signers(1_001), 1);
+        assert_eq!(agent.required_co_signers(10_000), 1);
+        assert_eq!(agent.required_co_signers(10_001), 2);
+        assert_eq!(agent.required_co_signers(100_001), 3);
+        assert_eq!(agent.required_co_signers(u64::MAX), 3);
+    }
+
+    #[test]
+    fn signature_fee_is_refunded_once_per_slot() {
+        let mut agent = agent();
+        agent.fee_tank = 3 * LAMPORTS_PER_SIGNATURE;
+        assert!(agent.signature_refund_due(7));
+
+        agent.fee_tank_refund_slot = 7;
+        assert!(!agent.signature_refund_due(7));
+        assert!(agent.signature_refund_due(8));
+    }
+
+    #[test]
+    fn band_bounds_are_exclusive_from_zero() {
+        let mut agent = agent();
+        agent.amount_bands = vec![band(0, 1)];
+
+        assert_eq!(agent.required_co_signers(0), 0);
+        assert_eq!(agent.required_co_signers(1), 1);
+    }
+}
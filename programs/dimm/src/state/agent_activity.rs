@@ -3,6 +3,7 @@ use crate::constants::*;
 
 /// Agent activity log
 #[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AgentActivity {
     /// Agent account this activity belongs to
     pub agent: Pubkey,
@@ -23,11 +24,36 @@ pub struct AgentActivity {
     pub timestamp: i64,
     
     /// Transaction signature
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
     pub signature: [u8; 64],
     
     /// Whether the activity was successful
     pub success: bool,
     
+    /// Operational notes sealed to the agent's reporting key (empty if none)
+    pub encrypted_payload: Vec<u8>,
+    
+    /// Position of this record in the agent's activity chain
+    pub sequence: u64,
+    
+    /// Chain hash of the previous record (zero for the first)
+    pub prev_hash: [u8; 32],
+    
+    /// Whether the rent pool paid this record's rent
+    pub rent_sponsored: bool,
+    
+    /// Account that paid this record's rent
+    pub payer: Pubkey,
+    
+    /// Whether the main wallet has flagged this record as disputed
+    pub disputed: bool,
+    
+    /// Program error code of a failed activity
+    pub error_code: Option<u32>,
+    
+    /// Why a failed activity failed
+    pub failure_category: Option<FailureCategory>,
+    
     /// Bump seed for PDA
     pub bump: u8,
 }
@@ -42,10 +68,36 @@ impl AgentActivity {
         8 +  // timestamp
         64 + // signature
         1 +  // success
+        4 + MAX_ENCRYPTED_PAYLOAD_LENGTH + // encrypted_payload
+        8 +  // sequence
+        32 + // prev_hash
+        1 +  // rent_sponsored
+        32 + // payer
+        1 +  // disputed
+        1 + 4 + // error_code
+        1 + 1 + // failure_category
         1;   // bump
+
+    /// Chain hash committing to this record and, through `prev_hash`, all before it
+    pub fn chain_hash(&self) -> Result<[u8; 32]> {
+        // The dispute flag is raised after the fact and is not part of the record
+        let mut record = self.clone();
+        record.disputed = false;
+        Ok(anchor_lang::solana_program::hash::hash(&record.try_to_vec()?).to_bytes())
+    }
+
+    /// Whether this record counts towards the agent's spend counters
+    pub fn is_spend(&self) -> bool {
+        self.success
+            && !matches!(
+                self.activity_type,
+                ActivityType::Funding | ActivityType::Withdrawal
+            )
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ActivityType {
     /// SOL transfer
     Transfer,
@@ -73,9 +125,35 @@ pub enum ActivityType {
     
     /// Other operation
     Other,
+    
+    /// Intentional burn or donation, budgeted apart from other spends
+    Donation,
+    
+    /// Fee-only maintenance spend (network fees, rent to unwind positions)
+    Maintenance,
+}
+
+impl ActivityType {
+    /// Permission a SOL execution of this type requires
+    pub fn required_permission(&self) -> Option<crate::state::AgentPermission> {
+        use crate::state::AgentPermission;
+
+        match self {
+            ActivityType::Transfer => Some(AgentPermission::TransferSol),
+            ActivityType::Swap => Some(AgentPermission::SwapTokens),
+            ActivityType::NftOperation => Some(AgentPermission::NftOperations),
+            ActivityType::Staking => Some(AgentPermission::Staking),
+            ActivityType::Governance => Some(AgentPermission::Governance),
+            ActivityType::DefiInteraction => Some(AgentPermission::DefiProtocols),
+            ActivityType::Donation => Some(AgentPermission::Donations),
+            ActivityType::Maintenance => Some(AgentPermission::Maintenance),
+            _ => Some(AgentPermission::ExecutePrograms),
+        }
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ActivityParams {
     /// Activity type
     pub activity_type: ActivityType,
@@ -90,18 +168,152 @@ pub struct ActivityParams {
     pub reason: String,
     
     /// Transaction signature
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
     pub signature: [u8; 64],
     
     /// Success status
     pub success: bool,
+    
+    /// Payload sealed to the agent's reporting key (empty if none)
+    pub encrypted_payload: Vec<u8>,
+    
+    /// Program error code the failed execution aborted with
+    pub error_code: Option<u32>,
+    
+    /// Failure category; derived from `error_code` when not given
+    pub failure_category: Option<FailureCategory>,
+}
+
+impl ActivityParams {
+    /// Check the reason and sealed payload fit their records
+    pub fn validate(&self, has_reporting_key: bool) -> Result<()> {
+        require!(
+            self.reason.len() <= MAX_REASON_LENGTH,
+            crate::errors::DimmError::ReasonTooLong
+        );
+
+        if !self.encrypted_payload.is_empty() {
+            require!(has_reporting_key, crate::errors::DimmError::ReportingKeyNotSet);
+            require!(
+                self.encrypted_payload.len() <= MAX_ENCRYPTED_PAYLOAD_LENGTH,
+                crate::errors::DimmError::EncryptedPayloadTooLong
+            );
+        }
+
+        if self.success {
+            require!(
+                self.error_code.is_none() && self.failure_category.is_none(),
+                crate::errors::DimmError::InvalidFailureRecord
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Category to record, falling back to the one `error_code` maps to
+    pub fn resolved_failure_category(&self) -> Option<FailureCategory> {
+        self.failure_category
+            .or_else(|| self.error_code.map(FailureCategory::from_error_code))
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FailureCategory {
+    /// A spending limit or budget was exceeded
+    Limit,
+    
+    /// The agent or signer was not allowed to act
+    Permission,
+    
+    /// The agent could not cover the amount, fee and reserve
+    Balance,
+    
+    /// The agent acted too often or too soon
+    RateLimit,
+    
+    /// A hook or invoked program failed
+    DownstreamCpi,
+    
+    /// Anything not classified above
+    Other,
+}
+
+impl FailureCategory {
+    /// Classify an error `execute_transaction` aborted with. Codes from
+    /// other programs cannot be told apart from ours by value alone, so
+    /// callers recording a failed CPI should pass `DownstreamCpi` explicitly.
+    pub fn from_error_code(code: u32) -> FailureCategory {
+        use crate::errors::DimmError::*;
+
+        let is_any = |errors: &[crate::errors::DimmError]| {
+            errors.iter().any(|error| u32::from(*error) == code)
+        };
+
+        if is_any(&[
+            ExceedsTransactionLimit,
+            ExceedsDailyLimit,
+            ExceedsHourlyLimit,
+            ExceedsTierCeiling,
+            ExceedsWalletBudget,
+            ExceedsMaintenanceAllowance,
+            ExceedsDonationAllowance,
+            TrialLimitExceeded,
+            ExceedsTierTransactionLimit,
+            ExceedsTierDailyLimit,
+            ExceedsDestinationTransactionLimit,
+            ExceedsDestinationDailyLimit,
+            ExceedsUsdTransactionLimit,
+            ExceedsUsdDailyLimit,
+            ExceedsScopedAmount,
+            SoftLimitNotAcknowledged,
+            SessionBudgetExceeded,
+        ]) {
+            FailureCategory::Limit
+        } else if is_any(&[
+            InsufficientPermissions,
+            Unauthorized,
+            AgentRevoked,
+            AgentFrozen,
+            AgentBlackedOut,
+            OutsideActiveHours,
+            SignerHasNoRole,
+            ExecuteNotAllowed,
+            DestinationNotWhitelisted,
+            DestinationBlacklisted,
+            ProgramNotWhitelisted,
+            ProgramOutOfScope,
+            UnlabeledProgram,
+            ApprovalRequired,
+            ProposalNotApproved,
+            CoSignersRequired,
+            ExecutionLeaseRequired,
+            SessionExpired,
+            SessionActivityNotAllowed,
+            RelayerNotAllowed,
+            ProtocolPaused,
+            EmergencyPaused,
+        ]) {
+            FailureCategory::Permission
+        } else if is_any(&[InsufficientAgentBalance, InsufficientBalance]) {
+            FailureCategory::Balance
+        } else if is_any(&[ColdStartRestricted, ExecutionLeaseHeld]) {
+            FailureCategory::RateLimit
+        } else if is_any(&[HookRejected, PostHookFailed]) {
+            FailureCategory::DownstreamCpi
+        } else {
+            FailureCategory::Other
+        }
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExecuteTransactionParams {
     /// Type of transaction
     pub activity_type: ActivityType,
     
-    /// Amount (if applicable)
+    /// Amount (if applicable); in the mint's base units for token transfers
     pub amount: u64,
     
     /// Destination (if applicable)
@@ -109,6 +321,62 @@ pub struct ExecuteTransactionParams {
     
     /// Additional instruction data
     pub instruction_data: Vec<u8>,
+    
+    /// Mint to transfer instead of SOL (transfers only)
+    pub mint: Option<Pubkey>,
+    
+    /// Network fees (base and priority) the runtime paid for this
+    /// transaction, as reported by the caller; bookkeeping only
+    pub fee_lamports: u64,
+    
+    /// Agent nonce the execution must run at; `None` skips the check
+    pub expected_nonce: Option<u64>,
+    
+    /// Memo logged through the SPL Memo program, signed by the agent
+    pub memo: Option<String>,
 }
 
+impl ExecuteTransactionParams {
+    /// Lamports the execution moves; token transfers move none
+    pub fn lamports(&self) -> u64 {
+        if self.mint.is_some() {
+            0
+        } else {
+            self.amount
+        }
+    }
 
+    /// Permission the activity type requires
+    pub fn required_permission(&self) -> Option<crate::state::AgentPermission> {
+        match self.activity_type {
+            ActivityType::Transfer if self.mint.is_some() => {
+                Some(crate::state::AgentPermission::TokenAccounts)
+            }
+            _ => self.activity_type.required_permission(),
+        }
+    }
+}
+
+/// Totals for a prospective execution, returned by `quote_execution`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExecutionQuote {
+    /// Protocol fee charged into the treasury (in lamports)
+    pub fee: u64,
+    
+    /// Balance the agent must keep after executing (in lamports)
+    pub reserve_requirement: u64,
+    
+    /// Balance the agent needs to execute: amount + fee + reserve (in lamports)
+    pub required_balance: u64,
+    
+    /// Agent's current balance (in lamports)
+    pub agent_balance: u64,
+    
+    /// Daily allowance left after the execution; the maintenance allowance
+    /// for maintenance spends (in lamports)
+    pub allowance_after: u64,
+    
+    /// Whether the execution would pass the agent's spending limits
+    pub within_limits: bool,
+}
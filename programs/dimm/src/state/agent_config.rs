@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::{AgentAccount, BlackoutWindow};
+
+/// Portable snapshot of an agent's configuration, exported from one
+/// deployment and imported into an agent under another
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AgentConfig {
+    /// Permission bitmask
+    pub permissions: u64,
+    
+    pub max_sol_per_transaction: u64,
+    pub daily_limit: u64,
+    pub soft_daily_limit: u64,
+    pub max_sol_per_hour: u64,
+    pub maintenance_allowance: u64,
+    pub donation_allowance: u64,
+    pub approval_threshold: u64,
+    pub lease_threshold: u64,
+    pub daily_limit_usd: u64,
+    pub max_usd_per_transaction: u64,
+    pub max_slippage_bps: u16,
+    pub withdrawal_threshold: u64,
+    pub withdrawal_delay: i64,
+    pub retention_max_records: u32,
+    pub retention_max_age: i64,
+    pub require_destination_whitelist: bool,
+    
+    /// Destination whitelist addresses (empty if the agent has none)
+    pub destination_whitelist: Vec<Pubkey>,
+    
+    /// Program whitelist addresses (empty if the agent has none)
+    pub program_whitelist: Vec<Pubkey>,
+    
+    /// Blackout windows that had not ended at export
+    pub blackouts: Vec<BlackoutWindow>,
+}
+
+impl AgentConfig {
+    /// Snapshot the scalar settings of an agent; lists are filled in by the caller
+    pub fn from_agent(agent_account: &AgentAccount) -> AgentConfig {
+        AgentConfig {
+            permissions: agent_account.permissions.bits,
+            max_sol_per_transaction: agent_account.max_sol_per_transaction,
+            daily_limit: agent_account.daily_limit,
+            soft_daily_limit: agent_account.soft_daily_limit,
+            max_sol_per_hour: agent_account.max_sol_per_hour,
+            maintenance_allowance: agent_account.maintenance_allowance,
+            donation_allowance: agent_account.donation_allowance,
+            approval_threshold: agent_account.approval_threshold,
+            lease_threshold: agent_account.lease_threshold,
+            daily_limit_usd: agent_account.daily_limit_usd,
+            max_usd_per_transaction: agent_account.max_usd_per_transaction,
+            max_slippage_bps: agent_account.max_slippage_bps,
+            withdrawal_threshold: agent_account.withdrawal_threshold,
+            withdrawal_delay: agent_account.withdrawal_delay,
+            retention_max_records: agent_account.retention_max_records,
+            retention_max_age: agent_account.retention_max_age,
+            require_destination_whitelist: agent_account.require_destination_whitelist,
+            destination_whitelist: Vec::new(),
+            program_whitelist: Vec::new(),
+            blackouts: Vec::new(),
+        }
+    }
+
+    /// Hash committing to the whole configuration
+    pub fn hash(&self) -> Result<[u8; 32]> {
+        Ok(anchor_lang::solana_program::hash::hash(&self.try_to_vec()?).to_bytes())
+    }
+
+    /// Check the settings the individual update instructions would reject
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.daily_limit >= self.max_sol_per_transaction
+                && self.soft_daily_limit <= self.daily_limit,
+            crate::errors::DimmError::InvalidLimitConfiguration
+        );
+        require!(
+            self.max_sol_per_hour == 0
+                || (self.max_sol_per_hour >= self.max_sol_per_transaction
+                    && self.max_sol_per_hour <= self.daily_limit),
+            crate::errors::DimmError::InvalidLimitConfiguration
+        );
+        require!(
+            self.daily_limit_usd == 0 || self.daily_limit_usd >= self.max_usd_per_transaction,
+            crate::errors::DimmError::InvalidLimitConfiguration
+        );
+        require!(
+            self.max_slippage_bps <= 10_000,
+            crate::errors::DimmError::InvalidSlippage
+        );
+        require!(
+            self.maintenance_allowance <= MAX_MAINTENANCE_ALLOWANCE,
+            crate::errors::DimmError::ExceedsMaintenanceAllowance
+        );
+        require!(
+            self.withdrawal_delay >= 0 && self.withdrawal_delay <= MAX_WITHDRAWAL_DELAY_SECONDS,
+            crate::errors::DimmError::InvalidWithdrawalPolicy
+        );
+        require!(
+            self.retention_max_records <= MAX_RETENTION_RECORDS && self.retention_max_age >= 0,
+            crate::errors::DimmError::InvalidRetentionPolicy
+        );
+        Ok(())
+    }
+}
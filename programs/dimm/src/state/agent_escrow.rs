@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+/// Holds an agent's idle funds while escrow funding is on; executions pull
+/// only what each spend needs into the agent
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AgentEscrow {
+    /// Agent the escrow funds
+    pub agent: Pubkey,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl AgentEscrow {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        1;   // bump
+
+    /// Lamports held above the escrow's rent
+    pub fn available(escrow: &AccountInfo) -> Result<u64> {
+        let rent_exempt = Rent::get()?.minimum_balance(escrow.data_len());
+        Ok(escrow.lamports().saturating_sub(rent_exempt))
+    }
+
+    /// Move `amount` lamports from the escrow into `destination`
+    pub fn release(escrow: &AccountInfo, destination: &AccountInfo, amount: u64) -> Result<()> {
+        require!(
+            Self::available(escrow)? >= amount,
+            crate::errors::DimmError::InsufficientAgentBalance
+        );
+        **escrow.try_borrow_mut_lamports()? -= amount;
+        **destination.try_borrow_mut_lamports()? += amount;
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+/// Escrowed sale offer for an agent
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AgentListing {
+    /// Agent being sold
+    pub agent: Pubkey,
+    
+    /// Main wallet selling the agent
+    pub seller: Pubkey,
+    
+    /// Asking price (in lamports)
+    pub price: u64,
+    
+    /// Timestamp when the agent was listed
+    pub listed_at: i64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+    
+    /// Reserved space
+    pub reserved: [u8; 32],
+}
+
+impl AgentListing {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        32 + // seller
+        8 +  // price
+        8 +  // listed_at
+        1 +  // bump
+        32;  // reserved
+}
@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 /// Detailed statistics for an agent
 #[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AgentStats {
     /// Agent account this belongs to
     pub agent: Pubkey,
@@ -30,6 +31,9 @@ pub struct AgentStats {
     /// Total SOL spent on DeFi
     pub sol_spent_defi: u64,
     
+    /// Total SOL burned or donated
+    pub sol_spent_donations: u64,
+    
     /// Average transaction size (in lamports)
     pub avg_transaction_size: u64,
     
@@ -42,7 +46,7 @@ pub struct AgentStats {
     /// Number of times transaction limit was hit
     pub tx_limit_hits: u32,
     
-    /// Total gas fees paid
+    /// Total network fees (base and priority) reported by executions
     pub total_gas_paid: u64,
     
     /// Last activity timestamp
@@ -51,17 +55,30 @@ pub struct AgentStats {
     /// Longest inactive period (in seconds)
     pub longest_inactive_period: i64,
     
-    /// Total unique destinations interacted with
+    /// Total unique destinations interacted with, estimated with `destination_filter`
     pub unique_destinations: u32,
     
+    /// Bloom filter of destinations paid so far
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
+    pub destination_filter: [u8; 512],
+    
+    /// Layout version, upgraded by `migrate_account`
+    pub version: u8,
+    
     /// Bump seed for PDA
     pub bump: u8,
     
     /// Reserved space
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
     pub reserved: [u8; 64],
 }
 
 impl AgentStats {
+    pub const DESTINATION_FILTER_BYTES: usize = 512;
+    
+    /// Bits set per destination in the bloom filter
+    pub const DESTINATION_FILTER_HASHES: usize = 4;
+    
     pub const LEN: usize = 8 + // discriminator
         32 + // agent
         8 +  // successful_transactions
@@ -72,6 +89,7 @@ impl AgentStats {
         8 +  // sol_spent_staking
         8 +  // sol_spent_governance
         8 +  // sol_spent_defi
+        8 +  // sol_spent_donations
         8 +  // avg_transaction_size
         8 +  // largest_transaction
         4 +  // daily_limit_hits
@@ -80,9 +98,93 @@ impl AgentStats {
         8 +  // last_activity
         8 +  // longest_inactive_period
         4 +  // unique_destinations
+        Self::DESTINATION_FILTER_BYTES + // destination_filter
+        1 +  // version
         1 +  // bump
         64;  // reserved
 
+    /// Load the agent's stats if they have been initialized.
+    /// Returns `None` when the PDA has not been initialized.
+    pub fn load(stats: &AccountInfo) -> Result<Option<AgentStats>> {
+        if stats.data_is_empty() {
+            return Ok(None);
+        }
+
+        require_keys_eq!(*stats.owner, crate::ID, crate::errors::DimmError::Unauthorized);
+        let data = stats.try_borrow_data()?;
+        Ok(Some(AgentStats::try_deserialize(&mut &data[..])?))
+    }
+
+    /// Write stats loaded with `load` back to their account
+    pub fn store(&self, stats: &AccountInfo) -> Result<()> {
+        let mut data = stats.try_borrow_mut_data()?;
+        self.try_serialize(&mut &mut data[..])
+    }
+
+    /// Track the gap since the previous activity
+    pub fn record_activity_time(&mut self, current_time: i64) {
+        if self.last_activity > 0 {
+            let inactive = current_time.saturating_sub(self.last_activity);
+            self.longest_inactive_period = self.longest_inactive_period.max(inactive);
+        }
+        self.last_activity = current_time;
+    }
+
+    /// Add `destination` to the bloom filter and count it if it was not
+    /// already there. False positives make `unique_destinations` a slight
+    /// undercount (about 1% at 400 destinations), never an overcount.
+    pub fn record_destination(&mut self, destination: &Pubkey) -> Result<bool> {
+        let hash = anchor_lang::solana_program::hash::hashv(&[
+            b"dimm_destination_filter",
+            destination.as_ref(),
+        ])
+        .to_bytes();
+
+        let filter_bits = Self::DESTINATION_FILTER_BYTES * 8;
+        let mut is_new = false;
+        for i in 0..Self::DESTINATION_FILTER_HASHES {
+            let bit = u32::from_le_bytes([hash[4 * i], hash[4 * i + 1], hash[4 * i + 2], hash[4 * i + 3]])
+                as usize
+                % filter_bits;
+            let mask = 1u8 << (bit % 8);
+            if self.destination_filter[bit / 8] & mask == 0 {
+                self.destination_filter[bit / 8] |= mask;
+                is_new = true;
+            }
+        }
+
+        if is_new {
+            self.unique_destinations = self.unique_destinations
+                .checked_add(1)
+                .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+        }
+        Ok(is_new)
+    }
+
+    /// Accumulate network fees reported by an execution
+    pub fn record_gas(&mut self, fee_lamports: u64) {
+        self.total_gas_paid = self.total_gas_paid.saturating_add(fee_lamports);
+    }
+
+    /// Count spends that reach the per-transaction limit or use up the
+    /// daily limit. Spends beyond a limit are rejected and leave no trace,
+    /// so a hit is recorded when a successful spend lands exactly on it.
+    pub fn record_limit_hits(&mut self, amount: u64, agent: &crate::state::AgentAccount) -> Result<()> {
+        if amount > 0 && amount >= agent.max_sol_per_transaction {
+            self.tx_limit_hits = self.tx_limit_hits
+                .checked_add(1)
+                .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+        }
+
+        if amount > 0 && agent.spent_today >= agent.daily_limit {
+            self.daily_limit_hits = self.daily_limit_hits
+                .checked_add(1)
+                .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+        }
+
+        Ok(())
+    }
+
     /// Update stats after a transaction
     pub fn record_transaction(
         &mut self,
@@ -127,6 +229,13 @@ impl AgentStats {
                         .checked_add(amount)
                         .ok_or(crate::errors::DimmError::NumericalOverflow)?;
                 }
+                // Kept out of the average and largest-transaction stats below
+                crate::state::ActivityType::Donation => {
+                    self.sol_spent_donations = self.sol_spent_donations
+                        .checked_add(amount)
+                        .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+                    return Ok(());
+                }
                 _ => {}
             }
             
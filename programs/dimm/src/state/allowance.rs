@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+/// Recurring budget paid to an agent from lamports escrowed in this account
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Allowance {
+    /// Agent receiving the allowance
+    pub agent: Pubkey,
+    
+    /// Main wallet that funds the escrow
+    pub main_wallet: Pubkey,
+    
+    /// Lamports paid out each period
+    pub amount_per_period: u64,
+    
+    /// Length of a period (in seconds)
+    pub period_seconds: i64,
+    
+    /// Earliest timestamp of the next claim
+    pub next_claim_at: i64,
+    
+    /// Lamports paid out so far
+    pub total_claimed: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Allowance {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        32 + // main_wallet
+        8 +  // amount_per_period
+        8 +  // period_seconds
+        8 +  // next_claim_at
+        8 +  // total_claimed
+        1;   // bump
+
+    /// Check if the current period's payout can be claimed
+    pub fn is_claimable(&self, current_time: i64) -> bool {
+        current_time >= self.next_claim_at
+    }
+}
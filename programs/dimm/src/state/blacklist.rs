@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+/// Destinations an agent may never send to, whatever its whitelist says
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Blacklist {
+    /// Agent this blacklist belongs to
+    pub agent: Pubkey,
+    
+    /// Blocked addresses (max 100), kept sorted for binary search
+    pub addresses: Vec<Pubkey>,
+    
+    /// Last updated timestamp
+    pub last_updated: i64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+    
+    /// Reserved space
+    pub reserved: [u8; 32],
+}
+
+impl Blacklist {
+    pub const MAX_ADDRESSES: usize = 100;
+    
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        4 + (32 * Self::MAX_ADDRESSES) + // addresses
+        8 +  // last_updated
+        1 +  // bump
+        32;  // reserved
+
+    /// Load the agent's blacklist; `None` if it was never initialized
+    pub fn load(blacklist: &AccountInfo) -> Result<Option<Blacklist>> {
+        if blacklist.data_is_empty() {
+            return Ok(None);
+        }
+
+        require_keys_eq!(*blacklist.owner, crate::ID, crate::errors::DimmError::Unauthorized);
+        let data = blacklist.try_borrow_data()?;
+        Ok(Some(Blacklist::try_deserialize(&mut &data[..])?))
+    }
+
+    /// Fail if the agent's blacklist, when it has one, blocks `destination`
+    pub fn require_not_blacklisted(blacklist: &AccountInfo, destination: &Pubkey) -> Result<()> {
+        if let Some(blacklist) = Self::load(blacklist)? {
+            require!(
+                !blacklist.contains(destination),
+                crate::errors::DimmError::DestinationBlacklisted
+            );
+        }
+        Ok(())
+    }
+
+    /// Binary search over the sorted address list
+    pub fn contains(&self, address: &Pubkey) -> bool {
+        self.addresses.binary_search(address).is_ok()
+    }
+
+    /// Block an address, keeping the list sorted
+    pub fn add_address(&mut self, address: Pubkey) -> Result<()> {
+        if let Err(index) = self.addresses.binary_search(&address) {
+            require!(
+                self.addresses.len() < Self::MAX_ADDRESSES,
+                crate::errors::DimmError::BlacklistFull
+            );
+            self.addresses.insert(index, address);
+        }
+        Ok(())
+    }
+
+    /// Unblock an address, keeping the list sorted
+    pub fn remove_address(&mut self, address: &Pubkey) {
+        if let Ok(index) = self.addresses.binary_search(address) {
+            self.addresses.remove(index);
+        }
+    }
+}
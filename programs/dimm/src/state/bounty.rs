@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+/// Bug bounty escrowed from the wallet's treasury
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bounty {
+    /// Treasury authority that funds and approves the bounty
+    pub authority: Pubkey,
+    
+    /// Bounty identifier (chosen by the authority, unique per authority)
+    pub bounty_id: u64,
+    
+    /// Reward escrowed in this account (in lamports)
+    pub reward: u64,
+    
+    /// Hash of the off-chain bounty scope
+    pub scope_hash: [u8; 32],
+    
+    /// Researcher who claimed the bounty
+    pub claimant: Option<Pubkey>,
+    
+    /// Hash of the researcher's off-chain report
+    pub report_hash: [u8; 32],
+    
+    /// Current status
+    pub status: BountyStatus,
+    
+    /// Timestamp when the bounty was opened
+    pub created_at: i64,
+    
+    /// Timestamp when the bounty was claimed (0 = not claimed)
+    pub claimed_at: i64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+    
+    /// Reserved space
+    pub reserved: [u8; 32],
+}
+
+impl Bounty {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        8 +  // bounty_id
+        8 +  // reward
+        32 + // scope_hash
+        1 + 32 + // claimant
+        32 + // report_hash
+        1 +  // status
+        8 +  // created_at
+        8 +  // claimed_at
+        1 +  // bump
+        32;  // reserved
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BountyStatus {
+    /// Open for claims
+    Open,
+    
+    /// A researcher submitted a report awaiting the authority
+    Claimed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreateBountyParams {
+    /// Bounty identifier (unique per authority)
+    pub bounty_id: u64,
+    
+    /// Reward escrowed from the treasury (in lamports)
+    pub reward: u64,
+    
+    /// Hash of the off-chain bounty scope
+    pub scope_hash: [u8; 32],
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_CO_SIGNERS;
+
+/// Keys that co-sign an agent's transactions in the higher amount bands
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CoSignerSet {
+    /// Agent the co-signers approve for
+    pub agent: Pubkey,
+    
+    /// Co-signer keys; approvals refer to them by index
+    pub co_signers: Vec<Pubkey>,
+    
+    /// Bumped on every change, invalidating approvals given to the old set
+    pub generation: u32,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl CoSignerSet {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        4 + (32 * MAX_CO_SIGNERS) + // co_signers
+        4 +  // generation
+        1;   // bump
+
+    /// Index of `key` in the set
+    pub fn index_of(&self, key: &Pubkey) -> Option<usize> {
+        self.co_signers.iter().position(|co_signer| co_signer == key)
+    }
+}
+
+/// Amounts above `above` need `co_signers` co-signer approvals
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AmountBand {
+    /// Lower bound of the band (in lamports, exclusive)
+    pub above: u64,
+    
+    /// Co-signer approvals required within the band
+    pub co_signers: u8,
+}
+
+impl AmountBand {
+    pub const LEN: usize = 8 + 1;
+}
@@ -0,0 +1,224 @@
+use anchor_lang::prelude::*;
+
+/// Interest-free working-capital line from the treasury to an agent
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreditLine {
+    /// Agent account this credit line belongs to
+    pub agent: Pubkey,
+    
+    /// Treasury the credit is drawn from
+    pub treasury: Pubkey,
+    
+    /// Maximum outstanding balance (in lamports)
+    pub credit_limit: u64,
+    
+    /// Currently borrowed and not yet repaid (in lamports)
+    pub outstanding: u64,
+    
+    /// Total borrowed all time (in lamports)
+    pub total_borrowed: u64,
+    
+    /// Total repaid all time (in lamports)
+    pub total_repaid: u64,
+    
+    /// Length of each repayment period (in seconds)
+    pub repayment_interval: i64,
+    
+    /// Minimum amount that must be repaid each period while outstanding
+    pub min_repayment: u64,
+    
+    /// End of the current repayment period
+    pub next_due_at: i64,
+    
+    /// Amount repaid in the current repayment period
+    pub repaid_this_period: u64,
+    
+    /// Whether the line is frozen after a missed repayment
+    pub frozen: bool,
+    
+    /// Timestamp when the line was approved
+    pub approved_at: i64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+    
+    /// Reserved space
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
+    pub reserved: [u8; 64],
+}
+
+impl CreditLine {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        32 + // treasury
+        8 +  // credit_limit
+        8 +  // outstanding
+        8 +  // total_borrowed
+        8 +  // total_repaid
+        8 +  // repayment_interval
+        8 +  // min_repayment
+        8 +  // next_due_at
+        8 +  // repaid_this_period
+        1 +  // frozen
+        8 +  // approved_at
+        1 +  // bump
+        64;  // reserved
+
+    /// Roll the repayment schedule forward past every elapsed period, freezing
+    /// the line if any of them was underpaid. Periods skipped entirely had no
+    /// repayment, so they count as missed whenever a minimum is due.
+    pub fn check_repayment_schedule(&mut self, current_time: i64) -> Result<()> {
+        if self.outstanding == 0 || current_time < self.next_due_at {
+            return Ok(());
+        }
+
+        let periods = (current_time - self.next_due_at) / self.repayment_interval + 1;
+
+        let required = self.min_repayment.min(self.outstanding);
+        if required > 0 && (self.repaid_this_period < required || periods > 1) {
+            self.frozen = true;
+        }
+
+        self.next_due_at = periods
+            .checked_mul(self.repayment_interval)
+            .and_then(|elapsed| self.next_due_at.checked_add(elapsed))
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+        self.repaid_this_period = 0;
+        
+        Ok(())
+    }
+
+    /// Record a draw against the line
+    pub fn record_draw(&mut self, amount: u64, current_time: i64) -> Result<()> {
+        let new_outstanding = self.outstanding
+            .checked_add(amount)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+
+        require!(
+            new_outstanding <= self.credit_limit,
+            crate::errors::DimmError::ExceedsCreditLimit
+        );
+
+        // First draw on a clean line starts a fresh repayment period
+        if self.outstanding == 0 {
+            self.next_due_at = current_time
+                .checked_add(self.repayment_interval)
+                .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+            self.repaid_this_period = 0;
+        }
+
+        self.outstanding = new_outstanding;
+        self.total_borrowed = self.total_borrowed
+            .checked_add(amount)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+            
+        Ok(())
+    }
+
+    /// Record a repayment; clearing the balance lifts a freeze
+    pub fn record_repayment(&mut self, amount: u64) -> Result<()> {
+        self.outstanding = self.outstanding
+            .checked_sub(amount)
+            .ok_or(crate::errors::DimmError::ExceedsOutstandingCredit)?;
+            
+        self.total_repaid = self.total_repaid
+            .checked_add(amount)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+            
+        self.repaid_this_period = self.repaid_this_period
+            .checked_add(amount)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+
+        if self.outstanding == 0 {
+            self.frozen = false;
+        }
+        
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreditLineParams {
+    /// Maximum outstanding balance (in lamports)
+    pub credit_limit: u64,
+    
+    /// Length of each repayment period (in seconds)
+    pub repayment_interval: i64,
+    
+    /// Minimum amount that must be repaid each period
+    pub min_repayment: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = 86_400;
+
+    fn line() -> CreditLine {
+        CreditLine {
+            agent: Pubkey::default(),
+            treasury: Pubkey::default(),
+            credit_limit: 1_000,
+            outstanding: 500,
+            total_borrowed: 500,
+            total_repaid: 0,
+            repayment_interval: DAY,
+            min_repayment: 100,
+            next_due_at: DAY,
+            repaid_this_period: 0,
+            frozen: false,
+            approved_at: 0,
+            bump: 0,
+            reserved: [0; 64],
+        }
+    }
+
+    #[test]
+    fn late_check_in_keeps_the_original_due_dates() {
+        let mut line = line();
+        line.repaid_this_period = 100;
+
+        line.check_repayment_schedule(DAY + DAY / 2).unwrap();
+
+        assert!(!line.frozen);
+        assert_eq!(line.next_due_at, 2 * DAY);
+        assert_eq!(line.repaid_this_period, 0);
+    }
+
+    #[test]
+    fn late_check_in_freezes_an_underpaid_period() {
+        let mut line = line();
+        line.repaid_this_period = 99;
+
+        line.check_repayment_schedule(DAY + DAY / 2).unwrap();
+
+        assert!(line.frozen);
+        assert_eq!(line.next_due_at, 2 * DAY);
+    }
+
+    #[test]
+    fn skipped_periods_freeze_even_when_the_first_was_paid() {
+        let mut line = line();
+        line.repaid_this_period = 100;
+
+        line.check_repayment_schedule(3 * DAY + 1).unwrap();
+
+        assert!(line.frozen);
+        assert_eq!(line.next_due_at, 4 * DAY);
+        assert_eq!(line.repaid_this_period, 0);
+    }
+
+    #[test]
+    fn no_minimum_never_freezes() {
+        let mut line = line();
+        line.min_repayment = 0;
+
+        line.check_repayment_schedule(5 * DAY).unwrap();
+
+        assert!(!line.frozen);
+        assert_eq!(line.next_due_at, 6 * DAY);
+    }
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::constants::DAILY_WINDOW_SECONDS;
+
+/// One agent's activity over one UTC day, so dashboards can chart history
+/// without replaying activity records
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DailySnapshot {
+    /// Agent this snapshot belongs to
+    pub agent: Pubkey,
+    
+    /// Days since the unix epoch
+    pub day_index: u64,
+    
+    /// Lamports spent by executions that day
+    pub spent: u64,
+    
+    /// Executions that day
+    pub transactions: u64,
+    
+    /// Failed actions reported with `record_activity` that day
+    pub failures: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl DailySnapshot {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        8 +  // day_index
+        8 +  // spent
+        8 +  // transactions
+        8 +  // failures
+        1;   // bump
+
+    /// Index of the UTC day containing `timestamp`
+    pub fn day_index(timestamp: i64) -> u64 {
+        timestamp.div_euclid(DAILY_WINDOW_SECONDS).max(0) as u64
+    }
+
+    /// Load a snapshot passed to an execution, which must be the agent's
+    /// snapshot for the day containing `current_time`
+    pub fn load_current(info: &AccountInfo, agent: &Pubkey, current_time: i64) -> Result<DailySnapshot> {
+        require_keys_eq!(*info.owner, crate::ID, crate::errors::DimmError::Unauthorized);
+        let data = info.try_borrow_data()?;
+        let snapshot = DailySnapshot::try_deserialize(&mut &data[..])?;
+        require!(
+            snapshot.agent == *agent && snapshot.day_index == Self::day_index(current_time),
+            crate::errors::DimmError::SnapshotMismatch
+        );
+        Ok(snapshot)
+    }
+
+    /// Write a snapshot loaded with `load_current` back to its account
+    pub fn store(&self, info: &AccountInfo) -> Result<()> {
+        let mut data = info.try_borrow_mut_data()?;
+        self.try_serialize(&mut &mut data[..])
+    }
+
+    /// Count an execution spending `amount` lamports
+    pub fn record_transaction(&mut self, amount: u64) -> Result<()> {
+        self.spent = self.spent
+            .checked_add(amount)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+        self.transactions = self.transactions
+            .checked_add(1)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+        Ok(())
+    }
+
+    /// Count a reported failure
+    pub fn record_failure(&mut self) -> Result<()> {
+        self.failures = self.failures
+            .checked_add(1)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 /// Delegation allows agents to delegate permissions to sub-agents
 #[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Delegation {
     /// Parent agent
     pub parent_agent: Pubkey,
@@ -37,6 +38,7 @@ pub struct Delegation {
     pub bump: u8,
     
     /// Reserved space
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
     pub reserved: [u8; 64],
 }
 
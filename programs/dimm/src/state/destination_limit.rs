@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use crate::constants::{DAILY_WINDOW_SECONDS, DESTINATION_LIMIT_SEED};
+
+/// Spending limits of an agent towards one destination, on top of its own
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DestinationLimit {
+    /// Agent these limits apply to
+    pub agent: Pubkey,
+    
+    /// Destination the limits apply to
+    pub destination: Pubkey,
+    
+    /// Maximum lamports per transaction
+    pub max_per_transaction: u64,
+    
+    /// Daily limit (in lamports)
+    pub daily_limit: u64,
+    
+    /// Lamports sent to the destination today
+    pub spent_today: u64,
+    
+    /// Timestamp of last daily reset
+    pub last_daily_reset: i64,
+    
+    /// Total lamports sent to the destination all time
+    pub total_spent: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+    
+    /// Reserved space
+    pub reserved: [u8; 32],
+}
+
+impl DestinationLimit {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        32 + // destination
+        8 +  // max_per_transaction
+        8 +  // daily_limit
+        8 +  // spent_today
+        8 +  // last_daily_reset
+        8 +  // total_spent
+        1 +  // bump
+        32;  // reserved
+
+    /// PDA holding the limits of `agent` towards `destination`
+    pub fn address(agent: &Pubkey, destination: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[DESTINATION_LIMIT_SEED, agent.as_ref(), destination.as_ref()],
+            &crate::ID,
+        )
+        .0
+    }
+
+    /// Load the limits; `None` if the destination has none
+    pub fn load(destination_limit: &AccountInfo) -> Result<Option<DestinationLimit>> {
+        if destination_limit.data_is_empty() {
+            return Ok(None);
+        }
+
+        require_keys_eq!(*destination_limit.owner, crate::ID, crate::errors::DimmError::Unauthorized);
+        let data = destination_limit.try_borrow_data()?;
+        Ok(Some(DestinationLimit::try_deserialize(&mut &data[..])?))
+    }
+
+    /// Write the limits back after recording a spend
+    pub fn store(&self, destination_limit: &AccountInfo) -> Result<()> {
+        let mut data = destination_limit.try_borrow_mut_data()?;
+        self.try_serialize(&mut &mut data[..])
+    }
+
+    /// Check a spend against the limits, resetting the daily window first,
+    /// and record it
+    pub fn record_spend(&mut self, amount: u64, current_time: i64) -> Result<()> {
+        let time_since_reset = current_time
+            .checked_sub(self.last_daily_reset)
+            .ok_or(crate::errors::DimmError::InvalidActivityWindow)?;
+        if time_since_reset >= DAILY_WINDOW_SECONDS {
+            self.spent_today = 0;
+            self.last_daily_reset = current_time;
+        }
+
+        let new_daily_total = self
+            .spent_today
+            .checked_add(amount)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+
+        require!(
+            amount <= self.max_per_transaction,
+            crate::errors::DimmError::ExceedsDestinationTransactionLimit
+        );
+        require!(
+            new_daily_total <= self.daily_limit,
+            crate::errors::DimmError::ExceedsDestinationDailyLimit
+        );
+
+        self.spent_today = new_daily_total;
+        self.total_spent = self
+            .total_spent
+            .checked_add(amount)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 /// Emergency pause state for the protocol
 #[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EmergencyState {
     /// Protocol authority
     pub authority: Pubkey,
@@ -28,6 +29,7 @@ pub struct EmergencyState {
     pub bump: u8,
     
     /// Reserved space
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
     pub reserved: [u8; 128],
 }
 
@@ -53,5 +55,30 @@ impl EmergencyState {
         }
         self.emergency_contacts.contains(caller)
     }
+
+    /// Load the emergency state if the wallet has one.
+    /// Returns `None` when the PDA has not been initialized.
+    pub fn load(emergency_state: &AccountInfo) -> Result<Option<EmergencyState>> {
+        if emergency_state.data_is_empty() {
+            return Ok(None);
+        }
+
+        require_keys_eq!(
+            *emergency_state.owner,
+            crate::ID,
+            crate::errors::DimmError::Unauthorized
+        );
+        let data = emergency_state.try_borrow_data()?;
+        Ok(Some(EmergencyState::try_deserialize(&mut &data[..])?))
+    }
+
+    /// Fail if the wallet's emergency pause is active
+    pub fn require_not_paused(emergency_state: &AccountInfo) -> Result<()> {
+        if let Some(emergency_state) = Self::load(emergency_state)? {
+            require!(!emergency_state.paused, crate::errors::DimmError::EmergencyPaused);
+        }
+
+        Ok(())
+    }
 }
 
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+/// Staging buffer for instruction data too large to pass alongside dimm's accounts
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExecutionBuffer {
+    /// Agent the buffered instruction executes for
+    pub agent: Pubkey,
+    
+    /// Program the buffered instruction targets
+    pub target_program: Pubkey,
+    
+    /// Final length of the instruction data
+    pub total_len: u32,
+    
+    /// Instruction data written so far
+    pub data: Vec<u8>,
+    
+    /// Timestamp when the buffer was opened
+    pub created_at: i64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ExecutionBuffer {
+    /// Account size for a buffer holding `total_len` bytes
+    pub fn space(total_len: u32) -> usize {
+        8 +  // discriminator
+        32 + // agent
+        32 + // target_program
+        4 +  // total_len
+        4 + total_len as usize + // data
+        8 +  // created_at
+        1    // bump
+    }
+
+    /// Check if every chunk has been written
+    pub fn is_complete(&self) -> bool {
+        self.data.len() == self.total_len as usize
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WriteExecutionBufferParams {
+    /// Program the buffered instruction targets
+    pub target_program: Pubkey,
+    
+    /// Final length of the instruction data
+    pub total_len: u32,
+    
+    /// Offset of this chunk; chunks must be written in order
+    pub offset: u32,
+    
+    /// Chunk of instruction data
+    pub chunk: Vec<u8>,
+}
@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+
+/// Layout version written to the first reserved byte. Zeroed reserved
+/// space (version 0) reads as an empty region.
+pub const EXTENSIONS_VERSION: u8 = 1;
+
+/// Optional per-account settings stored in reserved space as
+/// `[version] ([type][len][value])*`, terminated by a zero type byte.
+/// New settings get a new type here instead of a reallocation.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+pub enum ExtensionType {
+    /// Transfers below this amount are rejected (u64)
+    MinTransferAmount = 1,
+    
+    /// Integrator's identifier for the agent ([u8; 16])
+    ExternalId = 2,
+}
+
+/// Typed value of an extension
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExtensionValue {
+    MinTransferAmount(u64),
+    ExternalId([u8; 16]),
+}
+
+impl ExtensionValue {
+    pub fn extension_type(&self) -> ExtensionType {
+        match self {
+            ExtensionValue::MinTransferAmount(_) => ExtensionType::MinTransferAmount,
+            ExtensionValue::ExternalId(_) => ExtensionType::ExternalId,
+        }
+    }
+
+    /// Encoded value, without the type and length bytes
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(match self {
+            ExtensionValue::MinTransferAmount(amount) => amount.try_to_vec()?,
+            ExtensionValue::ExternalId(id) => id.try_to_vec()?,
+        })
+    }
+}
+
+/// Split a region into its `(type, value)` entries
+fn parse(region: &[u8]) -> Result<Vec<(u8, &[u8])>> {
+    let mut entries = Vec::new();
+    match region.first() {
+        None | Some(0) => return Ok(entries),
+        Some(&EXTENSIONS_VERSION) => {}
+        Some(_) => return err!(DimmError::InvalidExtensionData),
+    }
+
+    let mut offset = 1;
+    while offset < region.len() && region[offset] != 0 {
+        let extension_type = region[offset];
+        let len = *region.get(offset + 1).ok_or(DimmError::InvalidExtensionData)? as usize;
+        let start = offset + 2;
+        let value = region
+            .get(start..start + len)
+            .ok_or(DimmError::InvalidExtensionData)?;
+        entries.push((extension_type, value));
+        offset = start + len;
+    }
+
+    Ok(entries)
+}
+
+/// Re-encode `entries` over the whole region, zero-filling the rest
+fn encode(region: &mut [u8], entries: &[(u8, Vec<u8>)]) -> Result<()> {
+    let size = 1 + entries.iter().map(|(_, value)| 2 + value.len()).sum::<usize>();
+    require!(size <= region.len(), DimmError::ExtensionSpaceExhausted);
+
+    region.fill(0);
+    region[0] = EXTENSIONS_VERSION;
+    let mut offset = 1;
+    for (extension_type, value) in entries {
+        region[offset] = *extension_type;
+        region[offset + 1] = u8::try_from(value.len()).map_err(|_| error!(DimmError::InvalidExtensionData))?;
+        region[offset + 2..offset + 2 + value.len()].copy_from_slice(value);
+        offset += 2 + value.len();
+    }
+
+    Ok(())
+}
+
+/// Read the raw value of an extension, if set
+pub fn read_extension(region: &[u8], extension_type: ExtensionType) -> Result<Option<Vec<u8>>> {
+    Ok(parse(region)?
+        .into_iter()
+        .find(|(ty, _)| *ty == extension_type as u8)
+        .map(|(_, value)| value.to_vec()))
+}
+
+/// Set an extension, replacing any previous value
+pub fn write_extension(region: &mut [u8], value: &ExtensionValue) -> Result<()> {
+    let extension_type = value.extension_type() as u8;
+    let mut entries: Vec<(u8, Vec<u8>)> = parse(region)?
+        .into_iter()
+        .filter(|(ty, _)| *ty != extension_type)
+        .map(|(ty, value)| (ty, value.to_vec()))
+        .collect();
+    entries.push((extension_type, value.to_bytes()?));
+    encode(region, &entries)
+}
+
+/// Remove an extension if it is set
+pub fn remove_extension(region: &mut [u8], extension_type: ExtensionType) -> Result<()> {
+    let entries: Vec<(u8, Vec<u8>)> = parse(region)?
+        .into_iter()
+        .filter(|(ty, _)| *ty != extension_type as u8)
+        .map(|(ty, value)| (ty, value.to_vec()))
+        .collect();
+    encode(region, &entries)
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_REASON_LENGTH;
+
+/// Agent's request for SOL, waiting on the main wallet
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FundingRequest {
+    /// Agent asking for funds
+    pub agent: Pubkey,
+    
+    /// Main wallet that approves or denies
+    pub main_wallet: Pubkey,
+    
+    /// Agent signer that made the request and paid its rent
+    pub requester: Pubkey,
+    
+    /// Request identifier (unique per agent)
+    pub request_id: u64,
+    
+    /// Amount requested (in lamports)
+    pub amount: u64,
+    
+    /// Why the agent needs the funds
+    pub reason: String,
+    
+    /// Timestamp when the request was made
+    pub requested_at: i64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl FundingRequest {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        32 + // main_wallet
+        32 + // requester
+        8 +  // request_id
+        8 +  // amount
+        4 + MAX_REASON_LENGTH + // reason
+        8 +  // requested_at
+        1;   // bump
+}
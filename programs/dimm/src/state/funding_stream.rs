@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+/// Funding escrowed up front and released to the agent at a fixed rate
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FundingStream {
+    /// Agent receiving the stream
+    pub agent: Pubkey,
+    
+    /// Main wallet that funded the stream
+    pub main_wallet: Pubkey,
+    
+    /// Lamports vested per second
+    pub rate: u64,
+    
+    /// Timestamp vesting starts from
+    pub start_at: i64,
+    
+    /// Nothing can be withdrawn before this timestamp
+    pub cliff_at: i64,
+    
+    /// Timestamp vesting stops
+    pub end_at: i64,
+    
+    /// Lamports withdrawn so far
+    pub withdrawn: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl FundingStream {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        32 + // main_wallet
+        8 +  // rate
+        8 +  // start_at
+        8 +  // cliff_at
+        8 +  // end_at
+        8 +  // withdrawn
+        1;   // bump
+
+    /// Lamports streamed between `start_at` and `until`
+    fn streamed(&self, until: i64) -> Result<u64> {
+        let elapsed = until.min(self.end_at).saturating_sub(self.start_at).max(0) as u64;
+        elapsed
+            .checked_mul(self.rate)
+            .ok_or(error!(crate::errors::DimmError::NumericalOverflow))
+    }
+
+    /// Lamports the whole stream releases
+    pub fn total(&self) -> Result<u64> {
+        self.streamed(self.end_at)
+    }
+
+    /// Lamports vested by `current_time`, zero before the cliff
+    pub fn vested(&self, current_time: i64) -> Result<u64> {
+        if current_time < self.cliff_at {
+            return Ok(0);
+        }
+        self.streamed(current_time)
+    }
+
+    /// Lamports vested but not yet withdrawn
+    pub fn withdrawable(&self, current_time: i64) -> Result<u64> {
+        Ok(self.vested(current_time)?.saturating_sub(self.withdrawn))
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FundingStreamParams {
+    /// Lamports vested per second
+    pub rate: u64,
+    
+    /// Timestamp vesting starts from
+    pub start_at: i64,
+    
+    /// Nothing can be withdrawn before this timestamp
+    pub cliff_at: i64,
+    
+    /// Timestamp vesting stops
+    pub end_at: i64,
+}
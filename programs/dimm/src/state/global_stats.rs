@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+
+/// Protocol-wide usage counters across every wallet, for live TVL and
+/// usage dashboards
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GlobalStats {
+    /// Agents created across all wallets since the stats were initialized
+    pub total_agents: u64,
+    
+    /// Agents revoked across all wallets since the stats were initialized
+    pub revoked_agents: u64,
+    
+    /// Lamports moved by agent executions
+    pub total_volume: u64,
+    
+    /// Protocol fees charged on agent executions
+    pub total_fees: u64,
+    
+    /// Epoch `active_agents` is counted for
+    pub epoch: u64,
+    
+    /// Distinct agents that executed during `epoch`
+    pub active_agents: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+    
+    /// Reserved space for future upgrades
+    pub reserved: [u8; 32],
+}
+
+impl GlobalStats {
+    pub const LEN: usize = 8 + // discriminator
+        8 +  // total_agents
+        8 +  // revoked_agents
+        8 +  // total_volume
+        8 +  // total_fees
+        8 +  // epoch
+        8 +  // active_agents
+        1 +  // bump
+        32;  // reserved
+
+    /// Load the global stats if they have been initialized.
+    /// Returns `None` when the PDA has not been initialized.
+    pub fn load(global_stats: &AccountInfo) -> Result<Option<GlobalStats>> {
+        if global_stats.data_is_empty() {
+            return Ok(None);
+        }
+
+        require_keys_eq!(*global_stats.owner, crate::ID, crate::errors::DimmError::Unauthorized);
+        let data = global_stats.try_borrow_data()?;
+        Ok(Some(GlobalStats::try_deserialize(&mut &data[..])?))
+    }
+
+    /// Write stats loaded with `load` back to their account
+    pub fn store(&self, global_stats: &AccountInfo) -> Result<()> {
+        let mut data = global_stats.try_borrow_mut_data()?;
+        self.try_serialize(&mut &mut data[..])
+    }
+
+    /// Count a newly created agent
+    pub fn record_agent_created(&mut self) -> Result<()> {
+        self.total_agents = self.total_agents
+            .checked_add(1)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+        Ok(())
+    }
+
+    /// Count a revoked agent
+    pub fn record_agent_revoked(&mut self) -> Result<()> {
+        self.revoked_agents = self.revoked_agents
+            .checked_add(1)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+        Ok(())
+    }
+
+    /// Fold one execution into the totals. `first_this_epoch` is whether
+    /// this is the agent's first execution in `epoch`.
+    pub fn record_execution(
+        &mut self,
+        lamports: u64,
+        fee: u64,
+        epoch: u64,
+        first_this_epoch: bool,
+    ) -> Result<()> {
+        let overflow = || error!(crate::errors::DimmError::NumericalOverflow);
+
+        if epoch != self.epoch {
+            self.epoch = epoch;
+            self.active_agents = 0;
+        }
+        if first_this_epoch {
+            self.active_agents = self.active_agents.checked_add(1).ok_or_else(overflow)?;
+        }
+
+        self.total_volume = self.total_volume.checked_add(lamports).ok_or_else(overflow)?;
+        self.total_fees = self.total_fees.checked_add(fee).ok_or_else(overflow)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+/// `GovernanceInstruction::CastVote` tag in the SPL governance program
+pub const CAST_VOTE_TAG: u8 = 13;
+
+/// A vote on an SPL governance proposal, serialized the same way as
+/// spl-governance's `Vote`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GovernanceVote {
+    /// Vote for the given options
+    Approve(Vec<VoteChoice>),
+    
+    /// Vote against the proposal
+    Deny,
+    
+    /// Abstain, counting towards quorum only
+    Abstain,
+    
+    /// Veto the proposal
+    Veto,
+}
+
+/// Weight given to one option of a proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VoteChoice {
+    /// Rank of the option (0 for single-choice proposals)
+    pub rank: u8,
+    
+    /// Share of the voter's weight, in percent
+    pub weight_percentage: u8,
+}
+
+impl GovernanceVote {
+    /// Instruction data of the `CastVote` instruction for this vote
+    pub fn cast_vote_data(&self) -> Result<Vec<u8>> {
+        let mut data = vec![CAST_VOTE_TAG];
+        data.extend_from_slice(&self.try_to_vec()?);
+        Ok(data)
+    }
+}
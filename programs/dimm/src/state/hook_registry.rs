@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+
+/// External policy programs consulted by an agent's execute path
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HookRegistry {
+    /// Agent these hooks apply to
+    pub agent: Pubkey,
+    
+    /// Registered hook programs, invoked in order
+    pub hooks: Vec<PolicyHook>,
+    
+    /// Last updated timestamp
+    pub last_updated: i64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+    
+    /// Reserved space
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
+    pub reserved: [u8; 64],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PolicyHook {
+    /// Hook program ID
+    pub program_id: Pubkey,
+    
+    /// When and how the hook is invoked
+    pub kind: HookKind,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HookKind {
+    /// Invoked before execution; must approve the proposed action
+    Approval,
+    
+    /// Invoked after execution with its summary; `fatal` decides whether a
+    /// non-acknowledging hook fails the transaction or only emits a warning
+    PostExecution { fatal: bool },
+}
+
+impl HookRegistry {
+    pub const MAX_HOOKS: usize = 4;
+    
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        4 + (Self::MAX_HOOKS * (32 + 2)) + // hooks
+        8 +  // last_updated
+        1 +  // bump
+        64;  // reserved
+
+    /// Approval hooks in invocation order
+    pub fn approval_hooks(&self) -> impl Iterator<Item = &PolicyHook> {
+        self.hooks.iter().filter(|hook| hook.kind == HookKind::Approval)
+    }
+
+    /// Post-execution hooks in invocation order, with their fatal flag
+    pub fn post_execution_hooks(&self) -> impl Iterator<Item = (&Pubkey, bool)> {
+        self.hooks.iter().filter_map(|hook| match hook.kind {
+            HookKind::PostExecution { fatal } => Some((&hook.program_id, fatal)),
+            _ => None,
+        })
+    }
+
+    /// Load the registry if the agent has one.
+    /// Returns `None` when the PDA has not been initialized.
+    pub fn load(registry: &AccountInfo) -> Result<Option<HookRegistry>> {
+        if registry.data_is_empty() {
+            return Ok(None);
+        }
+
+        require_keys_eq!(*registry.owner, crate::ID, DimmError::Unauthorized);
+        let data = registry.try_borrow_data()?;
+        Ok(Some(HookRegistry::try_deserialize(&mut &data[..])?))
+    }
+}
@@ -0,0 +1,261 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::{AgentAccount, AgentStats, AnomalyRules, InactivityAction, PermissionSet, RateLimit};
+
+/// `AgentAccount` as laid out before it was versioned
+#[derive(AnchorDeserialize)]
+pub struct AgentAccountV0 {
+    pub main_wallet: Pubkey,
+    pub agent_id: u64,
+    pub name: String,
+    pub permissions: PermissionSet,
+    pub max_sol_per_transaction: u64,
+    pub daily_limit: u64,
+    pub spent_today: u64,
+    pub last_daily_reset: i64,
+    pub total_spent: u64,
+    pub total_transactions: u64,
+    pub revoked: bool,
+    pub created_at: i64,
+    pub last_used_at: i64,
+    pub leaf_index: u32,
+    pub bump: u8,
+    pub reserved: [u8; 128],
+}
+
+impl AgentAccountV0 {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // main_wallet
+        8 +  // agent_id
+        4 + MAX_AGENT_NAME_LENGTH + // name
+        4 + 20 + // permissions
+        8 +  // max_sol_per_transaction
+        8 +  // daily_limit
+        8 +  // spent_today
+        8 +  // last_daily_reset
+        8 +  // total_spent
+        8 +  // total_transactions
+        1 +  // revoked
+        8 +  // created_at
+        8 +  // last_used_at
+        4 +  // leaf_index
+        1 +  // bump
+        128; // reserved
+
+    /// Carry the agent over to the current layout. Settings it predates take
+    /// the defaults a new agent gets, except that the agent has no signer
+    /// until the main wallet sets one and is past its trial period.
+    pub fn into_current(self, merkle_tree: Pubkey) -> AgentAccount {
+        AgentAccount {
+            main_wallet: self.main_wallet,
+            agent_id: self.agent_id,
+            name: self.name,
+            agent_signer: Pubkey::default(),
+            permissions: self.permissions,
+            max_sol_per_transaction: self.max_sol_per_transaction,
+            daily_limit: self.daily_limit,
+            spent_today: self.spent_today,
+            last_daily_reset: self.last_daily_reset,
+            total_spent: self.total_spent,
+            total_transactions: self.total_transactions,
+            revoked: self.revoked,
+            created_at: self.created_at,
+            last_used_at: self.last_used_at,
+            leaf_index: self.leaf_index,
+            merkle_tree,
+            maintenance_allowance: DEFAULT_MAINTENANCE_ALLOWANCE,
+            maintenance_spent_today: 0,
+            activity_sequence: 0,
+            activity_hash: [0; 32],
+            retention_max_records: 0,
+            retention_max_age: 0,
+            frozen_until: 0,
+            withdrawal_threshold: 0,
+            withdrawal_delay: 0,
+            critical_reserve: 0,
+            fee_tank: 0,
+            fee_tank_cap: 0,
+            fee_tank_spent: 0,
+            require_destination_whitelist: false,
+            reporting_key: None,
+            approval_threshold: 0,
+            proposal_count: 0,
+            rent_sponsored: false,
+            trial_ends_at: 0,
+            donation_allowance: 0,
+            donation_spent_today: 0,
+            revoked_at: 0,
+            activity_root: [0; 32],
+            merkleized_through: 0,
+            merkleized_head: [0; 32],
+            receipt_sequence: 0,
+            soft_daily_limit: 0,
+            soft_limit_pending_ack: false,
+            lease_holder: Pubkey::default(),
+            lease_expires_at: 0,
+            lease_threshold: 0,
+            timed_permissions: Vec::new(),
+            daily_limit_usd: 0,
+            max_usd_per_transaction: 0,
+            usd_spent_today: 0,
+            max_slippage_bps: DEFAULT_MAX_SLIPPAGE_BPS,
+            open_token_accounts: 0,
+            last_active_epoch: 0,
+            description: None,
+            metadata_uri: None,
+            model_id: None,
+            schema_version: AGENT_SCHEMA_VERSION,
+            escrow_funding: false,
+            funding_request_count: 0,
+            inactivity_timeout_seconds: 0,
+            inactivity_action: InactivityAction::Revoke,
+            inactivity_keeper_reward: 0,
+            inactivity_reset_at: 0,
+            amount_bands: Vec::new(),
+            intent_nonce: 0,
+            nonce: 0,
+            active_hours_start: 0,
+            active_hours_end: 0,
+            active_weekdays: ALL_WEEKDAYS,
+            max_sol_per_hour: 0,
+            spent_this_hour: 0,
+            last_hourly_reset: 0,
+            trust_level: 0,
+            anomaly_rules: AnomalyRules::default(),
+            failures_this_hour: 0,
+            failure_window_start: 0,
+            change_delay_seconds: 0,
+            pending_permissions: 0,
+            pending_limits: None,
+            pending_change_delay: None,
+            pending_effective_at: 0,
+            config_locked: false,
+            cnft_burned: false,
+            pending_withdrawal_policy: None,
+            withdrawn_in_window: 0,
+            withdrawal_window_start: 0,
+            fee_tank_refund_slot: 0,
+            bump: self.bump,
+            reserved: self.reserved,
+        }
+    }
+}
+
+/// `AgentStats` as laid out before it was versioned
+#[derive(AnchorDeserialize)]
+pub struct AgentStatsV0 {
+    pub agent: Pubkey,
+    pub successful_transactions: u64,
+    pub failed_transactions: u64,
+    pub sol_spent_transfers: u64,
+    pub sol_spent_swaps: u64,
+    pub sol_spent_nfts: u64,
+    pub sol_spent_staking: u64,
+    pub sol_spent_governance: u64,
+    pub sol_spent_defi: u64,
+    pub avg_transaction_size: u64,
+    pub largest_transaction: u64,
+    pub daily_limit_hits: u32,
+    pub tx_limit_hits: u32,
+    pub total_gas_paid: u64,
+    pub last_activity: i64,
+    pub longest_inactive_period: i64,
+    pub unique_destinations: u32,
+    pub bump: u8,
+    pub reserved: [u8; 64],
+}
+
+impl AgentStatsV0 {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        8 +  // successful_transactions
+        8 +  // failed_transactions
+        8 +  // sol_spent_transfers
+        8 +  // sol_spent_swaps
+        8 +  // sol_spent_nfts
+        8 +  // sol_spent_staking
+        8 +  // sol_spent_governance
+        8 +  // sol_spent_defi
+        8 +  // avg_transaction_size
+        8 +  // largest_transaction
+        4 +  // daily_limit_hits
+        4 +  // tx_limit_hits
+        8 +  // total_gas_paid
+        8 +  // last_activity
+        8 +  // longest_inactive_period
+        4 +  // unique_destinations
+        1 +  // bump
+        64;  // reserved
+
+    /// Carry the stats over to the current layout. The destination filter
+    /// starts empty, so destinations paid before the migration may be
+    /// counted again once.
+    pub fn into_current(self) -> AgentStats {
+        AgentStats {
+            agent: self.agent,
+            successful_transactions: self.successful_transactions,
+            failed_transactions: self.failed_transactions,
+            sol_spent_transfers: self.sol_spent_transfers,
+            sol_spent_swaps: self.sol_spent_swaps,
+            sol_spent_nfts: self.sol_spent_nfts,
+            sol_spent_staking: self.sol_spent_staking,
+            sol_spent_governance: self.sol_spent_governance,
+            sol_spent_defi: self.sol_spent_defi,
+            sol_spent_donations: 0,
+            avg_transaction_size: self.avg_transaction_size,
+            largest_transaction: self.largest_transaction,
+            daily_limit_hits: self.daily_limit_hits,
+            tx_limit_hits: self.tx_limit_hits,
+            total_gas_paid: self.total_gas_paid,
+            last_activity: self.last_activity,
+            longest_inactive_period: self.longest_inactive_period,
+            unique_destinations: self.unique_destinations,
+            destination_filter: [0; AgentStats::DESTINATION_FILTER_BYTES],
+            version: AGENT_STATS_VERSION,
+            bump: self.bump,
+            reserved: self.reserved,
+        }
+    }
+}
+
+/// `RateLimit` as laid out before it was versioned
+#[derive(AnchorDeserialize)]
+pub struct RateLimitV0 {
+    pub agent: Pubkey,
+    pub max_tx_per_minute: u16,
+    pub max_tx_per_hour: u16,
+    pub minute_window_start: i64,
+    pub tx_this_minute: u16,
+    pub hour_window_start: i64,
+    pub tx_this_hour: u16,
+    pub cooldown_seconds: u32,
+    pub last_cooldown_start: i64,
+    pub in_cooldown: bool,
+    pub total_rate_limits: u32,
+    pub bump: u8,
+    pub reserved: [u8; 64],
+}
+
+impl RateLimitV0 {
+    pub const LEN: usize = RateLimit::LEN - 1; // without version
+
+    /// Carry the rate limit over to the current layout
+    pub fn into_current(self) -> RateLimit {
+        RateLimit {
+            agent: self.agent,
+            max_tx_per_minute: self.max_tx_per_minute,
+            max_tx_per_hour: self.max_tx_per_hour,
+            minute_window_start: self.minute_window_start,
+            tx_this_minute: self.tx_this_minute,
+            hour_window_start: self.hour_window_start,
+            tx_this_hour: self.tx_this_hour,
+            cooldown_seconds: self.cooldown_seconds,
+            last_cooldown_start: self.last_cooldown_start,
+            in_cooldown: self.in_cooldown,
+            total_rate_limits: self.total_rate_limits,
+            version: RATE_LIMIT_VERSION,
+            bump: self.bump,
+            reserved: self.reserved,
+        }
+    }
+}
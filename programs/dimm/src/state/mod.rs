@@ -7,6 +7,48 @@ pub mod whitelist;
 pub mod rate_limit;
 pub mod delegation;
 pub mod emergency;
+pub mod credit_line;
+pub mod watchtower;
+pub mod pending_withdrawal;
+pub mod withdrawal_addresses;
+pub mod hook_registry;
+pub mod priority_lanes;
+pub mod token_limit;
+pub mod agent_listing;
+pub mod execution_buffer;
+pub mod spend_approval;
+pub mod policy;
+pub mod pending_transaction;
+pub mod rent_pool;
+pub mod activity_schedule;
+pub mod wallet_stake;
+pub mod extensions;
+pub mod bounty;
+pub mod activity_proof;
+pub mod activity_tree;
+pub mod wallet_digest;
+pub mod address_book;
+pub mod destination_limit;
+pub mod blacklist;
+pub mod program_registry;
+pub mod plan;
+pub mod permissions;
+pub mod scoped_permissions;
+pub mod agent_config;
+pub mod price_feed;
+pub mod governance;
+pub mod daily_snapshot;
+pub mod global_stats;
+pub mod migration;
+pub mod allowance;
+pub mod funding_stream;
+pub mod agent_escrow;
+pub mod funding_request;
+pub mod co_signer_set;
+pub mod session_key;
+pub mod signed_intent;
+pub mod relayer_registry;
+pub mod wallet_budget;
 
 pub use protocol_config::*;
 pub use agent_account::*;
@@ -17,5 +59,47 @@ pub use whitelist::*;
 pub use rate_limit::*;
 pub use delegation::*;
 pub use emergency::*;
+pub use credit_line::*;
+pub use watchtower::*;
+pub use pending_withdrawal::*;
+pub use withdrawal_addresses::*;
+pub use hook_registry::*;
+pub use priority_lanes::*;
+pub use token_limit::*;
+pub use agent_listing::*;
+pub use execution_buffer::*;
+pub use spend_approval::*;
+pub use policy::*;
+pub use pending_transaction::*;
+pub use rent_pool::*;
+pub use activity_schedule::*;
+pub use wallet_stake::*;
+pub use extensions::*;
+pub use bounty::*;
+pub use activity_proof::*;
+pub use activity_tree::*;
+pub use wallet_digest::*;
+pub use address_book::*;
+pub use destination_limit::*;
+pub use blacklist::*;
+pub use program_registry::*;
+pub use plan::*;
+pub use permissions::*;
+pub use scoped_permissions::*;
+pub use agent_config::*;
+pub use price_feed::*;
+pub use governance::*;
+pub use daily_snapshot::*;
+pub use global_stats::*;
+pub use migration::*;
+pub use allowance::*;
+pub use funding_stream::*;
+pub use agent_escrow::*;
+pub use funding_request::*;
+pub use co_signer_set::*;
+pub use session_key::*;
+pub use signed_intent::*;
+pub use relayer_registry::*;
+pub use wallet_budget::*;
 
 
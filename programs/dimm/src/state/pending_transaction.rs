@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use crate::state::{ActivityType, ExecuteTransactionParams};
+
+/// Agent transaction above the approval threshold, awaiting the main wallet
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PendingTransaction {
+    /// Agent the transaction is executed from
+    pub agent: Pubkey,
+    
+    /// Main wallet that must approve
+    pub main_wallet: Pubkey,
+    
+    /// Signer that proposed the transaction and paid its rent
+    pub proposer: Pubkey,
+    
+    /// Proposal identifier (unique per agent)
+    pub proposal_id: u64,
+    
+    /// Proposed activity type
+    pub activity_type: ActivityType,
+    
+    /// Proposed amount
+    pub amount: u64,
+    
+    /// Proposed destination
+    pub destination: Option<Pubkey>,
+    
+    /// Proposed mint (token transfers only)
+    pub mint: Option<Pubkey>,
+    
+    /// Current status
+    pub status: ProposalStatus,
+    
+    /// Timestamp when the transaction was proposed
+    pub proposed_at: i64,
+    
+    /// Timestamp when the main wallet approved (0 = not approved)
+    pub approved_at: i64,
+    
+    /// Bitmask of co-signer indices that approved
+    pub co_signer_approvals: u8,
+    
+    /// `CoSignerSet` generation the approvals were given under
+    pub co_signer_generation: u32,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl PendingTransaction {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        32 + // main_wallet
+        32 + // proposer
+        8 +  // proposal_id
+        1 +  // activity_type
+        8 +  // amount
+        1 + 32 + // destination
+        1 + 32 + // mint
+        1 +  // status
+        8 +  // proposed_at
+        8 +  // approved_at
+        1 +  // co_signer_approvals
+        4 +  // co_signer_generation
+        1;   // bump
+
+    /// Co-signer approvals still valid for a set at `generation`
+    pub fn co_signer_approval_count(&self, generation: u32) -> u32 {
+        if self.co_signer_generation != generation {
+            return 0;
+        }
+        self.co_signer_approvals.count_ones()
+    }
+
+    /// Check if an execution is the one that was proposed
+    pub fn matches(&self, params: &ExecuteTransactionParams) -> bool {
+        self.activity_type == params.activity_type
+            && self.amount == params.amount
+            && self.destination == params.destination
+            && self.mint == params.mint
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProposalStatus {
+    /// Waiting for the main wallet
+    Pending,
+    
+    /// Approved; the proposer may execute it once
+    Approved,
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+/// Large withdrawal waiting out the agent's withdrawal delay
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PendingWithdrawal {
+    /// Agent the withdrawal is drawn from
+    pub agent: Pubkey,
+    
+    /// Main wallet receiving the funds
+    pub main_wallet: Pubkey,
+    
+    /// Amount to withdraw (in lamports)
+    pub amount: u64,
+    
+    /// Timestamp when the withdrawal was initiated
+    pub initiated_at: i64,
+    
+    /// Earliest timestamp the withdrawal can be finalized
+    pub executable_at: i64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl PendingWithdrawal {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        32 + // main_wallet
+        8 +  // amount
+        8 +  // initiated_at
+        8 +  // executable_at
+        1;   // bump
+
+    /// Check if the delay has elapsed
+    pub fn is_executable(&self, current_time: i64) -> bool {
+        current_time >= self.executable_at
+    }
+}
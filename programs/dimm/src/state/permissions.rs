@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use std::io::{Error, ErrorKind, Read};
+use crate::state::AgentPermission;
+
+/// Value of the length slot that marks the bitmask layout
+pub const PERMISSION_SET_LAYOUT: u32 = u32::MAX;
+
+/// An agent's permissions as a bitmask; bit `n` grants the permission
+/// with discriminant `n`.
+///
+/// Accounts written before the bitmask hold a `Vec<AgentPermission>`: a
+/// u32 length followed by one byte per permission. The bitmask layout puts
+/// `PERMISSION_SET_LAYOUT` in that length slot, so both layouts deserialize
+/// and older accounts switch to the bitmask the next time they are written.
+#[derive(AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PermissionSet {
+    /// Layout marker, always `PERMISSION_SET_LAYOUT` once written
+    pub layout: u32,
+    
+    /// Granted permissions
+    pub bits: u64,
+}
+
+impl PermissionSet {
+    /// Bytes the bitmask layout takes
+    pub const LEN: usize = 4 + 8;
+
+    /// Build a set from a bitmask, rejecting undefined bits
+    pub fn from_bits(bits: u64) -> Result<PermissionSet> {
+        require!(
+            bits & !AgentPermission::ALL == 0,
+            crate::errors::DimmError::InvalidPermission
+        );
+        Ok(PermissionSet {
+            layout: PERMISSION_SET_LAYOUT,
+            bits,
+        })
+    }
+
+    /// Check if a permission is granted
+    pub fn has(&self, permission: AgentPermission) -> bool {
+        self.bits & permission.bit() != 0
+    }
+
+    /// Grant a permission
+    pub fn grant(&mut self, permission: AgentPermission) {
+        self.bits |= permission.bit();
+    }
+
+    /// Revoke a permission
+    pub fn revoke(&mut self, permission: AgentPermission) {
+        self.bits &= !permission.bit();
+    }
+}
+
+impl Default for PermissionSet {
+    fn default() -> Self {
+        PermissionSet {
+            layout: PERMISSION_SET_LAYOUT,
+            bits: 0,
+        }
+    }
+}
+
+impl AnchorDeserialize for PermissionSet {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let length = u32::deserialize_reader(reader)?;
+        if length == PERMISSION_SET_LAYOUT {
+            let bits = u64::deserialize_reader(reader)?;
+            if bits & !AgentPermission::ALL != 0 {
+                return Err(Error::new(ErrorKind::InvalidData, "undefined permission bits"));
+            }
+            return Ok(PermissionSet { layout: length, bits });
+        }
+
+        // Legacy `Vec<AgentPermission>` layout
+        let mut set = PermissionSet::default();
+        for _ in 0..length {
+            set.grant(AgentPermission::deserialize_reader(reader)?);
+        }
+        Ok(set)
+    }
+}
+
+/// A permission granted until `expires_at`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimedPermission {
+    /// Permission granted
+    pub permission: AgentPermission,
+    
+    /// When the grant lapses (unix timestamp)
+    pub expires_at: i64,
+}
+
+impl TimedPermission {
+    pub const LEN: usize = 1 + 8;
+    
+    /// One entry per defined permission
+    pub const MAX_ENTRIES: usize = AgentPermission::ALL.count_ones() as usize;
+
+    /// Check if the grant has lapsed at `current_time`
+    pub fn is_expired(&self, current_time: i64) -> bool {
+        current_time >= self.expires_at
+    }
+}
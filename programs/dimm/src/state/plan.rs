@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use crate::constants::{MAX_PLAN_STEPS, MAX_PLAN_STEP_DATA};
+use crate::state::ActivityType;
+
+/// Multi-step execution (e.g. unstake -> swap -> transfer) run one step per
+/// transaction, budgeted as a single spend of its aggregate notional
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Plan {
+    /// Agent the plan executes from
+    pub agent: Pubkey,
+    
+    /// Plan identifier (unique per agent)
+    pub plan_id: u64,
+    
+    /// Signer that created the plan and paid its rent
+    pub creator: Pubkey,
+    
+    /// Steps, executed in order
+    pub steps: Vec<PlanStep>,
+    
+    /// Index of the next step to execute
+    pub next_step: u8,
+    
+    /// Current status
+    pub status: PlanStatus,
+    
+    /// Sum of the step amounts, charged to the daily limit by the first step
+    pub total_notional: u64,
+    
+    /// Sum of the amounts of the executed steps
+    pub executed_notional: u64,
+    
+    /// Agent balance after the last executed step (in lamports)
+    pub checkpoint_balance: u64,
+    
+    /// Timestamp of the last executed step
+    pub checkpoint_at: i64,
+    
+    /// Timestamp when the plan was created
+    pub created_at: i64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Plan {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        8 +  // plan_id
+        32 + // creator
+        4 + (PlanStep::LEN * MAX_PLAN_STEPS) + // steps
+        1 +  // next_step
+        1 +  // status
+        8 +  // total_notional
+        8 +  // executed_notional
+        8 +  // checkpoint_balance
+        8 +  // checkpoint_at
+        8 +  // created_at
+        1;   // bump
+
+    /// Check if steps remain to be executed
+    pub fn is_active(&self) -> bool {
+        matches!(self.status, PlanStatus::Pending | PlanStatus::InProgress)
+    }
+
+    /// Record the completion of the current step
+    pub fn checkpoint(&mut self, agent_balance: u64, current_time: i64) -> Result<()> {
+        let step = &self.steps[self.next_step as usize];
+        self.executed_notional = self
+            .executed_notional
+            .checked_add(step.amount)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+        self.next_step += 1;
+        self.checkpoint_balance = agent_balance;
+        self.checkpoint_at = current_time;
+        self.status = if self.next_step as usize == self.steps.len() {
+            PlanStatus::Completed
+        } else {
+            PlanStatus::InProgress
+        };
+        Ok(())
+    }
+}
+
+/// One step of a plan
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlanStep {
+    /// Type of the step, selecting the permission it needs
+    pub activity_type: ActivityType,
+    
+    /// Lamports the step may move: sent to `destination`, or at most this
+    /// much leaving the agent during a program call
+    pub amount: u64,
+    
+    /// Recipient of a SOL transfer step
+    pub destination: Option<Pubkey>,
+    
+    /// Program invoked by a program-call step
+    pub target_program: Option<Pubkey>,
+    
+    /// Instruction data for `target_program`
+    pub instruction_data: Vec<u8>,
+}
+
+impl PlanStep {
+    pub const LEN: usize = 1 + // activity_type
+        8 +  // amount
+        1 + 32 + // destination
+        1 + 32 + // target_program
+        4 + MAX_PLAN_STEP_DATA; // instruction_data
+
+    /// Check the step is either a SOL transfer or a program call
+    pub fn validate(&self) -> Result<()> {
+        let valid = match self.target_program {
+            Some(_) => self.instruction_data.len() <= MAX_PLAN_STEP_DATA,
+            None => {
+                self.activity_type == ActivityType::Transfer
+                    && self.destination.is_some()
+                    && self.instruction_data.is_empty()
+            }
+        };
+        require!(valid, crate::errors::DimmError::InvalidPlanStep);
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlanStatus {
+    /// No step executed yet
+    Pending,
+    
+    /// Some steps executed
+    InProgress,
+    
+    /// Every step executed
+    Completed,
+}
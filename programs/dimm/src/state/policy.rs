@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::AgentPermission;
+
+/// Spending policy enforced on-chain. Off-chain simulators and agent
+/// runtimes can depend on the crate without the `program` feature and
+/// evaluate the same checks against fetched accounts.
+pub trait SpendPolicy {
+    /// Reset the daily and hourly counters once their windows have elapsed
+    fn check_and_reset_daily_limit(&mut self, current_time: i64) -> Result<()>;
+
+    /// Check if the agent can spend the amount on an ordinary action
+    fn can_spend(&self, amount: u64) -> Result<bool> {
+        self.can_spend_in_lane(amount, false)
+    }
+
+    /// Check if the agent can spend the amount; only critical spends may
+    /// dip into the reserved slice of the daily limit
+    fn can_spend_in_lane(&self, amount: u64, critical: bool) -> Result<bool>;
+
+    /// Record a spend against the daily limit
+    fn record_spend(&mut self, amount: u64) -> Result<()>;
+
+    /// Record a fee-only maintenance spend against its separate allowance
+    fn record_maintenance_spend(&mut self, amount: u64) -> Result<()>;
+
+    /// Record a burn or donation against its separate allowance
+    fn record_donation_spend(&mut self, amount: u64) -> Result<()>;
+
+    /// Check if the agent is temporarily frozen
+    fn is_frozen(&self, current_time: i64) -> bool;
+
+    /// Check if the agent holds a permission
+    fn has_permission(&self, permission: &AgentPermission) -> bool;
+}
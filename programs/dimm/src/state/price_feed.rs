@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+
+/// Magic number at the start of every Pyth account
+const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+
+/// Pyth account type of a price account
+const PYTH_PRICE_ACCOUNT_TYPE: u32 = 3;
+
+/// Pyth aggregate status meaning the price is being published normally
+const PYTH_STATUS_TRADING: u32 = 1;
+
+/// Byte offsets into a Pyth price account
+const ACCOUNT_TYPE_OFFSET: usize = 8;
+const EXPONENT_OFFSET: usize = 20;
+const PUBLISH_TIME_OFFSET: usize = 96;
+const AGGREGATE_PRICE_OFFSET: usize = 208;
+const AGGREGATE_CONFIDENCE_OFFSET: usize = 216;
+const AGGREGATE_STATUS_OFFSET: usize = 224;
+const PRICE_ACCOUNT_MIN_LEN: usize = 240;
+
+/// SOL/USD aggregate price read from a Pyth price account.
+/// The value is `price * 10^exponent` dollars per SOL.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SolUsdPrice {
+    pub price: i64,
+    pub confidence: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+impl SolUsdPrice {
+    /// Parse a Pyth price account, which must be the protocol's configured
+    /// feed, and reject prices that are stale, not trading, or too uncertain
+    pub fn load(price_feed: &AccountInfo, expected_feed: &Pubkey, current_time: i64) -> Result<SolUsdPrice> {
+        require!(
+            *expected_feed != Pubkey::default() && price_feed.key() == *expected_feed,
+            crate::errors::DimmError::InvalidPriceFeed
+        );
+
+        let data = price_feed.try_borrow_data()?;
+        let price = Self::parse(&data)?;
+
+        require!(
+            current_time.saturating_sub(price.publish_time) <= MAX_PRICE_AGE_SECONDS,
+            crate::errors::DimmError::StalePrice
+        );
+        require!(
+            (price.confidence as u128) * 10_000
+                <= (price.price as u128) * MAX_PRICE_CONFIDENCE_BPS as u128,
+            crate::errors::DimmError::PriceConfidenceTooWide
+        );
+
+        Ok(price)
+    }
+
+    /// Decode the aggregate price from raw Pyth price account data
+    pub fn parse(data: &[u8]) -> Result<SolUsdPrice> {
+        require!(
+            data.len() >= PRICE_ACCOUNT_MIN_LEN
+                && read_u32(data, 0) == PYTH_MAGIC
+                && read_u32(data, ACCOUNT_TYPE_OFFSET) == PYTH_PRICE_ACCOUNT_TYPE,
+            crate::errors::DimmError::InvalidPriceFeed
+        );
+        require!(
+            read_u32(data, AGGREGATE_STATUS_OFFSET) == PYTH_STATUS_TRADING,
+            crate::errors::DimmError::StalePrice
+        );
+
+        let price = SolUsdPrice {
+            price: read_u64(data, AGGREGATE_PRICE_OFFSET) as i64,
+            confidence: read_u64(data, AGGREGATE_CONFIDENCE_OFFSET),
+            exponent: read_u32(data, EXPONENT_OFFSET) as i32,
+            publish_time: read_u64(data, PUBLISH_TIME_OFFSET) as i64,
+        };
+        require!(
+            price.price > 0 && (-18..=0).contains(&price.exponent),
+            crate::errors::DimmError::InvalidPriceFeed
+        );
+
+        Ok(price)
+    }
+
+    /// Value of `lamports` in USD cents, rounded up so limits are never
+    /// undercounted
+    pub fn lamports_to_usd_cents(&self, lamports: u64) -> Result<u64> {
+        let numerator = (lamports as u128)
+            .checked_mul(self.price as u128)
+            .and_then(|v| v.checked_mul(100))
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+        let denominator = (LAMPORTS_PER_SOL as u128)
+            .checked_mul(10u128.pow(self.exponent.unsigned_abs()))
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+        let cents = numerator.div_ceil(denominator);
+        u64::try_from(cents).map_err(|_| crate::errors::DimmError::NumericalOverflow.into())
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(&data[offset..offset + 4]);
+    u32::from_le_bytes(bytes)
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&data[offset..offset + 8]);
+    u64::from_le_bytes(bytes)
+}
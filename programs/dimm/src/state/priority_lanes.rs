@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use crate::state::ActivityType;
+
+/// Destinations and categories allowed to use an agent's critical reserve
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PriorityLanes {
+    /// Agent these lanes belong to
+    pub agent: Pubkey,
+    
+    /// Destinations treated as critical (e.g. loan repayment addresses)
+    pub destinations: Vec<Pubkey>,
+    
+    /// Activity types treated as critical
+    pub activity_types: Vec<ActivityType>,
+    
+    /// Last updated timestamp
+    pub last_updated: i64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+    
+    /// Reserved space
+    pub reserved: [u8; 32],
+}
+
+impl PriorityLanes {
+    pub const MAX_DESTINATIONS: usize = 16;
+    pub const MAX_ACTIVITY_TYPES: usize = 8;
+    
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        4 + (32 * Self::MAX_DESTINATIONS) + // destinations
+        4 + Self::MAX_ACTIVITY_TYPES + // activity_types
+        8 +  // last_updated
+        1 +  // bump
+        32;  // reserved
+
+    /// Check if a spend qualifies for the critical lane
+    pub fn is_critical(&self, activity_type: &ActivityType, destination: Option<&Pubkey>) -> bool {
+        if self.activity_types.contains(activity_type) {
+            return true;
+        }
+        destination
+            .map(|destination| self.destinations.contains(destination))
+            .unwrap_or(false)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PriorityLaneParams {
+    /// Slice of the daily limit reserved for critical spends (in lamports)
+    pub reserve: u64,
+    
+    /// Critical destinations
+    pub destinations: Vec<Pubkey>,
+    
+    /// Critical activity types
+    pub activity_types: Vec<ActivityType>,
+}
@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+
+/// Well-known programs (DEXes, marketplaces, lenders) labeled by the
+/// protocol authority, so wallets can whitelist them by id
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProgramRegistry {
+    /// Protocol config whose authority maintains the registry
+    pub protocol_config: Pubkey,
+    
+    /// Registered programs, kept sorted by id
+    pub entries: Vec<RegisteredProgram>,
+    
+    /// Last updated timestamp
+    pub last_updated: i64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+    
+    /// Reserved space
+    pub reserved: [u8; 32],
+}
+
+impl ProgramRegistry {
+    pub const MAX_ENTRIES: usize = 64;
+    
+    pub const LEN: usize = 8 + // discriminator
+        32 + // protocol_config
+        4 + (RegisteredProgram::LEN * Self::MAX_ENTRIES) + // entries
+        8 +  // last_updated
+        1 +  // bump
+        32;  // reserved
+
+    /// Load the registry; `None` if none was published
+    pub fn load(registry: &AccountInfo) -> Result<Option<ProgramRegistry>> {
+        if registry.data_is_empty() {
+            return Ok(None);
+        }
+
+        require_keys_eq!(*registry.owner, crate::ID, crate::errors::DimmError::Unauthorized);
+        let data = registry.try_borrow_data()?;
+        Ok(Some(ProgramRegistry::try_deserialize(&mut &data[..])?))
+    }
+
+    /// Fail if the wallet restricts unlabeled programs and `program` is not
+    /// in the registry
+    pub fn require_labeled(
+        registry: &AccountInfo,
+        restrict_unlabeled_programs: bool,
+        program: &Pubkey,
+    ) -> Result<()> {
+        if !restrict_unlabeled_programs {
+            return Ok(());
+        }
+
+        let labeled = Self::load(registry)?
+            .is_some_and(|registry| registry.find_program(program).is_some());
+        require!(labeled, crate::errors::DimmError::UnlabeledProgram);
+        Ok(())
+    }
+
+    /// Registered entry by id
+    pub fn get(&self, id: u16) -> Option<&RegisteredProgram> {
+        self.entries
+            .binary_search_by_key(&id, |entry| entry.id)
+            .ok()
+            .map(|index| &self.entries[index])
+    }
+
+    /// Registered entry for a program ID
+    pub fn find_program(&self, program_id: &Pubkey) -> Option<&RegisteredProgram> {
+        self.entries.iter().find(|entry| &entry.program_id == program_id)
+    }
+
+    /// Add or relabel an entry, keeping entries sorted by id
+    pub fn upsert(&mut self, entry: RegisteredProgram) -> Result<()> {
+        if let Some(existing) = self.find_program(&entry.program_id) {
+            require!(
+                existing.id == entry.id,
+                crate::errors::DimmError::ProgramAlreadyRegistered
+            );
+        }
+
+        match self.entries.binary_search_by_key(&entry.id, |existing| existing.id) {
+            Ok(index) => self.entries[index] = entry,
+            Err(index) => {
+                require!(
+                    self.entries.len() < Self::MAX_ENTRIES,
+                    crate::errors::DimmError::ProgramRegistryFull
+                );
+                self.entries.insert(index, entry);
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove an entry by id
+    pub fn remove(&mut self, id: u16) {
+        if let Ok(index) = self.entries.binary_search_by_key(&id, |entry| entry.id) {
+            self.entries.remove(index);
+        }
+    }
+}
+
+/// A labeled program in the registry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegisteredProgram {
+    /// Registry id wallets reference the program by
+    pub id: u16,
+    
+    /// Program ID
+    pub program_id: Pubkey,
+    
+    /// What the program does
+    pub category: ProgramCategory,
+    
+    /// Authority's risk assessment
+    pub risk: ProgramRisk,
+}
+
+impl RegisteredProgram {
+    pub const LEN: usize = 2 + 32 + 1 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProgramCategory {
+    Dex,
+    Marketplace,
+    Lending,
+    Staking,
+    Governance,
+    Other,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProgramRisk {
+    Low,
+    Medium,
+    High,
+}
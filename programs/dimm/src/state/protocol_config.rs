@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 /// Main protocol configuration account
 #[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ProtocolConfig {
     /// Main wallet authority
     pub authority: Pubkey,
@@ -9,7 +10,7 @@ pub struct ProtocolConfig {
     /// Merkle tree for storing agent cNFTs
     pub merkle_tree: Pubkey,
     
-    /// Total number of agents created
+    /// Total number of agents created; also the next agent's `agent_id`
     pub total_agents: u64,
     
     /// Protocol version
@@ -21,7 +22,52 @@ pub struct ProtocolConfig {
     /// Bump seed for PDA
     pub bump: u8,
     
+    /// Agents allowed per staked SOL, a cap the wallet sets on itself
+    /// (0 = no agent quota)
+    pub agents_per_staked_sol: u32,
+    
+    /// Aggregate daily limit allowed per staked SOL, a cap the wallet sets
+    /// on itself (0 = no limit quota)
+    pub daily_limit_per_staked_sol: u64,
+    
+    /// Sum of the daily limits of the wallet's live agents
+    pub aggregate_daily_limit: u64,
+    
+    /// Spends above this amount need a seasoned agent (0 = no cold-start policy)
+    pub cold_start_threshold: u64,
+    
+    /// Minimum agent age before spending above the threshold (in seconds)
+    pub cold_start_min_age: i64,
+    
+    /// Minimum successful transactions before spending above the threshold
+    pub cold_start_min_transactions: u64,
+    
+    /// Whether agents may only invoke programs labeled in the program registry
+    pub restrict_unlabeled_programs: bool,
+    
+    /// Pyth SOL/USD price account used for USD-denominated agent limits
+    pub sol_usd_price_feed: Pubkey,
+    
+    /// Limit ceilings and promotion requirements per agent trust level
+    pub trust_tiers: [TierConfig; 4],
+    
+    /// Highest daily limit any agent may be given (0 = no cap)
+    pub max_agent_daily_limit: u64,
+    
+    /// Highest per-transaction limit any agent may be given (0 = no cap)
+    pub max_agent_tx_limit: u64,
+    
+    /// Every tree agents have been or will be minted into, in minting order
+    pub merkle_trees: Vec<Pubkey>,
+    
+    /// Index of `merkle_tree` in `merkle_trees`; trees before it are full
+    pub current_tree: u8,
+    
+    /// Agents neither revoked, closed nor sold; counted against the stake cap
+    pub active_agents: u64,
+    
     /// Reserved space for future upgrades
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
     pub reserved: [u8; 64],
 }
 
@@ -33,16 +79,384 @@ impl ProtocolConfig {
         1 +  // version
         1 +  // paused
         1 +  // bump
+        4 +  // agents_per_staked_sol
+        8 +  // daily_limit_per_staked_sol
+        8 +  // aggregate_daily_limit
+        8 +  // cold_start_threshold
+        8 +  // cold_start_min_age
+        8 +  // cold_start_min_transactions
+        1 +  // restrict_unlabeled_programs
+        32 + // sol_usd_price_feed
+        (TierConfig::LEN * 4) + // trust_tiers
+        8 +  // max_agent_daily_limit
+        8 +  // max_agent_tx_limit
+        4 + (32 * crate::constants::MAX_MERKLE_TREES) + // merkle_trees
+        1 +  // current_tree
+        8 +  // active_agents
         64;  // reserved
+
+    /// Check agent count and aggregate daily limit against the quotas
+    /// earned by `effective_stake`
+    pub fn check_stake_quotas(
+        &self,
+        effective_stake: u64,
+        agent_count: u64,
+        aggregate_daily_limit: u64,
+    ) -> Result<()> {
+        let per_staked_sol = |rate: u64| -> Result<u64> {
+            let quota = (effective_stake as u128)
+                .checked_mul(rate as u128)
+                .map(|v| v / crate::constants::LAMPORTS_PER_SOL as u128)
+                .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+            Ok(u64::try_from(quota).unwrap_or(u64::MAX))
+        };
+
+        if self.agents_per_staked_sol > 0 {
+            require!(
+                agent_count <= per_staked_sol(self.agents_per_staked_sol as u64)?,
+                crate::errors::DimmError::AgentQuotaExceeded
+            );
+        }
+
+        if self.daily_limit_per_staked_sol > 0 {
+            require!(
+                aggregate_daily_limit <= per_staked_sol(self.daily_limit_per_staked_sol)?,
+                crate::errors::DimmError::LimitQuotaExceeded
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Check limits an agent at `trust_level` is being given against its
+    /// tier's ceilings
+    pub fn check_tier_limits(
+        &self,
+        trust_level: u8,
+        max_sol_per_transaction: u64,
+        daily_limit: u64,
+    ) -> Result<()> {
+        let tier = &self.trust_tiers[trust_level as usize];
+        require!(
+            (tier.max_sol_per_transaction == 0 || max_sol_per_transaction <= tier.max_sol_per_transaction)
+                && (tier.daily_limit == 0 || daily_limit <= tier.daily_limit),
+            crate::errors::DimmError::ExceedsTierCeiling
+        );
+
+        Ok(())
+    }
+
+    /// Check limits just set on `agent_account` against the price feed, its
+    /// tier's ceilings and the wallet's stake quota, and carry its new daily
+    /// limit into the aggregate
+    pub fn commit_agent_limits(
+        &mut self,
+        agent_account: &crate::state::AgentAccount,
+        previous_daily_limit: u64,
+        wallet_stake: &AccountInfo,
+    ) -> Result<()> {
+        require!(
+            !agent_account.has_usd_limits() || self.sol_usd_price_feed != Pubkey::default(),
+            crate::errors::DimmError::InvalidPriceFeed
+        );
+
+        self.check_tier_limits(
+            agent_account.trust_level,
+            agent_account.max_sol_per_transaction,
+            agent_account.daily_limit,
+        )?;
+        self.check_limit_caps(agent_account.max_sol_per_transaction, agent_account.daily_limit)?;
+
+        // Raising a daily limit must stay within the wallet's stake quota
+        let aggregate_daily_limit = self
+            .aggregate_daily_limit
+            .saturating_sub(previous_daily_limit)
+            .checked_add(agent_account.daily_limit)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+        if agent_account.daily_limit > previous_daily_limit {
+            let clock = Clock::get()?;
+            let effective_stake = crate::state::WalletStake::load_effective(wallet_stake, clock.epoch)?;
+            self.check_stake_quotas(effective_stake, self.active_agents, aggregate_daily_limit)?;
+        }
+        self.aggregate_daily_limit = aggregate_daily_limit;
+
+        Ok(())
+    }
+
+    /// Take an agent that is revoked, closed or sold out of the active count
+    /// and the aggregate daily limit, freeing its share of the stake quota
+    pub fn release_agent(&mut self, daily_limit: u64) {
+        self.active_agents = self.active_agents.saturating_sub(1);
+        self.aggregate_daily_limit = self.aggregate_daily_limit.saturating_sub(daily_limit);
+    }
+
+    /// Queue a tree for new agents to roll onto once the trees before it fill up
+    pub fn add_merkle_tree(&mut self, merkle_tree: Pubkey) -> Result<()> {
+        self.check_new_tree(&merkle_tree)?;
+        self.merkle_trees.push(merkle_tree);
+        Ok(())
+    }
+
+    /// Move new agents onto `merkle_tree` now, ahead of any queued trees
+    pub fn rotate_merkle_tree(&mut self, merkle_tree: Pubkey) -> Result<()> {
+        self.check_new_tree(&merkle_tree)?;
+        self.merkle_trees.insert(self.current_tree as usize + 1, merkle_tree);
+        self.advance_merkle_tree();
+        Ok(())
+    }
+
+    fn check_new_tree(&self, merkle_tree: &Pubkey) -> Result<()> {
+        require!(
+            !self.merkle_trees.contains(merkle_tree),
+            crate::errors::DimmError::InvalidMerkleTree
+        );
+        require!(
+            self.merkle_trees.len() < crate::constants::MAX_MERKLE_TREES,
+            crate::errors::DimmError::TooManyMerkleTrees
+        );
+        Ok(())
+    }
+
+    /// Move new agents onto the next queued tree. Returns false if none is queued.
+    pub fn advance_merkle_tree(&mut self) -> bool {
+        let next = self.current_tree as usize + 1;
+        match self.merkle_trees.get(next) {
+            Some(merkle_tree) => {
+                self.merkle_tree = *merkle_tree;
+                self.current_tree = next as u8;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Check limits an agent is being given against the protocol-wide caps
+    pub fn check_limit_caps(&self, max_sol_per_transaction: u64, daily_limit: u64) -> Result<()> {
+        require!(
+            (self.max_agent_tx_limit == 0 || max_sol_per_transaction <= self.max_agent_tx_limit)
+                && (self.max_agent_daily_limit == 0 || daily_limit <= self.max_agent_daily_limit),
+            crate::errors::DimmError::ExceedsProtocolCap
+        );
+
+        Ok(())
+    }
+
+    /// Check a freshly created agent is old and proven enough for `amount`.
+    /// Until it graduates, the agent can only make spends up to the threshold,
+    /// so its transaction count is a count of small successful spends.
+    pub fn check_cold_start(
+        &self,
+        agent_account: &crate::state::AgentAccount,
+        amount: u64,
+        current_time: i64,
+    ) -> Result<()> {
+        if self.cold_start_threshold == 0 || amount <= self.cold_start_threshold {
+            return Ok(());
+        }
+
+        let age = current_time.saturating_sub(agent_account.created_at);
+        require!(
+            age >= self.cold_start_min_age
+                && agent_account.total_transactions >= self.cold_start_min_transactions,
+            crate::errors::DimmError::ColdStartRestricted
+        );
+
+        Ok(())
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InitializeParams {
     /// Maximum depth of the merkle tree
     pub max_depth: u32,
     
     /// Maximum buffer size for the merkle tree
     pub max_buffer_size: u32,
+    
+    /// Highest daily limit any agent may be given (0 = no cap)
+    pub max_agent_daily_limit: u64,
+    
+    /// Highest per-transaction limit any agent may be given (0 = no cap)
+    pub max_agent_tx_limit: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UpdateProtocolConfigParams {
+    /// Pause or unpause the protocol
+    pub paused: Option<bool>,
+    
+    /// New protocol fee basis points (needs the treasury)
+    pub fee_bps: Option<u16>,
+    
+    /// New minimum fee in lamports (needs the treasury)
+    pub min_fee: Option<u64>,
+    
+    /// New cap on agent daily limits (0 = no cap)
+    pub max_agent_daily_limit: Option<u64>,
+    
+    /// New cap on agent per-transaction limits (0 = no cap)
+    pub max_agent_tx_limit: Option<u64>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LimitCapParams {
+    /// Highest daily limit any agent may be given (0 = no cap)
+    pub max_agent_daily_limit: u64,
+    
+    /// Highest per-transaction limit any agent may be given (0 = no cap)
+    pub max_agent_tx_limit: u64,
+}
+
+impl LimitCapParams {
+    /// Check the caps could both be met by one agent
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.max_agent_daily_limit == 0
+                || self.max_agent_tx_limit == 0
+                || self.max_agent_daily_limit >= self.max_agent_tx_limit,
+            crate::errors::DimmError::InvalidLimitConfiguration
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StakeQuotaParams {
+    /// Agents allowed per staked SOL (0 = no agent quota)
+    pub agents_per_staked_sol: u32,
+    
+    /// Aggregate daily limit allowed per staked SOL (0 = no limit quota)
+    pub daily_limit_per_staked_sol: u64,
+}
+
+/// What agents at one trust level may be given, and what earns it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TierConfig {
+    /// Highest per-transaction limit at this level (0 = no ceiling)
+    pub max_sol_per_transaction: u64,
+    
+    /// Highest daily limit at this level (0 = no ceiling)
+    pub daily_limit: u64,
+    
+    /// Successful transactions, with no failures, that earn this level
+    /// without the main wallet (0 = manual promotion only)
+    pub promote_after_transactions: u64,
+}
+
+impl TierConfig {
+    pub const LEN: usize = 8 + 8 + 8;
+
+    /// Check this tier's ceilings are at least as high as `lower`'s
+    pub fn loosens(&self, lower: &TierConfig) -> bool {
+        let at_least = |ceiling: u64, lower: u64| ceiling == 0 || (lower != 0 && ceiling >= lower);
+        at_least(self.max_sol_per_transaction, lower.max_sol_per_transaction)
+            && at_least(self.daily_limit, lower.daily_limit)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColdStartParams {
+    /// Spends above this amount need a seasoned agent (0 = disabled)
+    pub threshold: u64,
+    
+    /// Minimum agent age before spending above the threshold (in seconds)
+    pub min_age: i64,
+    
+    /// Minimum successful transactions before spending above the threshold
+    pub min_transactions: u64,
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::LAMPORTS_PER_SOL;
+
+    fn config(agents_per_staked_sol: u32, daily_limit_per_staked_sol: u64) -> ProtocolConfig {
+        ProtocolConfig {
+            authority: Pubkey::new_unique(),
+            merkle_tree: Pubkey::new_unique(),
+            total_agents: 0,
+            version: 1,
+            paused: false,
+            bump: 0,
+            agents_per_staked_sol,
+            daily_limit_per_staked_sol,
+            aggregate_daily_limit: 0,
+            cold_start_threshold: 0,
+            cold_start_min_age: 0,
+            cold_start_min_transactions: 0,
+            restrict_unlabeled_programs: false,
+            sol_usd_price_feed: Pubkey::default(),
+            trust_tiers: [TierConfig::default(); 4],
+            max_agent_daily_limit: 0,
+            max_agent_tx_limit: 0,
+            merkle_trees: Vec::new(),
+            current_tree: 0,
+            active_agents: 0,
+            reserved: [0; 64],
+        }
+    }
 
+    #[test]
+    fn zero_rates_disable_the_quotas() {
+        assert!(config(0, 0).check_stake_quotas(0, u64::MAX, u64::MAX).is_ok());
+    }
+
+    #[test]
+    fn agent_quota_scales_with_stake() {
+        let config = config(2, 0);
+        let stake = 3 * LAMPORTS_PER_SOL;
+
+        assert!(config.check_stake_quotas(stake, 6, 0).is_ok());
+        assert!(config.check_stake_quotas(stake, 7, 0).is_err());
+        // Partial SOL rounds the quota down
+        assert!(config.check_stake_quotas(LAMPORTS_PER_SOL / 2, 1, 0).is_ok());
+        assert!(config.check_stake_quotas(LAMPORTS_PER_SOL / 2, 2, 0).is_err());
+        assert!(config.check_stake_quotas(0, 1, 0).is_err());
+    }
+
+    #[test]
+    fn limit_quota_scales_with_stake() {
+        let config = config(0, LAMPORTS_PER_SOL / 10);
+        let stake = 5 * LAMPORTS_PER_SOL;
+
+        assert!(config.check_stake_quotas(stake, 1, LAMPORTS_PER_SOL / 2).is_ok());
+        assert!(config.check_stake_quotas(stake, 1, LAMPORTS_PER_SOL / 2 + 1).is_err());
+    }
+
+    #[test]
+    fn quota_math_does_not_overflow() {
+        let config = config(u32::MAX, u64::MAX);
+        assert!(config.check_stake_quotas(u64::MAX, u64::MAX, u64::MAX).is_ok());
+    }
+
+    #[test]
+    fn releasing_an_agent_frees_its_share_of_the_quota() {
+        let mut config = config(1, LAMPORTS_PER_SOL);
+        config.active_agents = 2;
+        config.aggregate_daily_limit = 2 * LAMPORTS_PER_SOL;
+        let stake = 2 * LAMPORTS_PER_SOL;
+        assert!(config.check_stake_quotas(stake, config.active_agents + 1, 0).is_err());
+
+        config.release_agent(LAMPORTS_PER_SOL);
+        assert_eq!(config.active_agents, 1);
+        assert_eq!(config.aggregate_daily_limit, LAMPORTS_PER_SOL);
+        let aggregate_daily_limit = config.aggregate_daily_limit + LAMPORTS_PER_SOL;
+        assert!(config
+            .check_stake_quotas(stake, config.active_agents + 1, aggregate_daily_limit)
+            .is_ok());
+
+        // Releasing more than is counted saturates at zero
+        config.release_agent(u64::MAX);
+        config.release_agent(0);
+        assert_eq!(config.active_agents, 0);
+        assert_eq!(config.aggregate_daily_limit, 0);
+    }
+}
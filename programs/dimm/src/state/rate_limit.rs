@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 /// Rate limiting configuration for agents
 #[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RateLimit {
     /// Agent this rate limit belongs to
     pub agent: Pubkey,
@@ -36,10 +37,14 @@ pub struct RateLimit {
     /// Total times rate limited
     pub total_rate_limits: u32,
     
+    /// Layout version, upgraded by `migrate_account`
+    pub version: u8,
+    
     /// Bump seed for PDA
     pub bump: u8,
     
     /// Reserved space
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
     pub reserved: [u8; 64],
 }
 
@@ -56,6 +61,7 @@ impl RateLimit {
         8 +  // last_cooldown_start
         1 +  // in_cooldown
         4 +  // total_rate_limits
+        1 +  // version
         1 +  // bump
         64;  // reserved
 
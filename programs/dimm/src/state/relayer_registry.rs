@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_RELAYERS;
+
+/// Relayers a main wallet lets pay the network fee for its agents'
+/// executions, and what each sponsored execution reimburses them
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RelayerRegistry {
+    /// Main wallet that owns the registry
+    pub main_wallet: Pubkey,
+    
+    /// Allowed fee payers
+    pub relayers: Vec<Pubkey>,
+    
+    /// Lamports paid from the agent to the relayer per sponsored execution (0 = none)
+    pub reimbursement_lamports: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl RelayerRegistry {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // main_wallet
+        4 + (32 * MAX_RELAYERS) + // relayers
+        8 +  // reimbursement_lamports
+        1;   // bump
+
+    /// Check if `key` may sponsor executions
+    pub fn is_relayer(&self, key: &Pubkey) -> bool {
+        self.relayers.contains(key)
+    }
+}
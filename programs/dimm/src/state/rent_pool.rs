@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+/// Fee-funded pool paying activity record rent for opted-in agents
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RentPool {
+    /// Protocol authority (main wallet)
+    pub authority: Pubkey,
+    
+    /// Total moved in from the treasury
+    pub total_funded: u64,
+    
+    /// Total rent paid for activity records
+    pub total_sponsored: u64,
+    
+    /// Total rent returned by pruned records
+    pub total_reclaimed: u64,
+    
+    /// Activity records whose rent the pool paid
+    pub records_sponsored: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+    
+    /// Reserved space
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
+    pub reserved: [u8; 32],
+}
+
+impl RentPool {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        8 +  // total_funded
+        8 +  // total_sponsored
+        8 +  // total_reclaimed
+        8 +  // records_sponsored
+        1 +  // bump
+        32;  // reserved
+
+    /// Lamports the pool can pay out while staying rent exempt
+    pub fn available(pool: &AccountInfo) -> Result<u64> {
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(RentPool::LEN);
+        Ok(pool.lamports().saturating_sub(rent_exempt_minimum))
+    }
+
+    /// Record rent paid for a new activity record
+    pub fn record_sponsorship(&mut self, rent: u64) -> Result<()> {
+        self.total_sponsored = self.total_sponsored
+            .checked_add(rent)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+        self.records_sponsored = self.records_sponsored
+            .checked_add(1)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+
+        Ok(())
+    }
+
+    /// Record rent returned by a pruned activity record
+    pub fn record_reclaim(&mut self, rent: u64) -> Result<()> {
+        self.total_reclaimed = self.total_reclaimed
+            .checked_add(rent)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+/// Programs an agent may call, each with its own amount cap, narrowing the
+/// all-or-nothing `ExecutePrograms` permission
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScopedPermissions {
+    /// Agent these scopes apply to
+    pub agent: Pubkey,
+    
+    /// Allowed programs and their caps
+    pub scopes: Vec<ScopedPermission>,
+    
+    /// Last updated timestamp
+    pub last_updated: i64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+    
+    /// Reserved space
+    pub reserved: [u8; 32],
+}
+
+impl ScopedPermissions {
+    pub const MAX_SCOPES: usize = 16;
+    
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        4 + (ScopedPermission::LEN * Self::MAX_SCOPES) + // scopes
+        8 +  // last_updated
+        1 +  // bump
+        32;  // reserved
+
+    /// Load the agent's scopes; `None` if it has none
+    pub fn load(scoped_permissions: &AccountInfo) -> Result<Option<ScopedPermissions>> {
+        if scoped_permissions.data_is_empty() {
+            return Ok(None);
+        }
+
+        require_keys_eq!(*scoped_permissions.owner, crate::ID, crate::errors::DimmError::Unauthorized);
+        let data = scoped_permissions.try_borrow_data()?;
+        Ok(Some(ScopedPermissions::try_deserialize(&mut &data[..])?))
+    }
+
+    /// Fail unless a call to `program` moving `amount` is within the agent's
+    /// scopes; agents without scopes keep plain `ExecutePrograms`
+    pub fn require_scope(scoped_permissions: &AccountInfo, program: &Pubkey, amount: u64) -> Result<()> {
+        let Some(scoped_permissions) = Self::load(scoped_permissions)? else {
+            return Ok(());
+        };
+
+        let scope = scoped_permissions
+            .find(program)
+            .ok_or(crate::errors::DimmError::ProgramOutOfScope)?;
+        require!(
+            amount <= scope.max_amount,
+            crate::errors::DimmError::ExceedsScopedAmount
+        );
+        Ok(())
+    }
+
+    /// Scope of a program
+    pub fn find(&self, program: &Pubkey) -> Option<&ScopedPermission> {
+        self.scopes.iter().find(|scope| &scope.program_id == program)
+    }
+
+    /// Add a scope or replace the cap of an existing one
+    pub fn upsert(&mut self, scope: ScopedPermission) -> Result<()> {
+        match self.scopes.iter_mut().find(|existing| existing.program_id == scope.program_id) {
+            Some(existing) => existing.max_amount = scope.max_amount,
+            None => {
+                require!(
+                    self.scopes.len() < Self::MAX_SCOPES,
+                    crate::errors::DimmError::TooManyScopes
+                );
+                self.scopes.push(scope);
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove a program's scope
+    pub fn remove(&mut self, program: &Pubkey) {
+        self.scopes.retain(|scope| &scope.program_id != program);
+    }
+}
+
+/// Permission to call one program with up to `max_amount` lamports per execution
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScopedPermission {
+    pub program_id: Pubkey,
+    pub max_amount: u64,
+}
+
+impl ScopedPermission {
+    pub const LEN: usize = 32 + 8;
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_SESSION_ACTIVITY_TYPES;
+use crate::state::ActivityType;
+
+/// Short-lived key allowed to execute for an agent within its own budget
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SessionKey {
+    /// Agent the session executes for
+    pub agent: Pubkey,
+    
+    /// Ephemeral key that signs executions
+    pub session_key: Pubkey,
+    
+    /// Main wallet or agent signer that created the session and paid its rent
+    pub created_by: Pubkey,
+    
+    /// When the session stops working (unix timestamp)
+    pub expires_at: i64,
+    
+    /// Lamports the session may spend over its lifetime
+    pub max_total_spend: u64,
+    
+    /// Lamports spent so far
+    pub spent: u64,
+    
+    /// Activity types the session may execute
+    pub allowed_activity_types: Vec<ActivityType>,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl SessionKey {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        32 + // session_key
+        32 + // created_by
+        8 +  // expires_at
+        8 +  // max_total_spend
+        8 +  // spent
+        4 + MAX_SESSION_ACTIVITY_TYPES + // allowed_activity_types
+        1;   // bump
+
+    /// Check if the session can still sign at `current_time`
+    pub fn is_active(&self, current_time: i64) -> bool {
+        current_time < self.expires_at
+    }
+
+    /// Check an execution against the session's activity types and charge
+    /// `amount` to its budget
+    pub fn record_execution(&mut self, activity_type: &ActivityType, amount: u64) -> Result<()> {
+        require!(
+            self.allowed_activity_types.contains(activity_type),
+            crate::errors::DimmError::SessionActivityNotAllowed
+        );
+
+        let spent = self.spent
+            .checked_add(amount)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+        require!(
+            spent <= self.max_total_spend,
+            crate::errors::DimmError::SessionBudgetExceeded
+        );
+        self.spent = spent;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreateSessionKeyParams {
+    /// Ephemeral key that will sign executions
+    pub session_key: Pubkey,
+    
+    /// When the session stops working (unix timestamp)
+    pub expires_at: i64,
+    
+    /// Lamports the session may spend over its lifetime
+    pub max_total_spend: u64,
+    
+    /// Activity types the session may execute
+    pub allowed_activity_types: Vec<ActivityType>,
+}
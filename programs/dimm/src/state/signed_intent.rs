@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use crate::constants::INTENT_DOMAIN;
+use crate::errors::DimmError;
+
+/// Transfer decided off-chain and signed by the agent signer, settled by
+/// any relayer through `execute_signed_intent`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SignedIntent {
+    /// Agent the intent spends from
+    pub agent: Pubkey,
+    
+    /// Lamports to transfer
+    pub amount: u64,
+    
+    /// Recipient of the transfer
+    pub destination: Pubkey,
+    
+    /// Must equal the agent's next intent nonce
+    pub nonce: u64,
+    
+    /// Intent cannot be settled at or after this time (unix timestamp)
+    pub expires_at: i64,
+}
+
+impl SignedIntent {
+    /// Size of an Ed25519 program header: signature count and padding
+    const ED25519_HEADER_LEN: usize = 2;
+
+    /// Size of one Ed25519 signature offsets entry
+    const ED25519_OFFSETS_LEN: usize = 14;
+
+    /// Bytes the agent signer signs: the domain tag, then the Borsh intent
+    pub fn message(&self) -> Result<Vec<u8>> {
+        let mut message = INTENT_DOMAIN.to_vec();
+        self.serialize(&mut message)?;
+        Ok(message)
+    }
+
+    /// Check that the instruction right before the current one is an Ed25519
+    /// program instruction verifying one signature by `signer` over this
+    /// intent. The Ed25519 program has already checked the signature itself.
+    pub fn verify(&self, instructions: &AccountInfo, signer: &Pubkey) -> Result<()> {
+        let current_index = load_current_index_checked(instructions)?;
+        require!(current_index > 0, DimmError::InvalidIntentSignature);
+        let ix = load_instruction_at_checked(current_index as usize - 1, instructions)?;
+        require!(
+            ix.program_id == ed25519_program::ID && ix.accounts.is_empty(),
+            DimmError::InvalidIntentSignature
+        );
+
+        let data = &ix.data;
+        require!(
+            data.len() >= Self::ED25519_HEADER_LEN + Self::ED25519_OFFSETS_LEN && data[0] == 1,
+            DimmError::InvalidIntentSignature
+        );
+        let offsets = &data[Self::ED25519_HEADER_LEN..Self::ED25519_HEADER_LEN + Self::ED25519_OFFSETS_LEN];
+        let read = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+        let public_key_offset = read(4) as usize;
+        let message_offset = read(8) as usize;
+        let message_size = read(10) as usize;
+
+        // Key, signature and message must all live in the Ed25519 instruction itself
+        require!(
+            read(2) == u16::MAX && read(6) == u16::MAX && read(12) == u16::MAX,
+            DimmError::InvalidIntentSignature
+        );
+
+        let public_key = data
+            .get(public_key_offset..public_key_offset + 32)
+            .ok_or(DimmError::InvalidIntentSignature)?;
+        let message = data
+            .get(message_offset..message_offset + message_size)
+            .ok_or(DimmError::InvalidIntentSignature)?;
+        require!(
+            public_key == signer.as_ref() && message == self.message()?.as_slice(),
+            DimmError::InvalidIntentSignature
+        );
+
+        Ok(())
+    }
+}
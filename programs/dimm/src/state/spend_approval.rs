@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+/// Allowance letting an external protocol pull funds from an agent via CPI
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpendApproval {
+    /// Agent funds are pulled from
+    pub agent: Pubkey,
+    
+    /// Signer the composing protocol calls with (typically its PDA)
+    pub spender: Pubkey,
+    
+    /// Total lamports the spender may pull
+    pub allowance: u64,
+    
+    /// Lamports pulled so far
+    pub spent: u64,
+    
+    /// Approval is unusable after this timestamp (0 = no expiry)
+    pub expires_at: i64,
+    
+    /// Last updated timestamp
+    pub last_updated: i64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+    
+    /// Reserved space
+    pub reserved: [u8; 32],
+}
+
+impl SpendApproval {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        32 + // spender
+        8 +  // allowance
+        8 +  // spent
+        8 +  // expires_at
+        8 +  // last_updated
+        1 +  // bump
+        32;  // reserved
+
+    /// Check if the approval has expired
+    pub fn is_expired(&self, current_time: i64) -> bool {
+        self.expires_at != 0 && current_time > self.expires_at
+    }
+
+    /// Lamports the spender may still pull
+    pub fn remaining(&self) -> u64 {
+        self.allowance.saturating_sub(self.spent)
+    }
+
+    /// Record a pull against the allowance
+    pub fn record_spend(&mut self, amount: u64) -> Result<()> {
+        require!(
+            amount <= self.remaining(),
+            crate::errors::DimmError::ExceedsSpendAllowance
+        );
+
+        self.spent = self.spent
+            .checked_add(amount)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ApproveSpenderParams {
+    /// Signer the composing protocol calls with
+    pub spender: Pubkey,
+    
+    /// Total lamports the spender may pull; replaces any previous allowance
+    pub allowance: u64,
+    
+    /// Expiry timestamp (0 = no expiry)
+    pub expires_at: i64,
+}
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+
+/// Per-mint spending limits for an agent, with cached mint metadata
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenLimit {
+    /// Agent these limits apply to
+    pub agent: Pubkey,
+    
+    /// Token mint
+    pub mint: Pubkey,
+    
+    /// Mint decimals, cached so events can carry normalized amounts
+    pub decimals: u8,
+    
+    /// Decimal places shown in normalized event amounts
+    pub display_decimals: u8,
+    
+    /// Maximum base units per transaction
+    pub max_per_transaction: u64,
+    
+    /// Daily limit (in base units)
+    pub daily_limit: u64,
+    
+    /// Total base units spent today
+    pub spent_today: u64,
+    
+    /// Timestamp of last daily reset
+    pub last_daily_reset: i64,
+    
+    /// Total base units spent all time
+    pub total_spent: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+    
+    /// Reserved space
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
+    pub reserved: [u8; 64],
+}
+
+impl TokenLimit {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        32 + // mint
+        1 +  // decimals
+        1 +  // display_decimals
+        8 +  // max_per_transaction
+        8 +  // daily_limit
+        8 +  // spent_today
+        8 +  // last_daily_reset
+        8 +  // total_spent
+        1 +  // bump
+        64;  // reserved
+
+    /// Check if the daily limit needs to be reset
+    pub fn check_and_reset_daily_limit(&mut self, current_time: i64) -> Result<()> {
+        let time_since_reset = current_time
+            .checked_sub(self.last_daily_reset)
+            .ok_or(crate::errors::DimmError::InvalidActivityWindow)?;
+
+        if time_since_reset >= crate::constants::DAILY_WINDOW_SECONDS {
+            self.spent_today = 0;
+            self.last_daily_reset = current_time;
+        }
+
+        Ok(())
+    }
+
+    /// Check if the agent can spend the specified base units of this mint
+    pub fn can_spend(&self, amount: u64) -> Result<bool> {
+        if amount > self.max_per_transaction {
+            return Ok(false);
+        }
+
+        let new_daily_total = self.spent_today
+            .checked_add(amount)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+
+        Ok(new_daily_total <= self.daily_limit)
+    }
+
+    /// Record a token spend
+    pub fn record_spend(&mut self, amount: u64) -> Result<()> {
+        self.spent_today = self.spent_today
+            .checked_add(amount)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+
+        self.total_spent = self.total_spent
+            .checked_add(amount)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+
+        Ok(())
+    }
+
+    /// Normalized amount for events and logs
+    pub fn ui_amount(&self, amount: u64) -> String {
+        crate::events::ui_amount(amount, self.decimals, self.display_decimals)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenLimitParams {
+    /// Maximum base units per transaction
+    pub max_per_transaction: u64,
+    
+    /// Daily limit (in base units)
+    pub daily_limit: u64,
+    
+    /// Decimal places shown in normalized event amounts
+    pub display_decimals: u8,
+}
@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 /// Treasury account for protocol fees and funds
 #[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Treasury {
     /// Protocol authority
     pub authority: Pubkey,
@@ -31,6 +32,7 @@ pub struct Treasury {
     pub bump: u8,
     
     /// Reserved space
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
     pub reserved: [u8; 128],
 }
 
@@ -57,5 +59,43 @@ impl Treasury {
             
         Ok(fee.max(self.min_fee))
     }
+
+    /// Record a collected fee
+    pub fn record_fee(&mut self, fee: u64, current_time: i64) -> Result<()> {
+        self.total_fees_collected = self.total_fees_collected
+            .checked_add(fee)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+        self.last_fee_collection = current_time;
+
+        Ok(())
+    }
+
+    /// Load the treasury if the wallet has one.
+    /// Returns `None` when the PDA has not been initialized.
+    pub fn load(treasury: &AccountInfo) -> Result<Option<Treasury>> {
+        if treasury.data_is_empty() {
+            return Ok(None);
+        }
+
+        require_keys_eq!(*treasury.owner, crate::ID, crate::errors::DimmError::Unauthorized);
+        let data = treasury.try_borrow_data()?;
+        Ok(Some(Treasury::try_deserialize(&mut &data[..])?))
+    }
+
+    /// Write a treasury loaded with `load` back to its account
+    pub fn store(&self, treasury: &AccountInfo) -> Result<()> {
+        let mut data = treasury.try_borrow_mut_data()?;
+        self.try_serialize(&mut &mut data[..])
+    }
 }
 
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InitTreasuryParams {
+    /// Protocol fee basis points (100 = 1%)
+    pub fee_bps: u16,
+    
+    /// Minimum fee in lamports
+    pub min_fee: u64,
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use crate::constants::DAILY_WINDOW_SECONDS;
+
+/// Daily cap shared by all of a wallet's agents, so adding agents does not
+/// multiply the owner's exposure
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WalletBudget {
+    /// Main wallet the budget belongs to
+    pub main_wallet: Pubkey,
+    
+    /// Most all agents together may spend per window (0 = no cap)
+    pub daily_cap: u64,
+    
+    /// Spent by all agents in the current window (in lamports)
+    pub spent_today: u64,
+    
+    /// Start of the current window
+    pub window_start: i64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl WalletBudget {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // main_wallet
+        8 +  // daily_cap
+        8 +  // spent_today
+        8 +  // window_start
+        1;   // bump
+
+    /// Load the wallet's budget if it has one.
+    /// Returns `None` when the PDA has not been initialized.
+    pub fn load(budget: &AccountInfo) -> Result<Option<WalletBudget>> {
+        if budget.data_is_empty() {
+            return Ok(None);
+        }
+
+        require_keys_eq!(*budget.owner, crate::ID, crate::errors::DimmError::Unauthorized);
+        let data = budget.try_borrow_data()?;
+        Ok(Some(WalletBudget::try_deserialize(&mut &data[..])?))
+    }
+
+    /// Write a budget loaded with `load` back to its account
+    pub fn store(&self, budget: &AccountInfo) -> Result<()> {
+        let mut data = budget.try_borrow_mut_data()?;
+        self.try_serialize(&mut &mut data[..])
+    }
+
+    /// Charge an agent's spend to the shared budget, failing if it would
+    /// take the wallet past its cap
+    pub fn debit(&mut self, amount: u64, current_time: i64) -> Result<()> {
+        if current_time.saturating_sub(self.window_start) >= DAILY_WINDOW_SECONDS {
+            self.spent_today = 0;
+            self.window_start = current_time;
+        }
+
+        let spent_today = self.spent_today
+            .checked_add(amount)
+            .ok_or(crate::errors::DimmError::NumericalOverflow)?;
+        require!(
+            self.daily_cap == 0 || spent_today <= self.daily_cap,
+            crate::errors::DimmError::ExceedsWalletBudget
+        );
+
+        self.spent_today = spent_today;
+        Ok(())
+    }
+
+    /// Debit the budget behind `budget`, if the wallet has one
+    pub fn charge(budget: &AccountInfo, amount: u64, current_time: i64) -> Result<()> {
+        if let Some(mut wallet_budget) = Self::load(budget)? {
+            wallet_budget.debit(amount, current_time)?;
+            wallet_budget.store(budget)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use crate::constants::DAILY_WINDOW_SECONDS;
+
+/// Activity of all of a wallet's agents, aggregated so dashboards read
+/// one account instead of every agent
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WalletDigest {
+    /// Main wallet the digest belongs to
+    pub main_wallet: Pubkey,
+    
+    /// Executions across all agents
+    pub total_transactions: u64,
+    
+    /// Token transfers among them
+    pub token_transfers: u64,
+    
+    /// SOL moved by agents (in lamports)
+    pub total_volume: u64,
+    
+    /// Protocol fees paid by agents (in lamports)
+    pub total_fees: u64,
+    
+    /// SOL moved by agents in the current window (in lamports)
+    pub volume_today: u64,
+    
+    /// Executions in the current window
+    pub transactions_today: u64,
+    
+    /// Start of the current window
+    pub window_start: i64,
+    
+    /// Agent that executed last
+    pub last_agent: Pubkey,
+    
+    /// Timestamp of the last execution
+    pub last_execution_at: i64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+    
+    /// Reserved space
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
+    pub reserved: [u8; 64],
+}
+
+impl WalletDigest {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // main_wallet
+        8 +  // total_transactions
+        8 +  // token_transfers
+        8 +  // total_volume
+        8 +  // total_fees
+        8 +  // volume_today
+        8 +  // transactions_today
+        8 +  // window_start
+        32 + // last_agent
+        8 +  // last_execution_at
+        1 +  // bump
+        64;  // reserved
+
+    /// Load the wallet's digest if it has been initialized.
+    /// Returns `None` when the PDA has not been initialized.
+    pub fn load(digest: &AccountInfo) -> Result<Option<WalletDigest>> {
+        if digest.data_is_empty() {
+            return Ok(None);
+        }
+
+        require_keys_eq!(*digest.owner, crate::ID, crate::errors::DimmError::Unauthorized);
+        let data = digest.try_borrow_data()?;
+        Ok(Some(WalletDigest::try_deserialize(&mut &data[..])?))
+    }
+
+    /// Write a digest loaded with `load` back to its account
+    pub fn store(&self, digest: &AccountInfo) -> Result<()> {
+        let mut data = digest.try_borrow_mut_data()?;
+        self.try_serialize(&mut &mut data[..])
+    }
+
+    /// Fold one agent execution into the digest
+    pub fn record_execution(
+        &mut self,
+        agent: Pubkey,
+        lamports: u64,
+        fee: u64,
+        token_transfer: bool,
+        current_time: i64,
+    ) -> Result<()> {
+        if current_time.saturating_sub(self.window_start) >= DAILY_WINDOW_SECONDS {
+            self.volume_today = 0;
+            self.transactions_today = 0;
+            self.window_start = current_time;
+        }
+
+        let overflow = || error!(crate::errors::DimmError::NumericalOverflow);
+        self.total_transactions = self.total_transactions.checked_add(1).ok_or_else(overflow)?;
+        self.transactions_today = self.transactions_today.checked_add(1).ok_or_else(overflow)?;
+        if token_transfer {
+            self.token_transfers = self.token_transfers.checked_add(1).ok_or_else(overflow)?;
+        }
+        self.total_volume = self.total_volume.checked_add(lamports).ok_or_else(overflow)?;
+        self.volume_today = self.volume_today.checked_add(lamports).ok_or_else(overflow)?;
+        self.total_fees = self.total_fees.checked_add(fee).ok_or_else(overflow)?;
+        self.last_agent = agent;
+        self.last_execution_at = current_time;
+
+        Ok(())
+    }
+}
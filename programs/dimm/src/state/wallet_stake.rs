@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+/// SOL a wallet has staked in the protocol; weights its agent quotas
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WalletStake {
+    /// Wallet that owns the stake
+    pub owner: Pubkey,
+    
+    /// Active stake (in lamports)
+    pub staked: u64,
+    
+    /// Stake that becomes active at `activation_epoch` (in lamports)
+    pub activating: u64,
+    
+    /// Epoch from which `activating` counts
+    pub activation_epoch: u64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+    
+    /// Reserved space
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
+    pub reserved: [u8; 32],
+}
+
+impl WalletStake {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        8 +  // staked
+        8 +  // activating
+        8 +  // activation_epoch
+        1 +  // bump
+        32;  // reserved
+
+    /// Stake counted towards quotas in `epoch`
+    pub fn effective_stake(&self, epoch: u64) -> u64 {
+        if epoch >= self.activation_epoch {
+            self.staked.saturating_add(self.activating)
+        } else {
+            self.staked
+        }
+    }
+
+    /// Fold activated stake into `staked`
+    pub fn settle(&mut self, epoch: u64) {
+        if epoch >= self.activation_epoch && self.activating > 0 {
+            self.staked = self.staked.saturating_add(self.activating);
+            self.activating = 0;
+        }
+    }
+
+    /// Effective stake of a wallet, 0 if it has never staked.
+    pub fn load_effective(stake: &AccountInfo, epoch: u64) -> Result<u64> {
+        if stake.data_is_empty() {
+            return Ok(0);
+        }
+
+        require_keys_eq!(*stake.owner, crate::ID, crate::errors::DimmError::Unauthorized);
+        let data = stake.try_borrow_data()?;
+        let stake = WalletStake::try_deserialize(&mut &data[..])?;
+        Ok(stake.effective_stake(epoch))
+    }
+}
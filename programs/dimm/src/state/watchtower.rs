@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+/// Third-party monitoring service attached to a main wallet
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Watchtower {
+    /// Main wallet being monitored
+    pub main_wallet: Pubkey,
+    
+    /// Monitoring service key
+    pub watchtower: Pubkey,
+    
+    /// Expected interval between heartbeats (in seconds)
+    pub heartbeat_interval: i64,
+    
+    /// Last heartbeat timestamp
+    pub last_heartbeat: i64,
+    
+    /// Timestamp when the watchtower was registered
+    pub registered_at: i64,
+    
+    /// Number of freezes issued by this watchtower
+    pub freeze_count: u32,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+    
+    /// Reserved space
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
+    pub reserved: [u8; 64],
+}
+
+impl Watchtower {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // main_wallet
+        32 + // watchtower
+        8 +  // heartbeat_interval
+        8 +  // last_heartbeat
+        8 +  // registered_at
+        4 +  // freeze_count
+        1 +  // bump
+        64;  // reserved
+
+    /// Load the wallet's watchtower; `None` if none is registered
+    pub fn load(watchtower: &AccountInfo) -> Result<Option<Watchtower>> {
+        if watchtower.data_is_empty() {
+            return Ok(None);
+        }
+
+        require_keys_eq!(*watchtower.owner, crate::ID, crate::errors::DimmError::Unauthorized);
+        let data = watchtower.try_borrow_data()?;
+        Ok(Some(Watchtower::try_deserialize(&mut &data[..])?))
+    }
+
+    /// Check if the watchtower has missed its heartbeat
+    pub fn is_overdue(&self, current_time: i64) -> bool {
+        if self.heartbeat_interval == 0 {
+            return false;
+        }
+        current_time.saturating_sub(self.last_heartbeat) > self.heartbeat_interval
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegisterWatchtowerParams {
+    /// Monitoring service key
+    pub watchtower: Pubkey,
+    
+    /// Expected interval between heartbeats (0 = no heartbeat expected)
+    pub heartbeat_interval: i64,
+}
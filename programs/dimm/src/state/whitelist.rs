@@ -2,11 +2,12 @@ use anchor_lang::prelude::*;
 
 /// Whitelist for approved destinations/programs
 #[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Whitelist {
     /// Protocol or agent this whitelist belongs to
     pub owner: Pubkey,
     
-    /// List of whitelisted addresses (max 100)
+    /// List of whitelisted addresses (max 100), kept sorted for binary search
     pub addresses: Vec<Pubkey>,
     
     /// Whether whitelist is enabled
@@ -22,6 +23,7 @@ pub struct Whitelist {
     pub bump: u8,
     
     /// Reserved space
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
     pub reserved: [u8; 64],
 }
 
@@ -42,31 +44,108 @@ impl Whitelist {
         if !self.enabled {
             return true; // If whitelist is disabled, all addresses are allowed
         }
-        self.addresses.contains(address)
+        self.contains(address)
     }
 
-    /// Add address to whitelist
+    /// Binary search over the sorted address list (at most 7 comparisons)
+    pub fn contains(&self, address: &Pubkey) -> bool {
+        self.addresses.binary_search(address).is_ok()
+    }
+
+    /// Add address to whitelist, keeping the list sorted
     pub fn add_address(&mut self, address: Pubkey) -> Result<()> {
-        require!(
-            self.addresses.len() < Self::MAX_ADDRESSES,
-            crate::errors::DimmError::MaxAgentsReached
-        );
-        
-        if !self.addresses.contains(&address) {
-            self.addresses.push(address);
+        if let Err(index) = self.addresses.binary_search(&address) {
+            require!(
+                self.addresses.len() < Self::MAX_ADDRESSES,
+                crate::errors::DimmError::WhitelistFull
+            );
+            self.addresses.insert(index, address);
         }
         
         Ok(())
     }
 
-    /// Remove address from whitelist
+    /// Remove address from whitelist, keeping the list sorted
     pub fn remove_address(&mut self, address: &Pubkey) -> Result<()> {
-        self.addresses.retain(|addr| addr != address);
+        if let Ok(index) = self.addresses.binary_search(address) {
+            self.addresses.remove(index);
+        }
+        Ok(())
+    }
+
+    /// Fail unless a program whitelist explicitly lists `program`; unlike
+    /// destinations, disabling a program whitelist does not open it up
+    pub fn require_program(&self, program: &Pubkey) -> Result<()> {
+        require!(
+            self.whitelist_type == WhitelistType::Programs,
+            crate::errors::DimmError::WhitelistRequired
+        );
+        require!(
+            self.contains(program),
+            crate::errors::DimmError::ProgramNotWhitelisted
+        );
         Ok(())
     }
+
+    /// Fail unless this is a token whitelist containing `mint`
+    pub fn require_token(&self, mint: &Pubkey) -> Result<()> {
+        require!(
+            self.whitelist_type == WhitelistType::Tokens,
+            crate::errors::DimmError::WhitelistRequired
+        );
+        require!(
+            self.contains(mint),
+            crate::errors::DimmError::TokenNotWhitelisted
+        );
+        Ok(())
+    }
+
+    /// Fail unless this is a validator whitelist containing `vote_account`
+    pub fn require_validator(&self, vote_account: &Pubkey) -> Result<()> {
+        require!(
+            self.whitelist_type == WhitelistType::Validators,
+            crate::errors::DimmError::WhitelistRequired
+        );
+        require!(
+            self.contains(vote_account),
+            crate::errors::DimmError::ValidatorNotWhitelisted
+        );
+        Ok(())
+    }
+
+    /// Fail unless this is a realm whitelist containing `realm`
+    pub fn require_realm(&self, realm: &Pubkey) -> Result<()> {
+        require!(
+            self.whitelist_type == WhitelistType::Realms,
+            crate::errors::DimmError::WhitelistRequired
+        );
+        require!(
+            self.contains(realm),
+            crate::errors::DimmError::RealmNotWhitelisted
+        );
+        Ok(())
+    }
+
+    /// Replace the address list with `addresses`, sorted and deduplicated
+    pub fn set_addresses(&mut self, mut addresses: Vec<Pubkey>) -> Result<()> {
+        addresses.sort();
+        addresses.dedup();
+        require!(
+            addresses.len() <= Self::MAX_ADDRESSES,
+            crate::errors::DimmError::WhitelistFull
+        );
+        self.addresses = addresses;
+        Ok(())
+    }
+
+    /// Verify the sorted, duplicate-free invariant the lookups rely on
+    pub fn is_sorted(&self) -> bool {
+        self.addresses.windows(2).all(|pair| pair[0] < pair[1])
+    }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WhitelistType {
     /// Whitelist for transfer destinations
     Destinations,
@@ -79,5 +158,100 @@ pub enum WhitelistType {
     
     /// Whitelist for NFT collections
     Collections,
+    
+    /// Whitelist for validator vote accounts agents may delegate to
+    Validators,
+    
+    /// Whitelist for SPL governance realms agents may vote in
+    Realms,
 }
 
+impl WhitelistType {
+    /// Seed byte distinguishing an owner's whitelists of different types
+    pub fn seed(&self) -> [u8; 1] {
+        [*self as u8]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn whitelist() -> Whitelist {
+        Whitelist {
+            owner: Pubkey::new_unique(),
+            addresses: Vec::new(),
+            enabled: true,
+            whitelist_type: WhitelistType::Destinations,
+            last_updated: 0,
+            bump: 0,
+            reserved: [0; 64],
+        }
+    }
+
+    fn keys(count: usize) -> Vec<Pubkey> {
+        (0..count).map(|_| Pubkey::new_unique()).collect()
+    }
+
+    #[test]
+    fn add_address_keeps_list_sorted_and_unique() {
+        let mut whitelist = whitelist();
+        let addresses = keys(20);
+        for address in addresses.iter().rev() {
+            whitelist.add_address(*address).unwrap();
+        }
+        whitelist.add_address(addresses[3]).unwrap();
+
+        assert!(whitelist.is_sorted());
+        assert_eq!(whitelist.addresses.len(), addresses.len());
+        assert!(addresses.iter().all(|address| whitelist.contains(address)));
+        assert!(!whitelist.contains(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn remove_address_keeps_list_sorted() {
+        let mut whitelist = whitelist();
+        let addresses = keys(10);
+        whitelist.set_addresses(addresses.clone()).unwrap();
+
+        whitelist.remove_address(&addresses[4]).unwrap();
+        whitelist.remove_address(&Pubkey::new_unique()).unwrap();
+
+        assert!(whitelist.is_sorted());
+        assert_eq!(whitelist.addresses.len(), addresses.len() - 1);
+        assert!(!whitelist.contains(&addresses[4]));
+        assert!(whitelist.contains(&addresses[5]));
+    }
+
+    #[test]
+    fn set_addresses_sorts_and_deduplicates() {
+        let mut whitelist = whitelist();
+        let mut addresses = keys(8);
+        addresses.push(addresses[0]);
+        addresses.reverse();
+
+        whitelist.set_addresses(addresses).unwrap();
+
+        assert!(whitelist.is_sorted());
+        assert_eq!(whitelist.addresses.len(), 8);
+    }
+
+    #[test]
+    fn whitelist_is_capped() {
+        let mut whitelist = whitelist();
+        assert!(whitelist.set_addresses(keys(Whitelist::MAX_ADDRESSES + 1)).is_err());
+
+        whitelist.set_addresses(keys(Whitelist::MAX_ADDRESSES)).unwrap();
+        assert!(whitelist.add_address(Pubkey::new_unique()).is_err());
+        // Re-adding a listed address is a no-op, even when full
+        let listed = whitelist.addresses[0];
+        assert!(whitelist.add_address(listed).is_ok());
+    }
+
+    #[test]
+    fn disabled_whitelist_allows_any_address() {
+        let mut whitelist = whitelist();
+        whitelist.enabled = false;
+        assert!(whitelist.is_whitelisted(&Pubkey::new_unique()));
+    }
+}
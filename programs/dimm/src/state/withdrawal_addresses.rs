@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use crate::errors::DimmError;
+
+/// Verified set of addresses a wallet's agents may withdraw to.
+/// Once created the policy stays on; additions only take effect after a timelock.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WithdrawalAddressBook {
+    /// Main wallet this address book belongs to
+    pub main_wallet: Pubkey,
+    
+    /// Verified withdrawal destinations
+    pub entries: Vec<WithdrawalAddress>,
+    
+    /// Last updated timestamp
+    pub last_updated: i64,
+    
+    /// Bump seed for PDA
+    pub bump: u8,
+    
+    /// Reserved space
+    #[cfg_attr(feature = "serde", serde(with = "serde_bytes"))]
+    pub reserved: [u8; 64],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WithdrawalAddress {
+    /// Destination address
+    pub address: Pubkey,
+    
+    /// Human-readable name for the destination
+    pub label: String,
+    
+    /// Timestamp from which the address may receive withdrawals
+    pub active_at: i64,
+}
+
+impl WithdrawalAddressBook {
+    pub const MAX_ADDRESSES: usize = 10;
+    pub const MAX_LABEL_LENGTH: usize = 32;
+    
+    pub const LEN: usize = 8 + // discriminator
+        32 + // main_wallet
+        4 + (Self::MAX_ADDRESSES * (32 + 4 + Self::MAX_LABEL_LENGTH + 8)) + // entries
+        8 +  // last_updated
+        1 +  // bump
+        64;  // reserved
+
+    /// Check if an address is verified and past its timelock
+    pub fn is_verified(&self, address: &Pubkey, current_time: i64) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| &entry.address == address && current_time >= entry.active_at)
+    }
+
+    /// Validate a withdrawal destination against the wallet's address book.
+    /// Without an address book, funds may only go back to the main wallet.
+    pub fn verify_destination(
+        address_book: &AccountInfo,
+        main_wallet: &Pubkey,
+        destination: &Pubkey,
+        current_time: i64,
+    ) -> Result<()> {
+        if address_book.data_is_empty() {
+            require_keys_eq!(*destination, *main_wallet, DimmError::DestinationNotVerified);
+            return Ok(());
+        }
+
+        require_keys_eq!(*address_book.owner, crate::ID, DimmError::Unauthorized);
+        let data = address_book.try_borrow_data()?;
+        let address_book = WithdrawalAddressBook::try_deserialize(&mut &data[..])?;
+        require!(
+            address_book.is_verified(destination, current_time),
+            DimmError::DestinationNotVerified
+        );
+
+        Ok(())
+    }
+}
@@ -13,6 +13,7 @@ export const DIMM_PROGRAM_ID = new PublicKey(
 export const PROTOCOL_SEED = Buffer.from('dimm_protocol');
 export const AGENT_SEED = Buffer.from('dimm_agent');
 export const ACTIVITY_SEED = Buffer.from('dimm_activity');
+export const ACTIVITY_SLOT_SEED = Buffer.from('dimm_activity_slot');
 export const TREE_AUTHORITY_SEED = Buffer.from('tree_authority');
 
 /**
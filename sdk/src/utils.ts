@@ -4,6 +4,7 @@ import {
   PROTOCOL_SEED,
   AGENT_SEED,
   ACTIVITY_SEED,
+  ACTIVITY_SLOT_SEED,
   DIMM_PROGRAM_ID,
   LAMPORTS_PER_SOL,
 } from './constants';
@@ -53,7 +54,7 @@ export async function getAgentAccountPDA(
 }
 
 /**
- * Get activity PDA
+ * Get the PDA of an activity record written before the retention ring
  */
 export async function getActivityPDA(
   agentAccount: PublicKey,
@@ -69,6 +70,28 @@ export async function getActivityPDA(
   );
 }
 
+/**
+ * Get the activity slot PDA the agent's next record is written to
+ */
+export async function getActivitySlotPDA(
+  agentAccount: PublicKey,
+  activitySequence: BN,
+  retentionMaxRecords: number,
+  programId: PublicKey = DIMM_PROGRAM_ID
+): Promise<[PublicKey, number]> {
+  const slot =
+    retentionMaxRecords === 0
+      ? activitySequence
+      : activitySequence.mod(new BN(retentionMaxRecords));
+  const slotBuffer = Buffer.alloc(8);
+  slotBuffer.writeBigUInt64LE(BigInt(slot.toString()));
+
+  return PublicKey.findProgramAddressSync(
+    [ACTIVITY_SLOT_SEED, agentAccount.toBuffer(), slotBuffer],
+    programId
+  );
+}
+
 /**
  * Validate agent name
  */
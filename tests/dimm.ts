@@ -15,9 +15,35 @@ describe("DIMM Tests", () => {
   let protocolConfigPDA: PublicKey;
   let agentAccountPDA: PublicKey;
   let merkleTree: Keypair;
+  let agentSigner: Keypair;
+  let walletStakePDA: PublicKey;
+  let globalStatsPDA: PublicKey;
+
+  const initiateWithdrawal = (amount: anchor.BN) => {
+    const [pendingWithdrawalPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dimm_withdrawal"), agentAccountPDA.toBuffer()],
+      program.programId
+    );
+    const [emergencyStatePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dimm_emergency"), mainWallet.publicKey.toBuffer()],
+      program.programId
+    );
+
+    return program.methods
+      .initiateWithdrawal(amount)
+      .accounts({
+        protocolConfig: protocolConfigPDA,
+        agentAccount: agentAccountPDA,
+        pendingWithdrawal: pendingWithdrawalPDA,
+        emergencyState: emergencyStatePDA,
+        mainWallet: mainWallet.publicKey,
+      })
+      .rpc();
+  };
 
   before(async () => {
     merkleTree = Keypair.generate();
+    agentSigner = Keypair.generate();
   });
 
   describe("Initialize Protocol", () => {
@@ -26,12 +52,22 @@ describe("DIMM Tests", () => {
         [Buffer.from("dimm_protocol"), mainWallet.publicKey.toBuffer()],
         program.programId
       );
+      [walletStakePDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("dimm_stake"), mainWallet.publicKey.toBuffer()],
+        program.programId
+      );
+      [globalStatsPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("dimm_global_stats")],
+        program.programId
+      );
 
       try {
         const tx = await program.methods
           .initialize({
             maxDepth: 14,
             maxBufferSize: 64,
+            maxAgentDailyLimit: new anchor.BN(0),
+            maxAgentTxLimit: new anchor.BN(0),
           })
           .accounts({
             protocolConfig: protocolConfigPDA,
@@ -71,7 +107,8 @@ describe("DIMM Tests", () => {
         const tx = await program.methods
           .createAgent({
             name: "TestAgent",
-            permissions: [{ transferSol: {} }],
+            agentSigner: agentSigner.publicKey,
+            permissions: new anchor.BN(1), // TransferSol
             maxSolPerTransaction: new anchor.BN(0.1 * LAMPORTS_PER_SOL),
             dailyLimit: new anchor.BN(1 * LAMPORTS_PER_SOL),
           })
@@ -79,6 +116,9 @@ describe("DIMM Tests", () => {
             protocolConfig: protocolConfigPDA,
             agentAccount: agentAccountPDA,
             mainWallet: mainWallet.publicKey,
+            walletStake: walletStakePDA,
+            globalStats: globalStatsPDA,
+            merkleTree: merkleTree.publicKey,
           })
           .rpc();
 
@@ -100,8 +140,10 @@ describe("DIMM Tests", () => {
         const tx = await program.methods
           .fundAgent(fundAmount)
           .accounts({
+            protocolConfig: protocolConfigPDA,
             agentAccount: agentAccountPDA,
             mainWallet: mainWallet.publicKey,
+            agentEscrow: null,
           })
           .rpc();
 
@@ -117,10 +159,7 @@ describe("DIMM Tests", () => {
     it("Updates agent permissions", async () => {
       try {
         const tx = await program.methods
-          .updatePermissions([
-            { transferSol: {} },
-            { swapTokens: {} },
-          ])
+          .updatePermissions(new anchor.BN(0b11)) // TransferSol | SwapTokens
           .accounts({
             agentAccount: agentAccountPDA,
             mainWallet: mainWallet.publicKey,
@@ -130,7 +169,7 @@ describe("DIMM Tests", () => {
         console.log("Update permissions transaction:", tx);
 
         const agent = await program.account.agentAccount.fetch(agentAccountPDA);
-        assert.equal(agent.permissions.length, 2);
+        assert.equal(agent.permissions.bits.toNumber(), 0b11);
       } catch (error) {
         console.log("Update permissions error:", error);
       }
@@ -145,6 +184,8 @@ describe("DIMM Tests", () => {
           })
           .accounts({
             agentAccount: agentAccountPDA,
+            protocolConfig: protocolConfigPDA,
+            walletStake: walletStakePDA,
             mainWallet: mainWallet.publicKey,
           })
           .rpc();
@@ -167,17 +208,111 @@ describe("DIMM Tests", () => {
         const recipient = Keypair.generate();
         const amount = new anchor.BN(0.01 * LAMPORTS_PER_SOL);
 
+        const [hookRegistryPDA] = PublicKey.findProgramAddressSync(
+          [Buffer.from("dimm_hooks"), agentAccountPDA.toBuffer()],
+          program.programId
+        );
+        const [schedulePDA] = PublicKey.findProgramAddressSync(
+          [Buffer.from("dimm_schedule"), agentAccountPDA.toBuffer()],
+          program.programId
+        );
+        const [statsPDA] = PublicKey.findProgramAddressSync(
+          [Buffer.from("dimm_stats"), agentAccountPDA.toBuffer()],
+          program.programId
+        );
+        const [digestPDA] = PublicKey.findProgramAddressSync(
+          [Buffer.from("dimm_digest"), mainWallet.publicKey.toBuffer()],
+          program.programId
+        );
+        const [walletBudgetPDA] = PublicKey.findProgramAddressSync(
+          [Buffer.from("dimm_wallet_budget"), mainWallet.publicKey.toBuffer()],
+          program.programId
+        );
+        const [addressBookPDA] = PublicKey.findProgramAddressSync(
+          [Buffer.from("dimm_address_book"), agentAccountPDA.toBuffer()],
+          program.programId
+        );
+        const [destinationLimitPDA] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("dimm_destination_limit"),
+            agentAccountPDA.toBuffer(),
+            recipient.publicKey.toBuffer(),
+          ],
+          program.programId
+        );
+        const [blacklistPDA] = PublicKey.findProgramAddressSync(
+          [Buffer.from("dimm_blacklist"), agentAccountPDA.toBuffer()],
+          program.programId
+        );
+        const [programRegistryPDA] = PublicKey.findProgramAddressSync(
+          [Buffer.from("dimm_program_registry"), protocolConfigPDA.toBuffer()],
+          program.programId
+        );
+        const [scopedPermissionsPDA] = PublicKey.findProgramAddressSync(
+          [Buffer.from("dimm_scoped_permissions"), agentAccountPDA.toBuffer()],
+          program.programId
+        );
+        const [emergencyStatePDA] = PublicKey.findProgramAddressSync(
+          [Buffer.from("dimm_emergency"), mainWallet.publicKey.toBuffer()],
+          program.programId
+        );
+        const [treasuryPDA] = PublicKey.findProgramAddressSync(
+          [Buffer.from("dimm_treasury"), mainWallet.publicKey.toBuffer()],
+          program.programId
+        );
+        const [watchtowerPDA] = PublicKey.findProgramAddressSync(
+          [Buffer.from("dimm_watchtower"), mainWallet.publicKey.toBuffer()],
+          program.programId
+        );
+
         const tx = await program.methods
           .executeTransaction({
             activityType: { transfer: {} },
             amount,
             destination: recipient.publicKey,
             instructionData: [],
+            mint: null,
+            feeLamports: new anchor.BN(5000),
+            expectedNonce: null,
+            memo: null,
           })
           .accounts({
             agentAccount: agentAccountPDA,
+            protocolConfig: protocolConfigPDA,
             destination: recipient.publicKey,
             authority: mainWallet.publicKey,
+            watchtower: watchtowerPDA,
+            destinationWhitelist: null,
+            blacklist: blacklistPDA,
+            programWhitelist: null,
+            targetProgram: null,
+            programRegistry: programRegistryPDA,
+            scopedPermissions: scopedPermissionsPDA,
+            priorityLanes: null,
+            emergencyState: emergencyStatePDA,
+            schedule: schedulePDA,
+            treasury: treasuryPDA,
+            hookRegistry: hookRegistryPDA,
+            stats: statsPDA,
+            digest: digestPDA,
+            walletBudget: walletBudgetPDA,
+            addressBook: addressBookPDA,
+            destinationLimit: destinationLimitPDA,
+            leaseHolder: null,
+            priceFeed: null,
+            snapshot: null,
+            globalStats: globalStatsPDA,
+            agentEscrow: null,
+            tokenLimit: null,
+            sourceTokenAccount: null,
+            destinationTokenAccount: null,
+            tokenProgram: null,
+            pendingTransaction: null,
+            coSignerSet: null,
+            sessionKey: null,
+            feePayer: null,
+            relayerRegistry: null,
+            memoProgram: null,
           })
           .rpc();
 
@@ -199,11 +334,22 @@ describe("DIMM Tests", () => {
           mainWallet.publicKey
         );
 
+        const [withdrawalAddressesPDA] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("dimm_withdrawal_addresses"),
+            mainWallet.publicKey.toBuffer(),
+          ],
+          program.programId
+        );
+
         const tx = await program.methods
           .withdrawFromAgent(withdrawAmount)
           .accounts({
+            protocolConfig: protocolConfigPDA,
             agentAccount: agentAccountPDA,
             mainWallet: mainWallet.publicKey,
+            withdrawalAddresses: withdrawalAddressesPDA,
+            destination: mainWallet.publicKey,
           })
           .rpc();
 
@@ -218,13 +364,257 @@ describe("DIMM Tests", () => {
       }
     });
 
+    it("Rejects a withdrawal request above the payable balance", async () => {
+      const balance = await provider.connection.getBalance(agentAccountPDA);
+
+      try {
+        await initiateWithdrawal(new anchor.BN(balance));
+        assert.fail("A request finalize_withdrawal could not pay should fail");
+      } catch (error) {
+        assert.include(error.toString(), "InsufficientBalance");
+      }
+    });
+
+    it("Rejects withdrawal requests while the protocol is paused", async () => {
+      await program.methods
+        .pauseProtocol()
+        .accounts({
+          protocolConfig: protocolConfigPDA,
+          authority: mainWallet.publicKey,
+        })
+        .rpc();
+
+      try {
+        await initiateWithdrawal(new anchor.BN(1000));
+        assert.fail("Withdrawal requests should fail while paused");
+      } catch (error) {
+        assert.include(error.toString(), "ProtocolPaused");
+      } finally {
+        await program.methods
+          .unpauseProtocol()
+          .accounts({
+            protocolConfig: protocolConfigPDA,
+            authority: mainWallet.publicKey,
+          })
+          .rpc();
+      }
+    });
+
+    it("Rejects withdrawal requests during an emergency pause", async () => {
+      const [emergencyStatePDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("dimm_emergency"), mainWallet.publicKey.toBuffer()],
+        program.programId
+      );
+      if (!(await program.account.emergencyState.fetchNullable(emergencyStatePDA))) {
+        await program.methods
+          .initEmergencyState([])
+          .accounts({
+            emergencyState: emergencyStatePDA,
+            authority: mainWallet.publicKey,
+          })
+          .rpc();
+      }
+
+      await program.methods
+        .emergencyPause("withdrawal test")
+        .accounts({
+          emergencyState: emergencyStatePDA,
+          caller: mainWallet.publicKey,
+        })
+        .rpc();
+
+      try {
+        await initiateWithdrawal(new anchor.BN(1000));
+        assert.fail("Withdrawal requests should fail during an emergency pause");
+      } catch (error) {
+        assert.include(error.toString(), "EmergencyPaused");
+      } finally {
+        await program.methods
+          .emergencyUnpause()
+          .accounts({
+            emergencyState: emergencyStatePDA,
+            caller: mainWallet.publicKey,
+          })
+          .rpc();
+      }
+    });
+
+    it("Rejects funding while the protocol is paused", async () => {
+      await program.methods
+        .pauseProtocol()
+        .accounts({
+          protocolConfig: protocolConfigPDA,
+          authority: mainWallet.publicKey,
+        })
+        .rpc();
+
+      try {
+        const config = await program.account.protocolConfig.fetch(protocolConfigPDA);
+        assert.equal(config.paused, true);
+
+        try {
+          await program.methods
+            .fundAgent(new anchor.BN(0.01 * LAMPORTS_PER_SOL))
+            .accounts({
+              protocolConfig: protocolConfigPDA,
+              agentAccount: agentAccountPDA,
+              mainWallet: mainWallet.publicKey,
+              agentEscrow: null,
+            })
+            .rpc();
+          assert.fail("Funding should fail while paused");
+        } catch (error) {
+          assert.include(error.toString(), "ProtocolPaused");
+        }
+      } finally {
+        // Unpause even when an assertion fails, so later tests aren't blocked
+        await program.methods
+          .unpauseProtocol()
+          .accounts({
+            protocolConfig: protocolConfigPDA,
+            authority: mainWallet.publicKey,
+          })
+          .rpc();
+      }
+
+      const config = await program.account.protocolConfig.fetch(protocolConfigPDA);
+      assert.equal(config.paused, false);
+    });
+
+    it("Rejects activity appended by a signer outside the agent", async () => {
+      const stranger = Keypair.generate();
+      const airdrop = await provider.connection.requestAirdrop(
+        stranger.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      const before = await program.account.agentAccount.fetch(agentAccountPDA);
+      const slot = Buffer.from(before.activitySequence.toArray("le", 8));
+      const [activityPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("dimm_activity_slot"), agentAccountPDA.toBuffer(), slot],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .recordActivity({
+            activityType: { transfer: {} },
+            amount: new anchor.BN(0),
+            destination: null,
+            reason: "forged",
+            signature: Array(64).fill(0),
+            success: true,
+            encryptedPayload: Buffer.from([]),
+            errorCode: null,
+            failureCategory: null,
+          })
+          .accounts({
+            agentAccount: agentAccountPDA,
+            activity: activityPDA,
+            payer: stranger.publicKey,
+            rentPool: null,
+            snapshot: null,
+          })
+          .signers([stranger])
+          .rpc();
+        assert.fail("A stranger should not extend the activity chain");
+      } catch (error) {
+        assert.include(error.toString(), "SignerHasNoRole");
+      }
+
+      const after = await program.account.agentAccount.fetch(agentAccountPDA);
+      assert.ok(after.activitySequence.eq(before.activitySequence));
+      assert.deepEqual(after.activityHash, before.activityHash);
+    });
+
+    it("Keeps the rent pool from paying a stranger's records", async () => {
+      const stranger = Keypair.generate();
+      const airdrop = await provider.connection.requestAirdrop(
+        stranger.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdrop);
+
+      await program.methods
+        .setRentSponsorship(true)
+        .accounts({
+          agentAccount: agentAccountPDA,
+          mainWallet: mainWallet.publicKey,
+        })
+        .rpc();
+
+      const [rentPoolPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("dimm_rent_pool"), mainWallet.publicKey.toBuffer()],
+        program.programId
+      );
+      const rentPool = await program.account.rentPool.fetchNullable(rentPoolPDA);
+      const poolBefore = await provider.connection.getBalance(rentPoolPDA);
+
+      const agent = await program.account.agentAccount.fetch(agentAccountPDA);
+      const slot = Buffer.from(agent.activitySequence.toArray("le", 8));
+      const [activityPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("dimm_activity_slot"), agentAccountPDA.toBuffer(), slot],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .recordActivity({
+            activityType: { transfer: {} },
+            amount: new anchor.BN(0),
+            destination: null,
+            reason: "junk",
+            signature: Array(64).fill(0),
+            success: true,
+            encryptedPayload: Buffer.from([]),
+            errorCode: null,
+            failureCategory: null,
+          })
+          .accounts({
+            agentAccount: agentAccountPDA,
+            activity: activityPDA,
+            payer: stranger.publicKey,
+            rentPool: rentPool ? rentPoolPDA : null,
+            snapshot: null,
+          })
+          .signers([stranger])
+          .rpc();
+        assert.fail("A stranger should not be paid from the rent pool");
+      } catch (error) {
+        assert.include(error.toString(), "SignerHasNoRole");
+      } finally {
+        await program.methods
+          .setRentSponsorship(false)
+          .accounts({
+            agentAccount: agentAccountPDA,
+            mainWallet: mainWallet.publicKey,
+          })
+          .rpc();
+      }
+
+      const poolAfter = await provider.connection.getBalance(rentPoolPDA);
+      assert.equal(poolAfter, poolBefore);
+    });
+
     it("Revokes an agent", async () => {
       try {
         const tx = await program.methods
-          .revokeAgent()
+          .revokeAgent({
+            // Leaf data and proof come from a DAS indexer in practice
+            root: Array(32).fill(0),
+            dataHash: Array(32).fill(0),
+            creatorHash: Array(32).fill(0),
+            nonce: new anchor.BN(0),
+            index: 0,
+          })
           .accounts({
             agentAccount: agentAccountPDA,
             mainWallet: mainWallet.publicKey,
+            protocolConfig: protocolConfigPDA,
+            globalStats: globalStatsPDA,
+            leafDelegate: mainWallet.publicKey,
+            merkleTree: merkleTree.publicKey,
           })
           .rpc();
 
@@ -236,6 +626,21 @@ describe("DIMM Tests", () => {
         console.log("Revoke agent error:", error);
       }
     });
+
+    it("Rejects withdrawal requests for a revoked agent", async () => {
+      const agent = await program.account.agentAccount.fetch(agentAccountPDA);
+      if (!agent.revoked) {
+        // Revocation needs a real compression proof; nothing to check without it
+        return;
+      }
+
+      try {
+        await initiateWithdrawal(new anchor.BN(1000));
+        assert.fail("A revoked agent should not open a withdrawal");
+      } catch (error) {
+        assert.include(error.toString(), "AgentRevoked");
+      }
+    });
   });
 });
 